@@ -49,8 +49,8 @@ pub fn get_audio_stream_info(
     tool_paths: &HashMap<String, String>,
 ) -> (Option<i32>, Option<i32>) {
     let out = match runner.run(&["mkvmerge", "-J", mkv_path], tool_paths) {
-        Some(o) => o,
-        None => return (None, None),
+        Ok(o) => o,
+        Err(_) => return (None, None),
     };
 
     let info: serde_json::Value = match serde_json::from_str(&out) {
@@ -108,9 +108,12 @@ pub fn decode_audio(
     let map_arg = format!("0:a:{stream_index}");
     let sr_str = sr.to_string();
 
-    let mut cmd: Vec<&str> = vec![
-        "ffmpeg", "-nostdin", "-v", "error", "-i", file_path, "-map", &map_arg,
-    ];
+    let mut cmd: Vec<&str> = vec!["ffmpeg", "-nostdin", "-v", "error"];
+    // Linked timelines are read through an ffmpeg concat playlist
+    if file_path.ends_with(".ffconcat") {
+        cmd.extend(&["-f", "concat", "-safe", "0"]);
+    }
+    cmd.extend(&["-i", file_path, "-map", &map_arg]);
 
     if use_soxr {
         cmd.extend(&["-resampler", "soxr"]);
@@ -120,7 +123,7 @@ pub fn decode_audio(
 
     let pcm_bytes = runner
         .run_binary(&cmd, tool_paths, None)
-        .ok_or_else(|| format!("ffmpeg decode failed for {}", Path::new(file_path).file_name().unwrap_or_default().to_string_lossy()))?;
+        .map_err(|e| format!("ffmpeg decode failed for {}: {e}", Path::new(file_path).file_name().unwrap_or_default().to_string_lossy()))?;
 
     runner.log_message(&format!(
         "[DECODE RAW] Received {} bytes for {}",
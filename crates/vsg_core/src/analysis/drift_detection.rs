@@ -1,14 +1,17 @@
 //! Drift detection — 1:1 port of `vsg_core/analysis/drift_detection.py`.
 //!
 //! Analyzes correlation chunks to diagnose sync issue type:
-//! uniform, PAL drift, linear drift, or stepping.
+//! uniform, PAL drift, speed-ratio drift, linear drift, or stepping.
 //! Implements DBSCAN clustering directly (no sklearn dependency).
 
 use std::collections::HashMap;
 
 use crate::io::runner::CommandRunner;
+use crate::models::enums::SpeedCorrectionMode;
 use crate::models::settings::AppSettings;
 
+use super::speed_ratio::{ratio_for_slope, snap_to_known_ratio};
+
 use super::types::*;
 
 /// Get video framerate via ffprobe — `_get_video_framerate`
@@ -27,8 +30,8 @@ fn get_video_framerate(
         tool_paths,
     );
     let out = match out {
-        Some(o) => o,
-        None => return 0.0,
+        Ok(o) => o,
+        Err(_) => return 0.0,
     };
     let trimmed = out.trim();
     if !trimmed.contains('/') {
@@ -366,6 +369,17 @@ pub fn diagnose_audio_issue(
             let r_squared = r_squared_calc(&delays, &predicted);
 
             if r_squared > r2_threshold {
+                if let Some(rate) = speed_ratio_slope(slope, settings) {
+                    runner.log_message(&format!(
+                        "[Speed Ratio Detected] R²={r_squared:.3}, slope={slope:.2} ms/s \
+                         -> speed ratio {:.6} ({rate:.3} ms/s).",
+                        ratio_for_slope(rate)
+                    ));
+                    return DiagnosisResult::Drift {
+                        diagnosis: "SPEED_RATIO".to_string(),
+                        rate,
+                    };
+                }
                 runner.log_message(&format!(
                     "[Linear Drift Detected] R²={r_squared:.3}, slope={slope:.2} ms/s."
                 ));
@@ -380,6 +394,21 @@ pub fn diagnose_audio_issue(
     }
 }
 
+/// Slope to correct as a speed mismatch, per `speed_correction_mode`
+///
+/// `Snap` returns the nearest known broadcast ratio's slope (or `None`, leaving
+/// plain linear drift correction); `Exact` keeps the fitted slope.
+fn speed_ratio_slope(slope: f64, settings: &AppSettings) -> Option<f64> {
+    match settings.speed_correction_mode {
+        SpeedCorrectionMode::Disabled => None,
+        SpeedCorrectionMode::Snap => {
+            snap_to_known_ratio(slope, settings.speed_correction_snap_tolerance_ms_s)
+                .map(|known| known.slope_ms_s())
+        }
+        SpeedCorrectionMode::Exact => Some(slope),
+    }
+}
+
 /// Format chunk numbers as ranges — `_format_chunk_range`
 /// e.g. [1,2,3,5,25,26,27] → "1-3,5,25-27"
 fn format_chunk_range(chunk_numbers: &[i32]) -> String {
@@ -4,6 +4,7 @@ pub mod delay_selection;
 pub mod drift_detection;
 pub mod global_shift;
 pub mod source_separation;
+pub mod speed_ratio;
 pub mod sync_stability;
 pub mod track_selection;
 pub mod types;
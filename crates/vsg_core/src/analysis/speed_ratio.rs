@@ -0,0 +1,105 @@
+//! Speed-ratio classification for linear drift.
+//!
+//! A source played back at `from` fps against a reference at `to` fps runs
+//! `k = from / to` times faster, so its delay grows by
+//! `1000 * (1 - 1/k)` ms per second of reference time (PAL 25 → 23.976
+//! gives the familiar ~40.9 ms/s). Correcting means stretching the source
+//! audio — and the subtitles and chapters of that source — by `k`.
+
+/// Frame rates that speed mismatches are built from.
+const BROADCAST_RATES: [(&str, f64); 5] = [
+    ("23.976", 24000.0 / 1001.0),
+    ("24", 24.0),
+    ("25", 25.0),
+    ("29.97", 30000.0 / 1001.0),
+    ("30", 30.0),
+];
+
+/// A speed mismatch between two broadcast frame rates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownSpeedRatio {
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Source speed relative to the reference (`from / to`).
+    pub ratio: f64,
+}
+
+impl KnownSpeedRatio {
+    /// Drift slope (ms/s) this ratio produces.
+    pub fn slope_ms_s(&self) -> f64 {
+        slope_for_ratio(self.ratio)
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}→{}", self.from, self.to)
+    }
+}
+
+/// All distinct ratios between broadcast rates (24→23.976 and 30→29.97 are
+/// the same ratio; the first pairing wins the label).
+pub fn known_speed_ratios() -> Vec<KnownSpeedRatio> {
+    let mut ratios: Vec<KnownSpeedRatio> = Vec::new();
+    for &(from, from_fps) in &BROADCAST_RATES {
+        for &(to, to_fps) in &BROADCAST_RATES {
+            let ratio = from_fps / to_fps;
+            if from == to || ratios.iter().any(|r| (r.ratio - ratio).abs() < 1e-9) {
+                continue;
+            }
+            ratios.push(KnownSpeedRatio { from, to, ratio });
+        }
+    }
+    ratios
+}
+
+/// Drift slope (ms/s) for a source running `ratio` times faster.
+pub fn slope_for_ratio(ratio: f64) -> f64 {
+    1000.0 * (1.0 - 1.0 / ratio)
+}
+
+/// Speed ratio implied by a measured drift slope (ms/s).
+pub fn ratio_for_slope(slope_ms_s: f64) -> f64 {
+    1000.0 / (1000.0 - slope_ms_s)
+}
+
+/// Nearest known ratio whose slope is within `tolerance_ms_s` of the measurement.
+pub fn snap_to_known_ratio(slope_ms_s: f64, tolerance_ms_s: f64) -> Option<KnownSpeedRatio> {
+    known_speed_ratios()
+        .into_iter()
+        .map(|r| ((r.slope_ms_s() - slope_ms_s).abs(), r))
+        .filter(|(distance, _)| *distance <= tolerance_ms_s)
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, r)| r)
+}
+
+/// Label of the known ratio equal to `ratio`, if any.
+pub fn known_ratio_label(ratio: f64) -> Option<String> {
+    known_speed_ratios()
+        .into_iter()
+        .find(|r| (r.ratio - ratio).abs() < 1e-9)
+        .map(|r| r.label())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pal_slope_matches_classic_detection() {
+        let pal = known_speed_ratios()
+            .into_iter()
+            .find(|r| r.label() == "25→23.976")
+            .unwrap();
+        assert!((pal.slope_ms_s() - 40.96).abs() < 0.01);
+        assert!((ratio_for_slope(pal.slope_ms_s()) - pal.ratio).abs() < 1e-12);
+    }
+
+    #[test]
+    fn snaps_to_nearest_ratio_within_tolerance() {
+        // 25→24 (40.0 ms/s) vs 25→23.976 (40.96 ms/s)
+        assert_eq!(snap_to_known_ratio(40.1, 0.5).unwrap().label(), "25→24");
+        assert_eq!(snap_to_known_ratio(40.8, 0.5).unwrap().label(), "25→23.976");
+        assert_eq!(snap_to_known_ratio(-1.0, 0.5).unwrap().label(), "23.976→24");
+        assert_eq!(snap_to_known_ratio(165.7, 0.5).unwrap().label(), "29.97→25");
+        assert!(snap_to_known_ratio(12.0, 0.5).is_none());
+    }
+}
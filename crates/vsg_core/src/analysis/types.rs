@@ -113,7 +113,7 @@ pub struct ClusterDiagnostic {
 pub enum DiagnosisResult {
     /// No drift or stepping detected
     Uniform,
-    /// Linear or PAL drift detected
+    /// Linear, PAL or speed-ratio drift detected
     Drift {
         diagnosis: String,
         rate: f64,
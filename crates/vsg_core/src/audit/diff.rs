@@ -0,0 +1,654 @@
+//! Audit trail comparison.
+//!
+//! Loads two `*.pipeline_audit_trail.json` files written by [`AuditTrail`]
+//! for the same job (e.g. before and after a settings change), aligns them
+//! by dot-separated path and reports what changed: per-source delays, the
+//! global shift, mkvmerge tokens and correlation chunk statistics. The first
+//! pipeline stage whose recorded values differ is reported as the point of
+//! divergence.
+//!
+//! [`AuditTrail`]: super::trail::AuditTrail
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Keys whose values change on every run and carry no timing information.
+const VOLATILE_KEYS: &[&str] = &[
+    "created_at",
+    "recorded_at",
+    "finalized_at",
+    "timestamp",
+    "temp_dir",
+    "command_preview",
+];
+
+/// Pipeline stages in the order they write to the audit trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStage {
+    Sources,
+    Correlation,
+    ContainerDelays,
+    DelayCalculation,
+    GlobalShift,
+    FinalDelays,
+    Extraction,
+    Stepping,
+    SubtitleProcessing,
+    Mux,
+}
+
+impl AuditStage {
+    /// All stages in pipeline order.
+    pub const ALL: [AuditStage; 10] = [
+        Self::Sources,
+        Self::Correlation,
+        Self::ContainerDelays,
+        Self::DelayCalculation,
+        Self::GlobalShift,
+        Self::FinalDelays,
+        Self::Extraction,
+        Self::Stepping,
+        Self::SubtitleProcessing,
+        Self::Mux,
+    ];
+
+    /// Audit trail path prefix owned by this stage.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Sources => "sources",
+            Self::Correlation => "analysis.correlations",
+            Self::ContainerDelays => "analysis.container_delays",
+            Self::DelayCalculation => "analysis.delay_calculations",
+            Self::GlobalShift => "analysis.global_shift",
+            Self::FinalDelays => "analysis.final_delays",
+            Self::Extraction => "extraction",
+            Self::Stepping => "stepping",
+            Self::SubtitleProcessing => "subtitle_processing",
+            Self::Mux => "mux",
+        }
+    }
+
+    /// Stage that owns a flattened path, if any.
+    pub fn from_path(path: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|stage| {
+            let prefix = stage.prefix();
+            path == prefix
+                || path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+        })
+    }
+}
+
+impl std::fmt::Display for AuditStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+/// How a single leaf path differs between the two trails.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One differing leaf value, aligned by path.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathDiff {
+    pub path: String,
+    pub stage: Option<AuditStage>,
+    pub kind: ChangeKind,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+/// Delay values for one source on each side.
+#[derive(Debug, Clone, Serialize)]
+pub struct DelayDiff {
+    pub source: String,
+    pub left_rounded_ms: Option<i64>,
+    pub right_rounded_ms: Option<i64>,
+    pub left_raw_ms: Option<f64>,
+    pub right_raw_ms: Option<f64>,
+}
+
+impl DelayDiff {
+    /// Whether the rounded delay (the value that reaches mkvmerge) changed.
+    pub fn rounded_changed(&self) -> bool {
+        self.left_rounded_ms != self.right_rounded_ms
+    }
+}
+
+/// Global shift on each side.
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalShiftDiff {
+    pub left_rounded_ms: Option<i64>,
+    pub right_rounded_ms: Option<i64>,
+    pub left_sync_mode: Option<String>,
+    pub right_sync_mode: Option<String>,
+}
+
+/// A single operation in the mkvmerge token diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", content = "token", rename_all = "snake_case")]
+pub enum TokenOp {
+    Keep(String),
+    Remove(String),
+    Add(String),
+}
+
+/// Summary of correlation chunks recorded for one source.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChunkStats {
+    pub total: usize,
+    pub accepted: usize,
+    pub mean_delay_ms: Option<f64>,
+    pub min_delay_ms: Option<i64>,
+    pub max_delay_ms: Option<i64>,
+    pub mean_match_pct: Option<f64>,
+}
+
+impl ChunkStats {
+    /// Compute stats from a `chunks` array as written by `record_correlation_chunk`.
+    pub fn from_chunks(chunks: &[Value]) -> Self {
+        let accepted: Vec<&Value> = chunks
+            .iter()
+            .filter(|c| c.get("accepted").and_then(Value::as_bool).unwrap_or(false))
+            .collect();
+        let delays: Vec<i64> = accepted
+            .iter()
+            .filter_map(|c| c.get("delay_ms").and_then(Value::as_i64))
+            .collect();
+        let matches: Vec<f64> = accepted
+            .iter()
+            .filter_map(|c| c.get("match_pct").and_then(Value::as_f64))
+            .collect();
+
+        let mean = |vals: &[f64]| {
+            if vals.is_empty() {
+                None
+            } else {
+                Some(vals.iter().sum::<f64>() / vals.len() as f64)
+            }
+        };
+        let delays_f: Vec<f64> = delays.iter().map(|&d| d as f64).collect();
+
+        Self {
+            total: chunks.len(),
+            accepted: accepted.len(),
+            mean_delay_ms: mean(&delays_f),
+            min_delay_ms: delays.iter().min().copied(),
+            max_delay_ms: delays.iter().max().copied(),
+            mean_match_pct: mean(&matches),
+        }
+    }
+}
+
+/// Chunk statistics for one source on each side.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkStatsDiff {
+    pub source: String,
+    pub left: ChunkStats,
+    pub right: ChunkStats,
+}
+
+/// Full comparison of two audit trails — `AuditDiff`
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditDiff {
+    pub left_path: Option<PathBuf>,
+    pub right_path: Option<PathBuf>,
+    /// Every differing leaf, in pipeline-stage order.
+    pub paths: Vec<PathDiff>,
+    /// Per-source delays (only sources whose values differ).
+    pub delays: Vec<DelayDiff>,
+    /// Global shift, if it differs.
+    pub global_shift: Option<GlobalShiftDiff>,
+    /// Token-level diff of `mux.tokens` (empty if identical).
+    pub mux_tokens: Vec<TokenOp>,
+    /// Chunk statistics per source (only sources whose stats differ).
+    pub chunk_stats: Vec<ChunkStatsDiff>,
+    /// Earliest pipeline stage with any differing value.
+    pub first_divergence: Option<AuditStage>,
+}
+
+impl AuditDiff {
+    /// Load and compare two audit trail files.
+    pub fn from_files(left: &Path, right: &Path) -> Result<Self, String> {
+        let left_data = load_trail(left)?;
+        let right_data = load_trail(right)?;
+        let mut diff = Self::compare(&left_data, &right_data);
+        diff.left_path = Some(left.to_path_buf());
+        diff.right_path = Some(right.to_path_buf());
+        Ok(diff)
+    }
+
+    /// Compare two already-parsed audit trails.
+    pub fn compare(left: &Value, right: &Value) -> Self {
+        let left_flat = flatten(left);
+        let right_flat = flatten(right);
+
+        let keys: BTreeSet<&String> = left_flat.keys().chain(right_flat.keys()).collect();
+        let mut paths: Vec<PathDiff> = keys
+            .into_iter()
+            .filter_map(|path| {
+                let l = left_flat.get(path);
+                let r = right_flat.get(path);
+                let kind = match (l, r) {
+                    (Some(a), Some(b)) if values_equal(a, b) => return None,
+                    (Some(_), Some(_)) => ChangeKind::Changed,
+                    (Some(_), None) => ChangeKind::Removed,
+                    (None, Some(_)) => ChangeKind::Added,
+                    (None, None) => return None,
+                };
+                Some(PathDiff {
+                    path: path.clone(),
+                    stage: AuditStage::from_path(path),
+                    kind,
+                    left: l.cloned(),
+                    right: r.cloned(),
+                })
+            })
+            .collect();
+
+        // Stage order first, unstaged paths (metadata) last, stable within a stage
+        paths.sort_by_key(|p| p.stage.map_or(usize::MAX, |s| s as usize));
+
+        let first_divergence = paths.iter().filter_map(|p| p.stage).min();
+
+        Self {
+            left_path: None,
+            right_path: None,
+            paths,
+            delays: diff_delays(left, right),
+            global_shift: diff_global_shift(left, right),
+            mux_tokens: diff_tokens(
+                &string_list(&left["mux"]["tokens"]),
+                &string_list(&right["mux"]["tokens"]),
+            ),
+            chunk_stats: diff_chunk_stats(left, right),
+            first_divergence,
+        }
+    }
+
+    /// True if no timing-relevant value differs.
+    pub fn is_identical(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Render a human-readable report.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        if let (Some(l), Some(r)) = (&self.left_path, &self.right_path) {
+            out.push_str(&format!("--- {}\n+++ {}\n", l.display(), r.display()));
+        }
+
+        if self.is_identical() {
+            out.push_str("Audit trails are identical (ignoring timestamps).\n");
+            return out;
+        }
+
+        if let Some(stage) = self.first_divergence {
+            out.push_str(&format!("First divergence: {stage}\n"));
+        }
+
+        if !self.delays.is_empty() {
+            out.push_str("\n[Delays]\n");
+            for d in &self.delays {
+                let marker = if d.rounded_changed() { "!" } else { "~" };
+                out.push_str(&format!(
+                    "  {marker} {}: {} -> {} (raw {} -> {})\n",
+                    d.source,
+                    fmt_ms(d.left_rounded_ms),
+                    fmt_ms(d.right_rounded_ms),
+                    fmt_raw(d.left_raw_ms),
+                    fmt_raw(d.right_raw_ms),
+                ));
+            }
+        }
+
+        if let Some(gs) = &self.global_shift {
+            out.push_str("\n[Global Shift]\n");
+            out.push_str(&format!(
+                "  {} -> {} (sync mode {} -> {})\n",
+                fmt_ms(gs.left_rounded_ms),
+                fmt_ms(gs.right_rounded_ms),
+                gs.left_sync_mode.as_deref().unwrap_or("-"),
+                gs.right_sync_mode.as_deref().unwrap_or("-"),
+            ));
+        }
+
+        if !self.chunk_stats.is_empty() {
+            out.push_str("\n[Correlation Chunks]\n");
+            for c in &self.chunk_stats {
+                out.push_str(&format!(
+                    "  {}: accepted {}/{} -> {}/{}, mean delay {} -> {}, match {} -> {}\n",
+                    c.source,
+                    c.left.accepted,
+                    c.left.total,
+                    c.right.accepted,
+                    c.right.total,
+                    fmt_raw(c.left.mean_delay_ms),
+                    fmt_raw(c.right.mean_delay_ms),
+                    fmt_pct(c.left.mean_match_pct),
+                    fmt_pct(c.right.mean_match_pct),
+                ));
+            }
+        }
+
+        if !self.mux_tokens.is_empty() {
+            out.push_str("\n[Mux Tokens]\n");
+            for op in &self.mux_tokens {
+                match op {
+                    TokenOp::Remove(t) => out.push_str(&format!("  - {t}\n")),
+                    TokenOp::Add(t) => out.push_str(&format!("  + {t}\n")),
+                    TokenOp::Keep(_) => {}
+                }
+            }
+        }
+
+        out.push_str(&format!("\n[All Differences] ({})\n", self.paths.len()));
+        for p in &self.paths {
+            let sign = match p.kind {
+                ChangeKind::Added => "+",
+                ChangeKind::Removed => "-",
+                ChangeKind::Changed => "~",
+            };
+            out.push_str(&format!(
+                "  {sign} {}: {} -> {}\n",
+                p.path,
+                p.left
+                    .as_ref()
+                    .map_or("<absent>".to_string(), Value::to_string),
+                p.right
+                    .as_ref()
+                    .map_or("<absent>".to_string(), Value::to_string),
+            ));
+        }
+
+        out
+    }
+}
+
+/// Read and parse an audit trail file.
+pub fn load_trail(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read audit trail {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse audit trail {}: {e}", path.display()))
+}
+
+/// Flatten a JSON tree into `path -> leaf` pairs, skipping volatile keys and
+/// the timestamped `events` log.
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    fn walk(value: &Value, path: &str, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    if VOLATILE_KEYS.contains(&k.as_str()) || (path.is_empty() && k == "events") {
+                        continue;
+                    }
+                    let child = if path.is_empty() {
+                        k.clone()
+                    } else {
+                        format!("{path}.{k}")
+                    };
+                    walk(v, &child, out);
+                }
+            }
+            Value::Array(items) => {
+                for (i, v) in items.iter().enumerate() {
+                    walk(v, &format!("{path}[{i}]"), out);
+                }
+            }
+            leaf => {
+                out.insert(path.to_string(), leaf.clone());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(value, "", &mut out);
+    out
+}
+
+/// Numeric-aware equality (`150` and `150.0` compare equal).
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => (x - y).abs() < 1e-9,
+        _ => a == b,
+    }
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn object_keys<'a>(left: &'a Value, right: &'a Value) -> BTreeSet<&'a String> {
+    left.as_object()
+        .into_iter()
+        .chain(right.as_object())
+        .flat_map(|m| m.keys())
+        .collect()
+}
+
+fn diff_delays(left: &Value, right: &Value) -> Vec<DelayDiff> {
+    let lf = &left["analysis"]["final_delays"];
+    let rf = &right["analysis"]["final_delays"];
+    let lc = &left["analysis"]["delay_calculations"];
+    let rc = &right["analysis"]["delay_calculations"];
+
+    let mut sources = object_keys(lf, rf);
+    sources.extend(object_keys(lc, rc));
+
+    sources
+        .into_iter()
+        .filter_map(|src| {
+            // Prefer final delays; fall back to the pre-shift calculation
+            let pick = |fin: &Value, calc: &Value, key: &str| -> Value {
+                let v = &fin[src.as_str()][key];
+                if v.is_null() {
+                    calc[src.as_str()]["before_global_shift"][key].clone()
+                } else {
+                    v.clone()
+                }
+            };
+            let d = DelayDiff {
+                source: src.clone(),
+                left_rounded_ms: pick(lf, lc, "rounded_ms").as_i64(),
+                right_rounded_ms: pick(rf, rc, "rounded_ms").as_i64(),
+                left_raw_ms: pick(lf, lc, "raw_ms").as_f64(),
+                right_raw_ms: pick(rf, rc, "raw_ms").as_f64(),
+            };
+            let raw_changed = match (d.left_raw_ms, d.right_raw_ms) {
+                (Some(a), Some(b)) => (a - b).abs() > 1e-6,
+                (a, b) => a.is_some() != b.is_some(),
+            };
+            (d.rounded_changed() || raw_changed).then_some(d)
+        })
+        .collect()
+}
+
+fn diff_global_shift(left: &Value, right: &Value) -> Option<GlobalShiftDiff> {
+    let l = &left["analysis"]["global_shift"];
+    let r = &right["analysis"]["global_shift"];
+    let d = GlobalShiftDiff {
+        left_rounded_ms: l["calculated_shift"]["rounded_ms"].as_i64(),
+        right_rounded_ms: r["calculated_shift"]["rounded_ms"].as_i64(),
+        left_sync_mode: l["sync_mode"].as_str().map(String::from),
+        right_sync_mode: r["sync_mode"].as_str().map(String::from),
+    };
+    (d.left_rounded_ms != d.right_rounded_ms || d.left_sync_mode != d.right_sync_mode).then_some(d)
+}
+
+fn diff_chunk_stats(left: &Value, right: &Value) -> Vec<ChunkStatsDiff> {
+    let lc = &left["analysis"]["correlations"];
+    let rc = &right["analysis"]["correlations"];
+    object_keys(lc, rc)
+        .into_iter()
+        .filter_map(|src| {
+            let stats = |v: &Value| {
+                ChunkStats::from_chunks(
+                    v[src.as_str()]["chunks"]
+                        .as_array()
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                )
+            };
+            let (l, r) = (stats(lc), stats(rc));
+            (l != r).then(|| ChunkStatsDiff {
+                source: src.clone(),
+                left: l,
+                right: r,
+            })
+        })
+        .collect()
+}
+
+/// LCS-based token diff. Returns an empty list when the token lists match.
+fn diff_tokens(left: &[String], right: &[String]) -> Vec<TokenOp> {
+    if left == right {
+        return Vec::new();
+    }
+
+    let (n, m) = (left.len(), right.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if left[i] == right[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if left[i] == right[j] {
+            ops.push(TokenOp::Keep(left[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(TokenOp::Remove(left[i].clone()));
+            i += 1;
+        } else {
+            ops.push(TokenOp::Add(right[j].clone()));
+            j += 1;
+        }
+    }
+    ops.extend(left[i..].iter().cloned().map(TokenOp::Remove));
+    ops.extend(right[j..].iter().cloned().map(TokenOp::Add));
+    ops
+}
+
+fn fmt_ms(v: Option<i64>) -> String {
+    v.map_or("-".to_string(), |ms| format!("{ms:+}ms"))
+}
+
+fn fmt_raw(v: Option<f64>) -> String {
+    v.map_or("-".to_string(), |ms| format!("{ms:+.3}ms"))
+}
+
+fn fmt_pct(v: Option<f64>) -> String {
+    v.map_or("-".to_string(), |p| format!("{p:.2}%"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn trail(delay: i64, shift: i64, tokens: &[&str]) -> Value {
+        json!({
+            "_metadata": { "created_at": "2024-01-01T00:00:00", "job_name": "ep01" },
+            "sources": { "Source 2": { "file_path": "/b.mkv", "recorded_at": "x" } },
+            "analysis": {
+                "correlations": { "Source 2": { "chunks": [
+                    { "chunk_idx": 0, "delay_ms": delay, "match_pct": 90.0, "accepted": true },
+                    { "chunk_idx": 1, "delay_ms": 999, "match_pct": 5.0, "accepted": false },
+                ]}},
+                "global_shift": {
+                    "sync_mode": "positive_only",
+                    "calculated_shift": { "raw_ms": shift as f64, "rounded_ms": shift },
+                },
+                "final_delays": { "Source 2": { "raw_ms": delay as f64, "rounded_ms": delay } },
+            },
+            "mux": { "tokens": tokens },
+            "events": [{ "timestamp": "x", "type": "info", "message": "hi" }],
+        })
+    }
+
+    #[test]
+    fn identical_trails_ignore_timestamps() {
+        let a = trail(-150, 150, &["--sync", "0:-150"]);
+        let mut b = a.clone();
+        b["_metadata"]["created_at"] = json!("2025-06-01T00:00:00");
+        b["events"] = json!([]);
+        let diff = AuditDiff::compare(&a, &b);
+        assert!(diff.is_identical());
+        assert!(diff.first_divergence.is_none());
+    }
+
+    #[test]
+    fn first_divergence_is_earliest_stage() {
+        let a = trail(-150, 150, &["--sync", "0:-150"]);
+        let b = trail(-120, 120, &["--sync", "0:-120"]);
+        let diff = AuditDiff::compare(&a, &b);
+        assert_eq!(diff.first_divergence, Some(AuditStage::Correlation));
+        assert_eq!(diff.delays.len(), 1);
+        assert_eq!(diff.delays[0].left_rounded_ms, Some(-150));
+        assert_eq!(diff.delays[0].right_rounded_ms, Some(-120));
+        assert!(diff.global_shift.is_some());
+        assert_eq!(diff.chunk_stats.len(), 1);
+        assert!(diff
+            .mux_tokens
+            .contains(&TokenOp::Remove("0:-150".to_string())));
+        assert!(diff
+            .mux_tokens
+            .contains(&TokenOp::Add("0:-120".to_string())));
+    }
+
+    #[test]
+    fn stage_from_path_respects_boundaries() {
+        assert_eq!(
+            AuditStage::from_path("mux.tokens[3]"),
+            Some(AuditStage::Mux)
+        );
+        assert_eq!(
+            AuditStage::from_path("analysis.global_shift.sync_mode"),
+            Some(AuditStage::GlobalShift)
+        );
+        assert_eq!(AuditStage::from_path("muxer.other"), None);
+        assert_eq!(AuditStage::from_path("_metadata.job_name"), None);
+    }
+
+    #[test]
+    fn chunk_stats_only_count_accepted_delays() {
+        let chunks = vec![
+            json!({ "delay_ms": 10, "match_pct": 80.0, "accepted": true }),
+            json!({ "delay_ms": 20, "match_pct": 90.0, "accepted": true }),
+            json!({ "delay_ms": 500, "match_pct": 1.0, "accepted": false }),
+        ];
+        let stats = ChunkStats::from_chunks(&chunks);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.accepted, 2);
+        assert_eq!(stats.mean_delay_ms, Some(15.0));
+        assert_eq!(stats.max_delay_ms, Some(20));
+    }
+}
@@ -1,6 +1,6 @@
 //! Pipeline audit trail — 1:1 port of `vsg_core/audit/trail.py`.
 //!
-//! Creates a JSON file in the job's temp folder that records every
+//! Creates a JSON file next to the job log that records every
 //! timing-related value at each pipeline step. Atomic writes, append-only.
 
 use std::fs;
@@ -9,19 +9,23 @@ use std::path::{Path, PathBuf};
 use chrono::Local;
 use serde_json::{json, Value};
 
+use crate::models::context_types::ToolCapabilities;
+
 /// Pipeline audit trail — `AuditTrail`
 pub struct AuditTrail {
-    temp_dir: PathBuf,
+    dir: PathBuf,
     file_path: PathBuf,
     data: Value,
 }
 
 impl AuditTrail {
     const VERSION: &'static str = "1.0";
-    const FILENAME: &'static str = "pipeline_audit_trail.json";
+    const SUFFIX: &'static str = "pipeline_audit_trail.json";
 
-    pub fn new(temp_dir: &Path, job_name: &str) -> Self {
-        let file_path = temp_dir.join(Self::FILENAME);
+    /// Create `{job_name}.pipeline_audit_trail.json` in `dir` (the log
+    /// directory, so the trail outlives the job's temp folder).
+    pub fn new(dir: &Path, temp_dir: &Path, job_name: &str) -> Self {
+        let file_path = dir.join(format!("{job_name}.{}", Self::SUFFIX));
         let data = json!({
             "_metadata": {
                 "version": Self::VERSION,
@@ -46,7 +50,7 @@ impl AuditTrail {
         });
 
         let trail = Self {
-            temp_dir: temp_dir.to_path_buf(),
+            dir: dir.to_path_buf(),
             file_path,
             data,
         };
@@ -134,6 +138,12 @@ impl AuditTrail {
         );
     }
 
+    /// Record detected tool versions and FFmpeg features.
+    pub fn record_tools(&mut self, caps: &ToolCapabilities) {
+        let value = serde_json::to_value(caps).unwrap_or_default();
+        self.record("tools", value, false);
+    }
+
     /// Record correlation chunk — `record_correlation_chunk`
     #[allow(clippy::too_many_arguments)]
     pub fn record_correlation_chunk(
@@ -300,11 +310,11 @@ impl AuditTrail {
 
     /// Atomic write to disk — `_write`
     fn write(&self) {
-        let _ = fs::create_dir_all(&self.temp_dir);
+        let _ = fs::create_dir_all(&self.dir);
         let json_str = serde_json::to_string_pretty(&self.data).unwrap_or_default();
 
         // Atomic write: temp file + rename
-        let temp_path = self.temp_dir.join("audit_.tmp");
+        let temp_path = self.file_path.with_extension("json.tmp");
         if fs::write(&temp_path, &json_str).is_ok() {
             let _ = fs::rename(&temp_path, &self.file_path);
         }
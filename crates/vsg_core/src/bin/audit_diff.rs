@@ -0,0 +1,48 @@
+//! `vsg-audit-diff` — compare two pipeline audit trails.
+//!
+//! Usage: `vsg-audit-diff [--json] <left_audit.json> <right_audit.json>`
+//!
+//! Exits 0 when the trails match, 1 when they differ and 2 on usage or
+//! read errors.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use vsg_core::audit::diff::AuditDiff;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let as_json = args.iter().any(|a| a == "--json");
+    let files: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+
+    if files.len() != 2 {
+        eprintln!("Usage: vsg-audit-diff [--json] <left_audit.json> <right_audit.json>");
+        return ExitCode::from(2);
+    }
+
+    let diff = match AuditDiff::from_files(Path::new(files[0]), Path::new(files[1])) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("[ERROR] {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if as_json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(s) => println!("{s}"),
+            Err(e) => {
+                eprintln!("[ERROR] Failed to serialize diff: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    } else {
+        print!("{}", diff.render_text());
+    }
+
+    if diff.is_identical() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
@@ -0,0 +1,730 @@
+//! Chapter generation from episode structure.
+//!
+//! When no source has chapters, the episodes of a batch still share an
+//! opening and an ending. Each episode's audio is fingerprinted, every pair
+//! of episodes is matched to find the stretches they have in common, and
+//! those are labelled Opening or Ending by position. A black-frame run that
+//! coincides with silence between them splits the body into Part A and
+//! Part B; anything before the opening is the Prologue and anything after
+//! the ending the Preview.
+//!
+//! The result is an OGM chapter file per episode, which `process_chapters`
+//! then snaps and renames like any other chapter source.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+use crate::analysis::correlation::decode::decode_audio;
+use crate::correction::stepping::boundary_refiner::find_silence_zones_rms;
+use crate::io::runner::CommandRunner;
+use crate::models::settings::AppSettings;
+
+/// Sample rate audio is decoded at for fingerprinting.
+const FP_SAMPLE_RATE: i64 = 8000;
+/// FFT window per fingerprint frame (256 ms).
+const FP_WINDOW: usize = 2048;
+/// Hop between fingerprint frames (32 ms).
+const FP_HOP: usize = 256;
+/// Frequency range the 33 energy bands (32 hash bits) are spread over.
+const FP_LOW_HZ: f64 = 300.0;
+const FP_HIGH_HZ: f64 = 3000.0;
+const FP_BANDS: usize = 33;
+
+/// Hashes more common than this in an episode carry no position information.
+const MAX_HASH_OCCURRENCES: usize = 16;
+/// Exact hash hits an alignment needs before it is examined.
+const MIN_OFFSET_VOTES: usize = 8;
+/// Best alignments examined per episode pair.
+const CANDIDATE_OFFSETS: usize = 4;
+/// Smoothed bit error rate below which aligned frames count as the same audio.
+const MATCH_BER: f64 = 0.35;
+/// Frames the bit error rate is averaged over (~1 s).
+const BER_SMOOTH_FRAMES: usize = 31;
+
+/// Longest recurring segment still treated as an opening or ending.
+const MAX_THEME_S: f64 = 150.0;
+/// Matches closer than this are one segment.
+const MERGE_GAP_S: f64 = 3.0;
+/// Shortest Prologue, Part or Preview worth its own chapter.
+const MIN_SECTION_S: f64 = 5.0;
+/// Shortest silence that can mark the Part A / Part B break.
+const BREAK_MIN_SILENCE_MS: f64 = 300.0;
+/// Slack when checking that a black run and a silence coincide.
+const BREAK_TOLERANCE_S: f64 = 0.5;
+
+static BLACKDETECT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"black_start:\s*([0-9.]+)\s+black_end:\s*([0-9.]+)").unwrap());
+
+// ─── Fingerprinting ──────────────────────────────────────────────────────────
+
+/// Seconds covered by `frames` fingerprint frames.
+fn frames_to_s(frames: usize) -> f64 {
+    (frames * FP_HOP) as f64 / FP_SAMPLE_RATE as f64
+}
+
+/// Fingerprint frames covering `seconds`.
+fn s_to_frames(seconds: f64) -> usize {
+    (seconds.max(0.0) * FP_SAMPLE_RATE as f64 / FP_HOP as f64).round() as usize
+}
+
+/// Compute a 32-bit spectral fingerprint per 32 ms frame of mono PCM.
+///
+/// Each bit is the sign of how the energy difference between two adjacent
+/// bands changed since the previous frame, which survives re-encoding and
+/// level changes. Digital silence hashes to zero.
+pub fn fingerprint(pcm: &[f32]) -> Vec<u32> {
+    if pcm.len() < FP_WINDOW {
+        return Vec::new();
+    }
+
+    let fft: Arc<dyn Fft<f32>> = FftPlanner::new().plan_fft_forward(FP_WINDOW);
+    let window: Vec<f32> = (0..FP_WINDOW)
+        .map(|n| {
+            let x = std::f32::consts::PI * 2.0 * n as f32 / FP_WINDOW as f32;
+            0.5 - 0.5 * x.cos()
+        })
+        .collect();
+
+    let bin_of = |hz: f64| (hz * FP_WINDOW as f64 / FP_SAMPLE_RATE as f64).round() as usize;
+    let ratio = (FP_HIGH_HZ / FP_LOW_HZ).powf(1.0 / FP_BANDS as f64);
+    let edges: Vec<usize> = (0..=FP_BANDS)
+        .map(|b| bin_of(FP_LOW_HZ * ratio.powi(b as i32)))
+        .collect();
+
+    let mut buffer = vec![Complex::new(0.0f32, 0.0); FP_WINDOW];
+    let mut previous = [0.0f64; FP_BANDS];
+    let mut hashes = Vec::with_capacity((pcm.len() - FP_WINDOW) / FP_HOP + 1);
+
+    let mut start = 0;
+    while start + FP_WINDOW <= pcm.len() {
+        for (slot, (&sample, &w)) in buffer
+            .iter_mut()
+            .zip(pcm[start..start + FP_WINDOW].iter().zip(&window))
+        {
+            *slot = Complex::new(sample * w, 0.0);
+        }
+        fft.process(&mut buffer);
+
+        let mut energies = [0.0f64; FP_BANDS];
+        for (b, energy) in energies.iter_mut().enumerate() {
+            let hi = edges[b + 1].max(edges[b] + 1);
+            *energy = buffer[edges[b]..hi]
+                .iter()
+                .map(|c| c.norm_sqr() as f64)
+                .sum();
+        }
+
+        let mut hash = 0u32;
+        for b in 0..FP_BANDS - 1 {
+            let now = energies[b] - energies[b + 1];
+            let before = previous[b] - previous[b + 1];
+            if now - before > 0.0 {
+                hash |= 1 << b;
+            }
+        }
+        hashes.push(hash);
+        previous = energies;
+        start += FP_HOP;
+    }
+
+    hashes
+}
+
+// ─── Matching ────────────────────────────────────────────────────────────────
+
+/// A stretch of audio shared by two episodes, in fingerprint frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentMatch {
+    /// `start..end` in the first episode.
+    pub a: (usize, usize),
+    /// `start..end` in the second episode.
+    pub b: (usize, usize),
+}
+
+/// Find stretches of at least `min_frames` that two fingerprints share.
+///
+/// Exact hash hits vote for an alignment; the strongest alignments are then
+/// walked frame by frame and kept where the smoothed bit error rate stays low.
+pub fn recurring_segments(a: &[u32], b: &[u32], min_frames: usize) -> Vec<SegmentMatch> {
+    let mut index: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (j, &hash) in b.iter().enumerate() {
+        if hash != 0 {
+            index.entry(hash).or_default().push(j);
+        }
+    }
+    index.retain(|_, positions| positions.len() <= MAX_HASH_OCCURRENCES);
+
+    let mut votes: HashMap<isize, usize> = HashMap::new();
+    for (i, hash) in a.iter().enumerate() {
+        for &j in index.get(hash).into_iter().flatten() {
+            *votes.entry(j as isize - i as isize).or_default() += 1;
+        }
+    }
+    let mut ranked: Vec<(isize, usize)> = votes
+        .into_iter()
+        .filter(|&(_, count)| count >= MIN_OFFSET_VOTES)
+        .collect();
+    ranked.sort_by(|x, y| y.1.cmp(&x.1).then(x.0.cmp(&y.0)));
+
+    // Neighbouring offsets are the same alignment off by a frame
+    let mut offsets: Vec<isize> = Vec::new();
+    for (offset, _) in ranked {
+        if offsets.len() == CANDIDATE_OFFSETS {
+            break;
+        }
+        if offsets.iter().all(|&o| (o - offset).abs() > 2) {
+            offsets.push(offset);
+        }
+    }
+
+    let mut matches = Vec::new();
+    for offset in offsets {
+        let first = (-offset).max(0) as usize;
+        let last = (a.len() as isize).min(b.len() as isize - offset).max(0) as usize;
+        if last <= first {
+            continue;
+        }
+
+        // Silence on both sides says nothing about whether the audio matches
+        let errors: Vec<f64> = (first..last)
+            .map(|i| {
+                let (x, y) = (a[i], b[(i as isize + offset) as usize]);
+                if x == 0 && y == 0 {
+                    0.5
+                } else {
+                    (x ^ y).count_ones() as f64 / 32.0
+                }
+            })
+            .collect();
+
+        let mut prefix = vec![0.0; errors.len() + 1];
+        for (k, e) in errors.iter().enumerate() {
+            prefix[k + 1] = prefix[k] + e;
+        }
+        let half = BER_SMOOTH_FRAMES / 2;
+        let smoothed = |k: usize| {
+            let lo = k.saturating_sub(half);
+            let hi = (k + half + 1).min(errors.len());
+            (prefix[hi] - prefix[lo]) / (hi - lo) as f64
+        };
+
+        let mut run_start: Option<usize> = None;
+        for k in 0..=errors.len() {
+            let matched = k < errors.len() && smoothed(k) < MATCH_BER;
+            match (matched, run_start) {
+                (true, None) => run_start = Some(k),
+                (false, Some(start)) => {
+                    run_start = None;
+                    if k - start >= min_frames {
+                        let (s, e) = (first + start, first + k);
+                        matches.push(SegmentMatch {
+                            a: (s, e),
+                            b: (
+                                (s as isize + offset) as usize,
+                                (e as isize + offset) as usize,
+                            ),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    matches
+}
+
+/// Merge overlapping `(start, end)` ranges and ones closer than `gap`.
+fn merge_ranges(mut ranges: Vec<(f64, f64)>, gap: f64) -> Vec<(f64, f64)> {
+    ranges.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + gap => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+// ─── Structure ───────────────────────────────────────────────────────────────
+
+/// A `(start, end)` range in seconds.
+pub type TimeRange = (f64, f64);
+
+/// Where an episode's sections fall, in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpisodeStructure {
+    pub duration_s: f64,
+    pub opening: Option<TimeRange>,
+    pub ending: Option<TimeRange>,
+    pub part_break: Option<f64>,
+}
+
+/// A generated chapter start and title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedChapter {
+    pub start_s: f64,
+    pub name: String,
+}
+
+/// Pick the opening and ending from an episode's recurring segments.
+///
+/// The opening is the first one starting in the first half, the ending the
+/// last one starting in the second half.
+pub fn classify_themes(
+    themes: &[TimeRange],
+    duration_s: f64,
+) -> (Option<TimeRange>, Option<TimeRange>) {
+    let midpoint = duration_s / 2.0;
+    let opening = themes.iter().copied().find(|t| t.0 < midpoint);
+    let ending = themes.iter().copied().rev().find(|t| t.0 >= midpoint);
+    (opening, ending)
+}
+
+/// Find the Part A / Part B break inside `body`: a black run that overlaps
+/// a silence, in the middle 60% of the body, closest to its midpoint.
+/// Part B starts where the black run ends.
+pub fn find_part_break(
+    black: &[(f64, f64)],
+    silences: &[(f64, f64)],
+    body: (f64, f64),
+) -> Option<f64> {
+    let length = body.1 - body.0;
+    if length < 2.0 * MIN_SECTION_S {
+        return None;
+    }
+    let (lo, hi) = (body.0 + 0.2 * length, body.0 + 0.8 * length);
+    let midpoint = body.0 + length / 2.0;
+
+    black
+        .iter()
+        .filter(|b| b.1 >= lo && b.0 <= hi)
+        .filter(|b| {
+            silences
+                .iter()
+                .any(|s| s.0 <= b.1 + BREAK_TOLERANCE_S && s.1 >= b.0 - BREAK_TOLERANCE_S)
+        })
+        .map(|b| b.1)
+        .min_by(|x, y| (x - midpoint).abs().total_cmp(&(y - midpoint).abs()))
+}
+
+/// Lay out Prologue / Opening / Part A / Part B / Ending / Preview chapters.
+///
+/// Returns nothing when neither an opening nor an ending was found.
+pub fn place_chapters(structure: &EpisodeStructure) -> Vec<GeneratedChapter> {
+    if structure.opening.is_none() && structure.ending.is_none() {
+        return Vec::new();
+    }
+
+    let mut chapters = Vec::new();
+    let mut push = |start_s: f64, name: &str| {
+        chapters.push(GeneratedChapter {
+            start_s,
+            name: name.to_string(),
+        })
+    };
+
+    let body_start = match structure.opening {
+        Some((start, end)) if start >= MIN_SECTION_S => {
+            push(0.0, "Prologue");
+            push(start, "Opening");
+            end
+        }
+        Some((_, end)) => {
+            push(0.0, "Opening");
+            end
+        }
+        None => 0.0,
+    };
+    let body_end = structure
+        .ending
+        .map_or(structure.duration_s, |(start, _)| start);
+
+    if body_end - body_start >= MIN_SECTION_S {
+        push(body_start, "Part A");
+        if let Some(part_break) = structure.part_break {
+            push(part_break, "Part B");
+        }
+    }
+
+    if let Some((start, end)) = structure.ending {
+        push(start, "Ending");
+        if structure.duration_s - end >= MIN_SECTION_S {
+            push(end, "Preview");
+        }
+    }
+
+    chapters
+}
+
+// ─── Per-episode analysis ────────────────────────────────────────────────────
+
+/// Fingerprint and silences of one episode.
+struct EpisodeAnalysis {
+    path: String,
+    hashes: Vec<u32>,
+    duration_s: f64,
+    silences: Vec<(f64, f64)>,
+}
+
+/// Decode an episode's first audio track and analyse it.
+fn analyze_episode(
+    path: &str,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+    settings: &AppSettings,
+) -> Result<EpisodeAnalysis, String> {
+    let pcm = decode_audio(path, 0, FP_SAMPLE_RATE, false, runner, tool_paths)?;
+    let duration_s = pcm.len() as f64 / FP_SAMPLE_RATE as f64;
+
+    let pcm_i32: Vec<i32> = pcm
+        .iter()
+        .map(|&s| (s.clamp(-1.0, 1.0) as f64 * i32::MAX as f64) as i32)
+        .collect();
+    let silences = find_silence_zones_rms(
+        &pcm_i32,
+        FP_SAMPLE_RATE as i32,
+        0.0,
+        duration_s,
+        settings.chapter_gen_silence_db,
+        BREAK_MIN_SILENCE_MS,
+    )
+    .into_iter()
+    .map(|z| (z.start_s, z.end_s))
+    .collect();
+
+    Ok(EpisodeAnalysis {
+        path: path.to_string(),
+        hashes: fingerprint(&pcm),
+        duration_s,
+        silences,
+    })
+}
+
+/// Black-frame runs in the first video track between `range` seconds.
+fn detect_black_frames(
+    path: &str,
+    range: (f64, f64),
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Vec<(f64, f64)> {
+    let start = format!("{:.3}", range.0);
+    let end = format!("{:.3}", range.1);
+    let cmd = [
+        "ffmpeg",
+        "-nostdin",
+        "-hide_banner",
+        "-nostats",
+        "-ss",
+        &start,
+        "-to",
+        &end,
+        "-i",
+        path,
+        "-map",
+        "0:v:0",
+        "-vf",
+        "blackdetect=d=0.1:pix_th=0.10",
+        "-an",
+        "-f",
+        "null",
+        "-",
+    ];
+    let Ok(output) = runner.run(&cmd, tool_paths) else {
+        runner.log_message("[WARN] Black frame detection failed; Part B will be skipped.");
+        return Vec::new();
+    };
+
+    // Input seeking restarts timestamps at zero
+    BLACKDETECT_RE
+        .captures_iter(&output)
+        .filter_map(|c| {
+            let black_start: f64 = c[1].parse().ok()?;
+            let black_end: f64 = c[2].parse().ok()?;
+            Some((range.0 + black_start, range.0 + black_end))
+        })
+        .collect()
+}
+
+/// Format seconds as an OGM chapter time (`HH:MM:SS.mmm`).
+fn fmt_ogm_time(seconds: f64) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hh, mm) = (total_ms / 3_600_000, (total_ms / 60_000) % 60);
+    let (ss, ms) = ((total_ms / 1000) % 60, total_ms % 1000);
+    format!("{hh:02}:{mm:02}:{ss:02}.{ms:03}")
+}
+
+/// Render chapters as an OGM simple chapter file.
+fn write_ogm_chapters(chapters: &[GeneratedChapter]) -> String {
+    let mut out = String::new();
+    for (i, chapter) in chapters.iter().enumerate() {
+        let n = i + 1;
+        out.push_str(&format!(
+            "CHAPTER{n:02}={}\nCHAPTER{n:02}NAME={}\n",
+            fmt_ogm_time(chapter.start_s),
+            chapter.name
+        ));
+    }
+    out
+}
+
+// ─── Public API ──────────────────────────────────────────────────────────────
+
+/// Generate chapters for every episode of a batch — `generate_batch_chapters`
+///
+/// `episodes` are the Source 1 files. Returns the generated OGM chapter
+/// file per episode; episodes without a recognizable opening or ending are
+/// left out.
+pub fn generate_batch_chapters(
+    episodes: &[String],
+    out_dir: &Path,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+    settings: &AppSettings,
+) -> HashMap<String, String> {
+    let mut generated = HashMap::new();
+    if episodes.len() < 2 {
+        runner.log_message(
+            "[Chapters] Chapter generation needs at least two episodes in the batch; skipping.",
+        );
+        return generated;
+    }
+
+    runner.log_message(&format!(
+        "[Chapters] Fingerprinting {} episodes for chapter generation...",
+        episodes.len()
+    ));
+    let analyses: Vec<EpisodeAnalysis> = episodes
+        .iter()
+        .filter_map(
+            |path| match analyze_episode(path, runner, tool_paths, settings) {
+                Ok(analysis) => Some(analysis),
+                Err(e) => {
+                    runner.log_message(&format!(
+                        "[WARN] Skipping {path} for chapter generation: {e}"
+                    ));
+                    None
+                }
+            },
+        )
+        .collect();
+
+    let min_frames = s_to_frames(settings.chapter_gen_min_theme_s);
+    let mut shared: Vec<Vec<(f64, f64)>> = vec![Vec::new(); analyses.len()];
+    for i in 0..analyses.len() {
+        for j in i + 1..analyses.len() {
+            for m in recurring_segments(&analyses[i].hashes, &analyses[j].hashes, min_frames) {
+                shared[i].push((frames_to_s(m.a.0), frames_to_s(m.a.1)));
+                shared[j].push((frames_to_s(m.b.0), frames_to_s(m.b.1)));
+            }
+        }
+    }
+
+    if let Err(e) = std::fs::create_dir_all(out_dir) {
+        runner.log_message(&format!(
+            "[ERROR] Could not create {}: {e}",
+            out_dir.display()
+        ));
+        return generated;
+    }
+
+    for (episode, ranges) in analyses.iter().zip(shared) {
+        let name = Path::new(&episode.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let themes: Vec<(f64, f64)> = merge_ranges(ranges, MERGE_GAP_S)
+            .into_iter()
+            .filter(|t| {
+                let length = t.1 - t.0;
+                length >= settings.chapter_gen_min_theme_s && length <= MAX_THEME_S
+            })
+            .collect();
+        let (opening, ending) = classify_themes(&themes, episode.duration_s);
+        if opening.is_none() && ending.is_none() {
+            runner.log_message(&format!(
+                "[Chapters] No recurring opening or ending found in {name}."
+            ));
+            continue;
+        }
+
+        let body = (
+            opening.map_or(0.0, |t| t.1),
+            ending.map_or(episode.duration_s, |t| t.0),
+        );
+        let black = if body.1 - body.0 >= 2.0 * MIN_SECTION_S {
+            detect_black_frames(&episode.path, body, runner, tool_paths)
+        } else {
+            Vec::new()
+        };
+        let structure = EpisodeStructure {
+            duration_s: episode.duration_s,
+            opening,
+            ending,
+            part_break: find_part_break(&black, &episode.silences, body),
+        };
+
+        let chapters = place_chapters(&structure);
+        runner.log_message(&format!("[Chapters] Generated chapters for {name}:"));
+        for chapter in &chapters {
+            runner.log_message(&format!(
+                "  - {} {}",
+                fmt_ogm_time(chapter.start_s),
+                chapter.name
+            ));
+        }
+
+        let stem = Path::new(&episode.path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let out_path = out_dir.join(format!("{stem}_generated_chapters.txt"));
+        match std::fs::write(&out_path, write_ogm_chapters(&chapters)) {
+            Ok(()) => {
+                generated.insert(episode.path.clone(), out_path.to_string_lossy().to_string());
+            }
+            Err(e) => runner.log_message(&format!(
+                "[ERROR] Could not write {}: {e}",
+                out_path.display()
+            )),
+        }
+    }
+
+    runner.log_message(&format!(
+        "[Chapters] Generated chapters for {}/{} episodes.",
+        generated.len(),
+        episodes.len()
+    ));
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random hashes (xorshift).
+    fn noise_hashes(seed: u32, len: usize) -> Vec<u32> {
+        let mut x = seed;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_silence_is_zero() {
+        let tone: Vec<f32> = (0..FP_SAMPLE_RATE as usize)
+            .map(|n| {
+                let t = n as f32 / FP_SAMPLE_RATE as f32;
+                (t * 440.0 * std::f32::consts::TAU).sin() * (1.0 + (t * 3.0).sin())
+            })
+            .collect();
+        let hashes = fingerprint(&tone);
+        assert_eq!(hashes.len(), (tone.len() - FP_WINDOW) / FP_HOP + 1);
+        assert_eq!(hashes, fingerprint(&tone));
+        assert!(hashes.iter().any(|&h| h != 0));
+
+        let silence = vec![0.0f32; FP_SAMPLE_RATE as usize];
+        assert!(fingerprint(&silence).iter().all(|&h| h == 0));
+    }
+
+    #[test]
+    fn recurring_segments_finds_shared_theme() {
+        // The same 40 s theme at 10 s in one episode and 95 s in the other
+        let theme = noise_hashes(7, s_to_frames(40.0));
+        let mut a = noise_hashes(1, s_to_frames(600.0));
+        let mut b = noise_hashes(2, s_to_frames(600.0));
+        let (at_a, at_b) = (s_to_frames(10.0), s_to_frames(95.0));
+        a[at_a..at_a + theme.len()].copy_from_slice(&theme);
+        b[at_b..at_b + theme.len()].copy_from_slice(&theme);
+
+        let matches = recurring_segments(&a, &b, s_to_frames(30.0));
+        assert_eq!(matches.len(), 1);
+        let m = matches[0];
+        assert!((frames_to_s(m.a.0) - 10.0).abs() < 1.0);
+        assert!((frames_to_s(m.a.1) - 50.0).abs() < 1.0);
+        assert!((frames_to_s(m.b.0) - 95.0).abs() < 1.0);
+
+        assert!(recurring_segments(&a, &noise_hashes(3, a.len()), 1).is_empty());
+    }
+
+    #[test]
+    fn merge_ranges_joins_close_ranges() {
+        let merged = merge_ranges(vec![(50.0, 80.0), (10.0, 40.0), (41.0, 45.0)], 3.0);
+        assert_eq!(merged, vec![(10.0, 45.0), (50.0, 80.0)]);
+    }
+
+    #[test]
+    fn part_break_needs_black_and_silence() {
+        let body = (90.0, 1290.0);
+        let silences = [(600.0, 601.5), (1000.0, 1001.0)];
+        // Black at 300 s has no silence; the one at 600 s does
+        let black = [(300.0, 300.5), (599.8, 601.0)];
+        assert_eq!(find_part_break(&black, &silences, body), Some(601.0));
+        assert_eq!(find_part_break(&black[..1], &silences, body), None);
+    }
+
+    #[test]
+    fn place_chapters_full_structure() {
+        let structure = EpisodeStructure {
+            duration_s: 1420.0,
+            opening: Some((60.0, 150.0)),
+            ending: Some((1290.0, 1380.0)),
+            part_break: Some(700.0),
+        };
+        let chapters = place_chapters(&structure);
+        let layout: Vec<(f64, &str)> = chapters
+            .iter()
+            .map(|c| (c.start_s, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            layout,
+            [
+                (0.0, "Prologue"),
+                (60.0, "Opening"),
+                (150.0, "Part A"),
+                (700.0, "Part B"),
+                (1290.0, "Ending"),
+                (1380.0, "Preview"),
+            ]
+        );
+        assert!(write_ogm_chapters(&chapters)
+            .contains("CHAPTER02=00:01:00.000\nCHAPTER02NAME=Opening\n"));
+    }
+
+    #[test]
+    fn place_chapters_opening_at_start_without_ending() {
+        let structure = EpisodeStructure {
+            duration_s: 1400.0,
+            opening: Some((0.5, 90.0)),
+            ending: None,
+            part_break: None,
+        };
+        let names: Vec<String> = place_chapters(&structure)
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, ["Opening", "Part A"]);
+
+        let (opening, ending) = classify_themes(&[(1300.0, 1390.0)], 1400.0);
+        assert_eq!((opening, ending), (None, Some((1300.0, 1390.0))));
+        assert!(place_chapters(&EpisodeStructure {
+            duration_s: 1400.0,
+            opening: None,
+            ending: None,
+            part_break: None,
+        })
+        .is_empty());
+    }
+}
@@ -1,8 +1,28 @@
 //! Keyframe probing — 1:1 port of `vsg_core/chapters/keyframes.py`.
+//!
+//! Keyframes are read from Matroska Cues when the file has a usable cue
+//! index, otherwise from a full ffprobe packet scan. Either way the result
+//! is cached on disk under `temp_root/keyframe_cache`, keyed by the file
+//! hash, so chapter snapping and the stepping boundary refiner only pay
+//! for the scan once per file.
 
 use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 
 use crate::io::runner::CommandRunner;
+use crate::mux::provenance::source_hash;
+
+/// Cues spaced wider than this on average may only index clusters (FFmpeg
+/// writes one per cluster, about every 5 s) rather than every keyframe, so
+/// such files are scanned with ffprobe instead.
+const MAX_CUE_SPACING_NS: i64 = 2_000_000_000;
+
+/// Largest master element read into memory while looking for Cues.
+const MAX_ELEMENT_BYTES: u64 = 256 * 1024 * 1024;
 
 /// Probe keyframes from video using ffprobe — `probe_keyframes_ns`
 ///
@@ -29,8 +49,8 @@ pub fn probe_keyframes_ns(
     );
 
     let out = match out {
-        Some(o) => o,
-        None => {
+        Ok(o) => o,
+        Err(_) => {
             runner.log_message("[WARN] ffprobe for keyframes produced no output.");
             return Vec::new();
         }
@@ -67,8 +87,474 @@ pub fn probe_keyframes_ns(
 
     kfs_ns.sort();
     runner.log_message(&format!(
-        "[Chapters] Found {} keyframes for snapping.",
+        "[Keyframes] Found {} keyframes by packet scan.",
         kfs_ns.len()
     ));
     kfs_ns
 }
+
+// ─── Keyframe cache ──────────────────────────────────────────────────────────
+
+/// Cached keyframe list for one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KeyframeCacheEntry {
+    /// `"cues"` or `"ffprobe"`.
+    source: String,
+    keyframes_ns: Vec<i64>,
+}
+
+/// Directory holding cached keyframe lists.
+pub fn keyframe_cache_dir(temp_root: &str) -> PathBuf {
+    PathBuf::from(temp_root).join("keyframe_cache")
+}
+
+/// Keyframes of the first video track, from cache, Cues or ffprobe.
+///
+/// Returns sorted keyframe timestamps in nanoseconds, or an empty list if
+/// none could be found. Results are only cached when non-empty.
+pub fn cached_keyframes_ns(
+    video_path: &str,
+    temp_root: &str,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Vec<i64> {
+    let cache_path = match source_hash(Path::new(video_path)) {
+        Ok(hash) => Some(keyframe_cache_dir(temp_root).join(format!("{}.json", &hash[..16]))),
+        Err(e) => {
+            runner.log_message(&format!(
+                "[Keyframes] Cache disabled, cannot hash file: {e}"
+            ));
+            None
+        }
+    };
+
+    if let Some(entry) = cache_path
+        .as_ref()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str::<KeyframeCacheEntry>(&s).ok())
+    {
+        runner.log_message(&format!(
+            "[Keyframes] Loaded {} keyframes from cache ({}).",
+            entry.keyframes_ns.len(),
+            entry.source
+        ));
+        return entry.keyframes_ns;
+    }
+
+    let entry = match read_cue_keyframes_ns(Path::new(video_path)) {
+        Ok(keyframes_ns) => {
+            runner.log_message(&format!(
+                "[Keyframes] Read {} keyframes from Matroska Cues.",
+                keyframes_ns.len()
+            ));
+            KeyframeCacheEntry {
+                source: "cues".to_string(),
+                keyframes_ns,
+            }
+        }
+        Err(reason) => {
+            runner.log_message(&format!(
+                "[Keyframes] Cues not usable ({reason}); scanning packets with ffprobe."
+            ));
+            KeyframeCacheEntry {
+                source: "ffprobe".to_string(),
+                keyframes_ns: probe_keyframes_ns(video_path, runner, tool_paths),
+            }
+        }
+    };
+
+    if let Some(path) = cache_path.filter(|_| !entry.keyframes_ns.is_empty()) {
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string(&entry).map_err(|e| e.to_string()))
+            .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            runner.log_message(&format!("[Keyframes] Could not write cache: {e}"));
+        }
+    }
+    entry.keyframes_ns
+}
+
+// ─── Matroska Cues ───────────────────────────────────────────────────────────
+
+const ID_EBML: u32 = 0x1A45_DFA3;
+const ID_SEGMENT: u32 = 0x1853_8067;
+const ID_SEEK_HEAD: u32 = 0x114D_9B74;
+const ID_SEEK: u32 = 0x4DBB;
+const ID_SEEK_ID: u32 = 0x53AB;
+const ID_SEEK_POSITION: u32 = 0x53AC;
+const ID_INFO: u32 = 0x1549_A966;
+const ID_TIMESTAMP_SCALE: u32 = 0x2A_D7B1;
+const ID_TRACKS: u32 = 0x1654_AE6B;
+const ID_TRACK_ENTRY: u32 = 0xAE;
+const ID_TRACK_NUMBER: u32 = 0xD7;
+const ID_TRACK_TYPE: u32 = 0x83;
+const ID_CUES: u32 = 0x1C53_BB6B;
+const ID_CUE_POINT: u32 = 0xBB;
+const ID_CUE_TIME: u32 = 0xB3;
+const ID_CUE_TRACK_POSITIONS: u32 = 0xB7;
+const ID_CUE_TRACK: u32 = 0xF7;
+const ID_CLUSTER: u32 = 0x1F43_B675;
+
+/// Matroska track type of video tracks.
+const TRACK_TYPE_VIDEO: u64 = 1;
+
+/// Decode an EBML variable-length integer — `(value, length)`.
+///
+/// IDs keep their length marker bits, sizes drop them. A size with every
+/// value bit set means "unknown" and is returned as `u64::MAX`.
+fn read_vint(data: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    if first == 0 {
+        return None;
+    }
+    let len = first.leading_zeros() as usize + 1;
+    if data.len() < len {
+        return None;
+    }
+    let marker = 0x80u8 >> (len - 1);
+    let mut value = u64::from(if keep_marker { first } else { first & !marker });
+    let mut all_ones = !keep_marker && value == u64::from(marker - 1);
+    for &b in &data[1..len] {
+        value = (value << 8) | u64::from(b);
+        all_ones &= b == 0xFF;
+    }
+    Some((if all_ones { u64::MAX } else { value }, len))
+}
+
+/// Parse an element header — `(id, size, header length)`.
+fn element_header(data: &[u8]) -> Option<(u32, u64, usize)> {
+    let (id, id_len) = read_vint(data, true)?;
+    if id_len > 4 {
+        return None;
+    }
+    let (size, size_len) = read_vint(&data[id_len..], false)?;
+    Some((id as u32, size, id_len + size_len))
+}
+
+/// Split a master element's payload into `(id, payload)` children.
+fn children(mut data: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut out = Vec::new();
+    while let Some((id, size, header)) = element_header(data) {
+        let end = match usize::try_from(size)
+            .ok()
+            .and_then(|s| s.checked_add(header))
+        {
+            Some(end) if end <= data.len() => end,
+            _ => break,
+        };
+        out.push((id, &data[header..end]));
+        data = &data[end..];
+    }
+    out
+}
+
+/// Big-endian unsigned integer payload.
+fn read_uint(data: &[u8]) -> u64 {
+    data.iter().fold(0, |acc, &b| (acc << 8) | u64::from(b))
+}
+
+/// Read the element header at `pos` — `(id, size, header length)`.
+fn header_at(file: &mut File, pos: u64) -> Result<(u32, u64, usize), String> {
+    let mut buf = [0u8; 12];
+    file.seek(SeekFrom::Start(pos)).map_err(|e| e.to_string())?;
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..]).map_err(|e| e.to_string())? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    element_header(&buf[..filled]).ok_or_else(|| format!("bad element header at byte {pos}"))
+}
+
+/// Read the payload of the element whose header starts at `pos`.
+fn payload_at(file: &mut File, pos: u64, header: usize, size: u64) -> Result<Vec<u8>, String> {
+    if size > MAX_ELEMENT_BYTES {
+        return Err(format!("element at byte {pos} is too large"));
+    }
+    let mut data = vec![0u8; size as usize];
+    file.seek(SeekFrom::Start(pos + header as u64))
+        .map_err(|e| e.to_string())?;
+    file.read_exact(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+/// Read keyframes of the first video track from the Matroska Cues.
+///
+/// Top-level elements are found by walking the segment up to the first
+/// cluster and then through the SeekHead, which is where muxers point to
+/// Cues written after the clusters. Fails when the file is not Matroska,
+/// has no Cues, or its Cues are too sparse to list every keyframe.
+pub fn read_cue_keyframes_ns(path: &Path) -> Result<Vec<i64>, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+
+    let (id, size, header) = header_at(&mut file, 0)?;
+    if id != ID_EBML {
+        return Err("not a Matroska file".to_string());
+    }
+    let segment_pos = header as u64 + size;
+    let (id, size, header) = header_at(&mut file, segment_pos)?;
+    if id != ID_SEGMENT {
+        return Err("no Matroska segment".to_string());
+    }
+    let segment_start = segment_pos + header as u64;
+    let segment_end = segment_start.saturating_add(size).min(file_len);
+
+    let mut found: HashMap<u32, Vec<u8>> = HashMap::new();
+    let mut seeks: HashMap<u32, u64> = HashMap::new();
+    let mut pos = segment_start;
+    while pos < segment_end {
+        let (id, size, header) = header_at(&mut file, pos)?;
+        if id == ID_CLUSTER || size == u64::MAX {
+            break;
+        }
+        match id {
+            ID_SEEK_HEAD => {
+                let data = payload_at(&mut file, pos, header, size)?;
+                for (_, seek) in children(&data).into_iter().filter(|(id, _)| *id == ID_SEEK) {
+                    let fields = children(seek);
+                    let seek_id = fields.iter().find(|(id, _)| *id == ID_SEEK_ID);
+                    let seek_pos = fields.iter().find(|(id, _)| *id == ID_SEEK_POSITION);
+                    if let (Some((_, seek_id)), Some((_, seek_pos))) = (seek_id, seek_pos) {
+                        seeks
+                            .entry(read_uint(seek_id) as u32)
+                            .or_insert(read_uint(seek_pos));
+                    }
+                }
+            }
+            ID_INFO | ID_TRACKS | ID_CUES => {
+                found.insert(id, payload_at(&mut file, pos, header, size)?);
+            }
+            _ => {}
+        }
+        pos += header as u64 + size;
+    }
+
+    for wanted in [ID_INFO, ID_TRACKS, ID_CUES] {
+        if found.contains_key(&wanted) {
+            continue;
+        }
+        if let Some(&offset) = seeks.get(&wanted) {
+            let pos = segment_start + offset;
+            let (id, size, header) = header_at(&mut file, pos)?;
+            if id == wanted && size != u64::MAX {
+                found.insert(id, payload_at(&mut file, pos, header, size)?);
+            }
+        }
+    }
+
+    let timestamp_scale = found
+        .get(&ID_INFO)
+        .and_then(|info| {
+            children(info)
+                .into_iter()
+                .find(|(id, _)| *id == ID_TIMESTAMP_SCALE)
+                .map(|(_, v)| read_uint(v))
+        })
+        .unwrap_or(1_000_000) as i64;
+
+    let tracks = found.get(&ID_TRACKS).ok_or("no Tracks element")?;
+    let video_track = children(tracks)
+        .into_iter()
+        .filter(|(id, _)| *id == ID_TRACK_ENTRY)
+        .find_map(|(_, entry)| {
+            let fields = children(entry);
+            let field = |wanted: u32| {
+                fields
+                    .iter()
+                    .find(|(id, _)| *id == wanted)
+                    .map(|(_, v)| read_uint(v))
+            };
+            (field(ID_TRACK_TYPE) == Some(TRACK_TYPE_VIDEO))
+                .then(|| field(ID_TRACK_NUMBER))
+                .flatten()
+        })
+        .ok_or("no video track")?;
+
+    let cues = found.get(&ID_CUES).ok_or("no Cues element")?;
+    let mut keyframes_ns: Vec<i64> = children(cues)
+        .into_iter()
+        .filter(|(id, _)| *id == ID_CUE_POINT)
+        .filter_map(|(_, point)| {
+            let fields = children(point);
+            let time = fields
+                .iter()
+                .find(|(id, _)| *id == ID_CUE_TIME)
+                .map(|(_, v)| read_uint(v))?;
+            let indexes_video = fields
+                .iter()
+                .filter(|(id, _)| *id == ID_CUE_TRACK_POSITIONS)
+                .any(|(_, positions)| {
+                    children(positions)
+                        .iter()
+                        .any(|(id, v)| *id == ID_CUE_TRACK && read_uint(v) == video_track)
+                });
+            // A corrupt CueTime that overflows is skipped, not wrapped
+            let time_ns = i64::try_from(time).ok()?.checked_mul(timestamp_scale)?;
+            indexes_video.then_some(time_ns)
+        })
+        .collect();
+    keyframes_ns.sort_unstable();
+    keyframes_ns.dedup();
+
+    match keyframes_ns.as_slice() {
+        [] | [_] => Err("no cues for the video track".to_string()),
+        [first, .., last]
+            if (last - first) / (keyframes_ns.len() as i64 - 1) > MAX_CUE_SPACING_NS =>
+        {
+            Err("cues are too sparse".to_string())
+        }
+        _ => Ok(keyframes_ns),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode one element with a fixed 8-byte size.
+    fn element(id: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out: Vec<u8> = id
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|&b| b == 0)
+            .collect();
+        out.push(0x01);
+        out.extend_from_slice(&(payload.len() as u64).to_be_bytes()[1..]);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn uint(id: u32, value: u64) -> Vec<u8> {
+        element(id, &value.to_be_bytes())
+    }
+
+    fn cue_point(time: u64, track: u64) -> Vec<u8> {
+        let positions = element(ID_CUE_TRACK_POSITIONS, &uint(ID_CUE_TRACK, track));
+        element(ID_CUE_POINT, &[uint(ID_CUE_TIME, time), positions].concat())
+    }
+
+    /// Minimal Matroska file with Cues after a cluster, found via SeekHead.
+    fn matroska_with_cues(cue_times: &[u64]) -> Vec<u8> {
+        let info = element(ID_INFO, &uint(ID_TIMESTAMP_SCALE, 1_000_000));
+        let audio = element(
+            ID_TRACK_ENTRY,
+            &[uint(ID_TRACK_NUMBER, 1), uint(ID_TRACK_TYPE, 2)].concat(),
+        );
+        let video = element(
+            ID_TRACK_ENTRY,
+            &[uint(ID_TRACK_NUMBER, 2), uint(ID_TRACK_TYPE, 1)].concat(),
+        );
+        let tracks = element(ID_TRACKS, &[audio, video].concat());
+        let cluster = element(ID_CLUSTER, &[0u8; 16]);
+        let mut points: Vec<u8> = cue_times.iter().flat_map(|&t| cue_point(t, 2)).collect();
+        points.extend(cue_point(500, 1));
+        let cues = element(ID_CUES, &points);
+
+        let seek_len = element(
+            ID_SEEK_HEAD,
+            &element(
+                ID_SEEK,
+                &[uint(ID_SEEK_ID, 0), uint(ID_SEEK_POSITION, 0)].concat(),
+            ),
+        )
+        .len();
+        let cues_offset = (seek_len + info.len() + tracks.len() + cluster.len()) as u64;
+        let seek_head = element(
+            ID_SEEK_HEAD,
+            &element(
+                ID_SEEK,
+                &[
+                    uint(ID_SEEK_ID, u64::from(ID_CUES)),
+                    uint(ID_SEEK_POSITION, cues_offset),
+                ]
+                .concat(),
+            ),
+        );
+
+        let segment = [seek_head, info, tracks, cluster, cues].concat();
+        [element(ID_EBML, &[]), element(ID_SEGMENT, &segment)].concat()
+    }
+
+    #[test]
+    fn read_vint_handles_markers_and_unknown_size() {
+        assert_eq!(
+            read_vint(&[0x1A, 0x45, 0xDF, 0xA3], true),
+            Some((0x1A45_DFA3, 4))
+        );
+        assert_eq!(read_vint(&[0x81], false), Some((1, 1)));
+        assert_eq!(read_vint(&[0x40, 0x02], false), Some((2, 2)));
+        assert_eq!(read_vint(&[0xFF], false), Some((u64::MAX, 1)));
+        assert_eq!(read_vint(&[0x00], false), None);
+    }
+
+    #[test]
+    fn cues_after_clusters_are_found_through_seek_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mkv");
+        fs::write(&path, matroska_with_cues(&[0, 1001, 2002, 3003])).unwrap();
+
+        assert_eq!(
+            read_cue_keyframes_ns(&path).unwrap(),
+            vec![0, 1_001_000_000, 2_002_000_000, 3_003_000_000]
+        );
+    }
+
+    #[test]
+    fn cached_keyframes_are_reused_after_first_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mkv");
+        fs::write(&path, matroska_with_cues(&[0, 1001, 2002])).unwrap();
+        let temp_root = dir.path().join("temp").to_string_lossy().to_string();
+        let runner = CommandRunner::new(Default::default(), Box::new(|_: &str| {}));
+        let video = path.to_string_lossy();
+
+        let first = cached_keyframes_ns(&video, &temp_root, &runner, &HashMap::new());
+        assert_eq!(first, vec![0, 1_001_000_000, 2_002_000_000]);
+        let cached: Vec<_> = fs::read_dir(keyframe_cache_dir(&temp_root))
+            .unwrap()
+            .collect();
+        assert_eq!(cached.len(), 1);
+
+        // Overwrite the cache entry; the next lookup must come from it
+        let entry = KeyframeCacheEntry {
+            source: "cues".to_string(),
+            keyframes_ns: vec![42],
+        };
+        let cache_file = cached[0].as_ref().unwrap().path();
+        fs::write(&cache_file, serde_json::to_string(&entry).unwrap()).unwrap();
+        assert_eq!(
+            cached_keyframes_ns(&video, &temp_root, &runner, &HashMap::new()),
+            vec![42]
+        );
+    }
+
+    #[test]
+    fn sparse_or_missing_cues_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let sparse = dir.path().join("sparse.mkv");
+        fs::write(&sparse, matroska_with_cues(&[0, 60_000, 120_000])).unwrap();
+        assert!(read_cue_keyframes_ns(&sparse).is_err());
+
+        // One cue per ~5 s cluster, as FFmpeg writes them
+        let per_cluster = dir.path().join("per_cluster.mkv");
+        fs::write(&per_cluster, matroska_with_cues(&[0, 5005, 10_010, 15_015])).unwrap();
+        assert!(read_cue_keyframes_ns(&per_cluster).is_err());
+
+        // An overflowing CueTime is dropped, leaving too few cues
+        let overflow = dir.path().join("overflow.mkv");
+        fs::write(&overflow, matroska_with_cues(&[0, u64::MAX / 2])).unwrap();
+        assert_eq!(
+            read_cue_keyframes_ns(&overflow),
+            Err("no cues for the video track".to_string())
+        );
+
+        let not_mkv = dir.path().join("video.mp4");
+        fs::write(&not_mkv, b"\0\0\0\x18ftypmp42").unwrap();
+        assert!(read_cue_keyframes_ns(&not_mkv).is_err());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod generate;
 pub mod keyframes;
+pub mod ordered;
 pub mod process;
@@ -0,0 +1,768 @@
+//! Ordered chapters and linked segments.
+//!
+//! A Matroska edition with `EditionFlagOrdered` plays its chapters in
+//! order, and a chapter with a `ChapterSegmentUID` plays that range from
+//! another file (typically a shared OP/ED) instead of the main segment. The
+//! timeline a viewer sees is therefore the concatenation of those ranges,
+//! not the main segment's own.
+//!
+//! This module reads the ordered edition, finds the linked files next to
+//! the source by their segment UID, and describes the assembled timeline:
+//! as flat chapters, as an ffmpeg concat playlist ("virtual" assembly),
+//! or remuxed into one file with mkvmerge ("physical" assembly).
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::reader::Reader;
+use quick_xml::writer::Writer;
+
+use crate::extraction::tracks::get_stream_info;
+use crate::io::runner::CommandRunner;
+
+use super::process::{fmt_ns, parse_ns};
+
+/// File extensions scanned for linked segments.
+const SEGMENT_EXTENSIONS: &[&str] = &["mkv", "mks", "mka"];
+
+// ─── Ordered edition parsing ─────────────────────────────────────────────────
+
+/// One chapter of an ordered edition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedChapter {
+    /// Linked segment (lowercase hex), `None` for the main segment.
+    pub segment_uid: Option<String>,
+    pub start_ns: i64,
+    pub end_ns: Option<i64>,
+    pub name: String,
+    pub language: String,
+}
+
+/// The ordered edition a player follows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedEdition {
+    pub chapters: Vec<OrderedChapter>,
+}
+
+impl OrderedEdition {
+    /// Segment UIDs the edition pulls from other files, in playback order.
+    pub fn linked_segment_uids(&self) -> Vec<String> {
+        let mut uids: Vec<String> = Vec::new();
+        for uid in self.chapters.iter().filter_map(|c| c.segment_uid.as_ref()) {
+            if !uids.contains(uid) {
+                uids.push(uid.clone());
+            }
+        }
+        uids
+    }
+}
+
+/// Normalize a segment UID to lowercase hex without separators.
+///
+/// Chapter XML writes UIDs as hex bytes, with or without spaces and `0x`
+/// prefixes; mkvmerge's JSON uses plain lowercase hex.
+pub fn normalize_segment_uid(uid: &str) -> String {
+    uid.split_whitespace()
+        .map(|part| part.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Parse the ordered edition from chapter XML.
+///
+/// Picks the default edition if it is ordered, else the first ordered
+/// edition. Disabled chapters are skipped, as players skip them. Returns
+/// `None` when no edition is ordered.
+pub fn parse_ordered_edition(xml_content: &str) -> Result<Option<OrderedEdition>, String> {
+    struct EditionState {
+        ordered: bool,
+        default: bool,
+        chapters: Vec<OrderedChapter>,
+    }
+
+    let mut reader = Reader::from_str(xml_content);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut editions: Vec<EditionState> = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut atom_depth = 0usize;
+    let mut chapter: Option<(OrderedChapter, bool)> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                match name.as_str() {
+                    "EditionEntry" => editions.push(EditionState {
+                        ordered: false,
+                        default: false,
+                        chapters: Vec::new(),
+                    }),
+                    "ChapterAtom" => {
+                        atom_depth += 1;
+                        // Only top-level atoms make up the ordered timeline
+                        if atom_depth == 1 {
+                            chapter = Some((
+                                OrderedChapter {
+                                    segment_uid: None,
+                                    start_ns: 0,
+                                    end_ns: None,
+                                    name: String::new(),
+                                    language: "und".to_string(),
+                                },
+                                true,
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Ok(Event::End(ref e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                path.pop();
+                if name == "ChapterAtom" {
+                    if atom_depth == 1 {
+                        if let (Some((c, enabled)), Some(edition)) =
+                            (chapter.take(), editions.last_mut())
+                        {
+                            if enabled {
+                                edition.chapters.push(c);
+                            }
+                        }
+                    }
+                    atom_depth = atom_depth.saturating_sub(1);
+                }
+            }
+            Ok(Event::Text(ref e)) => {
+                let text = e
+                    .decode()
+                    .map_err(|err| format!("XML text decode error: {err}"))?
+                    .trim()
+                    .to_string();
+                let element = path.last().map(String::as_str).unwrap_or("");
+                let parent = path.len().checked_sub(2).map(|i| path[i].as_str());
+
+                if parent == Some("EditionEntry") {
+                    if let Some(edition) = editions.last_mut() {
+                        match element {
+                            "EditionFlagOrdered" => edition.ordered = text == "1",
+                            "EditionFlagDefault" => edition.default = text == "1",
+                            _ => {}
+                        }
+                    }
+                } else if atom_depth == 1 {
+                    if let Some((c, enabled)) = chapter.as_mut() {
+                        match (parent, element) {
+                            (Some("ChapterAtom"), "ChapterTimeStart") => {
+                                c.start_ns = parse_ns(&text)
+                            }
+                            (Some("ChapterAtom"), "ChapterTimeEnd") => {
+                                c.end_ns = Some(parse_ns(&text))
+                            }
+                            (Some("ChapterAtom"), "ChapterFlagEnabled") => *enabled = text != "0",
+                            (Some("ChapterAtom"), "ChapterSegmentUID") => {
+                                let uid = normalize_segment_uid(&text);
+                                c.segment_uid = (!uid.is_empty()).then_some(uid);
+                            }
+                            (Some("ChapterDisplay"), "ChapterString") if c.name.is_empty() => {
+                                c.name = text;
+                            }
+                            (Some("ChapterDisplay"), "ChapterLanguage") if c.language == "und" => {
+                                c.language = text;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(format!(
+                    "XML parse error at position {}: {e}",
+                    reader.error_position()
+                ))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let index = editions
+        .iter()
+        .position(|e| e.ordered && e.default)
+        .or_else(|| editions.iter().position(|e| e.ordered));
+    Ok(index.map(|i| OrderedEdition {
+        chapters: std::mem::take(&mut editions[i].chapters),
+    }))
+}
+
+// ─── Linked timeline ─────────────────────────────────────────────────────────
+
+/// A Matroska file that can be part of a linked timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentFile {
+    pub path: String,
+    pub duration_ns: i64,
+}
+
+/// One played range of the assembled timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelinePart {
+    pub file: String,
+    pub start_ns: i64,
+    pub end_ns: i64,
+    pub name: String,
+    pub language: String,
+}
+
+/// The timeline a viewer sees for a source with ordered chapters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedTimeline {
+    pub main_file: String,
+    pub parts: Vec<TimelinePart>,
+    /// Files appended for physical assembly, main segment first.
+    pub files: Vec<SegmentFile>,
+}
+
+impl LinkedTimeline {
+    /// Total length of the assembled timeline.
+    pub fn duration_ns(&self) -> i64 {
+        self.parts.iter().map(|p| p.end_ns - p.start_ns).sum()
+    }
+
+    /// Whether any part comes from a file other than the main segment.
+    pub fn uses_linked_segments(&self) -> bool {
+        self.parts.iter().any(|p| p.file != self.main_file)
+    }
+
+    /// Consecutive parts that continue the same file are one range.
+    fn ranges(&self) -> Vec<(&str, i64, i64)> {
+        let mut ranges: Vec<(&str, i64, i64)> = Vec::new();
+        for part in &self.parts {
+            match ranges.last_mut() {
+                Some(last) if last.0 == part.file && last.2 == part.start_ns => {
+                    last.2 = part.end_ns
+                }
+                _ => ranges.push((&part.file, part.start_ns, part.end_ns)),
+            }
+        }
+        ranges
+    }
+
+    /// Flat chapter XML for the assembled timeline: one chapter per part,
+    /// starting where the part lands.
+    pub fn flat_chapters_xml(&self) -> Result<String, String> {
+        let err = |e: std::io::Error| format!("XML write error: {e}");
+        let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+            .map_err(err)?;
+        writer
+            .write_event(Event::Start(BytesStart::new("Chapters")))
+            .map_err(err)?;
+        writer
+            .write_event(Event::Start(BytesStart::new("EditionEntry")))
+            .map_err(err)?;
+
+        let mut position = 0i64;
+        for (i, part) in self.parts.iter().enumerate() {
+            let end = position + (part.end_ns - part.start_ns);
+            let name = if part.name.is_empty() {
+                format!("Chapter {:02}", i + 1)
+            } else {
+                part.name.clone()
+            };
+            writer
+                .create_element("ChapterAtom")
+                .write_inner_content(|w| {
+                    w.create_element("ChapterTimeStart")
+                        .write_text_content(BytesText::new(&fmt_ns(position)))?;
+                    w.create_element("ChapterTimeEnd")
+                        .write_text_content(BytesText::new(&fmt_ns(end)))?;
+                    w.create_element("ChapterDisplay")
+                        .write_inner_content(|d| {
+                            d.create_element("ChapterString")
+                                .write_text_content(BytesText::new(&name))?;
+                            d.create_element("ChapterLanguage")
+                                .write_text_content(BytesText::new(&part.language))?;
+                            Ok(())
+                        })?;
+                    Ok(())
+                })
+                .map_err(err)?;
+            position = end;
+        }
+
+        writer
+            .write_event(Event::End(BytesEnd::new("EditionEntry")))
+            .map_err(err)?;
+        writer
+            .write_event(Event::End(BytesEnd::new("Chapters")))
+            .map_err(err)?;
+        String::from_utf8(writer.into_inner().into_inner()).map_err(|e| format!("UTF-8 error: {e}"))
+    }
+
+    /// ffmpeg concat playlist that plays the assembled timeline.
+    pub fn ffconcat(&self) -> String {
+        let mut out = String::from("ffconcat version 1.0\n");
+        for (file, start_ns, end_ns) in self.ranges() {
+            out.push_str(&format!(
+                "file '{}'\ninpoint {:.9}\noutpoint {:.9}\n",
+                file.replace('\'', "'\\''"),
+                start_ns as f64 / 1e9,
+                end_ns as f64 / 1e9
+            ));
+        }
+        out
+    }
+
+    /// mkvmerge commands that remux the assembled timeline into `out_path`.
+    ///
+    /// Each played range is cut from its file into its own part next to
+    /// `out_path` (`--split parts:` on that file's own timeline); the last
+    /// command appends the parts in playback order.
+    pub fn mkvmerge_commands(&self, out_path: &Path) -> Vec<Vec<String>> {
+        let ranges = self.ranges();
+        let mut commands: Vec<Vec<String>> = ranges
+            .iter()
+            .enumerate()
+            .map(|(i, (file, start_ns, end_ns))| {
+                vec![
+                    "mkvmerge".to_string(),
+                    "-o".to_string(),
+                    part_path(out_path, i).to_string_lossy().to_string(),
+                    "--no-chapters".to_string(),
+                    "--split".to_string(),
+                    format!("parts:{}-{}", fmt_ns(*start_ns), fmt_ns(*end_ns)),
+                    file.to_string(),
+                ]
+            })
+            .collect();
+
+        let mut append = vec![
+            "mkvmerge".to_string(),
+            "-o".to_string(),
+            out_path.to_string_lossy().to_string(),
+        ];
+        for i in 0..ranges.len() {
+            if i > 0 {
+                append.push("+".to_string());
+            }
+            append.push(part_path(out_path, i).to_string_lossy().to_string());
+        }
+        commands.push(append);
+        commands
+    }
+}
+
+/// Where range `index` of the timeline is cut to before appending.
+fn part_path(out_path: &Path, index: usize) -> PathBuf {
+    out_path.with_extension(format!("part{index:03}.mkv"))
+}
+
+/// Build the assembled timeline from an ordered edition.
+///
+/// `segments` maps linked segment UIDs to their files; `main` is the file
+/// holding the edition. Chapters without an end play to the end of their
+/// segment.
+pub fn build_linked_timeline(
+    edition: &OrderedEdition,
+    main: &SegmentFile,
+    main_uid: Option<&str>,
+    segments: &HashMap<String, SegmentFile>,
+) -> Result<LinkedTimeline, String> {
+    let mut files = vec![main.clone()];
+    let mut parts = Vec::new();
+
+    for chapter in &edition.chapters {
+        let file = match chapter.segment_uid.as_deref() {
+            None => main,
+            Some(uid) if Some(uid) == main_uid => main,
+            Some(uid) => segments
+                .get(uid)
+                .ok_or_else(|| format!("Linked segment {uid} was not found"))?,
+        };
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+
+        let end_ns = chapter
+            .end_ns
+            .unwrap_or(file.duration_ns)
+            .min(file.duration_ns);
+        if end_ns <= chapter.start_ns {
+            continue;
+        }
+        parts.push(TimelinePart {
+            file: file.path.clone(),
+            start_ns: chapter.start_ns,
+            end_ns,
+            name: chapter.name.clone(),
+            language: chapter.language.clone(),
+        });
+    }
+
+    if parts.is_empty() {
+        return Err("Ordered edition has no playable chapters".to_string());
+    }
+    Ok(LinkedTimeline {
+        main_file: main.path.clone(),
+        parts,
+        files,
+    })
+}
+
+// ─── Detection and assembly ──────────────────────────────────────────────────
+
+/// Segment UID and duration of a Matroska file from `mkvmerge -J`.
+fn probe_segment(
+    path: &str,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Option<(Option<String>, SegmentFile)> {
+    let info = get_stream_info(path, runner, tool_paths)?;
+    let props = info.get("container")?.get("properties")?;
+    let uid = props
+        .get("segment_uid")
+        .and_then(|v| v.as_str())
+        .map(normalize_segment_uid);
+    let duration_ns = props.get("duration").and_then(|v| v.as_i64())?;
+    Some((
+        uid,
+        SegmentFile {
+            path: path.to_string(),
+            duration_ns,
+        },
+    ))
+}
+
+/// Find the files holding `uids` among the Matroska files next to `main_file`.
+fn find_linked_segments(
+    main_file: &str,
+    uids: &[String],
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> HashMap<String, SegmentFile> {
+    let mut found = HashMap::new();
+    let dir = Path::new(main_file).parent().unwrap_or(Path::new("."));
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return found;
+    };
+
+    let mut candidates: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| SEGMENT_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        })
+        .map(|p| p.to_string_lossy().to_string())
+        .filter(|p| p != main_file)
+        .collect();
+    candidates.sort();
+
+    for candidate in candidates {
+        if found.len() == uids.len() {
+            break;
+        }
+        if let Some((Some(uid), segment)) = probe_segment(&candidate, runner, tool_paths) {
+            if uids.contains(&uid) {
+                found.entry(uid).or_insert(segment);
+            }
+        }
+    }
+    found
+}
+
+/// Detect an ordered edition that links other segments — `detect_linked_timeline`
+///
+/// Returns `Ok(None)` when the file has no ordered edition or it only
+/// reorders the main segment's own ranges without linking anything.
+pub fn detect_linked_timeline(
+    source_file: &str,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<Option<LinkedTimeline>, String> {
+    let Ok(xml) = runner.run(&["mkvextract", source_file, "chapters", "-"], tool_paths) else {
+        return Ok(None);
+    };
+    let xml = xml.strip_prefix('\u{feff}').unwrap_or(&xml);
+    if xml.trim().is_empty() {
+        return Ok(None);
+    }
+    let Some(edition) = parse_ordered_edition(xml)? else {
+        return Ok(None);
+    };
+
+    let (main_uid, main) = probe_segment(source_file, runner, tool_paths)
+        .ok_or_else(|| format!("Could not read segment info of {source_file}"))?;
+    let linked: Vec<String> = edition
+        .linked_segment_uids()
+        .into_iter()
+        .filter(|uid| Some(uid) != main_uid.as_ref())
+        .collect();
+    if linked.is_empty() {
+        return Ok(None);
+    }
+
+    let segments = find_linked_segments(source_file, &linked, runner, tool_paths);
+    build_linked_timeline(&edition, &main, main_uid.as_deref(), &segments).map(Some)
+}
+
+/// Remux the assembled timeline into `out_path` with flat chapters — `assemble_linked_timeline`
+pub fn assemble_linked_timeline(
+    timeline: &LinkedTimeline,
+    out_path: &Path,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<(), String> {
+    let out_str = out_path.to_string_lossy().to_string();
+    let commands = timeline.mkvmerge_commands(out_path);
+    let (append, extracts) = commands.split_last().ok_or("Empty linked timeline")?;
+    let parts: Vec<PathBuf> = (0..extracts.len())
+        .map(|i| part_path(out_path, i))
+        .collect();
+    let assembled = cut_and_append(extracts, append, &parts, runner, tool_paths);
+    for part in &parts {
+        let _ = std::fs::remove_file(part);
+    }
+    assembled?;
+
+    // Chapters go on afterwards: the flat ones are already in output time
+    let chapters_path = out_path.with_extension("chapters.xml");
+    std::fs::write(&chapters_path, timeline.flat_chapters_xml()?)
+        .map_err(|e| format!("Could not write {}: {e}", chapters_path.display()))?;
+    let chapters_str = chapters_path.to_string_lossy().to_string();
+    runner
+        .run(
+            &["mkvpropedit", &out_str, "--chapters", &chapters_str],
+            tool_paths,
+        )
+        .map_err(|e| format!("mkvpropedit failed to write the assembled chapters: {e}"))?;
+    Ok(())
+}
+
+/// Run the per-range cuts into `parts`, then the append command.
+fn cut_and_append(
+    extracts: &[Vec<String>],
+    append: &[String],
+    parts: &[PathBuf],
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<(), String> {
+    for (args, part) in extracts.iter().zip(parts) {
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        runner
+            .run(&arg_refs, tool_paths)
+            .map_err(|e| format!("mkvmerge failed to cut {}: {e}", part.display()))?;
+        // mkvmerge may number split output even when only one part is kept
+        let numbered = part.with_file_name(format!(
+            "{}-001.mkv",
+            part.file_stem().unwrap_or_default().to_string_lossy()
+        ));
+        if !part.exists() && numbered.exists() {
+            std::fs::rename(&numbered, part)
+                .map_err(|e| format!("Could not rename {}: {e}", numbered.display()))?;
+        }
+    }
+    let arg_refs: Vec<&str> = append.iter().map(String::as_str).collect();
+    runner
+        .run(&arg_refs, tool_paths)
+        .map_err(|e| format!("mkvmerge failed to assemble the linked timeline: {e}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERED_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Chapters>
+  <EditionEntry>
+    <EditionFlagDefault>0</EditionFlagDefault>
+    <ChapterAtom>
+      <ChapterTimeStart>00:00:00.000000000</ChapterTimeStart>
+    </ChapterAtom>
+  </EditionEntry>
+  <EditionEntry>
+    <EditionFlagOrdered>1</EditionFlagOrdered>
+    <EditionFlagDefault>1</EditionFlagDefault>
+    <ChapterAtom>
+      <ChapterTimeStart>00:00:00.000000000</ChapterTimeStart>
+      <ChapterTimeEnd>00:01:00.000000000</ChapterTimeEnd>
+      <ChapterDisplay>
+        <ChapterString>Prologue</ChapterString>
+        <ChapterLanguage>eng</ChapterLanguage>
+      </ChapterDisplay>
+    </ChapterAtom>
+    <ChapterAtom>
+      <ChapterSegmentUID format="hex">0xAB 0xCD 0x01</ChapterSegmentUID>
+      <ChapterTimeStart>00:00:00.000000000</ChapterTimeStart>
+      <ChapterTimeEnd>00:01:30.000000000</ChapterTimeEnd>
+      <ChapterDisplay>
+        <ChapterString>Opening</ChapterString>
+      </ChapterDisplay>
+    </ChapterAtom>
+    <ChapterAtom>
+      <ChapterFlagEnabled>0</ChapterFlagEnabled>
+      <ChapterTimeStart>00:05:00.000000000</ChapterTimeStart>
+      <ChapterTimeEnd>00:06:00.000000000</ChapterTimeEnd>
+    </ChapterAtom>
+    <ChapterAtom>
+      <ChapterTimeStart>00:01:00.000000000</ChapterTimeStart>
+      <ChapterTimeEnd>00:20:00.000000000</ChapterTimeEnd>
+      <ChapterDisplay>
+        <ChapterString>Episode</ChapterString>
+      </ChapterDisplay>
+      <ChapterAtom>
+        <ChapterTimeStart>00:10:00.000000000</ChapterTimeStart>
+      </ChapterAtom>
+    </ChapterAtom>
+    <ChapterAtom>
+      <ChapterSegmentUID format="hex">ef01</ChapterSegmentUID>
+      <ChapterTimeStart>00:00:00.000000000</ChapterTimeStart>
+      <ChapterDisplay>
+        <ChapterString>Ending</ChapterString>
+      </ChapterDisplay>
+    </ChapterAtom>
+  </EditionEntry>
+</Chapters>"#;
+
+    fn seg(path: &str, seconds: i64) -> SegmentFile {
+        SegmentFile {
+            path: path.to_string(),
+            duration_ns: seconds * 1_000_000_000,
+        }
+    }
+
+    fn timeline() -> LinkedTimeline {
+        let edition = parse_ordered_edition(ORDERED_XML).unwrap().unwrap();
+        let segments = HashMap::from([
+            ("abcd01".to_string(), seg("/bd/op.mkv", 90)),
+            ("ef01".to_string(), seg("/bd/ed.mkv", 90)),
+        ]);
+        build_linked_timeline(
+            &edition,
+            &seg("/bd/ep01.mkv", 1200),
+            Some("0000"),
+            &segments,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_ordered_edition_reads_links() {
+        let edition = parse_ordered_edition(ORDERED_XML).unwrap().unwrap();
+        let names: Vec<&str> = edition.chapters.iter().map(|c| c.name.as_str()).collect();
+        // Disabled and nested atoms are not part of the timeline
+        assert_eq!(names, ["Prologue", "Opening", "Episode", "Ending"]);
+        assert_eq!(edition.chapters[0].language, "eng");
+        assert_eq!(edition.linked_segment_uids(), ["abcd01", "ef01"]);
+
+        let flat = "<Chapters><EditionEntry><ChapterAtom>\
+                    <ChapterTimeStart>00:00:00.000000000</ChapterTimeStart>\
+                    </ChapterAtom></EditionEntry></Chapters>";
+        assert_eq!(parse_ordered_edition(flat).unwrap(), None);
+    }
+
+    #[test]
+    fn linked_timeline_layout() {
+        let t = timeline();
+        assert!(t.uses_linked_segments());
+        assert_eq!(t.duration_ns(), (60 + 90 + 1140 + 90) * 1_000_000_000);
+        let files: Vec<&str> = t.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(files, ["/bd/ep01.mkv", "/bd/op.mkv", "/bd/ed.mkv"]);
+
+        let missing = build_linked_timeline(
+            &parse_ordered_edition(ORDERED_XML).unwrap().unwrap(),
+            &seg("/bd/ep01.mkv", 1200),
+            None,
+            &HashMap::new(),
+        );
+        assert!(missing.unwrap_err().contains("abcd01"));
+    }
+
+    #[test]
+    fn flat_chapters_follow_viewer_timeline() {
+        let xml = timeline().flat_chapters_xml().unwrap();
+        let starts: Vec<&str> = xml
+            .match_indices("<ChapterTimeStart>")
+            .map(|(i, tag)| &xml[i + tag.len()..i + tag.len() + 18])
+            .collect();
+        assert_eq!(
+            starts,
+            [
+                "00:00:00.000000000",
+                "00:01:00.000000000",
+                "00:02:30.000000000",
+                "00:21:30.000000000"
+            ]
+        );
+        assert!(xml.contains("<ChapterString>Opening</ChapterString>"));
+    }
+
+    #[test]
+    fn assembly_commands() {
+        let t = timeline();
+        let commands = t.mkvmerge_commands(Path::new("/tmp/out.mkv"));
+        assert_eq!(commands.len(), 5);
+
+        // One cut per range, each on its own file's timeline
+        let cuts: Vec<(&str, &str, &str)> = commands[..4]
+            .iter()
+            .map(|c| (c[2].as_str(), c[5].as_str(), c[6].as_str()))
+            .collect();
+        assert_eq!(
+            cuts,
+            [
+                (
+                    "/tmp/out.part000.mkv",
+                    "parts:00:00:00.000000000-00:01:00.000000000",
+                    "/bd/ep01.mkv"
+                ),
+                (
+                    "/tmp/out.part001.mkv",
+                    "parts:00:00:00.000000000-00:01:30.000000000",
+                    "/bd/op.mkv"
+                ),
+                (
+                    "/tmp/out.part002.mkv",
+                    "parts:00:01:00.000000000-00:20:00.000000000",
+                    "/bd/ep01.mkv"
+                ),
+                (
+                    "/tmp/out.part003.mkv",
+                    "parts:00:00:00.000000000-00:01:30.000000000",
+                    "/bd/ed.mkv"
+                ),
+            ]
+        );
+        assert!(commands[..4].iter().all(|c| c[3] == "--no-chapters"));
+
+        // Then the parts are appended in playback order
+        assert_eq!(
+            commands[4],
+            [
+                "mkvmerge",
+                "-o",
+                "/tmp/out.mkv",
+                "/tmp/out.part000.mkv",
+                "+",
+                "/tmp/out.part001.mkv",
+                "+",
+                "/tmp/out.part002.mkv",
+                "+",
+                "/tmp/out.part003.mkv"
+            ]
+        );
+
+        let playlist = t.ffconcat();
+        assert!(playlist.starts_with("ffconcat version 1.0\nfile '/bd/ep01.mkv'\ninpoint 0.000000000\noutpoint 60.000000000\n"));
+        assert_eq!(playlist.matches("file '").count(), 4);
+    }
+}
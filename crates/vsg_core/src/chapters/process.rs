@@ -3,7 +3,7 @@
 //! Handles chapter extraction, shifting, snapping, normalization,
 //! deduplication, and renaming. Uses quick-xml for both parsing and writing.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Cursor;
 use std::path::Path;
 
@@ -15,7 +15,7 @@ use quick_xml::writer::Writer;
 use crate::io::runner::CommandRunner;
 use crate::models::settings::AppSettings;
 
-use super::keyframes::probe_keyframes_ns;
+use super::keyframes::cached_keyframes_ns;
 
 // ─── Time format helpers ─────────────────────────────────────────────────────
 
@@ -383,6 +383,288 @@ fn write_text_element(
     Ok(())
 }
 
+// ─── External chapter files ──────────────────────────────────────────────────
+
+/// Where a job's chapters are read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChapterInput {
+    /// Chapters embedded in a job source's container (via mkvextract).
+    Container(String),
+    /// A standalone chapter file: Matroska XML, OGM simple text,
+    /// ffmetadata, or a timestamped text list.
+    File(String),
+}
+
+impl std::fmt::Display for ChapterInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChapterInput::Container(path) => write!(f, "container of {path}"),
+            ChapterInput::File(path) => write!(f, "chapter file {path}"),
+        }
+    }
+}
+
+/// Parse a chapter file of any supported format, detected from its content.
+fn parse_chapter_file(content: &str) -> Result<Vec<ChapterAtom>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let trimmed = content.trim_start();
+
+    let mut chapters = if trimmed.starts_with('<') {
+        parse_chapter_xml(content)?
+    } else if trimmed.starts_with(";FFMETADATA") || content.lines().any(|l| l.trim() == "[CHAPTER]")
+    {
+        parse_ffmetadata_chapters(content)?
+    } else if content
+        .lines()
+        .any(|l| ogm_chapter_key(l.trim()).is_some_and(|(_, is_name)| !is_name))
+    {
+        parse_ogm_chapters(content)?
+    } else {
+        parse_timestamp_list_chapters(content)?
+    };
+
+    chapters.sort_by_key(|c| c.start_ns);
+    Ok(chapters)
+}
+
+/// Display entry for a chapter read from a format without languages.
+fn untagged_display(name: &str, index: usize) -> Vec<ChapterDisplay> {
+    let name = name.trim();
+    vec![ChapterDisplay {
+        chapter_string: if name.is_empty() {
+            format!("Chapter {}", index + 1)
+        } else {
+            name.to_string()
+        },
+        chapter_language: "und".to_string(),
+        chapter_language_ietf: "und".to_string(),
+    }]
+}
+
+/// Parse a loose timestamp — `H:MM:SS.fff`, `MM:SS`, or either with a
+/// comma decimal separator — to nanoseconds.
+fn parse_loose_timestamp_ns(t: &str) -> Option<i64> {
+    let t = t.trim().replace(',', ".");
+    let parts: Vec<&str> = t.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+
+    let (sec_str, frac_str) = match parts[parts.len() - 1].split_once('.') {
+        Some((s, f)) => (s, f),
+        None => (parts[parts.len() - 1], ""),
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let fields = parts[..parts.len() - 1].iter().copied().chain([sec_str]);
+    let mut total_s: i64 = 0;
+    for field in fields {
+        if !all_digits(field) {
+            return None;
+        }
+        total_s = total_s * 60 + field.parse::<i64>().ok()?;
+    }
+    if !frac_str.is_empty() && !all_digits(frac_str) {
+        return None;
+    }
+
+    let mut frac_padded = frac_str.to_string();
+    while frac_padded.len() < 9 {
+        frac_padded.push('0');
+    }
+    frac_padded.truncate(9);
+    let frac: i64 = frac_padded.parse().ok()?;
+
+    Some(total_s * 1_000_000_000 + frac)
+}
+
+/// Split an OGM line key like `CHAPTER03` / `CHAPTER03NAME` into its
+/// number and whether it names the chapter.
+fn ogm_chapter_key(line: &str) -> Option<(u32, bool)> {
+    let (key, _) = line.split_once('=')?;
+    let rest = key.trim().to_ascii_uppercase();
+    let rest = rest.strip_prefix("CHAPTER")?;
+    let (digits, is_name) = match rest.strip_suffix("NAME") {
+        Some(d) => (d, true),
+        None => (rest, false),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((digits.parse().ok()?, is_name))
+}
+
+/// Parse OGM simple chapters (`CHAPTER01=00:00:00.000` / `CHAPTER01NAME=...`).
+fn parse_ogm_chapters(content: &str) -> Result<Vec<ChapterAtom>, String> {
+    let mut starts: BTreeMap<u32, i64> = BTreeMap::new();
+    let mut names: HashMap<u32, String> = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        let Some((number, is_name)) = ogm_chapter_key(line) else {
+            continue;
+        };
+        let value = line.split_once('=').map(|(_, v)| v).unwrap_or("");
+        if is_name {
+            names.insert(number, value.to_string());
+        } else {
+            let start_ns = parse_loose_timestamp_ns(value)
+                .ok_or_else(|| format!("Invalid OGM chapter time: {line}"))?;
+            starts.insert(number, start_ns);
+        }
+    }
+
+    Ok(starts
+        .into_iter()
+        .enumerate()
+        .map(|(i, (number, start_ns))| ChapterAtom {
+            start_ns,
+            end_ns: None,
+            displays: untagged_display(names.get(&number).map_or("", |n| n.as_str()), i),
+        })
+        .collect())
+}
+
+/// Undo ffmetadata escaping (`\=`, `\;`, `\#`, `\\`).
+fn unescape_ffmetadata(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Parse `[CHAPTER]` sections of an ffmpeg metadata file.
+fn parse_ffmetadata_chapters(content: &str) -> Result<Vec<ChapterAtom>, String> {
+    struct Section {
+        timebase: (i64, i64),
+        start: Option<i64>,
+        end: Option<i64>,
+        title: String,
+    }
+
+    let to_ns = |ticks: i64, (num, den): (i64, i64)| -> i64 {
+        (ticks as i128 * num as i128 * 1_000_000_000 / den as i128) as i64
+    };
+
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_chapter = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_chapter = line == "[CHAPTER]";
+            if in_chapter {
+                sections.push(Section {
+                    timebase: (1, 1_000),
+                    start: None,
+                    end: None,
+                    title: String::new(),
+                });
+            }
+            continue;
+        }
+        let Some(section) = sections.last_mut().filter(|_| in_chapter) else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "timebase" => {
+                let (num, den) = value
+                    .split_once('/')
+                    .and_then(|(n, d)| Some((n.trim().parse().ok()?, d.trim().parse().ok()?)))
+                    .filter(|&(n, d): &(i64, i64)| n > 0 && d > 0)
+                    .ok_or_else(|| format!("Invalid ffmetadata TIMEBASE: {value}"))?;
+                section.timebase = (num, den);
+            }
+            "start" => {
+                section.start = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid ffmetadata START: {value}"))?,
+                );
+            }
+            "end" => section.end = value.parse().ok(),
+            "title" => section.title = unescape_ffmetadata(value),
+            _ => {}
+        }
+    }
+
+    Ok(sections
+        .into_iter()
+        .filter_map(|s| s.start.map(|start| (start, s)))
+        .enumerate()
+        .map(|(i, (start, s))| ChapterAtom {
+            start_ns: to_ns(start, s.timebase),
+            end_ns: s.end.map(|end| to_ns(end, s.timebase)),
+            displays: untagged_display(&s.title, i),
+        })
+        .collect())
+}
+
+/// Parse a timestamped list — one `TIMESTAMP [-|] Title` per line, as
+/// found in video descriptions and track lists. Blank and `#` lines are
+/// ignored; any other line without a leading timestamp is an error.
+fn parse_timestamp_list_chapters(content: &str) -> Result<Vec<ChapterAtom>, String> {
+    let mut chapters = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (stamp, title) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let start_ns = parse_loose_timestamp_ns(stamp.trim_matches(['[', ']']))
+            .ok_or_else(|| format!("Unrecognized chapter line: {line}"))?;
+        let title = title
+            .trim_start()
+            .trim_start_matches(['-', '|', '\u{2013}', '\u{2014}']);
+        chapters.push(ChapterAtom {
+            start_ns,
+            end_ns: None,
+            displays: untagged_display(title, chapters.len()),
+        });
+    }
+    Ok(chapters)
+}
+
+/// Load chapters from a job source container or an external file.
+fn load_chapters(
+    input: &ChapterInput,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<Vec<ChapterAtom>, String> {
+    match input {
+        ChapterInput::Container(path) => {
+            let xml_content = match runner.run(&["mkvextract", path, "chapters", "-"], tool_paths) {
+                Ok(xml) => xml,
+                Err(_) => return Ok(Vec::new()),
+            };
+            if xml_content.trim().is_empty() {
+                return Ok(Vec::new());
+            }
+            // Strip BOM if present
+            let xml_content = xml_content.strip_prefix('\u{feff}').unwrap_or(&xml_content);
+            parse_chapter_xml(xml_content)
+        }
+        ChapterInput::File(path) => {
+            let bytes = std::fs::read(path)
+                .map_err(|e| format!("Could not read chapter file {path}: {e}"))?;
+            parse_chapter_file(&String::from_utf8_lossy(&bytes))
+        }
+    }
+}
+
 // ─── Processing functions ────────────────────────────────────────────────────
 
 /// Normalize chapter end times and remove duplicates — `_normalize_and_dedupe_chapters`
@@ -454,6 +736,38 @@ fn normalize_and_dedupe_chapters(chapters: &mut Vec<ChapterAtom>, runner: &Comma
     }
 }
 
+/// Scale chapter times by a source speed ratio (audio retimed by the same ratio).
+fn scale_chapter_times(chapters: &mut [ChapterAtom], ratio: f64) {
+    for chapter in chapters {
+        chapter.start_ns = (chapter.start_ns as f64 * ratio).round() as i64;
+        if let Some(ref mut end_ns) = chapter.end_ns {
+            *end_ns = (*end_ns as f64 * ratio).round() as i64;
+        }
+    }
+}
+
+/// Move chapters from their source's timeline onto the reference timeline.
+///
+/// Chapters pushed before zero collapse into one at zero: the last of them
+/// is the one playing when the reference starts.
+fn offset_chapter_times(chapters: &mut Vec<ChapterAtom>, offset_ns: i64) {
+    for chapter in chapters.iter_mut() {
+        chapter.start_ns += offset_ns;
+        if let Some(ref mut end_ns) = chapter.end_ns {
+            *end_ns += offset_ns;
+        }
+    }
+
+    chapters.retain(|c| c.end_ns.is_none_or(|end| end > 0));
+    let leading = chapters.iter().take_while(|c| c.start_ns <= 0).count();
+    if leading > 1 {
+        chapters.drain(..leading - 1);
+    }
+    if let Some(first) = chapters.first_mut() {
+        first.start_ns = first.start_ns.max(0);
+    }
+}
+
 /// Snap chapter times to keyframes — `_snap_chapter_times_inplace`
 fn snap_chapter_times(
     chapters: &mut [ChapterAtom],
@@ -583,29 +897,26 @@ fn snap_chapter_times(
 
 // ─── Public API ──────────────────────────────────────────────────────────────
 
-/// Process chapters from reference MKV — `process_chapters`
+/// Process chapters for the reference MKV — `process_chapters`
 ///
-/// Extracts, optionally snaps to keyframes, shifts by delay,
-/// normalizes, deduplicates, and optionally renames chapters.
+/// Loads chapters from `input` (any job source or an external file),
+/// optionally scales by the source's speed ratio, moves them onto the
+/// reference timeline by `offset_ms`, snaps to the reference's keyframes,
+/// shifts by delay, normalizes, deduplicates, and optionally renames them.
+#[allow(clippy::too_many_arguments)]
 pub fn process_chapters(
+    input: &ChapterInput,
     ref_mkv: &str,
     temp_dir: &Path,
     runner: &CommandRunner,
     tool_paths: &HashMap<String, String>,
     settings: &AppSettings,
+    offset_ms: i32,
     shift_ms: i32,
+    speed_ratio: Option<f64>,
 ) -> Option<String> {
-    let xml_content = runner.run(&["mkvextract", ref_mkv, "chapters", "-"], tool_paths)?;
-
-    if xml_content.trim().is_empty() {
-        runner.log_message("No chapters found in reference file.");
-        return None;
-    }
-
-    // Strip BOM if present
-    let xml_content = xml_content.strip_prefix('\u{feff}').unwrap_or(&xml_content);
-
-    let mut chapters = match parse_chapter_xml(xml_content) {
+    runner.log_message(&format!("[Chapters] Reading chapters from {input}"));
+    let mut chapters = match load_chapters(input, runner, tool_paths) {
         Ok(c) => c,
         Err(e) => {
             runner.log_message(&format!("[ERROR] Chapter processing failed: {e}"));
@@ -614,14 +925,34 @@ pub fn process_chapters(
     };
 
     if chapters.is_empty() {
-        runner.log_message("No chapters found in reference file.");
+        runner.log_message("No chapters found in chapter source.");
         return None;
     }
 
+    // Retime first so snapping sees the final video timeline
+    if let Some(ratio) = speed_ratio {
+        runner.log_message(&format!(
+            "[Chapters] Scaling timestamps by speed ratio {ratio:.6}."
+        ));
+        scale_chapter_times(&mut chapters, ratio);
+    }
+
+    // Chapters from another source sit on that source's timeline
+    if offset_ms != 0 {
+        runner.log_message(&format!(
+            "[Chapters] Offsetting by {offset_ms:+}ms onto the reference timeline."
+        ));
+        offset_chapter_times(&mut chapters, offset_ms as i64 * 1_000_000);
+        if chapters.is_empty() {
+            runner.log_message("No chapters remain inside the reference timeline.");
+            return None;
+        }
+    }
+
     // IMPORTANT: Snap FIRST (in video time), THEN shift to container time
     // This ensures chapters land on actual keyframes in the final muxed file
     if settings.snap_chapters {
-        let keyframes_ns = probe_keyframes_ns(ref_mkv, runner, tool_paths);
+        let keyframes_ns = cached_keyframes_ns(ref_mkv, &settings.temp_root, runner, tool_paths);
         if !keyframes_ns.is_empty() {
             snap_chapter_times(&mut chapters, &keyframes_ns, settings, runner);
         } else {
@@ -791,6 +1122,19 @@ mod tests {
         assert_eq!(chapters[1].end_ns, None);
     }
 
+    #[test]
+    fn scale_chapter_times_by_speed_ratio() {
+        let mut chapters = vec![ChapterAtom {
+            start_ns: 600_000_000_000,
+            end_ns: Some(1_200_000_000_000),
+            displays: Vec::new(),
+        }];
+        // 25 fps source against a 24 fps reference: 10:00 becomes 10:25
+        scale_chapter_times(&mut chapters, 25.0 / 24.0);
+        assert_eq!(chapters[0].start_ns, 625_000_000_000);
+        assert_eq!(chapters[0].end_ns, Some(1_250_000_000_000));
+    }
+
     #[test]
     fn parse_chapter_xml_with_namespace() {
         // Some MKVs use namespace in chapter XML
@@ -877,4 +1221,74 @@ mod tests {
             "Part <1> & \"intro\""
         );
     }
+
+    fn names(chapters: &[ChapterAtom]) -> Vec<&str> {
+        chapters
+            .iter()
+            .map(|c| c.displays[0].chapter_string.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parse_chapter_file_ogm() {
+        let text = "\u{feff}CHAPTER01=00:00:00.000\nCHAPTER01NAME=Prologue\n\
+                    CHAPTER02=00:01:30.500\nCHAPTER02NAME=Opening\nCHAPTER03=00:03:00.000\n";
+        let chapters = parse_chapter_file(text).unwrap();
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[1].start_ns, 90_500_000_000);
+        assert_eq!(names(&chapters), ["Prologue", "Opening", "Chapter 3"]);
+        assert_eq!(chapters[0].displays[0].chapter_language, "und");
+    }
+
+    #[test]
+    fn parse_chapter_file_ffmetadata() {
+        let text = ";FFMETADATA1\ntitle=Episode\n\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\n\
+                    END=90500\ntitle=Cold Open\n[CHAPTER]\nTIMEBASE=1/90000\nSTART=8145000\n\
+                    END=16245000\ntitle=Part A\\; the return\n";
+        let chapters = parse_chapter_file(text).unwrap();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].end_ns, Some(90_500_000_000));
+        assert_eq!(chapters[1].start_ns, 90_500_000_000);
+        assert_eq!(chapters[1].end_ns, Some(180_500_000_000));
+        assert_eq!(names(&chapters), ["Cold Open", "Part A; the return"]);
+    }
+
+    #[test]
+    fn parse_chapter_file_timestamp_list() {
+        let text =
+            "# Episode 4\n0:00 Intro\n01:30 - Opening\n1:02:03.25 | Credits\n[22:10] Preview\n";
+        let chapters = parse_chapter_file(text).unwrap();
+        let starts: Vec<i64> = chapters.iter().map(|c| c.start_ns).collect();
+        assert_eq!(
+            starts,
+            [0, 90_000_000_000, 1_330_000_000_000, 3_723_250_000_000]
+        );
+        assert_eq!(names(&chapters), ["Intro", "Opening", "Preview", "Credits"]);
+
+        assert!(parse_chapter_file("Intro at the start\n").is_err());
+    }
+
+    #[test]
+    fn parse_chapter_file_detects_xml() {
+        let xml = "<Chapters><EditionEntry><ChapterAtom>\
+                   <ChapterTimeStart>00:00:05.000000000</ChapterTimeStart>\
+                   </ChapterAtom></EditionEntry></Chapters>";
+        let chapters = parse_chapter_file(xml).unwrap();
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].start_ns, 5_000_000_000);
+    }
+
+    #[test]
+    fn offset_chapter_times_collapses_leading_chapters() {
+        let atom = |start_s: i64| ChapterAtom {
+            start_ns: start_s * 1_000_000_000,
+            end_ns: None,
+            displays: untagged_display("", 0),
+        };
+        let mut chapters = vec![atom(0), atom(5), atom(20), atom(60)];
+        offset_chapter_times(&mut chapters, -10_000_000_000);
+        let starts: Vec<i64> = chapters.iter().map(|c| c.start_ns).collect();
+        // The chapter at 5s is playing when the reference starts
+        assert_eq!(starts, [0, 10_000_000_000, 50_000_000_000]);
+    }
 }
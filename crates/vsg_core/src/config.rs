@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::chapters::keyframes::keyframe_cache_dir;
 use crate::models::settings::{AppSettings, PATH_SENTINEL};
 
 /// Application configuration manager — `AppConfig`
@@ -309,6 +310,14 @@ impl AppConfig {
         dir
     }
 
+    /// Returns the keyframe cache directory shared by chapter snapping and
+    /// the stepping boundary refiner.
+    pub fn get_keyframe_cache_dir(&self) -> PathBuf {
+        let dir = keyframe_cache_dir(&self.settings.temp_root);
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
     /// Clean up style editor temp files — `cleanup_style_editor_temp()`
     pub fn cleanup_style_editor_temp(&self) -> u32 {
         cleanup_dir_contents(&self.get_style_editor_temp_dir())
@@ -319,12 +328,35 @@ impl AppConfig {
         cleanup_dir_contents(&self.get_vs_index_dir())
     }
 
+    /// Clean up cached keyframe lists.
+    pub fn cleanup_keyframe_cache(&self) -> u32 {
+        cleanup_dir_contents(&self.get_keyframe_cache_dir())
+    }
+
     /// Clean up old files (> max_age_hours) in the style editor temp dir.
     /// 1:1 port of `cleanup_old_style_editor_temp()`.
     pub fn cleanup_old_style_editor_temp(&self, max_age_hours: f64) -> u32 {
         cleanup_old_dir_contents(&self.get_style_editor_temp_dir(), max_age_hours)
     }
 
+    /// Remove orphaned job temp dirs (`orch_*`) older than `max_age_hours`
+    /// from `temp_root`, left behind by crashed or killed runs.
+    pub fn cleanup_orphaned_job_temp(&self, max_age_hours: f64) -> u32 {
+        if max_age_hours <= 0.0 {
+            return 0;
+        }
+        cleanup_old_dir_entries(
+            Path::new(&self.settings.temp_root),
+            max_age_hours,
+            |path| {
+                path.is_dir()
+                    && path
+                        .file_name()
+                        .is_some_and(|n| n.to_string_lossy().starts_with("orch_"))
+            },
+        )
+    }
+
     /// Get a unique index directory for a specific video file.
     /// Uses MD5 hash of the video path (matches Python's hashlib.md5).
     /// 1:1 port of `get_vs_index_for_video()`.
@@ -458,6 +490,11 @@ fn cleanup_dir_contents(dir: &Path) -> u32 {
 /// Remove items older than `max_age_hours` from a directory.
 /// 1:1 port of `cleanup_old_style_editor_temp_files()`.
 fn cleanup_old_dir_contents(dir: &Path, max_age_hours: f64) -> u32 {
+    cleanup_old_dir_entries(dir, max_age_hours, |_| true)
+}
+
+/// Remove entries matching `filter` and older than `max_age_hours` from a directory.
+fn cleanup_old_dir_entries(dir: &Path, max_age_hours: f64, filter: impl Fn(&Path) -> bool) -> u32 {
     if !dir.exists() {
         return 0;
     }
@@ -469,6 +506,9 @@ fn cleanup_old_dir_contents(dir: &Path, max_age_hours: f64) -> u32 {
     if let Ok(entries) = fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
+            if !filter(&path) {
+                continue;
+            }
             let is_old = path
                 .metadata()
                 .ok()
@@ -585,4 +625,28 @@ mod tests {
         assert!(PathBuf::from(&config.settings.temp_root).exists());
         assert!(config.get_config_dir().exists());
     }
+
+    #[test]
+    fn cleanup_removes_only_stale_orch_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AppConfig::new(dir.path()).unwrap();
+        let temp_root = PathBuf::from(&config.settings.temp_root);
+
+        let stale = temp_root.join("orch_old_1700000000");
+        let fresh = temp_root.join("orch_new_1800000000");
+        let other = temp_root.join("vs_indexes");
+        for d in [&stale, &fresh, &other] {
+            fs::create_dir_all(d).unwrap();
+        }
+        let two_hours_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(7200);
+        for d in [&stale, &other] {
+            fs::File::open(d).unwrap().set_modified(two_hours_ago).unwrap();
+        }
+
+        assert_eq!(config.cleanup_orphaned_job_temp(1.0), 1);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(other.exists());
+        assert_eq!(config.cleanup_orphaned_job_temp(0.0), 0);
+    }
 }
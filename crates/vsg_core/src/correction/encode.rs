@@ -0,0 +1,404 @@
+//! Post-correction encode — re-encodes corrected audio per codec family.
+//!
+//! Every corrector writes FLAC. Depending on `corrected_audio_encode` (and
+//! its per-family overrides) the FLAC is kept, re-encoded to the original
+//! codec at the original bitrate, or mapped to Opus/AAC. The channel layout
+//! comes from the source track's `StreamProps`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::io::runner::CommandRunner;
+use crate::models::enums::CorrectedAudioEncode;
+use crate::models::media::StreamProps;
+use crate::models::settings::AppSettings;
+
+/// Result of the post-correction encode for one track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectedEncode {
+    /// File to mux (the FLAC itself when it was kept)
+    pub path: PathBuf,
+    /// Codec ID recorded on the track ("FLAC" when kept)
+    pub codec_id: String,
+    /// Target codec and bitrate when re-encoded, e.g. "Opus 256k"
+    pub transcode: Option<String>,
+}
+
+/// An ffmpeg encode target.
+struct Target {
+    encoder: &'static str,
+    codec_id: &'static str,
+    ext: &'static str,
+    format: Option<&'static str>,
+    label: &'static str,
+    max_channels: i32,
+    kbps_per_channel: i32,
+    max_kbps: i32,
+}
+
+const OPUS: Target = Target {
+    encoder: "libopus",
+    codec_id: "A_OPUS",
+    ext: "opus",
+    format: None,
+    label: "Opus",
+    max_channels: 8,
+    kbps_per_channel: 64,
+    max_kbps: 512,
+};
+const AAC: Target = Target {
+    encoder: "aac",
+    codec_id: "A_AAC",
+    ext: "aac",
+    format: Some("adts"),
+    label: "AAC",
+    max_channels: 8,
+    kbps_per_channel: 96,
+    max_kbps: 768,
+};
+const AC3: Target = Target {
+    encoder: "ac3",
+    codec_id: "A_AC3",
+    ext: "ac3",
+    format: None,
+    label: "AC-3",
+    max_channels: 6,
+    kbps_per_channel: 112,
+    max_kbps: 640,
+};
+const EAC3: Target = Target {
+    encoder: "eac3",
+    codec_id: "A_EAC3",
+    ext: "eac3",
+    format: None,
+    label: "E-AC-3",
+    max_channels: 6,
+    kbps_per_channel: 128,
+    max_kbps: 1536,
+};
+const VORBIS: Target = Target {
+    encoder: "libvorbis",
+    codec_id: "A_VORBIS",
+    ext: "ogg",
+    format: None,
+    label: "Vorbis",
+    max_channels: 8,
+    kbps_per_channel: 80,
+    max_kbps: 500,
+};
+const MP3: Target = Target {
+    encoder: "libmp3lame",
+    codec_id: "A_MPEG/L3",
+    ext: "mp3",
+    format: None,
+    label: "MP3",
+    max_channels: 2,
+    kbps_per_channel: 160,
+    max_kbps: 320,
+};
+
+/// Codec family of a Matroska codec ID, as used by the encode overrides.
+pub fn codec_family(codec_id: &str) -> &'static str {
+    let cid = codec_id.to_uppercase();
+    if cid.contains("TRUEHD")
+        || cid.contains("FLAC")
+        || cid.contains("PCM")
+        || cid.contains("MS/ACM")
+        || cid.contains("MLP")
+    {
+        "lossless"
+    } else if cid.contains("AC3") {
+        "ac3"
+    } else if cid.contains("DTS") {
+        "dts"
+    } else if cid.contains("AAC") {
+        "aac"
+    } else if cid.contains("OPUS") {
+        "opus"
+    } else if cid.contains("VORBIS") {
+        "vorbis"
+    } else if cid.contains("MPEG/L3") || cid.contains("MP3") {
+        "mp3"
+    } else {
+        "other"
+    }
+}
+
+/// Picks the policy for `family`: an override entry wins over the default.
+fn resolve_policy(
+    default: CorrectedAudioEncode,
+    overrides: &str,
+    family: &str,
+) -> CorrectedAudioEncode {
+    overrides
+        .split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(family))
+        .and_then(|(_, value)| parse_policy(value.trim()))
+        .unwrap_or(default)
+}
+
+fn parse_policy(value: &str) -> Option<CorrectedAudioEncode> {
+    match value.to_lowercase().as_str() {
+        "keep_flac" | "flac" => Some(CorrectedAudioEncode::KeepFlac),
+        "match_original" | "original" => Some(CorrectedAudioEncode::MatchOriginal),
+        "opus" => Some(CorrectedAudioEncode::Opus),
+        "aac" => Some(CorrectedAudioEncode::Aac),
+        _ => None,
+    }
+}
+
+/// Encoder for `policy`; `None` keeps the FLAC.
+fn target_for(policy: CorrectedAudioEncode, codec_id: &str) -> Option<&'static Target> {
+    match policy {
+        CorrectedAudioEncode::KeepFlac => None,
+        CorrectedAudioEncode::Opus => Some(&OPUS),
+        CorrectedAudioEncode::Aac => Some(&AAC),
+        CorrectedAudioEncode::MatchOriginal => match codec_family(codec_id) {
+            "ac3" if codec_id.to_uppercase().contains("EAC3") => Some(&EAC3),
+            "ac3" => Some(&AC3),
+            "aac" => Some(&AAC),
+            "opus" => Some(&OPUS),
+            "vorbis" => Some(&VORBIS),
+            "mp3" => Some(&MP3),
+            // Lossless stays FLAC; DTS/TrueHD encoders in ffmpeg are experimental
+            _ => None,
+        },
+    }
+}
+
+/// ffmpeg channel layout name for a channel count.
+fn channel_layout(channels: i32) -> Option<&'static str> {
+    match channels {
+        1 => Some("mono"),
+        2 => Some("stereo"),
+        3 => Some("2.1"),
+        4 => Some("quad"),
+        5 => Some("5.0"),
+        6 => Some("5.1"),
+        7 => Some("6.1"),
+        8 => Some("7.1"),
+        _ => None,
+    }
+}
+
+/// Applies the encode policy to a corrected FLAC.
+///
+/// `original_path` is the extracted source track (probed for its bitrate in
+/// match-original mode). Any failure falls back to keeping the FLAC.
+pub fn encode_corrected_audio(
+    flac_path: &Path,
+    original_path: &Path,
+    original: &StreamProps,
+    settings: &AppSettings,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> CorrectedEncode {
+    let keep = CorrectedEncode {
+        path: flac_path.to_path_buf(),
+        codec_id: "FLAC".to_string(),
+        transcode: None,
+    };
+
+    let family = codec_family(&original.codec_id);
+    let policy = resolve_policy(
+        settings.corrected_audio_encode,
+        &settings.corrected_audio_encode_overrides,
+        family,
+    );
+    let target = match target_for(policy, &original.codec_id) {
+        Some(t) => t,
+        None => {
+            if policy == CorrectedAudioEncode::MatchOriginal {
+                runner.log_message(&format!(
+                    "[CorrectionEncode] No re-encode for {family} audio ({}); keeping FLAC.",
+                    original.codec_id
+                ));
+            }
+            return keep;
+        }
+    };
+
+    let channels = original
+        .audio_channels
+        .map(|c| c.clamp(1, target.max_channels));
+    let default_kbps = target.kbps_per_channel * channels.unwrap_or(2);
+    let kbps = match policy {
+        CorrectedAudioEncode::MatchOriginal => {
+            probe_bitrate_kbps(original_path, runner, tool_paths)
+        }
+        _ => Some(settings.corrected_audio_bitrate_kbps).filter(|k| *k > 0),
+    }
+    .unwrap_or(default_kbps)
+    .min(target.max_kbps);
+
+    let out_path = flac_path.with_extension(target.ext);
+    let flac_str = flac_path.to_string_lossy().to_string();
+    let out_str = out_path.to_string_lossy().to_string();
+    let bitrate = format!("{kbps}k");
+    let layout_filter = channels
+        .and_then(channel_layout)
+        .map(|l| format!("aformat=channel_layouts={l}"));
+
+    let mut cmd: Vec<&str> = vec!["ffmpeg", "-y", "-nostdin", "-v", "error", "-i", &flac_str];
+    if let Some(ref filter) = layout_filter {
+        cmd.extend(["-af", filter.as_str()]);
+    }
+    cmd.extend(["-c:a", target.encoder, "-b:a", &bitrate]);
+    if target.encoder == "libopus" {
+        // Opus only runs at 48 kHz; surround needs the Vorbis mapping family
+        cmd.extend(["-ar", "48000"]);
+        if channels.unwrap_or(2) > 2 {
+            cmd.extend(["-mapping_family", "1"]);
+        }
+    }
+    if let Some(format) = target.format {
+        cmd.extend(["-f", format]);
+    }
+    cmd.push(&out_str);
+
+    if runner.run(&cmd, tool_paths).is_err() {
+        runner.log_message(&format!(
+            "[CorrectionEncode] {} encode failed for {}; keeping FLAC.",
+            target.label,
+            flac_path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        return keep;
+    }
+
+    let transcode = format!("{} {bitrate}", target.label);
+    runner.log_message(&format!(
+        "[CorrectionEncode] {} -> {transcode} ({})",
+        flac_path.file_name().unwrap_or_default().to_string_lossy(),
+        layout_filter
+            .as_deref()
+            .and_then(|f| f.strip_prefix("aformat=channel_layouts="))
+            .unwrap_or("source layout")
+    ));
+    CorrectedEncode {
+        path: out_path,
+        codec_id: target.codec_id.to_string(),
+        transcode: Some(transcode),
+    }
+}
+
+/// Name for a corrected track: the label goes in parentheses after the
+/// original name. `{codec}` in the label expands to the final codec; without
+/// it a re-encode is appended to the label.
+pub fn corrected_track_name(original_name: &str, label: &str, encode: &CorrectedEncode) -> String {
+    let label = if label.contains("{codec}") {
+        label.replace("{codec}", encode.transcode.as_deref().unwrap_or("FLAC"))
+    } else {
+        match (&encode.transcode, label.is_empty()) {
+            (Some(t), true) => t.clone(),
+            (Some(t), false) => format!("{label}, {t}"),
+            (None, _) => label.to_string(),
+        }
+    };
+
+    if label.is_empty() {
+        original_name.to_string()
+    } else if original_name.is_empty() {
+        label
+    } else {
+        format!("{original_name} ({label})")
+    }
+}
+
+/// Bitrate of the first audio stream in kb/s, via ffprobe.
+fn probe_bitrate_kbps(
+    path: &Path,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Option<i32> {
+    let path_str = path.to_string_lossy();
+    let out = runner
+        .run(
+            &[
+                "ffprobe",
+                "-v",
+                "error",
+                "-select_streams",
+                "a:0",
+                "-show_entries",
+                "stream=bit_rate:format=bit_rate",
+                "-of",
+                "json",
+                &path_str,
+            ],
+            tool_paths,
+        )
+        .ok()?;
+    let val: serde_json::Value = serde_json::from_str(&out).ok()?;
+    let parse = |v: &serde_json::Value| {
+        v.get("bit_rate").and_then(|b| {
+            b.as_str()
+                .and_then(|s| s.parse::<i64>().ok())
+                .or_else(|| b.as_i64())
+        })
+    };
+    val.get("streams")
+        .and_then(|s| s.as_array())
+        .and_then(|s| s.first())
+        .and_then(parse)
+        .or_else(|| val.get("format").and_then(parse))
+        .map(|bps| (bps / 1000) as i32)
+        .filter(|k| *k > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_pick_policy_per_family() {
+        let overrides = "ac3=match_original, lossless = keep_flac, aac=opus";
+        let default = CorrectedAudioEncode::Aac;
+        assert_eq!(
+            resolve_policy(default, overrides, codec_family("A_EAC3")),
+            CorrectedAudioEncode::MatchOriginal
+        );
+        assert_eq!(
+            resolve_policy(default, overrides, codec_family("A_TRUEHD")),
+            CorrectedAudioEncode::KeepFlac
+        );
+        assert_eq!(
+            resolve_policy(default, overrides, codec_family("A_DTS")),
+            CorrectedAudioEncode::Aac
+        );
+        let eac3 = target_for(CorrectedAudioEncode::MatchOriginal, "A_EAC3").unwrap();
+        assert_eq!(eac3.codec_id, "A_EAC3");
+        assert!(target_for(CorrectedAudioEncode::MatchOriginal, "A_DTS").is_none());
+    }
+
+    #[test]
+    fn track_name_records_transcode() {
+        let flac = CorrectedEncode {
+            path: PathBuf::from("a.flac"),
+            codec_id: "FLAC".to_string(),
+            transcode: None,
+        };
+        let opus = CorrectedEncode {
+            path: PathBuf::from("a.opus"),
+            codec_id: "A_OPUS".to_string(),
+            transcode: Some("Opus 128k".to_string()),
+        };
+        assert_eq!(
+            corrected_track_name("Main", "Corrected", &flac),
+            "Main (Corrected)"
+        );
+        assert_eq!(corrected_track_name("Main", "", &flac), "Main");
+        assert_eq!(
+            corrected_track_name("Main", "Corrected", &opus),
+            "Main (Corrected, Opus 128k)"
+        );
+        assert_eq!(
+            corrected_track_name("", "Fixed {codec}", &opus),
+            "Fixed Opus 128k"
+        );
+        assert_eq!(
+            corrected_track_name("Main", "{codec}", &flac),
+            "Main (FLAC)"
+        );
+    }
+}
@@ -1,17 +1,20 @@
 //! Linear drift correction — 1:1 port of `vsg_core/correction/linear.py`.
 //!
-//! Corrects constant audio drift by resampling the audio speed via ffmpeg.
+//! Corrects constant audio drift by resampling the audio speed via ffmpeg
+//! (or in-process with the native engine).
 //! Creates preserved copies of original tracks.
 
 use std::collections::HashMap;
 
+use crate::correction::encode::{corrected_track_name, encode_corrected_audio};
+use crate::correction::{failure_hint, native};
 use crate::io::runner::CommandRunner;
 use crate::models::enums::{ResampleEngine, TrackType};
 use crate::models::media::{StreamProps, Track};
 use crate::orchestrator::steps::context::Context;
 
 /// Helper to get the sample rate of the first audio stream via ffprobe.
-fn get_sample_rate(
+pub(crate) fn get_sample_rate(
     file_path: &str,
     runner: &CommandRunner,
     tool_paths: &HashMap<String, String>,
@@ -26,8 +29,8 @@ fn get_sample_rate(
     ];
 
     let out = match runner.run(&cmd, tool_paths) {
-        Some(o) => o,
-        None => {
+        Ok(o) => o,
+        Err(_) => {
             runner.log_message("[WARN] Could not probe sample rate, defaulting to 48000 Hz.");
             return 48000;
         }
@@ -126,6 +129,12 @@ pub fn run_linear_correction(ctx: &mut Context, runner: &CommandRunner) {
             let resample_engine = &ctx.settings.segment_resample_engine;
 
             let filter_chain = match resample_engine {
+                ResampleEngine::Native => {
+                    runner.log_message(
+                        "    - Using 'native' engine for in-process resampling."
+                    );
+                    None
+                }
                 ResampleEngine::Rubberband => {
                     runner.log_message(
                         "    - Using 'rubberband' engine for high-quality resampling."
@@ -146,45 +155,65 @@ pub fn run_linear_correction(ctx: &mut Context, runner: &CommandRunner) {
                         rb_opts.push("pitchq=on".to_string());
                     }
 
-                    format!("rubberband={}", rb_opts.join(":"))
+                    Some(format!("rubberband={}", rb_opts.join(":")))
                 }
                 ResampleEngine::Atempo => {
                     runner.log_message("    - Using 'atempo' engine for fast resampling.");
-                    format!("atempo={tempo_ratio}")
+                    Some(format!("atempo={tempo_ratio}"))
                 }
                 ResampleEngine::Aresample => {
                     runner.log_message(
                         "    - Using 'aresample' engine for high-quality resampling."
                     );
                     let new_sample_rate = sample_rate as f64 * tempo_ratio;
-                    format!("asetrate={new_sample_rate},aresample={sample_rate}")
+                    Some(format!("asetrate={new_sample_rate},aresample={sample_rate}"))
                 }
             };
 
             let original_path_str = original_path.to_string_lossy().to_string();
             let corrected_path_str = corrected_path.to_string_lossy().to_string();
 
-            let resample_cmd: Vec<&str> = vec![
-                "ffmpeg",
-                "-y",
-                "-nostdin",
-                "-v", "error",
-                "-i", &original_path_str,
-                "-af", &filter_chain,
-                "-c:a", "flac",
-                &corrected_path_str,
-            ];
-
-            if runner.run(&resample_cmd, &ctx.tool_paths).is_none() {
+            // On failure, the hint to append to the error
+            let failure = match &filter_chain {
+                Some(filter_chain) => {
+                    let resample_cmd: Vec<&str> = vec![
+                        "ffmpeg",
+                        "-y",
+                        "-nostdin",
+                        "-v", "error",
+                        "-i", &original_path_str,
+                        "-af", filter_chain,
+                        "-c:a", "flac",
+                        &corrected_path_str,
+                    ];
+                    runner.run(&resample_cmd, &ctx.tool_paths).err().map(|e| {
+                        failure_hint(&e, matches!(resample_engine, ResampleEngine::Rubberband))
+                    })
+                }
+                None => match native::stretch_file(
+                    &original_path,
+                    &corrected_path,
+                    tempo_ratio,
+                    ctx.settings.segment_rb_pitch_correct,
+                    runner,
+                    &ctx.tool_paths,
+                ) {
+                    Ok(()) => None,
+                    Err(e) => {
+                        runner.log_message(&format!("[ERROR] {e}"));
+                        Some(String::new())
+                    }
+                },
+            };
+
+            if let Some(hint) = failure {
                 let engine_str = resample_engine.to_string();
                 let mut error_msg = format!(
                     "Linear drift correction with '{}' failed for {}.",
                     engine_str,
                     original_path.file_name().unwrap_or_default().to_string_lossy()
                 );
-                if matches!(resample_engine, ResampleEngine::Rubberband) {
-                    error_msg.push_str(" (Ensure your FFmpeg build includes 'librubberband').");
-                }
+                error_msg.push_str(&hint);
                 runner.log_message(&format!("[ERROR] {error_msg}"));
                 continue;
             }
@@ -216,18 +245,26 @@ pub fn run_linear_correction(ctx: &mut Context, runner: &CommandRunner) {
                     codec_id: original_props.codec_id.clone(),
                     lang: original_props.lang.clone(),
                     name: preserved_name,
+                    audio_channels: original_props.audio_channels,
                 },
             };
 
-            // Update the main track to point to corrected FLAC
-            let corrected_name = if !original_props.name.is_empty() {
-                format!("{} (Drift Corrected)", original_props.name)
-            } else {
-                "Drift Corrected".to_string()
-            };
+            // Re-encode per policy, then point the main track at the result
+            let encoded = encode_corrected_audio(
+                &corrected_path,
+                &original_path,
+                &original_props,
+                &ctx.settings,
+                runner,
+                &ctx.tool_paths,
+            );
+            let corrected_name =
+                corrected_track_name(&original_props.name, "Drift Corrected", &encoded);
 
+            let items = ctx.extracted_items.as_mut().unwrap();
             let target_item = &mut items[idx];
-            target_item.extracted_path = Some(corrected_path.clone());
+            target_item.extracted_path = Some(encoded.path.clone());
+            target_item.correction_transcode = encoded.transcode.clone();
             target_item.is_corrected = true;
             target_item.container_delay_ms = 0;
             target_item.track = Track {
@@ -235,9 +272,10 @@ pub fn run_linear_correction(ctx: &mut Context, runner: &CommandRunner) {
                 id: target_item.track.id,
                 track_type: target_item.track.track_type,
                 props: StreamProps {
-                    codec_id: "FLAC".to_string(),
+                    codec_id: encoded.codec_id.clone(),
                     lang: original_props.lang.clone(),
                     name: corrected_name,
+                    audio_channels: original_props.audio_channels,
                 },
             };
             target_item.apply_track_name = true;
@@ -1,14 +1,37 @@
 //! Audio correction module — 1:1 port of `vsg_core/correction/__init__.py`.
 //!
-//! Three correction strategies:
-//! - **Linear**: constant drift correction via ffmpeg resampling
+//! Four correction strategies:
+//! - **Linear**: constant drift correction via ffmpeg or native resampling
 //! - **PAL**: PAL speed correction via rubberband tempo adjustment
+//! - **Speed**: any speed ratio (snapped or fitted), with or without pitch correction
 //! - **Stepping**: segmented correction for stepped delay changes
+//!
+//! `native` holds the in-process resampler and time-stretch used when
+//! `segment_resample_engine = native`.
+//!
+//! The corrected FLAC is then re-encoded per the encode policy (`encode`).
 
+pub mod encode;
 pub mod linear;
+pub mod native;
 pub mod pal;
+pub mod speed;
 pub mod stepping;
 
+pub use encode::{encode_corrected_audio, CorrectedEncode};
 pub use linear::run_linear_correction;
 pub use pal::run_pal_correction;
+pub use speed::run_speed_correction;
 pub use stepping::{run_stepping_correction, apply_plan_to_file, AudioSegment};
+
+use crate::io::runner::RunError;
+
+/// Suffix for a failed ffmpeg correction: a timeout is reported as such,
+/// any other failure may come from a build without rubberband.
+pub(crate) fn failure_hint(e: &RunError, uses_rubberband: bool) -> String {
+    match e {
+        RunError::TimedOut { .. } => format!(" ({e}; raise the tool timeout for long tracks)."),
+        _ if uses_rubberband => " (Ensure your FFmpeg build includes 'librubberband').".to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,856 @@
+//! Native in-process resampling and time-stretch.
+//!
+//! Backs `ResampleEngine::Native`: the track is decoded once, retimed in
+//! process and encoded straight to FLAC, instead of one ffmpeg filter pass
+//! per file or segment. Files are streamed block by block.
+//!
+//! - **Varispeed** (`resample_varispeed`): band-limited windowed-sinc
+//!   interpolation, the in-process equivalent of `asetrate,aresample`.
+//!   Pitch follows speed.
+//! - **Time-stretch** (`time_stretch_wsola`): WSOLA overlap-add, the
+//!   equivalent of `atempo`. Pitch is kept.
+//!
+//! `tempo` has the ffmpeg meaning throughout: output duration is
+//! `input duration / tempo`.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+
+use crate::correction::stepping::audio_assembly::get_audio_properties;
+use crate::io::runner::CommandRunner;
+
+/// Half-width of the interpolation kernel, in input samples.
+const SINC_HALF_WIDTH: usize = 32;
+/// Kernel phases between two input samples (linearly interpolated).
+const SINC_PHASES: usize = 256;
+/// Passband edge relative to the output Nyquist frequency.
+const SINC_ROLLOFF: f64 = 0.95;
+
+/// WSOLA frame length in seconds (50% overlap).
+const WSOLA_FRAME_S: f64 = 0.040;
+/// WSOLA similarity search radius in seconds.
+const WSOLA_SEARCH_S: f64 = 0.010;
+
+/// Retime interleaved PCM by `tempo`, keeping pitch when `preserve_pitch`.
+pub fn stretch_interleaved(
+    pcm: &[i32],
+    channels: usize,
+    sample_rate: i32,
+    tempo: f64,
+    preserve_pitch: bool,
+) -> Vec<i32> {
+    let planes = deinterleave(pcm, channels);
+    let out = if preserve_pitch {
+        time_stretch_wsola(&planes, sample_rate, tempo)
+    } else {
+        resample_varispeed(&planes, tempo)
+    };
+    interleave(&out)
+}
+
+/// Decode `input`, retime it by `tempo` and write FLAC to `output`.
+///
+/// Decoding, retiming and encoding run as one pipeline over blocks of about
+/// a second, so memory use does not grow with the track's length.
+pub fn stretch_file(
+    input: &Path,
+    output: &Path,
+    tempo: f64,
+    preserve_pitch: bool,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<(), String> {
+    let input_str = input.to_string_lossy().to_string();
+    let (channels, channel_layout, sample_rate) =
+        get_audio_properties(&input_str, 0, runner, tool_paths)?;
+    let channels = channels.max(1) as usize;
+    let ffmpeg = tool_paths.get("ffmpeg").map_or("ffmpeg", String::as_str);
+    let (sr, ch) = (sample_rate.to_string(), channels.to_string());
+
+    let mut decoder = Command::new(ffmpeg)
+        .args(["-nostdin", "-v", "error", "-i", &input_str, "-map", "0:a:0"])
+        .args(["-ac", &ch, "-ar", &sr, "-f", "s32le", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start ffmpeg to decode {input_str}: {e}"))?;
+    let mut encoder = match Command::new(ffmpeg)
+        .args([
+            "-y", "-v", "error", "-nostdin", "-f", "s32le", "-ar", &sr, "-ac", &ch,
+        ])
+        .args(["-channel_layout", &channel_layout, "-i", "-"])
+        .args(["-map_metadata", "-1", "-map_metadata:s:a", "-1"])
+        .args(["-fflags", "+bitexact", "-c:a", "flac"])
+        .arg(output)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = decoder.kill();
+            let _ = decoder.wait();
+            return Err(format!(
+                "Failed to start ffmpeg to encode {}: {e}",
+                output.display()
+            ));
+        }
+    };
+    let decode_log = drain(decoder.stderr.take());
+    let encode_log = drain(encoder.stderr.take());
+
+    let stretcher = Stretcher::new(channels, sample_rate, tempo, preserve_pitch);
+    let pumped = match (decoder.stdout.take(), encoder.stdin.take()) {
+        (Some(pcm_in), Some(pcm_out)) => pump(
+            pcm_in,
+            pcm_out,
+            stretcher,
+            channels,
+            sample_rate.max(1) as usize,
+        ),
+        _ => Err("ffmpeg pipes were not opened".to_string()),
+    };
+    if pumped.is_err() {
+        let _ = decoder.kill();
+        let _ = encoder.kill();
+    }
+    let decoded = decoder.wait().map(|s| s.success()).unwrap_or(false);
+    let encoded = encoder.wait().map(|s| s.success()).unwrap_or(false);
+    let decode_log = decode_log.join().unwrap_or_default();
+    let encode_log = encode_log.join().unwrap_or_default();
+
+    if !decoded {
+        return Err(format!(
+            "Failed to decode {input_str}: {}",
+            decode_log.trim()
+        ));
+    }
+    if !encoded {
+        return Err(format!(
+            "Failed to encode {}: {}",
+            output.display(),
+            encode_log.trim()
+        ));
+    }
+    pumped
+}
+
+/// Feed decoded PCM through `stretcher` into the encoder, `block` frames at
+/// a time. Dropping `pcm_out` at the end closes the encoder's input.
+fn pump(
+    mut pcm_in: impl Read,
+    mut pcm_out: impl Write,
+    mut stretcher: Stretcher,
+    channels: usize,
+    block: usize,
+) -> Result<(), String> {
+    let frame_bytes = 4 * channels;
+    let mut buf = vec![0u8; block * frame_bytes];
+    loop {
+        let filled = read_full(&mut pcm_in, &mut buf)
+            .map_err(|e| format!("Failed to read decoded audio: {e}"))?;
+        let whole = filled / frame_bytes * frame_bytes;
+        if whole > 0 {
+            let pcm: Vec<i32> = buf[..whole]
+                .chunks_exact(4)
+                .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            let out = stretcher.push(&deinterleave(&pcm, channels));
+            write_planes(&mut pcm_out, &out)?;
+        }
+        if filled < buf.len() {
+            break;
+        }
+    }
+    write_planes(&mut pcm_out, &stretcher.finish())
+}
+
+fn write_planes(pcm_out: &mut impl Write, planes: &[Vec<f64>]) -> Result<(), String> {
+    let bytes: Vec<u8> = interleave(planes)
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    pcm_out
+        .write_all(&bytes)
+        .map_err(|e| format!("Failed to write retimed audio: {e}"))
+}
+
+/// Read until `buf` is full or the stream ends; returns the bytes read.
+fn read_full(src: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match src.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Collect a child's stderr on a thread so it can't fill up and block.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut text);
+        }
+        text
+    })
+}
+
+/// Incremental retimer: takes planar blocks and returns the output that no
+/// later input can change. The concatenated output equals the one-shot
+/// `resample_varispeed` / `time_stretch_wsola` result.
+enum Stretcher {
+    Passthrough,
+    Varispeed(Varispeed),
+    Wsola(Wsola),
+}
+
+impl Stretcher {
+    fn new(channels: usize, sample_rate: i32, tempo: f64, preserve_pitch: bool) -> Self {
+        if channels == 0 || !(tempo.is_finite() && tempo > 0.0) {
+            Self::Passthrough
+        } else if preserve_pitch {
+            Self::Wsola(Wsola::new(channels, sample_rate, tempo))
+        } else {
+            Self::Varispeed(Varispeed::new(channels, tempo))
+        }
+    }
+
+    fn push(&mut self, block: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        match self {
+            Self::Passthrough => block.to_vec(),
+            Self::Varispeed(v) => {
+                v.append(block);
+                v.produce(false)
+            }
+            Self::Wsola(w) => {
+                w.append(block);
+                w.produce(false)
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Vec<Vec<f64>> {
+        match self {
+            Self::Passthrough => Vec::new(),
+            Self::Varispeed(v) => v.produce(true),
+            Self::Wsola(w) => w.finish(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Varispeed (windowed-sinc interpolation)
+// ---------------------------------------------------------------------------
+
+/// Resample each channel so it plays `tempo` times faster.
+pub fn resample_varispeed(planes: &[Vec<f64>], tempo: f64) -> Vec<Vec<f64>> {
+    if planes.is_empty() || !(tempo.is_finite() && tempo > 0.0) {
+        return planes.to_vec();
+    }
+    let mut varispeed = Varispeed::new(planes.len(), tempo);
+    varispeed.append(planes);
+    varispeed.produce(true)
+}
+
+/// Streaming varispeed state.
+struct Varispeed {
+    table: SincTable,
+    step: f64,
+    /// Buffered input per channel; `input[c][0]` is input frame `offset`.
+    input: Vec<Vec<f64>>,
+    offset: usize,
+    /// Input frames received so far.
+    n_in: usize,
+    /// Next output frame.
+    k: usize,
+}
+
+impl Varispeed {
+    fn new(channels: usize, tempo: f64) -> Self {
+        Self {
+            table: SincTable::new((SINC_ROLLOFF / tempo).min(SINC_ROLLOFF)),
+            step: tempo,
+            input: vec![Vec::new(); channels],
+            offset: 0,
+            n_in: 0,
+            k: 0,
+        }
+    }
+
+    fn append(&mut self, block: &[Vec<f64>]) {
+        for (buf, plane) in self.input.iter_mut().zip(block) {
+            buf.extend_from_slice(plane);
+        }
+        self.n_in += block.first().map_or(0, Vec::len);
+    }
+
+    /// Output every frame whose kernel is fully buffered, or at `eof` every
+    /// remaining frame (input past the end reads as silence).
+    fn produce(&mut self, eof: bool) -> Vec<Vec<f64>> {
+        let (start, step) = (self.k, self.step);
+        let end = if eof {
+            ((self.n_in as f64 / step).round() as usize).max(start)
+        } else {
+            // Output k reads input up to floor(k * step) + SINC_HALF_WIDTH
+            let mut end = start;
+            while (end as f64 * step).floor() as usize + SINC_HALF_WIDTH < self.n_in {
+                end += 1;
+            }
+            end
+        };
+
+        let (table, offset) = (&self.table, self.offset);
+        let out = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .input
+                .iter()
+                .map(|plane| scope.spawn(move || table.resample(plane, offset, step, start, end)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|_| vec![0.0; end - start]))
+                .collect()
+        });
+        self.k = end;
+
+        // Drop input that no later output frame reads
+        let first_needed =
+            ((end as f64 * step).floor() as usize).saturating_sub(SINC_HALF_WIDTH - 1);
+        let drop = first_needed
+            .saturating_sub(self.offset)
+            .min(self.n_in - self.offset);
+        if drop > 0 {
+            for buf in &mut self.input {
+                buf.drain(..drop);
+            }
+            self.offset += drop;
+        }
+        out
+    }
+}
+
+/// Oversampled Blackman-windowed sinc kernel.
+struct SincTable {
+    /// `(SINC_PHASES + 1)` rows of `2 * SINC_HALF_WIDTH` taps.
+    taps: Vec<f64>,
+}
+
+impl SincTable {
+    fn new(cutoff: f64) -> Self {
+        let width = 2 * SINC_HALF_WIDTH;
+        let mut taps = vec![0.0; (SINC_PHASES + 1) * width];
+        for phase in 0..=SINC_PHASES {
+            let frac = phase as f64 / SINC_PHASES as f64;
+            for j in 0..width {
+                let x = j as f64 - (SINC_HALF_WIDTH as f64 - 1.0) - frac;
+                taps[phase * width + j] = cutoff * sinc(cutoff * x) * blackman(x);
+            }
+        }
+        Self { taps }
+    }
+
+    /// Output frames `start..end`; `input[0]` is input frame `offset`.
+    fn resample(
+        &self,
+        input: &[f64],
+        offset: usize,
+        step: f64,
+        start: usize,
+        end: usize,
+    ) -> Vec<f64> {
+        let width = 2 * SINC_HALF_WIDTH;
+        let n_in = input.len() as isize;
+        let mut out = Vec::with_capacity(end - start);
+
+        for k in start..end {
+            let t = k as f64 * step;
+            let base = t.floor();
+            let pos = (t - base) * SINC_PHASES as f64;
+            let phase = (pos as usize).min(SINC_PHASES - 1);
+            let blend = pos - phase as f64;
+            let row_a = &self.taps[phase * width..(phase + 1) * width];
+            let row_b = &self.taps[(phase + 1) * width..(phase + 2) * width];
+
+            let first = base as isize - (SINC_HALF_WIDTH as isize - 1) - offset as isize;
+            let mut acc = 0.0;
+            for j in 0..width {
+                let idx = first + j as isize;
+                if idx < 0 || idx >= n_in {
+                    continue;
+                }
+                let h = row_a[j] + (row_b[j] - row_a[j]) * blend;
+                acc += input[idx as usize] * h;
+            }
+            out.push(acc);
+        }
+        out
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-12 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Blackman window over `[-SINC_HALF_WIDTH, SINC_HALF_WIDTH]`.
+fn blackman(x: f64) -> f64 {
+    let half = SINC_HALF_WIDTH as f64;
+    if x.abs() >= half {
+        return 0.0;
+    }
+    let a = std::f64::consts::PI * x / half;
+    0.42 + 0.5 * a.cos() + 0.08 * (2.0 * a).cos()
+}
+
+// ---------------------------------------------------------------------------
+// Time-stretch (WSOLA)
+// ---------------------------------------------------------------------------
+
+/// Stretch each channel by `tempo` without changing pitch.
+///
+/// Frame positions are chosen on the channel mix so every channel gets the
+/// same splice points and inter-channel phase is kept.
+pub fn time_stretch_wsola(planes: &[Vec<f64>], sample_rate: i32, tempo: f64) -> Vec<Vec<f64>> {
+    if planes.is_empty() || !(tempo.is_finite() && tempo > 0.0) {
+        return planes.to_vec();
+    }
+    let mut wsola = Wsola::new(planes.len(), sample_rate, tempo);
+    wsola.append(planes);
+    wsola.finish()
+}
+
+/// Streaming WSOLA state.
+struct Wsola {
+    tempo: f64,
+    frame: usize,
+    hop: usize,
+    radius: usize,
+    window: Vec<f64>,
+    /// Buffered input per channel and its mix; index 0 is input frame `offset`.
+    input: Vec<Vec<f64>>,
+    mix: Vec<f64>,
+    offset: usize,
+    /// Input frames received so far.
+    n_in: usize,
+    /// Overlap-add accumulator; index 0 is output frame `acc_offset`.
+    acc: Vec<Vec<f64>>,
+    acc_offset: usize,
+    /// Next WSOLA frame and the input position of the previous one.
+    k: usize,
+    prev_pos: usize,
+}
+
+impl Wsola {
+    fn new(channels: usize, sample_rate: i32, tempo: f64) -> Self {
+        let frame = ((WSOLA_FRAME_S * sample_rate as f64) as usize / 2 * 2).max(64);
+        let window = (0..frame)
+            .map(|n| 0.5 - 0.5 * (2.0 * std::f64::consts::PI * n as f64 / frame as f64).cos())
+            .collect();
+        Self {
+            tempo,
+            frame,
+            hop: frame / 2,
+            radius: ((WSOLA_SEARCH_S * sample_rate as f64) as usize).max(8),
+            window,
+            input: vec![Vec::new(); channels],
+            mix: Vec::new(),
+            offset: 0,
+            n_in: 0,
+            acc: vec![Vec::new(); channels],
+            acc_offset: 0,
+            k: 0,
+            prev_pos: 0,
+        }
+    }
+
+    fn append(&mut self, block: &[Vec<f64>]) {
+        let len = block.first().map_or(0, Vec::len);
+        for (buf, plane) in self.input.iter_mut().zip(block) {
+            buf.extend_from_slice(plane);
+        }
+        self.mix
+            .extend((0..len).map(|i| block.iter().map(|p| p[i]).sum::<f64>()));
+        self.n_in += len;
+    }
+
+    fn finish(&mut self) -> Vec<Vec<f64>> {
+        if self.n_in < self.frame + self.radius {
+            // Too short to splice; nothing has been output or dropped yet
+            return resample_varispeed(&self.input, self.tempo);
+        }
+        self.produce(true)
+    }
+
+    /// Place every frame whose search window is fully buffered (or at `eof`
+    /// every remaining frame) and output the samples no later frame overlaps.
+    fn produce(&mut self, eof: bool) -> Vec<Vec<f64>> {
+        let (frame, hop, radius) = (self.frame, self.hop, self.radius);
+        let n_out = (self.n_in as f64 / self.tempo).round() as usize;
+
+        loop {
+            let out_pos = self.k * hop;
+            let nominal = (out_pos as f64 * self.tempo).round() as usize;
+            if eof {
+                if out_pos >= n_out {
+                    break;
+                }
+            } else if (nominal + radius).max(self.prev_pos + hop) + frame > self.n_in {
+                break;
+            }
+
+            let max_pos = self.n_in - frame;
+            let pos = if self.k == 0 {
+                0
+            } else {
+                let target = (self.prev_pos + hop).min(max_pos);
+                let lo = nominal.saturating_sub(radius).min(max_pos);
+                let hi = (nominal + radius).min(max_pos);
+                let off = self.offset;
+                best_overlap(&self.mix, target - off, lo - off, hi - off, hop) + off
+            };
+
+            let start = out_pos - self.acc_offset;
+            for (plane, dest) in self.input.iter().zip(self.acc.iter_mut()) {
+                if dest.len() < start + frame {
+                    dest.resize(start + frame, 0.0);
+                }
+                let src = &plane[pos - self.offset..pos - self.offset + frame];
+                for n in 0..frame {
+                    // The first frame has no predecessor to overlap with
+                    let w = if self.k == 0 && n < hop {
+                        1.0
+                    } else {
+                        self.window[n]
+                    };
+                    dest[start + n] += src[n] * w;
+                }
+            }
+
+            self.prev_pos = pos;
+            self.k += 1;
+        }
+
+        // Later frames start at k * hop or after, so everything before is final
+        let ready = if eof { n_out } else { self.k * hop };
+        let count = ready.saturating_sub(self.acc_offset);
+        let out = self
+            .acc
+            .iter_mut()
+            .map(|dest| {
+                if dest.len() < count {
+                    dest.resize(count, 0.0);
+                }
+                dest.drain(..count).collect()
+            })
+            .collect();
+        self.acc_offset += count;
+
+        // Keep input from the earliest position the next frame can read
+        let next_nominal = ((self.k * hop) as f64 * self.tempo).round() as usize;
+        let keep = (self.prev_pos + hop)
+            .min(next_nominal.saturating_sub(radius))
+            .min(self.n_in.saturating_sub(frame));
+        let drop = keep.saturating_sub(self.offset);
+        if !eof && drop > 0 {
+            for buf in &mut self.input {
+                buf.drain(..drop);
+            }
+            self.mix.drain(..drop);
+            self.offset += drop;
+        }
+        out
+    }
+}
+
+/// Position in `lo..=hi` whose first `len` samples best continue `target`.
+///
+/// Coarse pass on every 4th lag and sample, then a full-resolution refine.
+fn best_overlap(mix: &[f64], target: usize, lo: usize, hi: usize, len: usize) -> usize {
+    let reference = &mix[target..target + len];
+    let score = |cand: usize, stride: usize| -> f64 {
+        let segment = &mix[cand..cand + len];
+        let (mut dot, mut energy) = (0.0, 0.0);
+        for i in (0..len).step_by(stride) {
+            dot += segment[i] * reference[i];
+            energy += segment[i] * segment[i];
+        }
+        if energy > 0.0 {
+            dot / energy.sqrt()
+        } else {
+            0.0
+        }
+    };
+
+    let mut best = lo;
+    let mut best_score = f64::NEG_INFINITY;
+    for cand in (lo..=hi).step_by(4) {
+        let s = score(cand, 4);
+        if s > best_score {
+            best_score = s;
+            best = cand;
+        }
+    }
+
+    let (fine_lo, fine_hi) = (best.saturating_sub(3).max(lo), (best + 3).min(hi));
+    best_score = f64::NEG_INFINITY;
+    for cand in fine_lo..=fine_hi {
+        let s = score(cand, 1);
+        if s > best_score {
+            best_score = s;
+            best = cand;
+        }
+    }
+    best
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn deinterleave(pcm: &[i32], channels: usize) -> Vec<Vec<f64>> {
+    let channels = channels.max(1);
+    (0..channels)
+        .map(|c| {
+            pcm.iter()
+                .skip(c)
+                .step_by(channels)
+                .map(|&s| s as f64)
+                .collect()
+        })
+        .collect()
+}
+
+fn interleave(planes: &[Vec<f64>]) -> Vec<i32> {
+    let frames = planes.first().map_or(0, |p| p.len());
+    let mut pcm = Vec::with_capacity(frames * planes.len());
+    for i in 0..frames {
+        for plane in planes {
+            pcm.push(plane[i].round().clamp(i32::MIN as f64, i32::MAX as f64) as i32);
+        }
+    }
+    pcm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: i32 = 48000;
+
+    fn sine(freq: f64, frames: usize, amp: f64) -> Vec<f64> {
+        (0..frames)
+            .map(|n| amp * (2.0 * std::f64::consts::PI * freq * n as f64 / SR as f64).sin())
+            .collect()
+    }
+
+    /// Best normalized cross-correlation of `a` and `b` within `±max_lag`,
+    /// ignoring `edge` samples at either end.
+    fn correlation(a: &[f64], b: &[f64], max_lag: isize, edge: isize) -> f64 {
+        let len = a.len().min(b.len()) as isize;
+        (-max_lag..=max_lag)
+            .map(|lag| {
+                let (mut dot, mut ea, mut eb) = (0.0, 0.0, 0.0);
+                for i in edge..len - edge {
+                    let j = i + lag;
+                    let (x, y) = (a[i as usize], b[j as usize]);
+                    dot += x * y;
+                    ea += x * x;
+                    eb += y * y;
+                }
+                dot / (ea * eb).sqrt()
+            })
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    fn zero_crossings(x: &[f64]) -> usize {
+        x.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count()
+    }
+
+    /// RMS in 10 ms blocks.
+    fn envelope(x: &[f64]) -> Vec<f64> {
+        x.chunks(480)
+            .map(|c| (c.iter().map(|v| v * v).sum::<f64>() / c.len() as f64).sqrt())
+            .collect()
+    }
+
+    /// Amplitude-modulated two-tone test signal.
+    fn test_signal(frames: usize) -> Vec<f64> {
+        (0..frames)
+            .map(|n| {
+                let t = n as f64 / SR as f64;
+                let env = 0.5 + 0.5 * (2.0 * std::f64::consts::PI * 3.0 * t).sin();
+                let tone = (2.0 * std::f64::consts::PI * 220.0 * t).sin()
+                    + 0.5 * (2.0 * std::f64::consts::PI * 1330.0 * t).sin();
+                1.0e8 * env * tone
+            })
+            .collect()
+    }
+
+    #[test]
+    fn varispeed_matches_analytic_resample() {
+        let tempo = 1000.0 / (1000.0 + 40.0);
+        let input = sine(1000.0, SR as usize, 1.0e9);
+        let out = resample_varispeed(std::slice::from_ref(&input), tempo);
+        assert_eq!(out[0].len(), (input.len() as f64 / tempo).round() as usize);
+
+        // A sine played `tempo` times faster is a sine at `freq * tempo`
+        let expected = sine(1000.0 * tempo, out[0].len(), 1.0e9);
+        assert!(correlation(&out[0], &expected, 0, 2000) > 0.9999);
+    }
+
+    #[test]
+    fn wsola_keeps_pitch_and_stretches_length() {
+        let tempo = 0.96;
+        let input = sine(1000.0, 2 * SR as usize, 1.0e9);
+        let out = time_stretch_wsola(&[input.clone(), input.clone()], SR, tempo);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].len(), (input.len() as f64 / tempo).round() as usize);
+        assert_eq!(out[0], out[1]);
+
+        let rate_in = zero_crossings(&input) as f64 / input.len() as f64;
+        let rate_out = zero_crossings(&out[0]) as f64 / out[0].len() as f64;
+        assert!((rate_out / rate_in - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn interleaved_round_trip_keeps_channel_order() {
+        let pcm: Vec<i32> = (0..2000).flat_map(|i| [i * 1000, -i * 1000]).collect();
+        let out = stretch_interleaved(&pcm, 2, SR, 1.0, false);
+        assert_eq!(out.len(), pcm.len());
+        assert!(out
+            .chunks(2)
+            .skip(40)
+            .take(1900)
+            .all(|f| f[0] > 0 && f[1] < 0));
+    }
+
+    #[test]
+    fn streamed_blocks_match_one_shot() {
+        let tempo = 1000.0 / (1000.0 + 40.0);
+        let planes = vec![
+            test_signal(SR as usize * 3 / 2),
+            sine(440.0, SR as usize * 3 / 2, 1.0e8),
+        ];
+
+        for preserve_pitch in [false, true] {
+            let whole = if preserve_pitch {
+                time_stretch_wsola(&planes, SR, tempo)
+            } else {
+                resample_varispeed(&planes, tempo)
+            };
+
+            // Uneven block sizes, including ones smaller than a kernel
+            let mut stretcher = Stretcher::new(2, SR, tempo, preserve_pitch);
+            let mut streamed = vec![Vec::new(); 2];
+            let mut start = 0;
+            for len in [1000, 7, 30000, 5, 12000].into_iter().cycle() {
+                if start >= planes[0].len() {
+                    break;
+                }
+                let end = (start + len).min(planes[0].len());
+                let block: Vec<Vec<f64>> = planes.iter().map(|p| p[start..end].to_vec()).collect();
+                for (dst, src) in streamed.iter_mut().zip(stretcher.push(&block)) {
+                    dst.extend(src);
+                }
+                start = end;
+            }
+            for (dst, src) in streamed.iter_mut().zip(stretcher.finish()) {
+                dst.extend(src);
+            }
+            assert_eq!(streamed, whole, "preserve_pitch = {preserve_pitch}");
+        }
+    }
+
+    /// Runs `input` through an ffmpeg audio filter as mono s32le.
+    fn ffmpeg_filter(ffmpeg: &Path, input: &[f64], filter: &str) -> Vec<f64> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let sr = SR.to_string();
+        let child = Command::new(ffmpeg)
+            .args([
+                "-nostdin", "-v", "error", "-f", "s32le", "-ar", &sr, "-ac", "1", "-i", "-",
+            ])
+            .args(["-af", filter, "-f", "s32le", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        assert!(
+            child.is_ok(),
+            "could not start {}: {child:?}",
+            ffmpeg.display()
+        );
+        let mut child = child.unwrap();
+
+        let bytes: Vec<u8> = input
+            .iter()
+            .flat_map(|&s| (s as i32).to_le_bytes())
+            .collect();
+        let stdin = child.stdin.take();
+        let writer = std::thread::spawn(move || stdin.map(|mut s| s.write_all(&bytes)));
+        let output = child.wait_with_output();
+        let fed = writer.join().map(|w| w.is_some_and(|r| r.is_ok()));
+        assert!(
+            matches!(fed, Ok(true)),
+            "could not feed samples to ffmpeg -af {filter}"
+        );
+        assert!(
+            output.is_ok(),
+            "ffmpeg -af {filter} did not finish: {output:?}"
+        );
+        let output = output.unwrap();
+        assert!(
+            output.status.success(),
+            "ffmpeg -af {filter} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        output
+            .stdout
+            .chunks_exact(4)
+            .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_ffmpeg_engines() {
+        // The comparison needs a real ffmpeg; nothing to check without one
+        let Ok(ffmpeg) = which::which("ffmpeg") else {
+            return;
+        };
+        let tempo = 1000.0 / (1000.0 - 25.0);
+        let input = test_signal(3 * SR as usize);
+
+        let aresample = format!("asetrate={},aresample={SR}", SR as f64 * tempo);
+        let reference = ffmpeg_filter(&ffmpeg, &input, &aresample);
+        let native = resample_varispeed(std::slice::from_ref(&input), tempo);
+        let corr = correlation(&native[0], &reference, 4, 2000);
+        assert!(
+            corr > 0.99,
+            "varispeed vs {aresample}: correlation {corr:.4}"
+        );
+
+        let reference = ffmpeg_filter(&ffmpeg, &input, &format!("atempo={tempo}"));
+        let native = time_stretch_wsola(&[input], SR, tempo);
+        let corr = correlation(&envelope(&native[0]), &envelope(&reference), 0, 5);
+        assert!(
+            corr > 0.95,
+            "WSOLA vs atempo={tempo}: envelope correlation {corr:.4}"
+        );
+    }
+}
@@ -3,6 +3,8 @@
 //! Corrects audio drift due to PAL speed-up using a pitch-corrected
 //! rubberband tempo adjustment via ffmpeg.
 
+use crate::correction::encode::{corrected_track_name, encode_corrected_audio};
+use crate::correction::failure_hint;
 use crate::io::runner::CommandRunner;
 use crate::models::enums::TrackType;
 use crate::models::media::{StreamProps, Track};
@@ -87,11 +89,11 @@ pub fn run_pal_correction(ctx: &mut Context, runner: &CommandRunner) {
                 &corrected_path_str,
             ];
 
-            if runner.run(&cmd, &ctx.tool_paths).is_none() {
+            if let Err(e) = runner.run(&cmd, &ctx.tool_paths) {
                 runner.log_message(&format!(
-                    "[ERROR] PAL drift correction failed for {}. \
-                     This may be because your ffmpeg build lacks librubberband support.",
-                    original_path.file_name().unwrap_or_default().to_string_lossy()
+                    "[ERROR] PAL drift correction failed for {}.{}",
+                    original_path.file_name().unwrap_or_default().to_string_lossy(),
+                    failure_hint(&e, true)
                 ));
                 continue;
             }
@@ -122,18 +124,26 @@ pub fn run_pal_correction(ctx: &mut Context, runner: &CommandRunner) {
                     codec_id: original_props.codec_id.clone(),
                     lang: original_props.lang.clone(),
                     name: preserved_name,
+                    audio_channels: original_props.audio_channels,
                 },
             };
 
-            // Update main track to point to corrected FLAC
-            let corrected_name = if !original_props.name.is_empty() {
-                format!("{} (PAL Corrected)", original_props.name)
-            } else {
-                "PAL Corrected".to_string()
-            };
+            // Re-encode per policy, then point the main track at the result
+            let encoded = encode_corrected_audio(
+                &corrected_path,
+                &original_path,
+                &original_props,
+                &ctx.settings,
+                runner,
+                &ctx.tool_paths,
+            );
+            let corrected_name =
+                corrected_track_name(&original_props.name, "PAL Corrected", &encoded);
 
+            let items = ctx.extracted_items.as_mut().unwrap();
             let target_item = &mut items[idx];
-            target_item.extracted_path = Some(corrected_path.clone());
+            target_item.extracted_path = Some(encoded.path.clone());
+            target_item.correction_transcode = encoded.transcode.clone();
             target_item.is_corrected = true;
             target_item.container_delay_ms = 0;
             target_item.track = Track {
@@ -141,9 +151,10 @@ pub fn run_pal_correction(ctx: &mut Context, runner: &CommandRunner) {
                 id: target_item.track.id,
                 track_type: target_item.track.track_type,
                 props: StreamProps {
-                    codec_id: "FLAC".to_string(),
+                    codec_id: encoded.codec_id.clone(),
                     lang: original_props.lang.clone(),
                     name: corrected_name,
+                    audio_channels: original_props.audio_channels,
                 },
             };
             target_item.apply_track_name = true;
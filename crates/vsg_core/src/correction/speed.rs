@@ -0,0 +1,233 @@
+//! Speed-ratio correction.
+//!
+//! Generalises PAL correction to any speed mismatch (24→25, 25→24,
+//! 29.97→25, 23.976→24, ...) or an exact fitted ratio. Audio is stretched
+//! by the ratio with rubberband (pitch kept) or by varispeed
+//! (asetrate + aresample, pitch follows speed). Subtitles and chapters of
+//! the same source are scaled by the same ratio in their own steps via
+//! `Context::speed_ratio_for_source`.
+
+use crate::correction::encode::{corrected_track_name, encode_corrected_audio};
+use crate::correction::failure_hint;
+use crate::correction::linear::get_sample_rate;
+use crate::io::runner::CommandRunner;
+use crate::models::enums::TrackType;
+use crate::models::media::{StreamProps, Track};
+use crate::models::settings::AppSettings;
+use crate::orchestrator::steps::context::Context;
+
+/// ffmpeg filter that stretches audio by `ratio` (source speed / reference speed).
+fn speed_filter(ratio: f64, sample_rate: i32, settings: &AppSettings) -> String {
+    let tempo = 1.0 / ratio;
+    if settings.speed_correction_pitch_correct {
+        let mut rb_opts = vec![
+            format!("tempo={tempo}"),
+            format!("transients={}", settings.segment_rb_transients),
+        ];
+        if settings.segment_rb_smoother {
+            rb_opts.push("smoother=on".to_string());
+        }
+        if settings.segment_rb_pitchq {
+            rb_opts.push("pitchq=on".to_string());
+        }
+        format!("rubberband={}", rb_opts.join(":"))
+    } else {
+        format!(
+            "asetrate={},aresample={sample_rate}",
+            sample_rate as f64 * tempo
+        )
+    }
+}
+
+/// Retimes audio tracks by their source's speed ratio — `run_speed_correction`
+pub fn run_speed_correction(ctx: &mut Context, runner: &CommandRunner) {
+    let speed_flags: Vec<(String, f64, Option<String>)> = ctx
+        .speed_ratio_flags
+        .iter()
+        .filter_map(|(k, v)| v.ratio.map(|ratio| (k.clone(), ratio, v.label.clone())))
+        .collect();
+
+    for (analysis_track_key, ratio, label) in speed_flags {
+        let source_key = analysis_track_key
+            .split('_')
+            .next()
+            .unwrap_or("")
+            .to_string();
+
+        let extracted_items = match ctx.extracted_items.as_ref() {
+            Some(items) => items,
+            None => continue,
+        };
+
+        // Find ALL audio tracks from this source that are not preserved
+        let target_indices: Vec<usize> = extracted_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                item.track.source == source_key
+                    && item.track.track_type == TrackType::Audio
+                    && !item.is_preserved
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        if target_indices.is_empty() {
+            runner.log_message(&format!(
+                "[SpeedCorrector] Could not find target audio tracks for {source_key} in the layout. Skipping."
+            ));
+            continue;
+        }
+
+        let ratio_desc = label.unwrap_or_else(|| format!("{ratio:.6}x"));
+        runner.log_message(&format!(
+            "[SpeedCorrector] Retiming {} track(s) from {source_key} ({ratio_desc}, {})...",
+            target_indices.len(),
+            if ctx.settings.speed_correction_pitch_correct {
+                "pitch corrected"
+            } else {
+                "varispeed"
+            }
+        ));
+
+        for &idx in &target_indices {
+            let items = ctx.extracted_items.as_ref().unwrap();
+            let original_path = match &items[idx].extracted_path {
+                Some(p) => p.clone(),
+                None => continue,
+            };
+
+            let corrected_path = original_path
+                .parent()
+                .unwrap_or(original_path.as_path())
+                .join(format!(
+                    "speed_corrected_{}.flac",
+                    original_path
+                        .file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                ));
+
+            let original_path_str = original_path.to_string_lossy().to_string();
+            let corrected_path_str = corrected_path.to_string_lossy().to_string();
+            let sample_rate = get_sample_rate(&original_path_str, runner, &ctx.tool_paths);
+            let filter_arg = speed_filter(ratio, sample_rate, &ctx.settings);
+
+            let cmd: Vec<&str> = vec![
+                "ffmpeg",
+                "-y",
+                "-nostdin",
+                "-v",
+                "error",
+                "-i",
+                &original_path_str,
+                "-af",
+                &filter_arg,
+                "-c:a",
+                "flac",
+                &corrected_path_str,
+            ];
+
+            if let Err(e) = runner.run(&cmd, &ctx.tool_paths) {
+                let mut error_msg = format!(
+                    "Speed correction failed for {}.",
+                    original_path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                );
+                error_msg.push_str(&failure_hint(
+                    &e,
+                    ctx.settings.speed_correction_pitch_correct,
+                ));
+                runner.log_message(&format!("[ERROR] {error_msg}"));
+                continue;
+            }
+
+            runner.log_message(&format!(
+                "[SUCCESS] Speed correction successful for '{}'",
+                original_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ));
+
+            // Build preserved item
+            let items = ctx.extracted_items.as_mut().unwrap();
+            let target_item = &items[idx];
+            let original_props = target_item.track.props.clone();
+
+            let preserved_name = if !original_props.name.is_empty() {
+                format!("{} (Original)", original_props.name)
+            } else {
+                "Original".to_string()
+            };
+            let mut preserved_item = target_item.clone();
+            preserved_item.is_preserved = true;
+            preserved_item.is_default = false;
+            preserved_item.track = Track {
+                source: preserved_item.track.source.clone(),
+                id: preserved_item.track.id,
+                track_type: preserved_item.track.track_type,
+                props: StreamProps {
+                    codec_id: original_props.codec_id.clone(),
+                    lang: original_props.lang.clone(),
+                    name: preserved_name,
+                    audio_channels: original_props.audio_channels,
+                },
+            };
+
+            // Re-encode per policy, then point the main track at the result
+            let encoded = encode_corrected_audio(
+                &corrected_path,
+                &original_path,
+                &original_props,
+                &ctx.settings,
+                runner,
+                &ctx.tool_paths,
+            );
+            let corrected_name =
+                corrected_track_name(&original_props.name, "Speed Corrected", &encoded);
+
+            let items = ctx.extracted_items.as_mut().unwrap();
+            let target_item = &mut items[idx];
+            target_item.extracted_path = Some(encoded.path.clone());
+            target_item.correction_transcode = encoded.transcode.clone();
+            target_item.is_corrected = true;
+            target_item.container_delay_ms = 0;
+            target_item.track = Track {
+                source: target_item.track.source.clone(),
+                id: target_item.track.id,
+                track_type: target_item.track.track_type,
+                props: StreamProps {
+                    codec_id: encoded.codec_id.clone(),
+                    lang: original_props.lang.clone(),
+                    name: corrected_name,
+                    audio_channels: original_props.audio_channels,
+                },
+            };
+            target_item.apply_track_name = true;
+
+            items.push(preserved_item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_stretches_by_inverse_ratio() {
+        let mut settings = AppSettings::default();
+        let ratio = 25.0 / 24.0;
+
+        settings.speed_correction_pitch_correct = false;
+        assert_eq!(
+            speed_filter(ratio, 48000, &settings),
+            "asetrate=46080,aresample=48000"
+        );
+
+        settings.speed_correction_pitch_correct = true;
+        assert!(speed_filter(ratio, 48000, &settings).starts_with("rubberband=tempo=0.96:"));
+    }
+}
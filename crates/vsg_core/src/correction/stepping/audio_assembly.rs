@@ -6,18 +6,21 @@
 //!   1. Inserts silence where the delay increases (gap between clusters)
 //!   2. Trims audio where the delay decreases (overlap)
 //!   3. Applies per-segment drift correction when needed
-//!   4. Concatenates all pieces via FFmpeg into a single FLAC
+//!   4. Optionally crossfades each splice (equal-power) and fills gaps with
+//!      room tone from the splice's silence zone
+//!   5. Concatenates all pieces via FFmpeg into a single FLAC
 
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
 use crate::analysis::correlation::decode::get_audio_stream_info;
+use crate::correction::{failure_hint, native};
 use crate::io::runner::CommandRunner;
-use crate::models::enums::ResampleEngine;
+use crate::models::enums::{ResampleEngine, SteppingGapFill};
 use crate::models::settings::AppSettings;
 
-use super::types::AudioSegment;
+use super::types::{AudioSegment, SilenceZone, SpliceMethod, SplicePoint};
 
 // ---------------------------------------------------------------------------
 // Audio probing / decoding helpers
@@ -42,7 +45,7 @@ pub fn get_audio_properties(
 
     let out = runner
         .run(&cmd, tool_paths)
-        .ok_or_else(|| format!("Could not probe audio properties for {file_path}"))?;
+        .map_err(|e| format!("Could not probe audio properties for {file_path}: {e}"))?;
 
     let info: serde_json::Value = serde_json::from_str(&out)
         .map_err(|e| format!("Failed to parse ffprobe JSON: {e}"))?;
@@ -110,7 +113,7 @@ pub fn decode_to_memory(
         "-",
     ];
 
-    let pcm_bytes = runner.run_binary(&cmd, tool_paths, None)?;
+    let pcm_bytes = runner.run_binary(&cmd, tool_paths, None).ok()?;
 
     // Ensure buffer alignment (4 bytes per i32 sample)
     let elem = 4;
@@ -144,9 +147,10 @@ pub fn decode_to_memory(
 ///
 /// If `target_pcm`, `channels`, `channel_layout`, `sample_rate` are supplied
 /// the file is not re-decoded. Otherwise the function probes and decodes
-/// `target_audio_path` itself.
+/// `target_audio_path` itself. `splice_points` supply the silence zones used
+/// for room-tone gap fill.
 ///
-/// Returns `true` on success.
+/// Returns how each EDL transition was joined, or `None` on failure.
 #[allow(clippy::too_many_arguments)]
 pub fn assemble_corrected_audio(
     edl: &[AudioSegment],
@@ -160,7 +164,8 @@ pub fn assemble_corrected_audio(
     cl_arg: Option<&str>,
     sr_arg: Option<i32>,
     pcm_arg: Option<&[i32]>,
-) -> bool {
+    splice_points: Option<&[SplicePoint]>,
+) -> Option<Vec<SpliceMethod>> {
     // Probe / decode if needed — resolve all audio parameters
     let owned_pcm: Vec<i32>;
     let channels: i32;
@@ -181,7 +186,7 @@ pub fn assemble_corrected_audio(
             Some(i) => i,
             None => {
                 log(&format!("[ERROR] No audio stream in {target_audio_path}"));
-                return false;
+                return None;
             }
         };
 
@@ -189,22 +194,141 @@ pub fn assemble_corrected_audio(
             Ok(p) => p,
             Err(e) => {
                 log(&format!("[ERROR] {e}"));
-                return false;
+                return None;
             }
         };
         channels = props.0;
         channel_layout = props.1;
         sample_rate = props.2;
 
-        owned_pcm = match decode_to_memory(
+        owned_pcm = decode_to_memory(
             target_audio_path, idx, sample_rate, runner, tool_paths, channels, Some(log),
-        ) {
-            Some(d) => d,
-            None => return false,
-        };
+        )?;
         pcm_slice = &owned_pcm;
     }
 
+    let ch = channels.max(1) as usize;
+    let total_frames = pcm_slice.len() / ch;
+    let to_frame = |t: f64| ((t * sample_rate as f64) as usize).min(total_frames);
+
+    // --- Lay out pieces: segment audio plus gap fills ---
+    let mut pieces: Vec<Piece> = Vec::new();
+    let mut methods: Vec<SpliceMethod> = Vec::new();
+    let base_delay_ms = edl[0].delay_ms;
+    let mut current_delay = base_delay_ms;
+
+    for (i, segment) in edl.iter().enumerate() {
+        let gap_ms = segment.delay_ms - current_delay;
+        let transition = (i > 0).then(|| {
+            methods.push(SpliceMethod {
+                src2_time_s: segment.start_s,
+                join: "hard_cut".to_string(),
+                crossfade_ms: 0.0,
+                gap_fill: None,
+                gap_ms,
+            });
+            methods.len() - 1
+        });
+
+        if gap_ms.abs() > 10 {
+            if gap_ms > 0 {
+                let gap_frames = ((gap_ms as f64 / 1000.0) * sample_rate as f64) as usize;
+                let zone = splice_points.and_then(|sps| {
+                    sps.iter()
+                        .find(|sp| (sp.src2_time_s - segment.start_s).abs() < 0.005)
+                        .and_then(|sp| sp.silence_zone.as_ref())
+                });
+                let (fill, kind) =
+                    gap_fill(pcm_slice, ch, sample_rate, gap_frames, zone, settings);
+                log(&format!(
+                    "    At {:.3}s: insert {}ms {}",
+                    segment.start_s,
+                    gap_ms,
+                    kind.replace('_', " ")
+                ));
+                if let Some(t) = transition {
+                    methods[t].gap_fill = Some(kind.to_string());
+                }
+                pieces.push(Piece {
+                    frames: fill.len() / ch,
+                    audio: PieceAudio::Fill(fill),
+                    drift_rate_ms_s: 0.0,
+                    transition,
+                    head_trim: 0,
+                    tail_trim: 0,
+                });
+            } else {
+                // Remove audio (negative gap)
+                log(&format!(
+                    "    At {:.3}s: remove {}ms audio",
+                    segment.start_s, -gap_ms
+                ));
+            }
+        }
+
+        current_delay = segment.delay_ms;
+
+        // This segment's audio
+        let seg_end = if i + 1 < edl.len() {
+            to_frame(edl[i + 1].start_s)
+        } else {
+            total_frames
+        };
+        let mut actual_start = segment.start_s;
+        if gap_ms < 0 {
+            actual_start += (-gap_ms) as f64 / 1000.0;
+        }
+        let seg_start = to_frame(actual_start);
+        if seg_end <= seg_start {
+            continue;
+        }
+
+        pieces.push(Piece {
+            audio: PieceAudio::Source {
+                start: seg_start,
+                end: seg_end,
+            },
+            drift_rate_ms_s: segment.drift_rate_ms_s,
+            transition,
+            frames: seg_end - seg_start,
+            head_trim: 0,
+            tail_trim: 0,
+        });
+    }
+
+    if pieces.is_empty() {
+        log("  [Assembly] ERROR: No segments generated for assembly.");
+        return None;
+    }
+
+    // --- Crossfade each junction the neighbouring pieces can afford ---
+    let mut crossfades: HashMap<usize, Vec<i32>> = HashMap::new();
+    if settings.stepping_crossfade_enabled && settings.stepping_crossfade_ms > 0.0 {
+        let half =
+            ((settings.stepping_crossfade_ms / 2000.0) * sample_rate as f64).round() as usize;
+        for k in 0..pieces.len().saturating_sub(1) {
+            let (left, right) = (&pieces[k], &pieces[k + 1]);
+            let drifting = left.drift_rate_ms_s.abs() > 0.5 || right.drift_rate_ms_s.abs() > 0.5;
+            if half == 0
+                || drifting
+                || left.frames < left.head_trim + half
+                || right.frames < half
+            {
+                continue;
+            }
+            crossfades.insert(
+                k,
+                equal_power_crossfade(left, right, half, pcm_slice, ch),
+            );
+            pieces[k].tail_trim = half;
+            pieces[k + 1].head_trim = half;
+            if let Some(t) = pieces[k + 1].transition {
+                methods[t].join = "crossfade".to_string();
+                methods[t].crossfade_ms = (2 * half) as f64 * 1000.0 / sample_rate as f64;
+            }
+        }
+    }
+
     log(&format!(
         "  [Assembly] Building {} segment(s) -> {}",
         edl.len(),
@@ -219,140 +343,95 @@ pub fn assemble_corrected_audio(
     let _ = fs::create_dir_all(&assembly_dir);
 
     let mut segment_files: Vec<String> = Vec::new();
-    let base_delay_ms = edl[0].delay_ms;
-    let mut current_delay = base_delay_ms;
 
     let result = (|| -> Result<(), String> {
-        let pcm_duration_s =
-            pcm_slice.len() as f64 / (sample_rate as f64 * channels as f64);
+        for (k, piece) in pieces.iter().enumerate() {
+            let samples: &[i32] = match &piece.audio {
+                PieceAudio::Source { start, end } => {
+                    &pcm_slice[(start + piece.head_trim) * ch..(end - piece.tail_trim) * ch]
+                }
+                PieceAudio::Fill(fill) => {
+                    &fill[piece.head_trim * ch..(piece.frames - piece.tail_trim) * ch]
+                }
+            };
+
+            if !samples.is_empty() {
+                // The native engine retimes the in-memory samples directly
+                let drifting = piece.drift_rate_ms_s.abs() > 0.5;
+                let native_drift =
+                    drifting && settings.segment_resample_engine == ResampleEngine::Native;
+                let stretched;
+                let samples = if native_drift {
+                    log(&format!(
+                        "    Drift correction ({:+.2} ms/s, native) on piece {k}",
+                        piece.drift_rate_ms_s
+                    ));
+                    stretched = native::stretch_interleaved(
+                        samples,
+                        ch,
+                        sample_rate,
+                        1000.0 / (1000.0 + piece.drift_rate_ms_s),
+                        settings.segment_rb_pitch_correct,
+                    );
+                    &stretched[..]
+                } else {
+                    samples
+                };
 
-        for (i, segment) in edl.iter().enumerate() {
-            let gap_ms = segment.delay_ms - current_delay;
+                let piece_file = assembly_dir.join(format!("piece_{k:03}.flac"));
+                if !encode_flac(
+                    samples,
+                    &piece_file,
+                    sample_rate,
+                    channels,
+                    &channel_layout,
+                    runner,
+                    tool_paths,
+                ) {
+                    return Err(format!("Piece {k} encode failed"));
+                }
 
-            if gap_ms.abs() > 10 {
-                if gap_ms > 0 {
-                    // Insert silence
+                // Apply drift correction if significant
+                let mut final_file = piece_file.clone();
+                if drifting && !native_drift {
                     log(&format!(
-                        "    At {:.3}s: insert {}ms silence",
-                        segment.start_s, gap_ms
+                        "    Drift correction ({:+.2} ms/s) on piece {k}",
+                        piece.drift_rate_ms_s
                     ));
-                    let silence_file =
-                        assembly_dir.join(format!("silence_{i:03}.flac"));
-                    let silence_samples =
-                        ((gap_ms as f64 / 1000.0) * sample_rate as f64) as usize
-                            * channels as usize;
-                    let silence_pcm = vec![0i32; silence_samples];
-
-                    if !encode_flac(
-                        &silence_pcm,
-                        &silence_file,
+                    let corrected_file =
+                        assembly_dir.join(format!("piece_{k:03}_corrected.flac"));
+                    if !apply_drift_correction(
+                        &piece_file,
+                        &corrected_file,
+                        piece.drift_rate_ms_s,
                         sample_rate,
-                        channels,
-                        &channel_layout,
+                        settings,
                         runner,
                         tool_paths,
+                        log,
                     ) {
-                        return Err(format!(
-                            "Silence encode failed at segment {i}"
-                        ));
+                        return Err(format!("Drift correction failed for piece {k}"));
                     }
-                    segment_files.push(format!(
-                        "file '{}'",
-                        silence_file
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                    ));
-                } else {
-                    // Remove audio (negative gap)
-                    log(&format!(
-                        "    At {:.3}s: remove {}ms audio",
-                        segment.start_s, -gap_ms
-                    ));
+                    final_file = corrected_file;
                 }
+                segment_files.push(concat_entry(&final_file));
             }
 
-            current_delay = segment.delay_ms;
-
-            // Extract this segment's audio
-            let seg_start = segment.start_s;
-            let seg_end = if i + 1 < edl.len() {
-                edl[i + 1].start_s
-            } else {
-                pcm_duration_s
-            };
-
-            let mut actual_start = seg_start;
-            if gap_ms < 0 {
-                actual_start += (-gap_ms) as f64 / 1000.0;
-            }
-
-            if seg_end <= actual_start {
-                continue;
-            }
-
-            let start_sample =
-                (actual_start * sample_rate as f64) as usize * channels as usize;
-            let end_sample = ((seg_end * sample_rate as f64) as usize
-                * channels as usize)
-                .min(pcm_slice.len());
-
-            if start_sample >= end_sample {
-                continue;
-            }
-
-            let chunk = &pcm_slice[start_sample..end_sample];
-            if chunk.is_empty() {
-                continue;
-            }
-
-            let seg_file =
-                assembly_dir.join(format!("segment_{i:03}.flac"));
-            if !encode_flac(
-                chunk,
-                &seg_file,
-                sample_rate,
-                channels,
-                &channel_layout,
-                runner,
-                tool_paths,
-            ) {
-                return Err(format!("Segment {i} encode failed"));
-            }
-
-            // Apply drift correction if significant
-            let mut final_seg_file = seg_file.clone();
-            if segment.drift_rate_ms_s.abs() > 0.5 {
-                log(&format!(
-                    "    Drift correction ({:+.2} ms/s) on segment {i}",
-                    segment.drift_rate_ms_s
-                ));
-                let corrected_file = assembly_dir
-                    .join(format!("segment_{i:03}_corrected.flac"));
-                if !apply_drift_correction(
-                    &seg_file,
-                    &corrected_file,
-                    segment.drift_rate_ms_s,
+            if let Some(xfade) = crossfades.get(&k) {
+                let xfade_file = assembly_dir.join(format!("xfade_{k:03}.flac"));
+                if !encode_flac(
+                    xfade,
+                    &xfade_file,
                     sample_rate,
-                    settings,
+                    channels,
+                    &channel_layout,
                     runner,
                     tool_paths,
-                    log,
                 ) {
-                    return Err(format!(
-                        "Drift correction failed for segment {i}"
-                    ));
+                    return Err(format!("Crossfade {k} encode failed"));
                 }
-                final_seg_file = corrected_file;
+                segment_files.push(concat_entry(&xfade_file));
             }
-
-            segment_files.push(format!(
-                "file '{}'",
-                final_seg_file
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-            ));
         }
 
         if segment_files.is_empty() {
@@ -381,16 +460,17 @@ pub fn assemble_corrected_audio(
             &output_path_str,
         ];
 
-        if runner.run(&concat_cmd, tool_paths).is_none() {
-            return Err("FFmpeg concat failed.".to_string());
+        if let Err(e) = runner.run(&concat_cmd, tool_paths) {
+            return Err(format!("FFmpeg concat failed: {e}"));
         }
 
         log(&format!(
-            "  [Assembly] Done: {}",
+            "  [Assembly] Done: {} ({} crossfaded splice(s))",
             output_path
                 .file_name()
                 .unwrap_or_default()
-                .to_string_lossy()
+                .to_string_lossy(),
+            crossfades.len()
         ));
         Ok(())
     })();
@@ -401,10 +481,10 @@ pub fn assemble_corrected_audio(
     }
 
     match result {
-        Ok(()) => true,
+        Ok(()) => Some(methods),
         Err(e) => {
             log(&format!("  [Assembly] ERROR: {e}"));
-            false
+            None
         }
     }
 }
@@ -413,8 +493,126 @@ pub fn assemble_corrected_audio(
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+/// Audio making up one assembled piece.
+enum PieceAudio {
+    /// Frame range `[start, end)` of the target PCM
+    Source { start: usize, end: usize },
+    /// Generated interleaved PCM (silence or room tone)
+    Fill(Vec<i32>),
+}
+
+/// One piece of the assembled output, in order.
+struct Piece {
+    audio: PieceAudio,
+    drift_rate_ms_s: f64,
+    /// Index into the splice methods for the transition this piece starts
+    transition: Option<usize>,
+    /// Length in frames
+    frames: usize,
+    /// Frames handed to the crossfade before / after this piece
+    head_trim: usize,
+    tail_trim: usize,
+}
+
+
+/// `file '<name>'` line for the concat demuxer list.
+fn concat_entry(path: &Path) -> String {
+    format!(
+        "file '{}'",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    )
+}
+
+/// Gap fill of `frames` frames: room tone copied from the splice's silence
+/// zone when enabled and quiet enough, otherwise digital silence.
+fn gap_fill(
+    pcm: &[i32],
+    ch: usize,
+    sample_rate: i32,
+    frames: usize,
+    zone: Option<&SilenceZone>,
+    settings: &AppSettings,
+) -> (Vec<i32>, &'static str) {
+    if settings.stepping_gap_fill == SteppingGapFill::RoomTone {
+        if let Some(zone) = zone.filter(|z| z.avg_db <= settings.stepping_room_tone_max_db) {
+            let total = pcm.len() / ch;
+            let start = ((zone.start_s * sample_rate as f64) as usize).min(total);
+            let end = ((zone.end_s * sample_rate as f64) as usize).min(total);
+            // Need at least 20 ms of tone to loop without an audible buzz
+            if end > start && end - start >= (sample_rate as usize / 50).max(1) {
+                return (
+                    tile_ping_pong(&pcm[start * ch..end * ch], ch, frames),
+                    "room_tone",
+                );
+            }
+        }
+    }
+    (vec![0i32; frames * ch], "silence")
+}
+
+/// Repeats `tone` forwards then backwards until `frames` frames are filled,
+/// so consecutive copies meet without a discontinuity.
+fn tile_ping_pong(tone: &[i32], ch: usize, frames: usize) -> Vec<i32> {
+    let len = tone.len() / ch;
+    let mut out = Vec::with_capacity(frames * ch);
+    for f in 0..frames {
+        let p = f % (2 * len);
+        let src = if p < len { p } else { 2 * len - 1 - p };
+        out.extend_from_slice(&tone[src * ch..(src + 1) * ch]);
+    }
+    out
+}
+
+/// Sample `c` of frame `f` counted from the start of `piece`. Frames past
+/// either end continue into the surrounding PCM (sources) or wrap (fills).
+fn piece_sample(piece: &Piece, f: isize, c: usize, pcm: &[i32], ch: usize) -> i32 {
+    match &piece.audio {
+        PieceAudio::Source { start, .. } => {
+            let abs = *start as isize + f;
+            if abs < 0 || abs as usize >= pcm.len() / ch {
+                0
+            } else {
+                pcm[abs as usize * ch + c]
+            }
+        }
+        PieceAudio::Fill(fill) => {
+            if piece.frames == 0 {
+                return 0;
+            }
+            let idx = f.rem_euclid(piece.frames as isize) as usize;
+            fill[idx * ch + c]
+        }
+    }
+}
+
+/// Equal-power crossfade of `2 * half` frames centred on the junction:
+/// `left` continues past its end fading out while `right` fades in from
+/// `half` frames before its start.
+fn equal_power_crossfade(
+    left: &Piece,
+    right: &Piece,
+    half: usize,
+    pcm: &[i32],
+    ch: usize,
+) -> Vec<i32> {
+    let n = 2 * half;
+    let left_from = left.frames as isize - half as isize;
+    let mut out = Vec::with_capacity(n * ch);
+    for i in 0..n {
+        let theta = (i as f64 + 0.5) / n as f64 * std::f64::consts::FRAC_PI_2;
+        let (gain_out, gain_in) = (theta.cos(), theta.sin());
+        for c in 0..ch {
+            let a = piece_sample(left, left_from + i as isize, c, pcm, ch) as f64;
+            let b = piece_sample(right, i as isize - half as isize, c, pcm, ch) as f64;
+            let mixed = (a * gain_out + b * gain_in).round();
+            out.push(mixed.clamp(i32::MIN as f64, i32::MAX as f64) as i32);
+        }
+    }
+    out
+}
+
 /// Encode raw i32 PCM to FLAC via ffmpeg — `_encode_flac`
-fn encode_flac(
+pub(crate) fn encode_flac(
     pcm: &[i32],
     out_path: &Path,
     sample_rate: i32,
@@ -452,7 +650,7 @@ fn encode_flac(
 
     runner
         .run_binary(&cmd, tool_paths, Some(&pcm_bytes))
-        .is_some()
+        .is_ok()
 }
 
 /// Apply tempo change to correct within-segment drift — `_apply_drift_correction`
@@ -471,6 +669,22 @@ fn apply_drift_correction(
     let engine = &settings.segment_resample_engine;
 
     let filter_chain: String = match engine {
+        ResampleEngine::Native => {
+            return match native::stretch_file(
+                input_path,
+                output_path,
+                tempo_ratio,
+                settings.segment_rb_pitch_correct,
+                runner,
+                tool_paths,
+            ) {
+                Ok(()) => true,
+                Err(e) => {
+                    log(&format!("    [ERROR] Drift correction with 'native' failed: {e}"));
+                    false
+                }
+            };
+        }
         ResampleEngine::Rubberband => {
             let mut rb_opts = vec![format!("tempo={tempo_ratio}")];
             if !settings.segment_rb_pitch_correct {
@@ -513,15 +727,52 @@ fn apply_drift_correction(
         &output_str,
     ];
 
-    let result = runner.run(&cmd, tool_paths);
-    if result.is_none() {
+    if let Err(e) = runner.run(&cmd, tool_paths) {
         let engine_str = engine.to_string();
         let mut msg = format!("Drift correction with '{engine_str}' failed.");
-        if matches!(engine, ResampleEngine::Rubberband) {
-            msg.push_str(" (Ensure FFmpeg includes librubberband).");
-        }
+        let rubberband = matches!(engine, ResampleEngine::Rubberband);
+        msg.push_str(&failure_hint(&e, rubberband));
         log(&format!("    [ERROR] {msg}"));
         return false;
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(start: usize, end: usize) -> Piece {
+        Piece {
+            audio: PieceAudio::Source { start, end },
+            drift_rate_ms_s: 0.0,
+            transition: None,
+            frames: end - start,
+            head_trim: 0,
+            tail_trim: 0,
+        }
+    }
+
+    #[test]
+    fn equal_power_crossfade_keeps_constant_power() {
+        // Left material is 1000 throughout, right is silent: a cos() fade-out
+        let pcm: Vec<i32> = (0..60).map(|i| if i < 30 { 1000 } else { 0 }).collect();
+        let xfade = equal_power_crossfade(&source(0, 20), &source(40, 60), 4, &pcm, 1);
+        assert_eq!(xfade.len(), 8);
+        assert!(xfade.windows(2).all(|w| w[0] > w[1]));
+        assert!(xfade[0] > 990 && xfade[7] < 100);
+
+        // Correlated material on both sides sums to +3 dB mid-fade
+        let flat = vec![1000i32; 40];
+        let xfade = equal_power_crossfade(&source(0, 20), &source(20, 40), 4, &flat, 1);
+        let mid = xfade[4] as f64;
+        assert!((mid - 1000.0 * std::f64::consts::SQRT_2).abs() < 150.0);
+    }
+
+    #[test]
+    fn room_tone_tiles_back_and_forth() {
+        let tone = [1, -1, 2, -2, 3, -3]; // 3 stereo frames
+        let out = tile_ping_pong(&tone, 2, 7);
+        assert_eq!(out, vec![1, -1, 2, -2, 3, -3, 3, -3, 2, -2, 1, -1, 1, -1]);
+    }
+}
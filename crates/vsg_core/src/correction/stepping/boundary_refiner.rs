@@ -12,6 +12,7 @@
 
 use std::collections::HashMap;
 
+use crate::chapters::keyframes::cached_keyframes_ns;
 use crate::io::runner::CommandRunner;
 use crate::models::settings::AppSettings;
 
@@ -673,34 +674,12 @@ fn snap_to_video_frame(
 ) -> Option<f64> {
     let max_offset = settings.stepping_video_snap_max_offset_s;
 
-    // Get keyframe positions via ffprobe
-    let cmd: Vec<&str> = vec![
-        "ffprobe",
-        "-v", "error",
-        "-select_streams", "v:0",
-        "-show_entries", "packet=pts_time,flags",
-        "-of", "json",
-        video_file,
-    ];
-
-    let result = runner.run(&cmd, tool_paths)?;
-    let data: serde_json::Value = serde_json::from_str(&result).ok()?;
-
-    let packets = data.get("packets")?.as_array()?;
-    let keyframes: Vec<f64> = packets
-        .iter()
-        .filter(|p| {
-            p.get("flags")
-                .and_then(|v| v.as_str())
-                .map(|f| f.contains('K'))
-                .unwrap_or(false)
-        })
-        .filter_map(|p| {
-            p.get("pts_time")
-                .and_then(|v| v.as_str())
-                .and_then(|s| s.parse::<f64>().ok())
-        })
-        .collect();
+    // Get keyframe positions from the shared keyframe cache
+    let keyframes: Vec<f64> =
+        cached_keyframes_ns(video_file, &settings.temp_root, runner, tool_paths)
+            .into_iter()
+            .map(|ns| ns as f64 / 1_000_000_000.0)
+            .collect();
 
     if keyframes.is_empty() {
         return None;
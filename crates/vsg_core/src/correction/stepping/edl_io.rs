@@ -0,0 +1,253 @@
+//! EDL import / export for stepping correction.
+//!
+//! The final `AudioSegment` list can be written as JSON (exact) and as a
+//! CMX3600-style text EDL for viewing in an NLE. Either format can be read
+//! back as a manual override that replaces detection and boundary
+//! refinement for a source.
+//!
+//! In the CMX3600 file each segment is one event: source timecodes are the
+//! Source 2 timeline, record timecodes are the reference timeline offset by
+//! one hour (so negative delays stay representable). A `* VSG:` comment
+//! after each event carries the exact values; without it the delay is taken
+//! from the timecodes at frame precision.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::AudioSegment;
+
+/// Frame rate for CMX3600 timecodes.
+const CMX_FPS: u32 = 24;
+/// Record timecodes start at 01:00:00:00.
+const RECORD_OFFSET_S: f64 = 3600.0;
+
+/// JSON EDL file layout.
+#[derive(Serialize, Deserialize)]
+struct EdlJson {
+    #[serde(default)]
+    source: String,
+    segments: Vec<AudioSegment>,
+}
+
+/// Writes `{stem}.{source}.edl.json` and `{stem}.{source}.edl` into `dir`.
+///
+/// `duration_s` (target audio length) closes the last CMX3600 event.
+pub fn export_edl(
+    dir: &Path,
+    stem: &str,
+    source_key: &str,
+    edl: &[AudioSegment],
+    duration_s: Option<f64>,
+) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create EDL dir: {e}"))?;
+    let base = format!("{stem}.{}", source_key.replace(' ', "_"));
+
+    let json_path = dir.join(format!("{base}.edl.json"));
+    let payload = EdlJson {
+        source: source_key.to_string(),
+        segments: edl.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&payload)
+        .map_err(|e| format!("JSON serialization failed: {e}"))?;
+    fs::write(&json_path, json).map_err(|e| format!("Failed to write EDL JSON: {e}"))?;
+
+    let cmx_path = dir.join(format!("{base}.edl"));
+    let title = format!("{stem} {source_key}");
+    fs::write(&cmx_path, edl_to_cmx3600(&title, edl, duration_s))
+        .map_err(|e| format!("Failed to write CMX3600 EDL: {e}"))?;
+
+    Ok(vec![json_path, cmx_path])
+}
+
+/// Reads a JSON (`{"segments": [...]}` or a bare array) or CMX3600 EDL.
+///
+/// Segments are sorted and the first one is anchored at 0 s. A missing
+/// `delay_raw` falls back to `delay_ms`.
+pub fn load_edl(path: &Path) -> Result<Vec<AudioSegment>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read EDL {}: {e}", path.display()))?;
+    let trimmed = text.trim_start();
+
+    let mut edl = if trimmed.starts_with('{') {
+        serde_json::from_str::<EdlJson>(trimmed)
+            .map(|f| f.segments)
+            .map_err(|e| format!("Failed to parse EDL JSON: {e}"))?
+    } else if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<AudioSegment>>(trimmed)
+            .map_err(|e| format!("Failed to parse EDL JSON: {e}"))?
+    } else {
+        parse_cmx3600(&text)?
+    };
+
+    if edl.is_empty() {
+        return Err(format!("EDL {} contains no segments", path.display()));
+    }
+    edl.sort_by(|a, b| {
+        a.start_s
+            .partial_cmp(&b.start_s)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    edl[0].start_s = 0.0;
+    for seg in &mut edl {
+        if seg.delay_raw == 0.0 && seg.delay_ms != 0 {
+            seg.delay_raw = seg.delay_ms as f64;
+        }
+    }
+    Ok(edl)
+}
+
+/// Formats the EDL as CMX3600 text, one audio event per segment.
+pub fn edl_to_cmx3600(title: &str, edl: &[AudioSegment], duration_s: Option<f64>) -> String {
+    let mut out = format!("TITLE: {title}\nFCM: NON-DROP FRAME\n\n");
+    for (i, seg) in edl.iter().enumerate() {
+        let src_in = seg.start_s;
+        let src_out = match edl.get(i + 1) {
+            Some(next) => next.start_s,
+            None => duration_s.unwrap_or(seg.end_s).max(src_in),
+        };
+        let offset = RECORD_OFFSET_S + seg.delay_ms as f64 / 1000.0;
+        out.push_str(&format!(
+            "{:03}  AX       A     C        {} {} {} {}\n",
+            i + 1,
+            timecode(src_in),
+            timecode(src_out),
+            timecode(src_in + offset),
+            timecode(src_out + offset),
+        ));
+        out.push_str(&format!(
+            "* VSG: start_s={} delay_ms={} delay_raw={} drift_rate_ms_s={}\n\n",
+            seg.start_s, seg.delay_ms, seg.delay_raw, seg.drift_rate_ms_s
+        ));
+    }
+    out
+}
+
+/// Parses CMX3600 events (and `* VSG:` comments) back into segments.
+fn parse_cmx3600(text: &str) -> Result<Vec<AudioSegment>, String> {
+    let mut edl: Vec<AudioSegment> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("* VSG:") {
+            let Some(seg) = edl.last_mut() else {
+                continue;
+            };
+            for (key, value) in rest.split_whitespace().filter_map(|kv| kv.split_once('=')) {
+                match key {
+                    "start_s" => seg.start_s = value.parse().unwrap_or(seg.start_s),
+                    "delay_ms" => seg.delay_ms = value.parse().unwrap_or(seg.delay_ms),
+                    "delay_raw" => seg.delay_raw = value.parse().unwrap_or(seg.delay_raw),
+                    "drift_rate_ms_s" => {
+                        seg.drift_rate_ms_s = value.parse().unwrap_or(seg.drift_rate_ms_s)
+                    }
+                    _ => {}
+                }
+            }
+            seg.end_s = seg.start_s;
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let is_event = fields
+            .first()
+            .is_some_and(|f| f.chars().all(|c| c.is_ascii_digit()));
+        let tcs: Vec<f64> = fields.iter().filter_map(|f| parse_timecode(f)).collect();
+        if !is_event || tcs.len() < 4 {
+            continue;
+        }
+
+        let (src_in, rec_in) = (tcs[0], tcs[2]);
+        let delay = (rec_in - RECORD_OFFSET_S - src_in) * 1000.0;
+        edl.push(AudioSegment {
+            start_s: src_in,
+            end_s: src_in,
+            delay_ms: delay.round() as i32,
+            delay_raw: delay,
+            drift_rate_ms_s: 0.0,
+        });
+    }
+
+    if edl.is_empty() {
+        return Err("No CMX3600 events found in EDL".to_string());
+    }
+    Ok(edl)
+}
+
+/// `HH:MM:SS:FF` at `CMX_FPS`.
+fn timecode(seconds: f64) -> String {
+    let total_frames = (seconds.max(0.0) * CMX_FPS as f64).round() as u64;
+    let fps = CMX_FPS as u64;
+    let frames = total_frames % fps;
+    let total_s = total_frames / fps;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_s / 3600,
+        (total_s / 60) % 60,
+        total_s % 60,
+        frames
+    )
+}
+
+fn parse_timecode(tc: &str) -> Option<f64> {
+    let parts: Vec<u64> = tc
+        .split([':', ';'])
+        .map(|p| p.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.len() != 4 {
+        return None;
+    }
+    let seconds = parts[0] * 3600 + parts[1] * 60 + parts[2];
+    Some(seconds as f64 + parts[3] as f64 / CMX_FPS as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_edl() -> Vec<AudioSegment> {
+        vec![
+            AudioSegment {
+                start_s: 0.0,
+                end_s: 0.0,
+                delay_ms: -120,
+                delay_raw: -119.75,
+                drift_rate_ms_s: 0.0,
+            },
+            AudioSegment {
+                start_s: 754.3125,
+                end_s: 754.3125,
+                delay_ms: 880,
+                delay_raw: 880.25,
+                drift_rate_ms_s: 0.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn both_formats_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let edl = sample_edl();
+        let paths = export_edl(dir.path(), "movie", "Source 2", &edl, Some(1500.0)).unwrap();
+        assert!(paths[0].ends_with("movie.Source_2.edl.json"));
+
+        for path in &paths {
+            let back = load_edl(path).unwrap();
+            assert_eq!(back, edl);
+            assert_eq!(back[1].delay_raw, 880.25);
+        }
+    }
+
+    #[test]
+    fn cmx_timecodes_alone_give_frame_precise_delays() {
+        let text = "TITLE: manual\nFCM: NON-DROP FRAME\n\n\
+                    001  AX       A     C        00:00:00:00 00:10:00:00 00:59:59:21 01:09:59:21\n\
+                    002  AX       A     C        00:10:00:00 00:20:00:00 01:10:01:00 01:20:01:00\n";
+        let edl = parse_cmx3600(text).unwrap();
+        assert_eq!(edl.len(), 2);
+        assert_eq!(edl[0].delay_ms, -125);
+        assert_eq!(edl[1].start_s, 600.0);
+        assert_eq!(edl[1].delay_ms, 1000);
+    }
+}
@@ -5,6 +5,7 @@
 //! - `timeline`: Reference <-> Source 2 timeline conversion
 //! - `data_io`: Dense analysis data serialization (JSON in temp folder)
 //! - `edl_builder`: Build transition zones from dense cluster data
+//! - `edl_io`: EDL export (JSON, CMX3600) and manual override loading
 //! - `boundary_refiner`: Silence detection (RMS + VAD), video snap
 //! - `audio_assembly`: FFmpeg segment extraction, drift correction, concat
 //! - `qa_check`: Post-correction quality verification
@@ -14,6 +15,7 @@ pub mod audio_assembly;
 pub mod boundary_refiner;
 pub mod data_io;
 pub mod edl_builder;
+pub mod edl_io;
 pub mod qa_check;
 pub mod run;
 pub mod timeline;
@@ -3,19 +3,25 @@
 //! `run_stepping_correction` is the main coordinator called by
 //! `AudioCorrectionStep`. It orchestrates the pipeline:
 //!
-//!   1. Load dense analysis data from temp folder
+//!   1. Load dense analysis data from temp folder (or a manual EDL override,
+//!      which skips steps 1-3)
 //!   2. Build transition zones from clusters
 //!   3. Refine boundaries with silence detection in Source 2
 //!   4. Assemble corrected audio from EDL
 //!   5. QA-check the result
 //!   6. Apply the verified EDL to all audio tracks from the same source
+//!
+//! With `stepping_export_edl` the final EDL is also written to the output
+//! folder as JSON and CMX3600.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::analysis::correlation::decode::get_audio_stream_info;
+use crate::correction::encode::{corrected_track_name, encode_corrected_audio, CorrectedEncode};
 use crate::extraction::tracks::extract_tracks;
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::SegmentFlagsEntry;
 use crate::models::enums::TrackType;
 use crate::models::media::{StreamProps, Track};
 use crate::models::settings::AppSettings;
@@ -25,8 +31,9 @@ use super::audio_assembly::{assemble_corrected_audio, decode_to_memory, get_audi
 use super::boundary_refiner::refine_boundaries;
 use super::data_io::load_stepping_data;
 use super::edl_builder::{build_segments_from_splice_points, find_transition_zones};
+use super::edl_io::{export_edl, load_edl};
 use super::qa_check::verify_correction;
-use super::types::AudioSegment;
+use super::types::{AudioSegment, SplicePoint};
 
 // ---------------------------------------------------------------------------
 // Main entry point
@@ -82,150 +89,41 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
             continue;
         }
 
-        // --- Load dense data ---
-        let data_path = match flag_info.stepping_data_path.as_deref() {
-            Some(p) => p.to_string(),
-            None => {
-                log(&format!(
-                    "[SteppingCorrection] ERROR: No dense data for {analysis_track_key}. Cannot proceed."
-                ));
-                continue;
-            }
-        };
-
-        log(&format!(
-            "[SteppingCorrection] Loading dense data from {}...",
-            Path::new(&data_path)
-                .file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-        ));
-
-        let stepping_data = match load_stepping_data(&data_path) {
-            Ok(d) => d,
-            Err(e) => {
-                log(&format!("[SteppingCorrection] ERROR: {e}"));
-                continue;
-            }
-        };
-
-        log(&format!(
-            "  {} windows, {} clusters",
-            stepping_data.windows.len(),
-            stepping_data.clusters.len()
-        ));
-
-        // --- Build transition zones from clusters ---
-        let zones = find_transition_zones(
-            &stepping_data,
-            &flag_info,
-            &ctx.settings,
-            &log,
-        );
-
-        if zones.is_empty() {
-            log("[SteppingCorrection] No transitions found. Audio delay appears uniform.");
-            continue;
-        }
-
-        // --- Decode Source 2 mono PCM for silence detection ---
-        let analysis_path = match find_analysis_track(ctx, analysis_track_key, runner) {
-            Some(p) => p,
-            None => continue,
-        };
-
-        let analysis_path_str = analysis_path.to_string_lossy().to_string();
-        let (idx, _) =
-            get_audio_stream_info(&analysis_path_str, None, runner, &ctx.tool_paths);
-        let idx = match idx {
-            Some(i) => i,
-            None => {
-                log(&format!("[ERROR] No audio stream in {analysis_path_str}"));
-                continue;
-            }
-        };
+        // --- Manual EDL override or detection ---
+        let manual_edl = flag_info.edl_override.as_deref();
+        let (edl, splice_points, decoded) = if let Some(edl_path) = manual_edl {
+            let edl = match load_edl(Path::new(edl_path)) {
+                Ok(e) => e,
+                Err(e) => {
+                    log(&format!("[SteppingCorrection] ERROR: {e}"));
+                    continue;
+                }
+            };
+            log(&format!(
+                "[SteppingCorrection] Using manual EDL {} ({} segments) -- skipping detection.",
+                Path::new(edl_path)
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+                edl.len()
+            ));
 
-        let (_, _, src2_sr) = match get_audio_properties(
-            &analysis_path_str,
-            idx,
-            runner,
-            &ctx.tool_paths,
-        ) {
-            Ok(props) => props,
-            Err(e) => {
-                log(&format!("[ERROR] {e}"));
-                continue;
+            let decoded = if subs_only {
+                None
+            } else {
+                match decode_analysis_track(ctx, analysis_track_key, runner, &log) {
+                    Some(d) => Some(d),
+                    None => continue,
+                }
+            };
+            (edl, Vec::new(), decoded)
+        } else {
+            match detect_edl(ctx, analysis_track_key, &flag_info, runner, &log) {
+                Some((edl, splice_points, decoded)) => (edl, splice_points, Some(decoded)),
+                None => continue,
             }
         };
 
-        let src2_pcm = match decode_to_memory(
-            &analysis_path_str,
-            idx,
-            src2_sr,
-            runner,
-            &ctx.tool_paths,
-            1, // mono
-            Some(&log),
-        ) {
-            Some(pcm) => pcm,
-            None => continue,
-        };
-
-        // --- Refine boundaries (silence detection in Source 2) ---
-        let splice_points = refine_boundaries(
-            &zones,
-            &src2_pcm,
-            src2_sr,
-            &ctx.settings,
-            &log,
-            ref_file_path.as_deref(),
-            Some(&ctx.tool_paths),
-            Some(runner),
-        );
-
-        if splice_points.is_empty() {
-            log("[SteppingCorrection] Boundary refinement produced no splice points.");
-            continue;
-        }
-
-        // --- Build final EDL ---
-        // Anchor = first cluster's delay
-        let first_cluster = stepping_data
-            .clusters
-            .iter()
-            .min_by(|a, b| {
-                a.time_range
-                    .0
-                    .partial_cmp(&b.time_range.0)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-
-        let first_cluster = match first_cluster {
-            Some(c) => c,
-            None => continue,
-        };
-
-        let anchor_ms = first_cluster.mean_delay_ms.round() as i32;
-        let anchor_raw = first_cluster.mean_delay_ms;
-
-        // Convert splice points to segment tuples
-        let seg_tuples: Vec<(f64, f64, f64)> = splice_points
-            .iter()
-            .map(|sp| (sp.src2_time_s, sp.delay_after_ms, sp.delay_after_ms))
-            .collect();
-
-        let edl = build_segments_from_splice_points(
-            anchor_ms,
-            anchor_raw,
-            &seg_tuples,
-            &log,
-        );
-
-        if edl.len() <= 1 {
-            log("[SteppingCorrection] Only one segment -- no stepping correction needed.");
-            continue;
-        }
-
         // Store EDL for subtitle adjustment (as JSON)
         let edl_json: Vec<serde_json::Value> = edl
             .iter()
@@ -239,14 +137,20 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
                 "[SteppingCorrection] Subs-only mode -- EDL with {} segments stored.",
                 edl.len()
             ));
+            export_final_edl(ctx, &source_key, &edl, None, &log);
             continue;
         }
 
+        let Some((analysis_path_str, src2_sr, src2_pcm)) = decoded else {
+            continue;
+        };
+        let anchor_ms = edl[0].delay_ms;
+
         // --- QA: Assemble a mono check track and verify ---
         let qa_filename = format!("qa_{}.flac", source_key.replace(' ', "_"));
         let qa_path = ctx.temp_dir.join(&qa_filename);
 
-        let qa_ok = assemble_corrected_audio(
+        let splice_methods = match assemble_corrected_audio(
             &edl,
             &analysis_path_str,
             &qa_path,
@@ -258,13 +162,15 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
             Some("mono"),
             Some(src2_sr),
             Some(&src2_pcm),
-        );
-
-        if !qa_ok {
-            log("[SteppingCorrection] QA assembly failed.");
-            ctx.stepping_edls.remove(&source_key);
-            continue;
-        }
+            Some(&splice_points),
+        ) {
+            Some(methods) => methods,
+            None => {
+                log("[SteppingCorrection] QA assembly failed.");
+                ctx.stepping_edls.remove(&source_key);
+                continue;
+            }
+        };
 
         let qa_path_str = qa_path.to_string_lossy().to_string();
         let (passed, _qa_meta) = verify_correction(
@@ -284,12 +190,18 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
             continue;
         }
 
+        let duration_s = src2_pcm.len() as f64 / src2_sr.max(1) as f64;
+        export_final_edl(ctx, &source_key, &edl, Some(duration_s), &log);
+
         // Store audit metadata from splice points for post-mux auditor
         if ctx.segment_flags.contains_key(analysis_track_key) {
             let boundary_audit: Vec<serde_json::Value> = splice_points
                 .iter()
                 .map(|sp| {
                     let br = sp.boundary_result.as_ref();
+                    let method = splice_methods
+                        .iter()
+                        .find(|m| (m.src2_time_s - sp.src2_time_s).abs() < 0.005);
                     serde_json::json!({
                         "target_time_s": sp.src2_time_s,
                         "delay_change_ms": sp.correction_ms,
@@ -303,6 +215,9 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
                         "video_snap_skipped": sp.snap_metadata.get("video_snap_skipped")
                             .and_then(|v| v.as_bool())
                             .unwrap_or(false),
+                        "join": method.map(|m| m.join.as_str()).unwrap_or("hard_cut"),
+                        "crossfade_ms": method.map(|m| m.crossfade_ms).unwrap_or(0.0),
+                        "gap_fill": method.and_then(|m| m.gap_fill.clone()),
                     })
                 })
                 .collect();
@@ -349,7 +264,9 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
                 None,
                 None,
                 None,
-            );
+                Some(&splice_points),
+            )
+            .is_some();
 
             if !ok {
                 log(&format!(
@@ -362,7 +279,16 @@ pub fn run_stepping_correction(ctx: &mut Context, runner: &CommandRunner) {
                 continue;
             }
 
-            swap_corrected_track(ctx, idx, &corrected_path, &ctx.settings.clone(), &log);
+            let encoded = encode_corrected_audio(
+                &corrected_path,
+                &extracted_path,
+                &ctx.extracted_items.as_ref().unwrap()[idx].track.props,
+                &ctx.settings,
+                runner,
+                &ctx.tool_paths,
+            );
+
+            swap_corrected_track(ctx, idx, &encoded, &ctx.settings.clone(), &log);
         }
 
         // Drop src2_pcm to free memory (it goes out of scope naturally)
@@ -412,7 +338,9 @@ pub fn apply_plan_to_file(
         None,
         None,
         None,
-    );
+        None,
+    )
+    .is_some();
 
     if ok {
         Some(corrected_path)
@@ -425,6 +353,184 @@ pub fn apply_plan_to_file(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Detected EDL, its splice points and the decoded analysis track.
+type DetectedEdl = (Vec<AudioSegment>, Vec<SplicePoint>, DecodedTrack);
+
+/// Analysis track path, sample rate and mono PCM.
+type DecodedTrack = (String, i32, Vec<i32>);
+
+/// Dense data -> transition zones -> refined splice points -> EDL.
+///
+/// Returns `None` (after logging why) when no correction should be made.
+fn detect_edl(
+    ctx: &Context,
+    analysis_track_key: &str,
+    flag_info: &SegmentFlagsEntry,
+    runner: &CommandRunner,
+    log: &dyn Fn(&str),
+) -> Option<DetectedEdl> {
+    let ref_file_path = ctx.sources.get("Source 1").cloned();
+
+    // --- Load dense data ---
+    let data_path = match flag_info.stepping_data_path.as_deref() {
+        Some(p) => p.to_string(),
+        None => {
+            log(&format!(
+                "[SteppingCorrection] ERROR: No dense data for {analysis_track_key}. Cannot proceed."
+            ));
+            return None;
+        }
+    };
+
+    log(&format!(
+        "[SteppingCorrection] Loading dense data from {}...",
+        Path::new(&data_path)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+    ));
+
+    let stepping_data = match load_stepping_data(&data_path) {
+        Ok(d) => d,
+        Err(e) => {
+            log(&format!("[SteppingCorrection] ERROR: {e}"));
+            return None;
+        }
+    };
+
+    log(&format!(
+        "  {} windows, {} clusters",
+        stepping_data.windows.len(),
+        stepping_data.clusters.len()
+    ));
+
+    // --- Build transition zones from clusters ---
+    let zones = find_transition_zones(&stepping_data, flag_info, &ctx.settings, log);
+
+    if zones.is_empty() {
+        log("[SteppingCorrection] No transitions found. Audio delay appears uniform.");
+        return None;
+    }
+
+    // --- Decode Source 2 mono PCM for silence detection ---
+    let (analysis_path_str, src2_sr, src2_pcm) =
+        decode_analysis_track(ctx, analysis_track_key, runner, log)?;
+
+    // --- Refine boundaries (silence detection in Source 2) ---
+    let splice_points = refine_boundaries(
+        &zones,
+        &src2_pcm,
+        src2_sr,
+        &ctx.settings,
+        log,
+        ref_file_path.as_deref(),
+        Some(&ctx.tool_paths),
+        Some(runner),
+    );
+
+    if splice_points.is_empty() {
+        log("[SteppingCorrection] Boundary refinement produced no splice points.");
+        return None;
+    }
+
+    // --- Build final EDL ---
+    // Anchor = first cluster's delay
+    let first_cluster = stepping_data.clusters.iter().min_by(|a, b| {
+        a.time_range
+            .0
+            .partial_cmp(&b.time_range.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })?;
+
+    let anchor_ms = first_cluster.mean_delay_ms.round() as i32;
+    let anchor_raw = first_cluster.mean_delay_ms;
+
+    // Convert splice points to segment tuples
+    let seg_tuples: Vec<(f64, f64, f64)> = splice_points
+        .iter()
+        .map(|sp| (sp.src2_time_s, sp.delay_after_ms, sp.delay_after_ms))
+        .collect();
+
+    let edl = build_segments_from_splice_points(anchor_ms, anchor_raw, &seg_tuples, log);
+
+    if edl.len() <= 1 {
+        log("[SteppingCorrection] Only one segment -- no stepping correction needed.");
+        return None;
+    }
+
+    Some((edl, splice_points, (analysis_path_str, src2_sr, src2_pcm)))
+}
+
+/// Locate the analysis track and decode it to mono PCM at its native rate.
+fn decode_analysis_track(
+    ctx: &Context,
+    analysis_track_key: &str,
+    runner: &CommandRunner,
+    log: &dyn Fn(&str),
+) -> Option<DecodedTrack> {
+    let analysis_path = find_analysis_track(ctx, analysis_track_key, runner)?;
+
+    let analysis_path_str = analysis_path.to_string_lossy().to_string();
+    let (idx, _) = get_audio_stream_info(&analysis_path_str, None, runner, &ctx.tool_paths);
+    let idx = match idx {
+        Some(i) => i,
+        None => {
+            log(&format!("[ERROR] No audio stream in {analysis_path_str}"));
+            return None;
+        }
+    };
+
+    let (_, _, src2_sr) =
+        match get_audio_properties(&analysis_path_str, idx, runner, &ctx.tool_paths) {
+            Ok(props) => props,
+            Err(e) => {
+                log(&format!("[ERROR] {e}"));
+                return None;
+            }
+        };
+
+    let src2_pcm = decode_to_memory(
+        &analysis_path_str,
+        idx,
+        src2_sr,
+        runner,
+        &ctx.tool_paths,
+        1, // mono
+        Some(log),
+    )?;
+
+    Some((analysis_path_str, src2_sr, src2_pcm))
+}
+
+/// Write the final EDL next to the output when `stepping_export_edl` is on.
+fn export_final_edl(
+    ctx: &Context,
+    source_key: &str,
+    edl: &[AudioSegment],
+    duration_s: Option<f64>,
+    log: &dyn Fn(&str),
+) {
+    if !ctx.settings.stepping_export_edl || ctx.output_dir.is_empty() {
+        return;
+    }
+
+    let stem = ctx
+        .sources
+        .get("Source 1")
+        .and_then(|p| Path::new(p).file_stem())
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+
+    match export_edl(Path::new(&ctx.output_dir), &stem, source_key, edl, duration_s) {
+        Ok(paths) => {
+            for path in paths {
+                log(&format!("[SteppingCorrection] EDL exported: {}", path.display()));
+            }
+        }
+        Err(e) => log(&format!("[WARN] EDL export failed for {source_key}: {e}")),
+    }
+}
+
 /// Locate the extracted analysis audio track, extracting if needed — `_find_analysis_track`
 fn find_analysis_track(
     ctx: &Context,
@@ -482,11 +588,11 @@ fn find_analysis_track(
     }
 }
 
-/// Preserve the original and point the item to the corrected FLAC — `_swap_corrected_track`
+/// Preserve the original and point the item to the corrected audio — `_swap_corrected_track`
 fn swap_corrected_track(
     ctx: &mut Context,
     target_idx: usize,
-    corrected_path: &Path,
+    encoded: &CorrectedEncode,
     settings: &AppSettings,
     log: &dyn Fn(&str),
 ) {
@@ -521,23 +627,20 @@ fn swap_corrected_track(
             codec_id: original_props.codec_id.clone(),
             lang: original_props.lang.clone(),
             name: preserved_name,
+            audio_channels: original_props.audio_channels,
         },
     };
 
-    // Update main track -> corrected FLAC
-    let corrected_label = &settings.stepping_corrected_track_label;
-    let corrected_name = if !corrected_label.is_empty() {
-        if !original_props.name.is_empty() {
-            format!("{} ({})", original_props.name, corrected_label)
-        } else {
-            corrected_label.clone()
-        }
-    } else {
-        original_props.name.clone()
-    };
+    // Update main track -> corrected (possibly re-encoded) audio
+    let corrected_name = corrected_track_name(
+        &original_props.name,
+        &settings.stepping_corrected_track_label,
+        encoded,
+    );
 
     let target_item = &mut items[target_idx];
-    target_item.extracted_path = Some(corrected_path.to_path_buf());
+    target_item.extracted_path = Some(encoded.path.clone());
+    target_item.correction_transcode = encoded.transcode.clone();
     target_item.is_corrected = true;
     target_item.container_delay_ms = 0;
     target_item.track = Track {
@@ -545,9 +648,10 @@ fn swap_corrected_track(
         id: target_item.track.id,
         track_type: target_item.track.track_type,
         props: StreamProps {
-            codec_id: "FLAC".to_string(),
+            codec_id: encoded.codec_id.clone(),
             lang: original_props.lang.clone(),
             name: corrected_name,
+            audio_channels: original_props.audio_channels,
         },
     };
     target_item.apply_track_name = true;
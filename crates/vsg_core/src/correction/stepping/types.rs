@@ -113,3 +113,18 @@ pub struct SplicePoint {
     /// Video snap metadata
     pub snap_metadata: HashMap<String, serde_json::Value>,
 }
+
+/// How assembly joined the audio at one EDL transition — reported in the QA audit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpliceMethod {
+    /// Transition position in target (Source 2) timeline
+    pub src2_time_s: f64,
+    /// "hard_cut" or "crossfade"
+    pub join: String,
+    /// Crossfade length (0 for hard cuts)
+    pub crossfade_ms: f64,
+    /// "silence" or "room_tone" when audio was inserted, None when trimmed
+    pub gap_fill: Option<String>,
+    /// Inserted (positive) or removed (negative) audio
+    pub gap_ms: i32,
+}
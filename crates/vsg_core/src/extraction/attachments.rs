@@ -32,7 +32,7 @@ const FONT_EXTENSIONS: &[&str] = &[
 const FONT_EXTENSIONS_BINARY: &[&str] = &[".ttf", ".otf", ".ttc", ".woff", ".woff2"];
 
 /// Check if an attachment is a font file — comprehensive detection covering all common cases.
-fn is_font_attachment(mime_type: &str, file_name: &str) -> bool {
+pub fn is_font_attachment(mime_type: &str, file_name: &str) -> bool {
     let mime_lower = mime_type.to_lowercase();
     let name_lower = file_name.to_lowercase();
 
@@ -133,7 +133,7 @@ pub fn extract_attachments(
         let mut mkvextract_args: Vec<&str> = vec!["mkvextract", mkv, "attachments"];
         let spec_refs: Vec<&str> = specs.iter().map(|s| s.as_str()).collect();
         mkvextract_args.extend(&spec_refs);
-        runner.run(&mkvextract_args, tool_paths);
+        let _ = runner.run(&mkvextract_args, tool_paths);
     } else {
         runner.log_message(&format!(
             "[Attachments] Found {total_attachments} attachments, but none were identified as fonts."
@@ -143,6 +143,23 @@ pub fn extract_attachments(
     files
 }
 
+/// Attachment name as stored in its source, without the
+/// `{role}_att_{id}_` prefix `extract_attachments` adds.
+pub fn original_attachment_name(path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if let Some((_, rest)) = file_name.split_once("_att_") {
+        if let Some((id, name)) = rest.split_once('_') {
+            if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) && !name.is_empty() {
+                return name.to_string();
+            }
+        }
+    }
+    file_name
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +189,14 @@ mod tests {
         assert!(!is_font_attachment("", "image.png"));
     }
 
+    #[test]
+    fn original_name_strips_extraction_prefix() {
+        let name = |p: &str| original_attachment_name(Path::new(p));
+        assert_eq!(name("/tmp/job/Source 2_att_14_Font_att_1.ttf"), "Font_att_1.ttf");
+        assert_eq!(name("/fonts/Open_Sans.ttf"), "Open_Sans.ttf");
+        assert_eq!(name("cover_att_x_front.jpg"), "cover_att_x_front.jpg");
+    }
+
     #[test]
     fn font_detection_mime_keywords() {
         assert!(is_font_attachment("application/x-font-whatever", "test"));
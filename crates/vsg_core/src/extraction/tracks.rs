@@ -6,7 +6,7 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::io::runner::CommandRunner;
+use crate::io::runner::{CommandRunner, RunError};
 
 // ─── Codec ID mapping ────────────────────────────────────────────────────────
 
@@ -323,7 +323,7 @@ pub fn get_stream_info(
     runner: &CommandRunner,
     tool_paths: &HashMap<String, String>,
 ) -> Option<serde_json::Value> {
-    let out = runner.run(&["mkvmerge", "-J", mkv_path], tool_paths)?;
+    let out = runner.run(&["mkvmerge", "-J", mkv_path], tool_paths).ok()?;
     match serde_json::from_str(&out) {
         Ok(v) => Some(v),
         Err(_) => {
@@ -339,7 +339,7 @@ pub fn get_stream_info_with_delays(
     runner: &CommandRunner,
     tool_paths: &HashMap<String, String>,
 ) -> Option<serde_json::Value> {
-    let out = runner.run(&["mkvmerge", "-J", mkv_path], tool_paths)?;
+    let out = runner.run(&["mkvmerge", "-J", mkv_path], tool_paths).ok()?;
     let mut info: serde_json::Value = match serde_json::from_str(&out) {
         Ok(v) => v,
         Err(_) => {
@@ -399,8 +399,8 @@ pub fn get_detailed_stream_info(
     );
 
     let out = match out {
-        Some(o) => o,
-        None => return HashMap::new(),
+        Ok(o) => o,
+        Err(_) => return HashMap::new(),
     };
 
     let ffprobe_data: serde_json::Value = match serde_json::from_str(&out) {
@@ -433,6 +433,8 @@ pub struct ExtractedTrack {
     pub path: String,
     pub codec_id: String,
     pub source: String,
+    #[serde(default)]
+    pub audio_channels: Option<i32>,
 }
 
 /// Extract tracks from MKV with enhanced error detection — `extract_tracks`
@@ -515,6 +517,14 @@ pub fn extract_tracks(
             path: out_path.to_string_lossy().to_string(),
             codec_id: codec.to_string(),
             source: role.to_string(),
+            audio_channels: if ttype == "audio" {
+                props
+                    .get("audio_channels")
+                    .and_then(|v| v.as_i64())
+                    .map(|c| c as i32)
+            } else {
+                None
+            },
         };
 
         if ttype == "audio" && codec.to_uppercase().contains("A_MS/ACM") {
@@ -557,9 +567,25 @@ pub fn extract_tracks(
         let spec_refs: Vec<&str> = specs.iter().map(|s| s.as_str()).collect();
         mkvextract_args.extend(&spec_refs);
 
-        let result = runner.run(&mkvextract_args, tool_paths);
+        let result = match runner.run(&mkvextract_args, tool_paths) {
+            // Outputs are verified below, so warnings alone aren't fatal
+            Err(RunError::Warnings { .. }) => {
+                runner.log_message(&format!(
+                    "[{role}] [WARNING] mkvextract finished with warnings."
+                ));
+                Ok(String::new())
+            }
+            // Partial outputs of a killed run aren't worth diagnosing
+            Err(e @ RunError::TimedOut { .. }) => {
+                return Err(format!(
+                    "[{role}] Extraction from {mkv} stopped: {e}. \
+                     Raise the tool timeout for large sources."
+                ));
+            }
+            result => result,
+        };
 
-        if result.is_none() {
+        if result.is_err() {
             runner.log_message(&format!("[{role}] [ERROR] mkvextract command failed!"));
 
             // Check which tracks succeeded/failed
@@ -767,7 +793,15 @@ pub fn extract_tracks(
             "-sn", "-c:a", "copy", &job.out,
         ];
 
-        if runner.run(&copy_cmd, tool_paths).is_none() {
+        let copied = runner.run(&copy_cmd, tool_paths);
+        if let Err(e @ RunError::TimedOut { .. }) = &copied {
+            // A PCM conversion of the same stream would only time out too
+            return Err(format!(
+                "[{role}] A_MS/ACM extraction of track {} stopped: {e}.",
+                job.tid
+            ));
+        }
+        if copied.is_err() {
             runner.log_message(&format!(
                 "[{role}] Stream copy refused. Falling back to PCM ({})...",
                 job.pcm
@@ -778,7 +812,7 @@ pub fn extract_tracks(
                 "-sn", "-acodec", &job.pcm, &job.out,
             ];
 
-            if runner.run(&pcm_cmd, tool_paths).is_none() {
+            if runner.run(&pcm_cmd, tool_paths).is_err() {
                 let mkv_name = Path::new(mkv)
                     .file_name()
                     .map(|s| s.to_string_lossy().to_string())
@@ -1,104 +1,97 @@
 //! Font manager — 1:1 port of `vsg_core/font_manager.py`.
 //!
 //! Font scanning, parsing, and replacement tracking for subtitle files.
-//! Uses fontconfig (fc-query) for font info, with freetype-rs planned for
-//! direct font parsing (matching Python's fonttools TTFont usage).
+//! Font names are read directly from the sfnt `name` table (TTF, OTF and
+//! every face of a TTC/OTC collection) instead of fonttools/fontconfig, so
+//! matching sees the same localized and typographic names libass does.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 use serde_json::Value;
 
-// ─── Fontconfig helpers ──────────────────────────────────────────────────────
-
-/// Get font info using fontconfig's fc-query — `_get_fontconfig_info`
-fn get_fontconfig_info(font_path: &Path) -> HashMap<String, String> {
-    let mut result = HashMap::new();
-    result.insert("family".to_string(), String::new());
-    result.insert("fullname".to_string(), String::new());
-    result.insert("style".to_string(), String::new());
-
-    let output = Command::new("fc-query")
-        .args(["-f", "%{family}\\n%{fullname}\\n%{style}", &font_path.to_string_lossy()])
-        .output();
-
-    if let Ok(output) = output {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let lines: Vec<&str> = stdout.trim().split('\n').collect();
-            if let Some(line) = lines.first() {
-                result.insert("family".to_string(), line.split(',').next().unwrap_or("").trim().to_string());
-            }
-            if let Some(line) = lines.get(1) {
-                result.insert("fullname".to_string(), line.split(',').next().unwrap_or("").trim().to_string());
-            }
-            if let Some(line) = lines.get(2) {
-                result.insert("style".to_string(), line.split(',').next().unwrap_or("").trim().to_string());
-            }
-        }
-    }
-
-    result
-}
-
 // ─── FontInfo ────────────────────────────────────────────────────────────────
 
-/// Information about a font file — `FontInfo`
+/// Information about a font face — `FontInfo`
 #[derive(Debug, Clone)]
 pub struct FontInfo {
     pub file_path: PathBuf,
     pub filename: String,
+    /// Index of the face within a TTC/OTC collection (0 otherwise)
+    pub face_index: u32,
     pub family_name: String,
     pub subfamily: String,
     pub full_name: String,
     pub postscript_name: String,
+    /// Typographic family/subfamily (name IDs 16/17), empty when absent
+    pub typographic_family: String,
+    pub typographic_subfamily: String,
+    /// Family names in every language the font provides
+    pub localized_families: Vec<String>,
     pub is_valid: bool,
     pub error: Option<String>,
 }
 
 impl FontInfo {
+    /// Info for the first face of `file_path`.
     pub fn new(file_path: &Path) -> Self {
-        let mut info = Self {
+        Self::from_file(file_path).swap_remove(0)
+    }
+
+    /// Info for every face in `file_path` — one for TTF/OTF, all faces of a
+    /// collection. A file that can't be parsed yields a single invalid entry
+    /// named after the file stem.
+    pub fn from_file(file_path: &Path) -> Vec<Self> {
+        match read_font_faces(file_path) {
+            Ok(faces) if !faces.is_empty() => faces.into_iter().map(Self::from_face).collect(),
+            Ok(_) => vec![Self::invalid(file_path, "Font contains no faces".to_string())],
+            Err(e) => vec![Self::invalid(file_path, e)],
+        }
+    }
+
+    fn from_face(face: FontFace) -> Self {
+        let mut localized_families: Vec<String> = Vec::new();
+        for family in face.family_names() {
+            if !localized_families.iter().any(|f| f == family) {
+                localized_families.push(family.to_string());
+            }
+        }
+        let filename = face.file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let full_name = if face.full_name.is_empty() { face.family_name.clone() } else { face.full_name };
+        Self {
+            file_path: face.file_path,
+            filename,
+            face_index: face.face_index,
+            family_name: face.family_name,
+            subfamily: face.subfamily,
+            full_name,
+            postscript_name: face.postscript_name,
+            typographic_family: face.typographic_family,
+            typographic_subfamily: face.typographic_subfamily,
+            localized_families,
+            is_valid: true,
+            error: None,
+        }
+    }
+
+    fn invalid(file_path: &Path, error: String) -> Self {
+        // Fallback to filename stem
+        let stem = file_path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self {
             file_path: file_path.to_path_buf(),
             filename: file_path.file_name().unwrap_or_default().to_string_lossy().to_string(),
-            family_name: String::new(),
+            face_index: 0,
+            family_name: stem.clone(),
             subfamily: String::new(),
-            full_name: String::new(),
+            full_name: stem,
             postscript_name: String::new(),
+            typographic_family: String::new(),
+            typographic_subfamily: String::new(),
+            localized_families: Vec::new(),
             is_valid: false,
-            error: None,
-        };
-        info.parse_font();
-        info
-    }
-
-    fn parse_font(&mut self) {
-        // TODO: When freetype-rs is added, use it for direct font parsing
-        // (matching Python's fonttools TTFont for name table IDs 1,2,4,6).
-        // For now, use fontconfig (fc-query) which is what libass uses.
-
-        let fc_info = get_fontconfig_info(&self.file_path);
-        let family = fc_info.get("family").map(|s| s.as_str()).unwrap_or("");
-
-        if !family.is_empty() {
-            self.family_name = family.to_string();
-            let fullname = fc_info.get("fullname").map(|s| s.as_str()).unwrap_or("");
-            self.full_name = if fullname.is_empty() {
-                family.to_string()
-            } else {
-                fullname.to_string()
-            };
-            self.subfamily = fc_info.get("style").map(|s| s.to_string()).unwrap_or_default();
-            self.is_valid = true;
-        } else {
-            // Fallback to filename stem
-            let stem = self.file_path.file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-            self.family_name = stem.clone();
-            self.full_name = stem;
-            self.is_valid = true;
+            error: Some(error),
         }
     }
 }
@@ -108,10 +101,10 @@ impl FontInfo {
 /// Scans directories for font files — `FontScanner`
 pub struct FontScanner {
     fonts_dir: PathBuf,
-    font_cache: HashMap<String, FontInfo>,
+    font_cache: HashMap<String, Vec<FontInfo>>,
 }
 
-const FONT_EXTENSIONS: &[&str] = &[".ttf", ".otf", ".ttc", ".woff", ".woff2"];
+const FONT_EXTENSIONS: &[&str] = &[".ttf", ".otf", ".ttc", ".otc", ".woff", ".woff2"];
 
 impl FontScanner {
     pub fn new(fonts_dir: &Path) -> Self {
@@ -121,6 +114,7 @@ impl FontScanner {
         }
     }
 
+    /// Every face of every font file under the fonts directory.
     pub fn scan(&mut self, include_subdirs: bool) -> Vec<FontInfo> {
         if !self.fonts_dir.exists() {
             return Vec::new();
@@ -161,22 +155,23 @@ impl FontScanner {
                 }
                 seen.insert(key.clone());
 
-                if !self.font_cache.contains_key(&key) {
-                    self.font_cache.insert(key.clone(), FontInfo::new(&path));
-                }
-                if let Some(info) = self.font_cache.get(&key) {
-                    fonts.push(info.clone());
-                }
+                let faces = self.font_cache.entry(key).or_insert_with(|| FontInfo::from_file(&path));
+                fonts.extend(faces.iter().cloned());
             } else if path.is_dir() && recurse {
                 self.scan_dir(&path, true, fonts, seen);
             }
         }
     }
 
+    /// Faces whose family — in any language, or typographic — is `family_name`.
     pub fn get_font_by_family(&mut self, family_name: &str) -> Vec<FontInfo> {
         let fonts = self.scan(true);
         fonts.into_iter()
-            .filter(|f| f.family_name.to_lowercase() == family_name.to_lowercase())
+            .filter(|f| {
+                f.family_name.eq_ignore_ascii_case(family_name)
+                    || f.typographic_family.eq_ignore_ascii_case(family_name)
+                    || f.localized_families.iter().any(|n| n.eq_ignore_ascii_case(family_name))
+            })
             .collect()
     }
 
@@ -280,6 +275,301 @@ impl FontReplacementManager {
     }
 }
 
+// ─── Font tables ─────────────────────────────────────────────────────────────
+
+/// `name` table IDs read from each face.
+const NAME_FAMILY: u16 = 1;
+const NAME_SUBFAMILY: u16 = 2;
+const NAME_FULL: u16 = 4;
+const NAME_POSTSCRIPT: u16 = 6;
+const NAME_TYPOGRAPHIC_FAMILY: u16 = 16;
+const NAME_TYPOGRAPHIC_SUBFAMILY: u16 = 17;
+
+/// Windows language ID for English (United States).
+const LANGUAGE_EN_US: u16 = 0x409;
+
+/// One decoded record of a face's `name` table — `FontName`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontName {
+    pub name_id: u16,
+    /// 0 = Unicode, 1 = Macintosh, 3 = Windows
+    pub platform_id: u16,
+    /// Platform-specific language ID, e.g. 0x409 (en-US) or 0x411 (ja-JP)
+    pub language_id: u16,
+    pub value: String,
+}
+
+/// A face read directly from a TrueType/OpenType file — `FontFace`
+///
+/// The preferred-language name fields are filled from `names`, which keeps
+/// every record so renderers' localized matching can be reproduced.
+#[derive(Debug, Clone, Default)]
+pub struct FontFace {
+    pub file_path: PathBuf,
+    /// Index of the face within a TTC/OTC collection (0 otherwise)
+    pub face_index: u32,
+    pub family_name: String,
+    pub subfamily: String,
+    pub full_name: String,
+    pub postscript_name: String,
+    /// Typographic family/subfamily (name IDs 16/17), empty when absent
+    pub typographic_family: String,
+    pub typographic_subfamily: String,
+    /// Every decodable `name` record, in all languages
+    pub names: Vec<FontName>,
+    /// `OS/2` usWeightClass (400 when the table is missing)
+    pub weight: u16,
+    pub bold: bool,
+    pub italic: bool,
+    /// Sorted, merged inclusive code point ranges from the `cmap` table
+    pub coverage: Vec<(u32, u32)>,
+}
+
+impl FontFace {
+    /// Whether the face has a glyph for `c`.
+    pub fn covers(&self, c: char) -> bool {
+        let cp = c as u32;
+        let idx = self.coverage.partition_point(|&(_, end)| end < cp);
+        self.coverage.get(idx).is_some_and(|&(start, _)| start <= cp)
+    }
+
+    /// Family (ID 1) and typographic family (ID 16) names in every language.
+    pub fn family_names(&self) -> impl Iterator<Item = &str> {
+        self.names_with_ids(&[NAME_FAMILY, NAME_TYPOGRAPHIC_FAMILY])
+    }
+
+    /// Full (ID 4) and PostScript (ID 6) names in every language.
+    pub fn full_names(&self) -> impl Iterator<Item = &str> {
+        self.names_with_ids(&[NAME_FULL, NAME_POSTSCRIPT])
+    }
+
+    /// Whether `\fn`-style `name` selects this face's family. Like libass,
+    /// the comparison is ASCII case-insensitive.
+    pub fn matches_family(&self, name: &str) -> bool {
+        self.family_names().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether `name` is this exact face's full or PostScript name.
+    pub fn matches_full_name(&self, name: &str) -> bool {
+        self.full_names().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    fn names_with_ids<'a>(&'a self, ids: &'a [u16]) -> impl Iterator<Item = &'a str> {
+        self.names
+            .iter()
+            .filter(move |n| ids.contains(&n.name_id))
+            .map(|n| n.value.as_str())
+    }
+}
+
+/// Read every face of a TTF/OTF/TTC/OTC file — `read_font_faces`
+pub fn read_font_faces(path: &Path) -> Result<Vec<FontFace>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    let mut faces = parse_font_faces(&data)?;
+    for face in &mut faces {
+        face.file_path = path.to_path_buf();
+    }
+    Ok(faces)
+}
+
+/// Parse every face of an sfnt font or collection held in memory.
+pub fn parse_font_faces(data: &[u8]) -> Result<Vec<FontFace>, String> {
+    match read_u32(data, 0)? {
+        0x0001_0000 | 0x4F54_544F | 0x7472_7565 => Ok(vec![parse_face(data, 0)?]),
+        // 'ttcf' — TrueType/OpenType collection
+        0x7474_6366 => {
+            let count = read_u32(data, 8)?;
+            (0..count)
+                .map(|i| {
+                    let offset = read_u32(data, 12 + i as usize * 4)? as usize;
+                    let mut face = parse_face(data, offset)?;
+                    face.face_index = i;
+                    Ok(face)
+                })
+                .collect()
+        }
+        0x774F_4646 | 0x774F_4632 => Err("WOFF fonts are not supported".to_string()),
+        _ => Err("Not a TrueType/OpenType font".to_string()),
+    }
+}
+
+/// Parse the face whose table directory starts at `face_offset`.
+fn parse_face(data: &[u8], face_offset: usize) -> Result<FontFace, String> {
+    let num_tables = read_u16(data, face_offset + 4)? as usize;
+    let mut tables: HashMap<[u8; 4], &[u8]> = HashMap::new();
+    for i in 0..num_tables {
+        let record = face_offset + 12 + i * 16;
+        let tag = data.get(record..record + 4).ok_or("Truncated table directory")?;
+        let offset = read_u32(data, record + 8)? as usize;
+        let length = read_u32(data, record + 12)? as usize;
+        let table = data.get(offset..offset.saturating_add(length)).ok_or("Table out of bounds")?;
+        tables.insert([tag[0], tag[1], tag[2], tag[3]], table);
+    }
+
+    let mut face = FontFace { weight: 400, ..FontFace::default() };
+    if let Some(name) = tables.get(b"name") {
+        face.names = read_names(name)?;
+        face.family_name = preferred_name(&face.names, NAME_FAMILY);
+        face.subfamily = preferred_name(&face.names, NAME_SUBFAMILY);
+        face.full_name = preferred_name(&face.names, NAME_FULL);
+        face.postscript_name = preferred_name(&face.names, NAME_POSTSCRIPT);
+        face.typographic_family = preferred_name(&face.names, NAME_TYPOGRAPHIC_FAMILY);
+        face.typographic_subfamily = preferred_name(&face.names, NAME_TYPOGRAPHIC_SUBFAMILY);
+    }
+    if let Some(os2) = tables.get(b"OS/2") {
+        face.weight = read_u16(os2, 4)?;
+        let fs_selection = read_u16(os2, 62)?;
+        face.bold = face.weight >= 700 || fs_selection & 0x20 != 0;
+        face.italic = fs_selection & 0x201 != 0;
+    } else if let Some(head) = tables.get(b"head") {
+        let mac_style = read_u16(head, 44)?;
+        face.bold = mac_style & 0x1 != 0;
+        face.italic = mac_style & 0x2 != 0;
+        if face.bold {
+            face.weight = 700;
+        }
+    }
+    if let Some(cmap) = tables.get(b"cmap") {
+        face.coverage = read_cmap_coverage(cmap)?;
+    }
+    Ok(face)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Truncated font data".to_string())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Truncated font data".to_string())
+}
+
+/// Decode every Unicode, Windows and Mac Roman record of a `name` table.
+fn read_names(name: &[u8]) -> Result<Vec<FontName>, String> {
+    let count = read_u16(name, 2)? as usize;
+    let strings = read_u16(name, 4)? as usize;
+    let mut names = Vec::with_capacity(count);
+    for i in 0..count {
+        let record = 6 + i * 12;
+        let platform_id = read_u16(name, record)?;
+        let encoding_id = read_u16(name, record + 2)?;
+        let language_id = read_u16(name, record + 4)?;
+        let name_id = read_u16(name, record + 6)?;
+        let length = read_u16(name, record + 8)? as usize;
+        let offset = strings + read_u16(name, record + 10)? as usize;
+        let Some(raw) = name.get(offset..offset + length) else { continue };
+        let value = match (platform_id, encoding_id) {
+            // Windows symbol (0), Unicode BMP (1) and full repertoire (10)
+            (0, _) | (3, 0) | (3, 1) | (3, 10) => {
+                let units: Vec<u16> = raw.chunks_exact(2).map(|b| u16::from_be_bytes([b[0], b[1]])).collect();
+                String::from_utf16_lossy(&units)
+            }
+            (1, 0) => encoding_rs::MACINTOSH.decode_without_bom_handling(raw).0.into_owned(),
+            _ => continue,
+        };
+        let value = value.trim_end_matches('\0').to_string();
+        if !value.is_empty() {
+            names.push(FontName { name_id, platform_id, language_id, value });
+        }
+    }
+    Ok(names)
+}
+
+/// The English (US) Windows record for `name_id`, else any Windows or
+/// Unicode record, else Macintosh.
+fn preferred_name(names: &[FontName], name_id: u16) -> String {
+    names
+        .iter()
+        .filter(|n| n.name_id == name_id)
+        .min_by_key(|n| match (n.platform_id, n.language_id) {
+            (3, LANGUAGE_EN_US) => 0,
+            (3, _) | (0, _) => 1,
+            _ => 2,
+        })
+        .map(|n| n.value.clone())
+        .unwrap_or_default()
+}
+
+/// Code point ranges from the best Unicode `cmap` subtable (format 12 or 4).
+fn read_cmap_coverage(cmap: &[u8]) -> Result<Vec<(u32, u32)>, String> {
+    let count = read_u16(cmap, 2)? as usize;
+    let mut format4 = None;
+    let mut format12 = None;
+    for i in 0..count {
+        let record = 4 + i * 8;
+        let platform = read_u16(cmap, record)?;
+        let encoding = read_u16(cmap, record + 2)?;
+        let offset = read_u32(cmap, record + 4)? as usize;
+        let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+        if !unicode {
+            continue;
+        }
+        match read_u16(cmap, offset)? {
+            12 => format12 = Some(offset),
+            4 => format4 = Some(offset),
+            _ => {}
+        }
+    }
+
+    let mut ranges = Vec::new();
+    if let Some(offset) = format12 {
+        let groups = read_u32(cmap, offset + 12)? as usize;
+        for g in 0..groups {
+            let group = offset + 16 + g * 12;
+            let start = read_u32(cmap, group)?;
+            let end = read_u32(cmap, group + 4)?;
+            let first_glyph = read_u32(cmap, group + 8)?;
+            // A group starting at glyph 0 maps its first code point to .notdef
+            let start = if first_glyph == 0 { start + 1 } else { start };
+            if start <= end {
+                ranges.push((start, end));
+            }
+        }
+    } else if let Some(offset) = format4 {
+        let segments = read_u16(cmap, offset + 6)? as usize / 2;
+        let ends = offset + 14;
+        let starts = ends + segments * 2 + 2;
+        let deltas = starts + segments * 2;
+        let range_offsets = deltas + segments * 2;
+        for s in 0..segments {
+            let end = read_u16(cmap, ends + s * 2)?;
+            let start = read_u16(cmap, starts + s * 2)?;
+            let delta = read_u16(cmap, deltas + s * 2)?;
+            let range_offset = read_u16(cmap, range_offsets + s * 2)? as usize;
+            if start == 0xFFFF || start > end {
+                continue;
+            }
+            for c in start..=end {
+                let glyph = if range_offset == 0 {
+                    c.wrapping_add(delta)
+                } else {
+                    let addr = range_offsets + s * 2 + range_offset + (c - start) as usize * 2;
+                    match read_u16(cmap, addr)? {
+                        0 => 0,
+                        g => g.wrapping_add(delta),
+                    }
+                };
+                if glyph != 0 {
+                    ranges.push((c as u32, c as u32));
+                }
+            }
+        }
+    }
+
+    ranges.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    Ok(merged)
+}
+
 // ─── Validation (standalone functions) ───────────────────────────────────────
 
 /// Validate font replacements — `validate_font_replacements`
@@ -317,3 +607,123 @@ pub fn validate_font_replacements(
 
     serde_json::from_value(result).unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn be16(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    /// Table directory plus table data for one face, with table offsets
+    /// relative to `base` (where the face data will sit in the file).
+    fn face_tables(base: usize, tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut out = be16(&[1, 0, tables.len() as u16, 0, 0, 0]);
+        let mut offset = base + 12 + tables.len() * 16;
+        for (tag, data) in tables {
+            out.extend_from_slice(*tag);
+            out.extend_from_slice(&[0; 4]);
+            out.extend_from_slice(&(offset as u32).to_be_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += data.len();
+        }
+        for (_, data) in tables {
+            out.extend_from_slice(data);
+        }
+        out
+    }
+
+    /// A `name` table from `(platform, encoding, language, name_id, text)`.
+    fn name_table(records: &[(u16, u16, u16, u16, &str)]) -> Vec<u8> {
+        let mut strings = Vec::new();
+        let mut out = be16(&[0, records.len() as u16, 6 + records.len() as u16 * 12]);
+        for &(platform, encoding, language, name_id, text) in records {
+            let encoded = if platform == 1 {
+                text.bytes().collect()
+            } else {
+                be16(&text.encode_utf16().collect::<Vec<_>>())
+            };
+            out.extend(be16(&[platform, encoding, language, name_id]));
+            out.extend(be16(&[encoded.len() as u16, strings.len() as u16]));
+            strings.extend(encoded);
+        }
+        out.extend(strings);
+        out
+    }
+
+    fn os2(weight: u16, fs_selection: u16) -> Vec<u8> {
+        let mut os2 = vec![0u8; 64];
+        os2[4..6].copy_from_slice(&weight.to_be_bytes());
+        os2[62..64].copy_from_slice(&fs_selection.to_be_bytes());
+        os2
+    }
+
+    #[test]
+    fn parses_family_style_and_cmap_coverage() {
+        let name = name_table(&[(3, 1, 0x409, 1, "Test Sans")]);
+
+        // Format 4: A-C by delta, U+00E9 unmapped and U+00EA mapped via glyphIdArray
+        let mut cmap = be16(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(be16(&[4, 0, 0, 6, 0, 0, 0]));
+        cmap.extend(be16(&[0x43, 0xEA, 0xFFFF, 0]));
+        cmap.extend(be16(&[0x41, 0xE9, 0xFFFF]));
+        cmap.extend(be16(&[0xFFC0, 0, 1]));
+        cmap.extend(be16(&[0, 4, 0]));
+        cmap.extend(be16(&[0, 5]));
+
+        let tables = [(b"OS/2", os2(700, 1)), (b"cmap", cmap), (b"name", name)];
+        let faces = parse_font_faces(&face_tables(0, &tables)).unwrap();
+        let face = &faces[0];
+        assert_eq!(face.family_name, "Test Sans");
+        assert!(face.bold && face.italic);
+        assert_eq!(face.coverage, vec![(0x41, 0x43), (0xEA, 0xEA)]);
+        assert!(face.covers('B') && face.covers('ê'));
+        assert!(!face.covers('é') && !face.covers('D'));
+        assert!(parse_font_faces(b"wOFF\0\0\0\0").is_err());
+    }
+
+    #[test]
+    fn reads_localized_typographic_names_from_every_collection_face() {
+        let regular = name_table(&[
+            (1, 0, 0, 1, "Mac Gothic"),
+            (3, 1, 0x411, 1, "ゴシック"),
+            (3, 1, 0x409, 1, "Gothic"),
+            (3, 1, 0x409, 2, "Regular"),
+            (3, 1, 0x409, 4, "Gothic Regular"),
+            (3, 1, 0x409, 6, "Gothic-Regular"),
+        ]);
+        let heavy = name_table(&[
+            (3, 1, 0x409, 1, "Gothic Heavy"),
+            (3, 1, 0x409, 2, "Regular"),
+            (3, 1, 0x409, 16, "Gothic"),
+            (3, 1, 0x409, 17, "Heavy"),
+        ]);
+
+        // 'ttcf' header with two face offsets, then both faces
+        let header_len = 20;
+        let first = face_tables(header_len, &[(b"name", regular)]);
+        let second = face_tables(header_len + first.len(), &[(b"OS/2", os2(900, 0)), (b"name", heavy)]);
+        let mut ttc = b"ttcf".to_vec();
+        ttc.extend(be16(&[1, 0, 0, 2, 0, header_len as u16]));
+        ttc.extend(((header_len + first.len()) as u32).to_be_bytes());
+        ttc.extend(first);
+        ttc.extend(second);
+
+        let faces = parse_font_faces(&ttc).unwrap();
+        assert_eq!(faces.len(), 2);
+
+        let regular = &faces[0];
+        assert_eq!(regular.family_name, "Gothic");
+        assert_eq!(regular.full_name, "Gothic Regular");
+        assert_eq!(regular.postscript_name, "Gothic-Regular");
+        assert_eq!(regular.weight, 400);
+        assert!(regular.matches_family("ゴシック") && regular.matches_family("mac gothic"));
+        assert!(regular.matches_full_name("GOTHIC-REGULAR"));
+
+        let heavy = &faces[1];
+        assert_eq!(heavy.face_index, 1);
+        assert_eq!((heavy.typographic_family.as_str(), heavy.typographic_subfamily.as_str()), ("Gothic", "Heavy"));
+        assert!(heavy.bold && heavy.matches_family("Gothic") && heavy.matches_family("Gothic Heavy"));
+    }
+}
@@ -1,26 +1,122 @@
 //! Command runner — 1:1 port of `vsg_core/io/runner.py`.
 //!
 //! Wrapper for running external command-line processes (mkvmerge, ffmpeg, etc.).
+//!
+//! Beyond the Python original, every command runs under a timeout (resolved
+//! per tool and per step), hung children are killed, transient failures are
+//! retried with back-off, and children can be started under `nice`/`ionice`
+//! and an address-space limit. Failures are classified as [`RunError`].
 
 use std::collections::{HashMap, VecDeque};
-use std::process::{Command, Stdio};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 
+use crate::models::events::{EventCallback, JobEvent};
 use crate::models::settings::AppSettings;
 
 /// Log callback type — receives formatted log lines.
 pub type LogCallback = Box<dyn Fn(&str) + Send + Sync>;
 
+/// Poll interval while waiting on a child that has a timeout.
+const WAIT_POLL: Duration = Duration::from_millis(20);
+
+/// mkvtoolnix tools exit with 1 for warnings and 2 for errors.
+const MKVTOOLNIX_TOOLS: &[&str] = &["mkvmerge", "mkvextract", "mkvpropedit", "mkvinfo"];
+
+/// Classified failure of an external command.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RunError {
+    #[error("empty command")]
+    EmptyCommand,
+    #[error("failed to start {tool}: {message}")]
+    Spawn {
+        tool: String,
+        message: String,
+        transient: bool,
+    },
+    #[error("I/O error while running {tool}: {message}")]
+    Io { tool: String, message: String },
+    #[error("{tool} timed out after {timeout_s}s and was killed")]
+    TimedOut { tool: String, timeout_s: u64 },
+    #[error("{tool} was terminated by a signal")]
+    Killed { tool: String },
+    #[error("{tool} finished with warnings (exit code {exit_code})")]
+    Warnings { tool: String, exit_code: i32 },
+    #[error("{tool} failed with exit code {exit_code}")]
+    Failed { tool: String, exit_code: i32 },
+}
+
+impl RunError {
+    /// Whether the failure is worth retrying. Only spawn and I/O failures
+    /// are; exit codes are deterministic, though mkvtoolnix warnings can
+    /// be retried via `tool_retry_warnings`.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            RunError::Spawn { transient, .. } => *transient,
+            RunError::Io { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The child's exit code, when it exited normally.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            RunError::Warnings { exit_code, .. } | RunError::Failed { exit_code, .. } => {
+                Some(*exit_code)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Executes external commands and streams output — `CommandRunner`
 pub struct CommandRunner {
     settings: AppSettings,
     log: LogCallback,
+    events: Option<EventCallback>,
+    step: Option<String>,
 }
 
 impl CommandRunner {
     pub fn new(settings: AppSettings, log: LogCallback) -> Self {
-        Self { settings, log }
+        Self {
+            settings,
+            log,
+            events: None,
+            step: None,
+        }
+    }
+
+    /// Emit a `ToolInvoked` event for every command this runner executes.
+    pub fn set_event_callback(&mut self, events: EventCallback) {
+        self.events = Some(events);
+    }
+
+    /// Name the pipeline step this runner serves, for per-step timeouts.
+    pub fn set_step(&mut self, step: &str) {
+        self.step = Some(step.to_string());
+    }
+
+    fn emit_tool_invoked(
+        &self,
+        cmd: &[&str],
+        exit_code: Option<i32>,
+        ok: bool,
+        started: Instant,
+    ) {
+        if let Some(ref events) = self.events {
+            events(&JobEvent::ToolInvoked {
+                tool: cmd[0].to_string(),
+                args: cmd[1..].iter().map(|s| s.to_string()).collect(),
+                exit_code,
+                ok,
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+        }
     }
 
     /// Formats and sends a timestamped message to the log callback — `_log_message`
@@ -32,13 +128,13 @@ impl CommandRunner {
 
     /// Executes a command and handles logging based on configuration — `run()`
     ///
-    /// Returns captured stdout as a String.
-    /// Returns None on failure (non-zero exit code or execution error).
+    /// Returns captured stdout as a String, or the classified failure
+    /// (non-zero exit code, timeout, signal or execution error).
     pub fn run(
         &self,
         cmd: &[&str],
         tool_paths: &HashMap<String, String>,
-    ) -> Option<String> {
+    ) -> Result<String, RunError> {
         self.run_with_options(cmd, tool_paths, false, None)
     }
 
@@ -48,12 +144,76 @@ impl CommandRunner {
         cmd: &[&str],
         tool_paths: &HashMap<String, String>,
         input_data: Option<&[u8]>,
-    ) -> Option<Vec<u8>> {
-        if cmd.is_empty() {
-            return None;
+    ) -> Result<Vec<u8>, RunError> {
+        self.with_retries(|| self.run_binary_once(cmd, tool_paths, input_data))
+    }
+
+    /// Full run implementation with all options — `run()` from Python.
+    pub fn run_with_options(
+        &self,
+        cmd: &[&str],
+        tool_paths: &HashMap<String, String>,
+        is_binary: bool,
+        input_data: Option<&[u8]>,
+    ) -> Result<String, RunError> {
+        if is_binary {
+            // Binary mode delegates to run_binary and converts
+            return self
+                .run_binary(cmd, tool_paths, input_data)
+                .map(|bytes| String::from_utf8_lossy(&bytes).to_string());
+        }
+
+        self.with_retries(|| self.run_text_once(cmd, tool_paths, input_data))
+    }
+
+    /// Timeout for `tool` in the current step, if any.
+    pub fn timeout_for(&self, tool: &str) -> Option<Duration> {
+        resolve_timeout(
+            &self.settings.tool_timeout_overrides,
+            self.settings.tool_timeout_s,
+            self.step.as_deref(),
+            tool,
+        )
+    }
+
+    // -----------------------------------------------------------------
+    // Internals
+    // -----------------------------------------------------------------
+
+    /// Runs `attempt` again while it fails transiently, doubling the back-off.
+    fn with_retries<T>(
+        &self,
+        mut attempt: impl FnMut() -> Result<T, RunError>,
+    ) -> Result<T, RunError> {
+        let retries = self.settings.tool_retry_count.max(0) as u32;
+        let mut backoff =
+            Duration::from_millis(self.settings.tool_retry_backoff_ms.max(0) as u64);
+        let mut tries = 0;
+        loop {
+            match attempt() {
+                Err(e) if self.should_retry(&e) && tries < retries => {
+                    tries += 1;
+                    self.log_message(&format!(
+                        "[!] {e}; retrying in {}ms (attempt {tries}/{retries})",
+                        backoff.as_millis()
+                    ));
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                result => return result,
+            }
         }
+    }
 
+    fn should_retry(&self, e: &RunError) -> bool {
+        e.is_transient()
+            || (self.settings.tool_retry_warnings && matches!(e, RunError::Warnings { .. }))
+    }
+
+    /// Resolves the tool path and logs the command line.
+    fn prepare(&self, cmd: &[&str], tool_paths: &HashMap<String, String>) -> Vec<String> {
         let tool_name = cmd[0];
+        // Use `or` logic: dict.get returns None if key exists with empty value
         let resolved = tool_paths
             .get(tool_name)
             .filter(|p| !p.is_empty())
@@ -70,45 +230,99 @@ impl CommandRunner {
             .join(" ");
         self.log_message(&format!("$ {pretty_cmd}"));
 
-        let mut command = Command::new(&full_cmd[0]);
-        command.args(&full_cmd[1..]);
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::piped()); // Separate stderr in binary mode
+        full_cmd
+    }
+
+    /// Prefixes `nice`/`ionice`/`prlimit` wrappers when configured and available.
+    fn build_command(&self, full_cmd: &[String]) -> Command {
+        let mut argv: Vec<String> = Vec::new();
+        if cfg!(unix) {
+            let s = &self.settings;
+            if s.tool_nice_level != 0 && which::which("nice").is_ok() {
+                argv.push("nice".to_string());
+                argv.push("-n".to_string());
+                argv.push(s.tool_nice_level.to_string());
+            }
+            if s.tool_ionice_class > 0 && which::which("ionice").is_ok() {
+                argv.push("ionice".to_string());
+                argv.push("-c".to_string());
+                argv.push(s.tool_ionice_class.to_string());
+            }
+            if s.tool_memory_limit_mb > 0 && which::which("prlimit").is_ok() {
+                let bytes = s.tool_memory_limit_mb as u64 * 1024 * 1024;
+                argv.push("prlimit".to_string());
+                argv.push(format!("--as={bytes}"));
+                argv.push("--".to_string());
+            }
+        }
+        argv.extend(full_cmd.iter().cloned());
+
+        let mut command = Command::new(&argv[0]);
+        command.args(&argv[1..]);
+        command
+    }
+
+    /// Spawns the command and waits for it under the resolved timeout.
+    fn execute(
+        &self,
+        cmd: &[&str],
+        full_cmd: &[String],
+        input_data: Option<&[u8]>,
+    ) -> Result<Output, RunError> {
+        let tool = tool_label(cmd[0]);
 
+        let mut command = self.build_command(full_cmd);
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
         if input_data.is_some() {
             command.stdin(Stdio::piped());
         }
 
+        let started = Instant::now();
         let child = match command.spawn() {
             Ok(c) => c,
             Err(e) => {
                 self.log_message(&format!("[!] Failed to execute command: {e}"));
-                return None;
+                self.emit_tool_invoked(cmd, None, false, started);
+                return Err(RunError::Spawn {
+                    tool,
+                    transient: matches!(
+                        e.kind(),
+                        std::io::ErrorKind::Interrupted | std::io::ErrorKind::WouldBlock
+                    ),
+                    message: e.to_string(),
+                });
             }
         };
 
-        let output = if let Some(input) = input_data {
-            use std::io::Write;
-            let mut child = child;
-            if let Some(mut stdin) = child.stdin.take() {
-                let _ = stdin.write_all(input);
+        let timeout = self.timeout_for(&tool);
+        let result = wait_with_timeout(child, input_data, timeout, &tool);
+        match &result {
+            Ok(output) => {
+                let ok = classify_exit(&tool, output.status).is_ok();
+                self.emit_tool_invoked(cmd, output.status.code(), ok, started);
             }
-            match child.wait_with_output() {
-                Ok(o) => o,
-                Err(e) => {
-                    self.log_message(&format!("[!] Failed to execute command: {e}"));
-                    return None;
-                }
-            }
-        } else {
-            match child.wait_with_output() {
-                Ok(o) => o,
-                Err(e) => {
-                    self.log_message(&format!("[!] Failed to execute command: {e}"));
-                    return None;
-                }
+            Err(e) => {
+                self.log_message(&format!("[!] {e}"));
+                self.emit_tool_invoked(cmd, None, false, started);
             }
-        };
+        }
+        result
+    }
+
+    fn run_binary_once(
+        &self,
+        cmd: &[&str],
+        tool_paths: &HashMap<String, String>,
+        input_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, RunError> {
+        if cmd.is_empty() {
+            return Err(RunError::EmptyCommand);
+        }
+
+        let full_cmd = self.prepare(cmd, tool_paths);
+        // Separate stderr in binary mode
+        let output = self.execute(cmd, &full_cmd, input_data)?;
 
         // Log stderr separately in binary mode
         if !output.stderr.is_empty() {
@@ -119,98 +333,33 @@ impl CommandRunner {
             }
         }
 
-        if !output.status.success() {
-            let rc = output.status.code().unwrap_or(-1);
-            self.log_message(&format!("[!] Command failed with exit code {rc}"));
-            return None;
+        if let Err(e) = classify_exit(&tool_label(cmd[0]), output.status) {
+            self.log_failure(&e);
+            return Err(e);
         }
 
-        Some(output.stdout)
+        Ok(output.stdout)
     }
 
-    /// Full run implementation with all options — `run()` from Python.
-    pub fn run_with_options(
+    fn run_text_once(
         &self,
         cmd: &[&str],
         tool_paths: &HashMap<String, String>,
-        is_binary: bool,
         input_data: Option<&[u8]>,
-    ) -> Option<String> {
-        if is_binary {
-            // Binary mode delegates to run_binary and converts
-            return self
-                .run_binary(cmd, tool_paths, input_data)
-                .map(|bytes| String::from_utf8_lossy(&bytes).to_string());
-        }
-
+    ) -> Result<String, RunError> {
         if cmd.is_empty() {
-            return None;
+            return Err(RunError::EmptyCommand);
         }
 
-        let tool_name = cmd[0];
-        // Use `or` logic: dict.get returns None if key exists with empty value
-        let resolved = tool_paths
-            .get(tool_name)
-            .filter(|p| !p.is_empty())
-            .map(|p| p.as_str())
-            .unwrap_or(tool_name);
-
-        let mut full_cmd: Vec<String> = vec![resolved.to_string()];
-        full_cmd.extend(cmd[1..].iter().map(|s| s.to_string()));
-
-        let pretty_cmd = full_cmd
-            .iter()
-            .map(|c| shell_quote(c))
-            .collect::<Vec<_>>()
-            .join(" ");
-        self.log_message(&format!("$ {pretty_cmd}"));
+        let full_cmd = self.prepare(cmd, tool_paths);
 
         let compact = self.settings.log_compact;
         let tail_ok = self.settings.log_tail_lines;
         let err_tail = self.settings.log_error_tail;
         let prog_step = self.settings.log_progress_step.max(1);
 
-        let mut command = Command::new(&full_cmd[0]);
-        command.args(&full_cmd[1..]);
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::piped());
-
-        if input_data.is_some() {
-            command.stdin(Stdio::piped());
-        }
-
-        let child = match command.spawn() {
-            Ok(c) => c,
-            Err(e) => {
-                self.log_message(&format!("[!] Failed to execute command: {e}"));
-                return None;
-            }
-        };
-
-        let output = if let Some(input) = input_data {
-            use std::io::Write;
-            let mut child = child;
-            if let Some(mut stdin) = child.stdin.take() {
-                let _ = stdin.write_all(input);
-            }
-            match child.wait_with_output() {
-                Ok(o) => o,
-                Err(e) => {
-                    self.log_message(&format!("[!] Failed to execute command: {e}"));
-                    return None;
-                }
-            }
-        } else {
-            match child.wait_with_output() {
-                Ok(o) => o,
-                Err(e) => {
-                    self.log_message(&format!("[!] Failed to execute command: {e}"));
-                    return None;
-                }
-            }
-        };
-
-        let rc = output.status.code().unwrap_or(0);
+        let output = self.execute(cmd, &full_cmd, input_data)?;
+        let status = classify_exit(&tool_label(cmd[0]), output.status);
 
         // Combine stdout + stderr for text mode (matches Python's subprocess.STDOUT)
         let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
@@ -248,8 +397,8 @@ impl CommandRunner {
                 }
             }
 
-            if rc != 0 {
-                self.log_message(&format!("[!] Command failed with exit code {rc}"));
+            if let Err(e) = status {
+                self.log_failure(&e);
                 if err_tail > 0 && !tail_buffer.is_empty() {
                     let skip = tail_buffer
                         .len()
@@ -263,7 +412,7 @@ impl CommandRunner {
                         ));
                     }
                 }
-                return None;
+                return Err(e);
             }
 
             if tail_ok > 0 && !tail_buffer.is_empty() {
@@ -284,16 +433,144 @@ impl CommandRunner {
                 self.log_message(line);
             }
 
-            if rc != 0 {
-                self.log_message(&format!("[!] Command failed with exit code {rc}"));
-                return None;
+            if let Err(e) = status {
+                self.log_failure(&e);
+                return Err(e);
             }
         }
 
-        Some(combined)
+        Ok(combined)
+    }
+
+    fn log_failure(&self, e: &RunError) {
+        match e.exit_code() {
+            Some(rc) => self.log_message(&format!("[!] Command failed with exit code {rc}")),
+            None => self.log_message(&format!("[!] Command failed: {e}")),
+        }
+    }
+}
+
+/// Tool name used for classification and timeouts, e.g. `/usr/bin/ffmpeg` → `ffmpeg`.
+fn tool_label(cmd0: &str) -> String {
+    Path::new(cmd0)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| cmd0.to_string())
+}
+
+/// Maps an exit status to `Ok` or a classified `RunError`.
+fn classify_exit(tool: &str, status: ExitStatus) -> Result<(), RunError> {
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(1) if MKVTOOLNIX_TOOLS.contains(&tool) => Err(RunError::Warnings {
+            tool: tool.to_string(),
+            exit_code: 1,
+        }),
+        Some(exit_code) => Err(RunError::Failed {
+            tool: tool.to_string(),
+            exit_code,
+        }),
+        None => Err(RunError::Killed {
+            tool: tool.to_string(),
+        }),
     }
 }
 
+/// Waits for `child`, feeding stdin and draining both pipes on helper
+/// threads so a chatty child can't deadlock. Kills it once `timeout` passes.
+fn wait_with_timeout(
+    mut child: Child,
+    input_data: Option<&[u8]>,
+    timeout: Option<Duration>,
+    tool: &str,
+) -> Result<Output, RunError> {
+    let io_err = |e: std::io::Error| RunError::Io {
+        tool: tool.to_string(),
+        message: e.to_string(),
+    };
+
+    std::thread::scope(|scope| {
+        if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input_data) {
+            scope.spawn(move || {
+                let _ = stdin.write_all(input);
+            });
+        }
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let stdout_reader = scope.spawn(move || read_all(stdout));
+        let stderr_reader = scope.spawn(move || read_all(stderr));
+
+        let status = match timeout {
+            None => child.wait().map_err(io_err),
+            Some(limit) => {
+                let deadline = Instant::now() + limit;
+                loop {
+                    match child.try_wait() {
+                        Ok(Some(status)) => break Ok(status),
+                        Ok(None) if Instant::now() >= deadline => {
+                            let _ = child.kill();
+                            let _ = child.wait();
+                            break Err(RunError::TimedOut {
+                                tool: tool.to_string(),
+                                timeout_s: limit.as_secs(),
+                            });
+                        }
+                        Ok(None) => std::thread::sleep(WAIT_POLL),
+                        Err(e) => break Err(io_err(e)),
+                    }
+                }
+            }
+        };
+
+        let stdout = stdout_reader.join().unwrap_or_default();
+        let stderr = stderr_reader.join().unwrap_or_default();
+        status.map(|status| Output {
+            status,
+            stdout,
+            stderr,
+        })
+    })
+}
+
+fn read_all(pipe: Option<impl Read>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut buf);
+    }
+    buf
+}
+
+/// Resolves the timeout for `tool` in `step`.
+///
+/// `overrides` is a comma-separated list of `key=seconds` where key is
+/// `step.tool`, `tool` or `step` (checked in that order); anything else falls
+/// back to `default_s`. A value of 0 disables the timeout.
+fn resolve_timeout(
+    overrides: &str,
+    default_s: i32,
+    step: Option<&str>,
+    tool: &str,
+) -> Option<Duration> {
+    let table: HashMap<&str, i64> = overrides
+        .split(',')
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('=')?;
+            Some((key.trim(), value.trim().parse::<i64>().ok()?))
+        })
+        .collect();
+
+    let scoped = step.map(|s| format!("{s}.{tool}"));
+    let seconds = scoped
+        .as_deref()
+        .and_then(|k| table.get(k))
+        .or_else(|| table.get(tool))
+        .or_else(|| step.and_then(|s| table.get(s)))
+        .copied()
+        .unwrap_or(default_s as i64);
+
+    (seconds > 0).then(|| Duration::from_secs(seconds as u64))
+}
+
 /// Parse a progress percentage from a line like "Progress: 42%"
 fn parse_progress_pct(line: &str) -> Option<i32> {
     let trimmed = line.trim();
@@ -339,4 +616,63 @@ mod tests {
         assert_eq!(shell_quote("file (1).mkv"), "'file (1).mkv'");
         assert_eq!(shell_quote("it's"), "\"it'\\''s\"".replace('"', "'"));
     }
+
+    #[test]
+    fn timeout_resolution_order() {
+        let spec = "ffmpeg=1800, analysis=600, analysis.ffmpeg=300, mkvmerge=0";
+        let secs = |step, tool| resolve_timeout(spec, 7200, step, tool).map(|d| d.as_secs());
+        assert_eq!(secs(Some("analysis"), "ffmpeg"), Some(300));
+        assert_eq!(secs(Some("extraction"), "ffmpeg"), Some(1800));
+        assert_eq!(secs(Some("analysis"), "ffprobe"), Some(600));
+        assert_eq!(secs(None, "mkvextract"), Some(7200));
+        assert_eq!(secs(None, "mkvmerge"), None);
+        assert_eq!(resolve_timeout("", 0, None, "ffmpeg"), None);
+    }
+
+    #[test]
+    fn mkvtoolnix_warnings_are_retried_only_when_enabled() {
+        let warn = RunError::Warnings {
+            tool: "mkvmerge".to_string(),
+            exit_code: 1,
+        };
+        let fail = RunError::Failed {
+            tool: "mkvmerge".to_string(),
+            exit_code: 2,
+        };
+        assert!(!warn.is_transient());
+        assert!(!fail.is_transient());
+        assert!(RunError::Io {
+            tool: "ffmpeg".to_string(),
+            message: "broken pipe".to_string(),
+        }
+        .is_transient());
+        assert_eq!(fail.exit_code(), Some(2));
+        assert_eq!(tool_label("/usr/bin/mkvmerge"), "mkvmerge");
+
+        let log: LogCallback = Box::new(|_: &str| {});
+        let runner = CommandRunner::new(AppSettings::default(), log);
+        assert!(!runner.should_retry(&warn));
+        let settings = AppSettings {
+            tool_retry_warnings: true,
+            ..AppSettings::default()
+        };
+        let runner = CommandRunner::new(settings, Box::new(|_: &str| {}));
+        assert!(runner.should_retry(&warn));
+        assert!(!runner.should_retry(&fail));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hung_child_is_killed_on_timeout() {
+        let settings = AppSettings {
+            tool_timeout_overrides: "sleep=1".to_string(),
+            ..AppSettings::default()
+        };
+        let runner = CommandRunner::new(settings, Box::new(|_: &str| {}));
+
+        let started = Instant::now();
+        let result = runner.run(&["sleep", "30"], &HashMap::new());
+        assert!(matches!(result, Err(RunError::TimedOut { .. })));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
 }
@@ -0,0 +1,583 @@
+//! Tolerant layout compatibility — track-level remapping between jobs.
+//!
+//! `EnhancedSignatureGenerator::structures_are_compatible` only accepts an
+//! identical structure. Here the saved file's tracks are matched to the
+//! current file's tracks one by one instead. Tracks are paired within the
+//! same source and type, and must have the same language. Codec, name and
+//! position then rank the candidates. The saved layout is rewritten to the
+//! matched track IDs, and per-source settings that index audio tracks follow
+//! their track. Added, removed and ambiguous tracks are reported
+//! rather than refusing the copy.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+/// Per-source settings holding an index among the source's audio tracks.
+const AUDIO_INDEX_SETTINGS: [&str; 2] = ["correlation_ref_track", "correlation_source_track"];
+
+/// Score for an identical codec ID.
+const CODEC_SCORE: i32 = 3;
+/// Score for an identical, non-empty track name.
+const NAME_SCORE: i32 = 4;
+/// Score for the same position within source and type.
+const POSITION_SCORE: i32 = 2;
+
+/// One track as seen by the mapper.
+#[derive(Debug, Clone)]
+struct TrackRef {
+    source: String,
+    track_type: String,
+    id: i64,
+    /// Index among the source's tracks of the same type.
+    position: usize,
+    lang: String,
+    codec: String,
+    /// `None` when the saved layout didn't record the name.
+    name: Option<String>,
+}
+
+impl TrackRef {
+    fn describe(&self) -> String {
+        let mut out = format!(
+            "{} {} track {} [{}, {}",
+            self.source, self.track_type, self.id, self.lang, self.codec
+        );
+        if let Some(name) = self.name.as_deref().filter(|n| !n.is_empty()) {
+            out.push_str(&format!(", '{name}'"));
+        }
+        out.push(']');
+        out
+    }
+
+    /// Match score, or `None` if the tracks can't be the same track.
+    fn score(&self, other: &TrackRef) -> Option<i32> {
+        if self.lang != other.lang {
+            return None;
+        }
+        let mut score = 0;
+        if self.codec.eq_ignore_ascii_case(&other.codec) {
+            score += CODEC_SCORE;
+        }
+        if let (Some(a), Some(b)) = (&self.name, &other.name) {
+            if !a.is_empty() && a.trim().eq_ignore_ascii_case(b.trim()) {
+                score += NAME_SCORE;
+            }
+        }
+        match self.position.abs_diff(other.position) {
+            0 => score += POSITION_SCORE,
+            1 => score += 1,
+            _ => {}
+        }
+        Some(score)
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+fn lang_field(value: &Value) -> String {
+    match str_field(value, "lang").trim().to_lowercase() {
+        lang if lang.is_empty() => "und".to_string(),
+        lang => lang,
+    }
+}
+
+/// Tracks of the file the layout was saved for.
+///
+/// Taken from the saved structure signature, with names filled in from
+/// the layout entries that reference them. Sources missing from the
+/// signature fall back to the layout entries alone.
+fn saved_tracks(structure_signature: &Value, enhanced_layout: &[Value]) -> Vec<TrackRef> {
+    let layout_names: HashMap<(&str, i64), &str> = enhanced_layout
+        .iter()
+        .filter_map(|item| {
+            let id = item.get("id")?.as_i64()?;
+            Some(((str_field(item, "source"), id), str_field(item, "name")))
+        })
+        .collect();
+
+    let mut tracks = Vec::new();
+    let structure = structure_signature
+        .get("structure")
+        .and_then(|v| v.as_object());
+    if let Some(structure) = structure {
+        for (source, types) in structure {
+            for track_type in ["video", "audio", "subtitles"] {
+                let list = types.get(track_type).and_then(|v| v.as_array());
+                for (position, track) in list.into_iter().flatten().enumerate() {
+                    let Some(id) = track.get("id").and_then(|v| v.as_i64()) else {
+                        continue;
+                    };
+                    tracks.push(TrackRef {
+                        source: source.clone(),
+                        track_type: track_type.to_string(),
+                        id,
+                        position,
+                        lang: lang_field(track),
+                        codec: str_field(track, "codec_id").to_string(),
+                        name: layout_names
+                            .get(&(source.as_str(), id))
+                            .map(|n| n.to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    // Sources the signature doesn't describe: rebuild from layout entries
+    let described: HashSet<String> = tracks.iter().map(|t| t.source.clone()).collect();
+    let mut fallback: Vec<&Value> = enhanced_layout
+        .iter()
+        .filter(|item| {
+            !item
+                .get("is_generated")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+                && !described.contains(str_field(item, "source"))
+        })
+        .collect();
+    fallback.sort_by_key(|item| item.get("id").and_then(|v| v.as_i64()).unwrap_or(0));
+    let mut positions: HashMap<(String, String), usize> = HashMap::new();
+    for item in fallback {
+        let Some(id) = item.get("id").and_then(|v| v.as_i64()) else {
+            continue;
+        };
+        let key = (
+            str_field(item, "source").to_string(),
+            str_field(item, "type").to_string(),
+        );
+        let position = positions.entry(key.clone()).or_insert(0);
+        tracks.push(TrackRef {
+            source: key.0,
+            track_type: key.1,
+            id,
+            position: *position,
+            lang: lang_field(item),
+            codec: str_field(item, "codec_id").to_string(),
+            name: Some(str_field(item, "name").to_string()),
+        });
+        *position += 1;
+    }
+    tracks
+}
+
+/// Tracks of the current file, from `get_track_info_for_dialog` data.
+fn current_tracks(track_info: &HashMap<String, Vec<Value>>) -> Vec<TrackRef> {
+    let mut tracks = Vec::new();
+    for (source, list) in track_info {
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for track in list {
+            let track_type = str_field(track, "type");
+            let position = positions.entry(track_type).or_insert(0);
+            tracks.push(TrackRef {
+                source: source.clone(),
+                track_type: track_type.to_string(),
+                id: track.get("id").and_then(|v| v.as_i64()).unwrap_or(0),
+                position: *position,
+                lang: lang_field(track),
+                codec: str_field(track, "codec_id").to_string(),
+                name: Some(str_field(track, "name").to_string()),
+            });
+            *position += 1;
+        }
+    }
+    tracks
+}
+
+/// Track-level mapping from a saved layout's file to the current file.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutMapping {
+    /// `(source, saved track ID)` → current track ID.
+    pub id_map: HashMap<(String, i64), i64>,
+    /// Current tracks with no counterpart in the saved file.
+    pub added: Vec<String>,
+    /// Saved tracks with no counterpart in the current file.
+    pub removed: Vec<String>,
+    /// Saved tracks that matched more than one current track equally well.
+    pub ambiguous: Vec<String>,
+}
+
+impl LayoutMapping {
+    /// True when every track maps to itself and nothing was added or removed.
+    pub fn is_exact(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.ambiguous.is_empty()
+            && self
+                .id_map
+                .iter()
+                .all(|((_, saved), current)| saved == current)
+    }
+
+    /// Warnings describing every difference, for the log or the user.
+    pub fn warnings(&self) -> Vec<String> {
+        let added = self
+            .added
+            .iter()
+            .map(|t| format!("New track not in layout: {t}"));
+        let removed = self
+            .removed
+            .iter()
+            .map(|t| format!("Saved track missing from this file: {t}"));
+        let ambiguous = self
+            .ambiguous
+            .iter()
+            .map(|t| format!("Ambiguous match, verify the layout: {t}"));
+        added.chain(removed).chain(ambiguous).collect()
+    }
+}
+
+/// Map the tracks of a saved layout's file to the current file's tracks.
+///
+/// Pairs are taken greedily from the highest score down, so an exact
+/// duplicate of a saved track wins over a track that only shares its
+/// language.
+pub fn map_layout_tracks(
+    structure_signature: &Value,
+    enhanced_layout: &[Value],
+    target_track_info: &HashMap<String, Vec<Value>>,
+) -> LayoutMapping {
+    let saved = saved_tracks(structure_signature, enhanced_layout);
+    let current = current_tracks(target_track_info);
+
+    // (score, position distance, saved index, current index)
+    let mut pairs: Vec<(i32, usize, usize, usize)> = Vec::new();
+    for (si, s) in saved.iter().enumerate() {
+        for (ci, c) in current.iter().enumerate() {
+            if s.source != c.source || s.track_type != c.track_type {
+                continue;
+            }
+            if let Some(score) = s.score(c) {
+                pairs.push((score, s.position.abs_diff(c.position), si, ci));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut mapping = LayoutMapping::default();
+    let mut saved_used: HashSet<usize> = HashSet::new();
+    let mut current_used: HashSet<usize> = HashSet::new();
+    for &(score, distance, si, ci) in &pairs {
+        if saved_used.contains(&si) || current_used.contains(&ci) {
+            continue;
+        }
+        saved_used.insert(si);
+        current_used.insert(ci);
+        mapping
+            .id_map
+            .insert((saved[si].source.clone(), saved[si].id), current[ci].id);
+
+        let rivals: Vec<String> = pairs
+            .iter()
+            .filter(|&&(s, d, other_si, other_ci)| {
+                other_si == si && other_ci != ci && s == score && d == distance
+            })
+            .map(|&(_, _, _, other_ci)| current[other_ci].describe())
+            .collect();
+        if !rivals.is_empty() {
+            mapping.ambiguous.push(format!(
+                "{} -> {} (also matches {})",
+                saved[si].describe(),
+                current[ci].describe(),
+                rivals.join(", ")
+            ));
+        }
+    }
+
+    mapping.removed = (0..saved.len())
+        .filter(|si| !saved_used.contains(si))
+        .map(|si| saved[si].describe())
+        .collect();
+    mapping.added = (0..current.len())
+        .filter(|ci| !current_used.contains(ci))
+        .map(|ci| current[ci].describe())
+        .collect();
+    mapping.removed.sort();
+    mapping.added.sort();
+    mapping.ambiguous.sort();
+    mapping
+}
+
+/// Rewrite layout entries to the current file's tracks.
+///
+/// Entries keep their user configuration; track identity and stream
+/// properties come from the matched current track. Entries whose track was
+/// removed, and generated tracks whose source track was removed, are
+/// dropped and reported. Returns the remapped entries in user order and
+/// the warnings for dropped entries.
+pub fn remap_layout(
+    mapping: &LayoutMapping,
+    enhanced_layout: &[Value],
+    target_track_info: &HashMap<String, Vec<Value>>,
+) -> (Vec<Value>, Vec<String>) {
+    let mut items: Vec<&Value> = enhanced_layout.iter().collect();
+    items.sort_by_key(|item| {
+        item.get("user_order_index")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0)
+    });
+
+    let mut layout = Vec::with_capacity(items.len());
+    let mut warnings = Vec::new();
+    for item in items {
+        let source = str_field(item, "source");
+        let describe = || {
+            format!(
+                "{source} {} track {}",
+                str_field(item, "type"),
+                item.get("id").cloned().unwrap_or(Value::Null)
+            )
+        };
+        let Some(current_tracks) = target_track_info.get(source) else {
+            // External files aren't part of the track info
+            layout.push(item.clone());
+            continue;
+        };
+
+        let is_generated = item
+            .get("is_generated")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let mut remapped = item.clone();
+        if is_generated {
+            let source_track = item.get("source_track_id").and_then(|v| v.as_i64());
+            match source_track.and_then(|id| mapping.id_map.get(&(source.to_string(), id))) {
+                Some(&new_id) => remapped["source_track_id"] = Value::from(new_id),
+                None => {
+                    warnings.push(format!(
+                        "Dropped generated {}: its source track is missing",
+                        describe()
+                    ));
+                    continue;
+                }
+            }
+        } else {
+            let saved_id = item.get("id").and_then(|v| v.as_i64());
+            let current = saved_id
+                .and_then(|id| mapping.id_map.get(&(source.to_string(), id)))
+                .and_then(|new_id| {
+                    current_tracks
+                        .iter()
+                        .find(|t| t.get("id").and_then(|v| v.as_i64()) == Some(*new_id))
+                });
+            let Some(current) = current else {
+                warnings.push(format!(
+                    "Dropped layout entry {}: no matching track in this file",
+                    describe()
+                ));
+                continue;
+            };
+            if let (Some(obj), Some(fresh)) = (remapped.as_object_mut(), current.as_object()) {
+                for (k, v) in fresh {
+                    obj.insert(k.clone(), v.clone());
+                }
+            }
+        }
+        layout.push(remapped);
+    }
+    (layout, warnings)
+}
+
+/// Rewrite per-source settings that point at audio tracks by position.
+///
+/// `correlation_ref_track` and `correlation_source_track` index the
+/// source's audio tracks, so they follow their track through `mapping`.
+/// A setting whose track has no counterpart is removed and reported.
+pub fn remap_source_settings(
+    mapping: &LayoutMapping,
+    structure_signature: &Value,
+    enhanced_layout: &[Value],
+    source_settings: &Value,
+    target_track_info: &HashMap<String, Vec<Value>>,
+) -> (Value, Vec<String>) {
+    let saved = saved_tracks(structure_signature, enhanced_layout);
+    let current = current_tracks(target_track_info);
+    let audio_at = |tracks: &[TrackRef], source: &str, position: usize| {
+        tracks
+            .iter()
+            .find(|t| t.source == source && t.track_type == "audio" && t.position == position)
+            .map(|t| t.id)
+    };
+
+    let mut settings = source_settings.clone();
+    let mut warnings = Vec::new();
+    let Some(sources) = settings.as_object_mut() else {
+        return (settings, warnings);
+    };
+    for (source, values) in sources.iter_mut() {
+        let Some(values) = values.as_object_mut() else {
+            continue;
+        };
+        for key in AUDIO_INDEX_SETTINGS {
+            let Some(position) = values.get(key).and_then(|v| v.as_u64()) else {
+                continue;
+            };
+            let remapped = audio_at(&saved, source, position as usize)
+                .and_then(|id| mapping.id_map.get(&(source.clone(), id)))
+                .and_then(|&id| {
+                    current
+                        .iter()
+                        .find(|t| t.source == *source && t.track_type == "audio" && t.id == id)
+                })
+                .map(|t| t.position);
+            match remapped {
+                Some(new_position) => {
+                    values.insert(key.to_string(), Value::from(new_position));
+                }
+                None => {
+                    values.remove(key);
+                    warnings.push(format!(
+                        "Dropped {source} {key}: audio track {position} has no matching track in this file"
+                    ));
+                }
+            }
+        }
+    }
+    (settings, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::job_layouts::signature::EnhancedSignatureGenerator;
+
+    fn track(id: i64, ttype: &str, codec: &str, lang: &str, name: &str) -> Value {
+        json!({"source": "Source 1", "id": id, "type": ttype, "codec_id": codec,
+               "lang": lang, "name": name})
+    }
+
+    fn info(tracks: Vec<Value>) -> HashMap<String, Vec<Value>> {
+        HashMap::from([("Source 1".to_string(), tracks)])
+    }
+
+    fn episode() -> Vec<Value> {
+        vec![
+            track(0, "video", "V_MPEGH/ISO/HEVC", "jpn", ""),
+            track(1, "audio", "A_FLAC", "jpn", ""),
+            track(2, "subtitles", "S_TEXT/ASS", "eng", "Full"),
+            track(3, "subtitles", "S_TEXT/ASS", "eng", "Signs"),
+        ]
+    }
+
+    /// Saved layout using every track, with user flags on the signs track.
+    fn saved(tracks: &[Value]) -> (Value, Vec<Value>) {
+        let signature =
+            EnhancedSignatureGenerator::generate_structure_signature(&info(tracks.to_vec()));
+        let layout = tracks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let mut item = t.clone();
+                item["user_order_index"] = json!(i);
+                if item["name"] == "Signs" {
+                    item["is_forced_display"] = json!(true);
+                }
+                item
+            })
+            .collect();
+        (signature, layout)
+    }
+
+    fn ids(layout: &[Value]) -> Vec<i64> {
+        layout.iter().map(|t| t["id"].as_i64().unwrap()).collect()
+    }
+
+    #[test]
+    fn identical_structure_maps_exactly() {
+        let (signature, layout) = saved(&episode());
+        let mapping = map_layout_tracks(&signature, &layout, &info(episode()));
+        assert!(mapping.is_exact());
+        assert!(mapping.warnings().is_empty());
+    }
+
+    #[test]
+    fn extra_commentary_track_is_reported_and_ids_shift() {
+        let (signature, layout) = saved(&episode());
+        let mut target = episode();
+        target.insert(2, track(2, "audio", "A_AAC", "jpn", "Commentary"));
+        for (i, t) in target.iter_mut().enumerate() {
+            t["id"] = json!(i);
+        }
+
+        let mapping = map_layout_tracks(&signature, &layout, &info(target.clone()));
+        assert!(!mapping.is_exact());
+        assert_eq!(mapping.added.len(), 1);
+        assert!(mapping.added[0].contains("Commentary"));
+        assert!(mapping.removed.is_empty());
+
+        let (remapped, warnings) = remap_layout(&mapping, &layout, &info(target));
+        assert!(warnings.is_empty());
+        assert_eq!(ids(&remapped), vec![0, 1, 3, 4]);
+        assert_eq!(remapped[3]["name"], "Signs");
+        assert_eq!(remapped[3]["is_forced_display"], true);
+    }
+
+    #[test]
+    fn missing_sign_track_drops_its_entry() {
+        let (signature, layout) = saved(&episode());
+        let target = episode()[..3].to_vec();
+
+        let mapping = map_layout_tracks(&signature, &layout, &info(target.clone()));
+        assert_eq!(mapping.removed.len(), 1);
+        assert!(mapping.removed[0].contains("Signs"));
+
+        let (remapped, warnings) = remap_layout(&mapping, &layout, &info(target));
+        assert_eq!(ids(&remapped), vec![0, 1, 2]);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn indistinguishable_tracks_are_ambiguous() {
+        let (signature, layout) = saved(&[
+            track(0, "subtitles", "S_HDMV/PGS", "fre", ""),
+            track(1, "subtitles", "S_HDMV/PGS", "eng", ""),
+        ]);
+        let target = vec![
+            track(0, "subtitles", "S_HDMV/PGS", "eng", ""),
+            track(1, "subtitles", "S_HDMV/PGS", "jpn", ""),
+            track(2, "subtitles", "S_HDMV/PGS", "eng", ""),
+        ];
+
+        let mapping = map_layout_tracks(&signature, &layout, &info(target));
+        assert_eq!(mapping.ambiguous.len(), 1);
+        assert_eq!(mapping.removed.len(), 1);
+        assert_eq!(mapping.added.len(), 2);
+    }
+
+    #[test]
+    fn correlation_tracks_follow_their_audio_track() {
+        let (signature, layout) = saved(&episode());
+        let settings = json!({"Source 1": {"correlation_ref_track": 0, "note": "keep"}});
+
+        // A commentary track inserted before the correlated audio
+        let mut target = episode();
+        target.insert(1, track(1, "audio", "A_AAC", "jpn", "Commentary"));
+        for (i, t) in target.iter_mut().enumerate() {
+            t["id"] = json!(i);
+        }
+        let mapping = map_layout_tracks(&signature, &layout, &info(target.clone()));
+        let (remapped, warnings) =
+            remap_source_settings(&mapping, &signature, &layout, &settings, &info(target));
+        assert!(warnings.is_empty());
+        assert_eq!(
+            remapped,
+            json!({"Source 1": {"correlation_ref_track": 1, "note": "keep"}})
+        );
+
+        // No audio track left to correlate against
+        let target: Vec<Value> = episode()
+            .into_iter()
+            .filter(|t| t["type"] != "audio")
+            .collect();
+        let mapping = map_layout_tracks(&signature, &layout, &info(target.clone()));
+        let (remapped, warnings) =
+            remap_source_settings(&mapping, &signature, &layout, &settings, &info(target));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("correlation_ref_track"));
+        assert_eq!(remapped, json!({"Source 1": {"note": "keep"}}));
+    }
+}
@@ -10,8 +10,10 @@ use std::sync::Arc;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 
+use super::compatibility::{map_layout_tracks, remap_layout, remap_source_settings};
 use super::persistence::LayoutPersistence;
 use super::signature::EnhancedSignatureGenerator;
+use super::templates::{resolve_template, template_from_layout};
 use super::validation::LayoutValidator;
 
 /// Log callback type.
@@ -88,6 +90,7 @@ impl JobLayoutManager {
         job_id: &str,
         layout: &[Value],
         attachment_sources: &[String],
+        extra_attachments: &[String],
         sources: &HashMap<String, String>,
         track_info: &HashMap<String, Vec<Value>>,
         source_settings: Option<&HashMap<String, Value>>,
@@ -103,6 +106,7 @@ impl JobLayoutManager {
             "sources": sources,
             "enhanced_layout": enhanced_layout,
             "attachment_sources": attachment_sources,
+            "extra_attachments": extra_attachments,
             "track_signature": track_sig,
             "structure_signature": struct_sig,
             "source_settings": source_settings.unwrap_or(&HashMap::new()),
@@ -133,36 +137,138 @@ impl JobLayoutManager {
         }
     }
 
-    /// Copies a layout if the source and target files are structurally compatible.
+    /// Copies a layout to another job.
+    ///
+    /// Structurally identical files get the layout unchanged. Otherwise,
+    /// if `tolerant`, the layout is remapped track by track onto the target
+    /// file and every added, removed, ambiguous or dropped track is
+    /// returned as a warning; if not, the copy is refused.
     pub fn copy_layout_between_jobs(
         &self,
         source_job_id: &str,
         target_job_id: &str,
         target_sources: &HashMap<String, String>,
         target_track_info: &HashMap<String, Vec<Value>>,
-    ) -> bool {
-        let source_data = match self.load_job_layout(source_job_id) {
-            Some(d) => d,
-            None => {
-                (self.log)(&format!(
-                    "[LayoutManager] Cannot copy: Source layout {source_job_id} not found."
-                ));
-                return false;
-            }
-        };
+        tolerant: bool,
+    ) -> Result<Vec<String>, String> {
+        let source_data = self.load_job_layout(source_job_id).ok_or_else(|| {
+            let msg = format!("Cannot copy: Source layout {source_job_id} not found.");
+            (self.log)(&format!("[LayoutManager] {msg}"));
+            msg
+        })?;
+
+        self.copy_layout_data(
+            &source_data,
+            source_job_id,
+            target_job_id,
+            target_sources,
+            target_track_info,
+            tolerant,
+        )
+    }
+
+    /// Builds a portable template from a job's saved layout.
+    pub fn create_template(
+        &self,
+        job_id: &str,
+        name: &str,
+        fonts_dir: &Path,
+    ) -> Result<Value, String> {
+        let layout_data = self
+            .load_job_layout(job_id)
+            .ok_or_else(|| format!("No saved layout for job {job_id}."))?;
+        Ok(template_from_layout(name, &layout_data, fonts_dir))
+    }
+
+    /// Applies a layout template to a job, remapping tracks where the
+    /// target file differs from the one the template was made from.
+    /// Replacement fonts are looked up in `fonts_dir`.
+    pub fn apply_template(
+        &self,
+        template: &Value,
+        target_job_id: &str,
+        target_sources: &HashMap<String, String>,
+        target_track_info: &HashMap<String, Vec<Value>>,
+        fonts_dir: &Path,
+    ) -> Result<Vec<String>, String> {
+        let (is_valid, reason) = LayoutValidator::validate_template(template);
+        if !is_valid {
+            return Err(format!("Invalid layout template: {reason}"));
+        }
+        let name = template["name"].as_str().unwrap_or_default();
+        let (resolved, mut warnings) = resolve_template(template, fonts_dir);
+        for warning in &warnings {
+            (self.log)(&format!("[LayoutManager] {target_job_id}: {warning}"));
+        }
+        warnings.extend(self.copy_layout_data(
+            &resolved,
+            &format!("template:{name}"),
+            target_job_id,
+            target_sources,
+            target_track_info,
+            true,
+        )?);
+        Ok(warnings)
+    }
 
+    /// Saves `source_data`'s layout for the target job — shared by job
+    /// copies and templates.
+    fn copy_layout_data(
+        &self,
+        source_data: &Value,
+        source_job_id: &str,
+        target_job_id: &str,
+        target_sources: &HashMap<String, String>,
+        target_track_info: &HashMap<String, Vec<Value>>,
+        tolerant: bool,
+    ) -> Result<Vec<String>, String> {
         let source_struct_sig = &source_data["structure_signature"];
         let target_struct_sig =
             EnhancedSignatureGenerator::generate_structure_signature(target_track_info);
 
+        let mut enhanced_layout = source_data["enhanced_layout"].clone();
+        let mut source_settings = source_data
+            .get("source_settings")
+            .cloned()
+            .unwrap_or(json!({}));
+        let mut warnings = Vec::new();
         if !EnhancedSignatureGenerator::structures_are_compatible(
             source_struct_sig,
             &target_struct_sig,
         ) {
-            (self.log)(&format!(
-                "[LayoutManager] Cannot copy: Incompatible track structures between {source_job_id} and {target_job_id}."
-            ));
-            return false;
+            if !tolerant {
+                let msg = format!(
+                    "Cannot copy: Incompatible track structures between {source_job_id} and {target_job_id}."
+                );
+                (self.log)(&format!("[LayoutManager] {msg}"));
+                return Err(msg);
+            }
+
+            let saved_layout = enhanced_layout.as_array().cloned().unwrap_or_default();
+            let mapping = map_layout_tracks(source_struct_sig, &saved_layout, target_track_info);
+            let (remapped, dropped) = remap_layout(&mapping, &saved_layout, target_track_info);
+            if remapped.is_empty() && !saved_layout.is_empty() {
+                let msg = format!(
+                    "Cannot copy: No track of {source_job_id}'s layout exists in {target_job_id}."
+                );
+                (self.log)(&format!("[LayoutManager] {msg}"));
+                return Err(msg);
+            }
+            let (settings, dropped_settings) = remap_source_settings(
+                &mapping,
+                source_struct_sig,
+                &saved_layout,
+                &source_settings,
+                target_track_info,
+            );
+            warnings = mapping.warnings();
+            warnings.extend(dropped);
+            warnings.extend(dropped_settings);
+            enhanced_layout = json!(Self::create_enhanced_layout(&remapped));
+            source_settings = settings;
+            for warning in &warnings {
+                (self.log)(&format!("[LayoutManager] {target_job_id}: {warning}"));
+            }
         }
 
         let target_track_sig =
@@ -171,16 +277,23 @@ impl JobLayoutManager {
         let mut target_layout_data = json!({
             "job_id": target_job_id,
             "sources": target_sources,
-            "enhanced_layout": source_data["enhanced_layout"],
+            "enhanced_layout": enhanced_layout,
             "attachment_sources": source_data.get("attachment_sources").cloned().unwrap_or(json!([])),
-            "source_settings": source_data.get("source_settings").cloned().unwrap_or(json!({})),
+            "extra_attachments": source_data.get("extra_attachments").cloned().unwrap_or(json!([])),
+            "source_settings": source_settings,
             "track_signature": target_track_sig,
             "structure_signature": target_struct_sig,
             "copied_from": source_job_id,
         });
 
-        self.persistence
+        if self
+            .persistence
             .save_layout(target_job_id, &mut target_layout_data)
+        {
+            Ok(warnings)
+        } else {
+            Err(format!("Could not save the layout for {target_job_id}."))
+        }
     }
 
     /// Adds positional metadata to a layout for robust ordering.
@@ -8,11 +8,17 @@
 //! - `signature::EnhancedSignatureGenerator` — creates track and structure signatures
 //! - `persistence::LayoutPersistence` — handles JSON storage/loading
 //! - `validation::LayoutValidator` — ensures loaded layouts are well-formed
+//! - `compatibility::map_layout_tracks` — tolerant track-level remapping
 //! - `manager::JobLayoutManager` — main API coordinating all operations
+//! - `rules::evaluate_layout_rules` — builds a layout from declarative rules
+//! - `templates::LayoutTemplateStore` — named, portable layout templates
 
+pub mod compatibility;
 pub mod manager;
 pub mod persistence;
+pub mod rules;
 pub mod signature;
+pub mod templates;
 pub mod validation;
 
 pub use manager::JobLayoutManager;
@@ -0,0 +1,513 @@
+//! Layout rules — declarative automatic track layout.
+//!
+//! A rule set describes a layout in terms of track properties instead of
+//! concrete track IDs, e.g. "video from Source 1; jpn audio from Source 1
+//! then eng from Source 2; eng subs from Source 2, signs/songs forced; drop
+//! commentary". Evaluating it against `get_track_info_for_dialog` data gives
+//! a `ManualLayoutItem` list for any job, plus an explanation for every
+//! rule that placed nothing.
+//!
+//! Include rules run in order and each places the matching tracks that are
+//! still free, in source order. Drop rules apply wherever they appear in the
+//! list, so "drop commentary" at the end still keeps commentary out of every
+//! include rule before it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::analysis::correlation::normalize_lang;
+use crate::models::context_types::ManualLayoutItem;
+
+/// File name of the saved rule set inside the config directory.
+const RULES_FILE: &str = "layout_rules.json";
+
+/// What a rule does with the tracks it matches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    /// Append matching tracks to the layout.
+    #[default]
+    Include,
+    /// Keep matching tracks out of every include rule.
+    Drop,
+}
+
+/// One layout rule. Unset criteria match any track.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayoutRule {
+    /// Optional name shown in explanations.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    #[serde(default)]
+    pub action: RuleAction,
+
+    // Criteria
+    /// `video`, `audio` or `subtitles`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub track_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Any of these languages (2- or 3-letter codes).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lang: Vec<String>,
+    /// Case-insensitive regex the track name must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_matches: Option<String>,
+    /// Case-insensitive regex the track name must not match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_excludes: Option<String>,
+    /// Case-insensitive substring of the codec ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+
+    // Placement
+    /// Place at most this many tracks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(rename = "default", default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    #[serde(rename = "forced", default, skip_serializing_if = "Option::is_none")]
+    pub is_forced_display: Option<bool>,
+}
+
+impl LayoutRule {
+    /// Human-readable criteria, e.g. `type=audio, source=Source 2, lang=eng`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(t) = &self.track_type {
+            parts.push(format!("type={t}"));
+        }
+        if let Some(s) = &self.source {
+            parts.push(format!("source={s}"));
+        }
+        if !self.lang.is_empty() {
+            parts.push(format!("lang={}", self.lang.join("|")));
+        }
+        if let Some(n) = &self.name_matches {
+            parts.push(format!("name~/{n}/"));
+        }
+        if let Some(n) = &self.name_excludes {
+            parts.push(format!("name!~/{n}/"));
+        }
+        if let Some(c) = &self.codec {
+            parts.push(format!("codec~{c}"));
+        }
+        if parts.is_empty() {
+            "any track".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    fn title(&self, index: usize) -> String {
+        if self.label.is_empty() {
+            format!("Rule {}", index + 1)
+        } else {
+            format!("Rule {} ({})", index + 1, self.label)
+        }
+    }
+}
+
+/// An ordered list of layout rules, saved as `layout_rules.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutRuleSet {
+    pub version: u32,
+    #[serde(default)]
+    pub rules: Vec<LayoutRule>,
+}
+
+impl Default for LayoutRuleSet {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl LayoutRuleSet {
+    const VERSION: u32 = 1;
+
+    /// Path of the rule set inside `config_dir`.
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(RULES_FILE)
+    }
+
+    /// Load the saved rule set; an empty set if none was saved yet.
+    pub fn load(config_dir: &Path) -> Result<Self, String> {
+        let path = Self::path(config_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid layout rules in {}: {e}", path.display()))
+    }
+
+    /// Save the rule set after checking that it compiles.
+    pub fn save(&self, config_dir: &Path) -> Result<(), String> {
+        compile_rules(&self.rules)?;
+        fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        let path = Self::path(config_dir);
+        fs::write(&path, json).map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+}
+
+/// Result of evaluating a rule set against one job's tracks.
+#[derive(Debug, Clone, Default)]
+pub struct RuleEvaluation {
+    pub layout: Vec<ManualLayoutItem>,
+    /// One line per rule that placed or dropped nothing, and why.
+    pub explanations: Vec<String>,
+}
+
+/// A rule with its regexes compiled.
+struct CompiledRule<'a> {
+    rule: &'a LayoutRule,
+    name_matches: Option<Regex>,
+    name_excludes: Option<Regex>,
+    langs: Vec<String>,
+}
+
+impl CompiledRule<'_> {
+    fn matches(&self, source: &str, track: &Value) -> bool {
+        let field = |key: &str| track.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        let rule = self.rule;
+
+        if rule
+            .track_type
+            .as_deref()
+            .is_some_and(|t| t != field("type"))
+        {
+            return false;
+        }
+        if rule.source.as_deref().is_some_and(|s| s != source) {
+            return false;
+        }
+        if !self.langs.is_empty() {
+            let lang = normalize_lang(Some(field("lang"))).unwrap_or_else(|| "und".to_string());
+            if !self.langs.contains(&lang) {
+                return false;
+            }
+        }
+        if self
+            .name_matches
+            .as_ref()
+            .is_some_and(|re| !re.is_match(field("name")))
+        {
+            return false;
+        }
+        if self
+            .name_excludes
+            .as_ref()
+            .is_some_and(|re| re.is_match(field("name")))
+        {
+            return false;
+        }
+        if let Some(codec) = &rule.codec {
+            let codec_id = field("codec_id").to_lowercase();
+            if !codec_id.contains(&codec.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn compile_rules(rules: &[LayoutRule]) -> Result<Vec<CompiledRule<'_>>, String> {
+    let regex = |pattern: &Option<String>, index: usize| -> Result<Option<Regex>, String> {
+        pattern
+            .as_deref()
+            .map(|p| {
+                Regex::new(&format!("(?i){p}"))
+                    .map_err(|e| format!("Rule {}: invalid name pattern '{p}': {e}", index + 1))
+            })
+            .transpose()
+    };
+    rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            Ok(CompiledRule {
+                rule,
+                name_matches: regex(&rule.name_matches, i)?,
+                name_excludes: regex(&rule.name_excludes, i)?,
+                langs: rule
+                    .lang
+                    .iter()
+                    .map(|l| normalize_lang(Some(l)).unwrap_or_else(|| "und".to_string()))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Sort key putting `Source 2` before `Source 10`.
+fn source_order(key: &str) -> (u32, String) {
+    let number = key
+        .rsplit(' ')
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(u32::MAX);
+    (number, key.to_string())
+}
+
+/// Evaluate `rules` against dialog track info (source key → track records).
+///
+/// Fails only on an invalid name pattern; rules that match nothing are
+/// reported in `explanations` rather than treated as errors.
+pub fn evaluate_layout_rules(
+    rules: &[LayoutRule],
+    track_info: &HashMap<String, Vec<Value>>,
+) -> Result<RuleEvaluation, String> {
+    let compiled = compile_rules(rules)?;
+
+    let mut sources: Vec<&String> = track_info.keys().collect();
+    sources.sort_by_key(|k| source_order(k));
+    let tracks: Vec<(usize, &str, &Value)> = sources
+        .iter()
+        .flat_map(|source| {
+            track_info[*source]
+                .iter()
+                .map(move |t| (source.as_str(), t))
+        })
+        .enumerate()
+        .map(|(i, (source, t))| (i, source, t))
+        .collect();
+
+    let mut evaluation = RuleEvaluation::default();
+
+    // Drop rules apply regardless of their position
+    let mut dropped: HashSet<usize> = HashSet::new();
+    for (i, rule) in compiled.iter().enumerate() {
+        if rule.rule.action != RuleAction::Drop {
+            continue;
+        }
+        let matching: Vec<usize> = tracks
+            .iter()
+            .filter(|(_, source, t)| rule.matches(source, t))
+            .map(|(idx, _, _)| *idx)
+            .collect();
+        let reason = if matching.is_empty() {
+            Some(format!("no track matched {}", rule.rule.describe()))
+        } else if matching.iter().all(|idx| dropped.contains(idx)) {
+            Some("every matching track was already dropped by an earlier rule".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            evaluation.explanations.push(format!(
+                "{}: dropped nothing, {reason}.",
+                rule.rule.title(i)
+            ));
+        }
+        dropped.extend(matching);
+    }
+
+    let mut used: HashSet<usize> = HashSet::new();
+    for (i, rule) in compiled.iter().enumerate() {
+        if rule.rule.action != RuleAction::Include {
+            continue;
+        }
+        let matching: Vec<(usize, &str, &Value)> = tracks
+            .iter()
+            .copied()
+            .filter(|(_, source, t)| rule.matches(source, t))
+            .collect();
+        let not_dropped: Vec<_> = matching
+            .iter()
+            .copied()
+            .filter(|(idx, _, _)| !dropped.contains(idx))
+            .collect();
+        let allowed: Vec<_> = not_dropped
+            .iter()
+            .copied()
+            .filter(|(_, source, t)| {
+                // Video can only come from the reference source
+                t.get("type").and_then(|v| v.as_str()) != Some("video") || *source == "Source 1"
+            })
+            .collect();
+        let free: Vec<_> = allowed
+            .iter()
+            .copied()
+            .filter(|(idx, _, _)| !used.contains(idx))
+            .take(rule.rule.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        let reason = if matching.is_empty() {
+            Some(format!("no track matched {}", rule.rule.describe()))
+        } else if not_dropped.is_empty() {
+            Some("every matching track is dropped by a drop rule".to_string())
+        } else if allowed.is_empty() {
+            Some("video tracks can only come from Source 1".to_string())
+        } else if free.is_empty() {
+            Some("every matching track was already placed by an earlier rule".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            evaluation
+                .explanations
+                .push(format!("{}: placed nothing, {reason}.", rule.rule.title(i)));
+            continue;
+        }
+
+        for (idx, source, track) in free {
+            used.insert(idx);
+            let field = |key: &str| track.get(key).and_then(|v| v.as_str()).map(String::from);
+            evaluation.layout.push(ManualLayoutItem {
+                source: Some(source.to_string()),
+                id: track.get("id").and_then(|v| v.as_i64()).map(|id| id as i32),
+                track_type: field("type"),
+                codec_id: field("codec_id"),
+                lang: field("lang"),
+                name: field("name"),
+                is_default: Some(rule.rule.is_default.unwrap_or(false)),
+                is_forced_display: Some(rule.rule.is_forced_display.unwrap_or(false)),
+                ..Default::default()
+            });
+        }
+    }
+
+    Ok(evaluation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn track(id: i64, ttype: &str, lang: &str, name: &str) -> Value {
+        json!({"id": id, "type": ttype, "codec_id": "X", "lang": lang, "name": name})
+    }
+
+    fn track_info() -> HashMap<String, Vec<Value>> {
+        HashMap::from([
+            (
+                "Source 1".to_string(),
+                vec![
+                    track(0, "video", "jpn", ""),
+                    track(1, "audio", "jpn", ""),
+                    track(2, "audio", "jpn", "Commentary"),
+                ],
+            ),
+            (
+                "Source 2".to_string(),
+                vec![
+                    track(0, "video", "eng", ""),
+                    track(1, "audio", "eng", ""),
+                    track(2, "subtitles", "eng", "Signs & Songs"),
+                    track(3, "subtitles", "en", "Full Subtitles"),
+                ],
+            ),
+        ])
+    }
+
+    fn rules_from(value: Value) -> Vec<LayoutRule> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn placed(evaluation: &RuleEvaluation) -> Vec<(String, i32)> {
+        evaluation
+            .layout
+            .iter()
+            .map(|i| (i.source.clone().unwrap(), i.id.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn example_rule_set_builds_expected_layout() {
+        let rules = rules_from(json!([
+            {"type": "video", "source": "Source 1", "limit": 1},
+            {"type": "audio", "source": "Source 1", "lang": ["jpn"], "default": true},
+            {"type": "audio", "source": "Source 2", "lang": ["eng"]},
+            {"type": "subtitles", "source": "Source 2", "lang": ["eng"],
+             "name_excludes": "sign|song", "default": true},
+            {"type": "subtitles", "source": "Source 2", "lang": ["eng"],
+             "name_matches": "sign|song", "forced": true},
+            {"action": "drop", "name_matches": "commentary"}
+        ]));
+
+        let evaluation = evaluate_layout_rules(&rules, &track_info()).unwrap();
+
+        assert_eq!(
+            placed(&evaluation),
+            vec![
+                ("Source 1".to_string(), 0),
+                ("Source 1".to_string(), 1),
+                ("Source 2".to_string(), 1),
+                ("Source 2".to_string(), 3),
+                ("Source 2".to_string(), 2),
+            ]
+        );
+        assert_eq!(evaluation.layout[4].is_forced_display, Some(true));
+        assert!(evaluation.explanations.is_empty());
+    }
+
+    #[test]
+    fn unmatched_rules_are_explained() {
+        let rules = rules_from(json!([
+            {"type": "audio", "lang": ["fre"]},
+            {"label": "Dub video", "type": "video", "source": "Source 2"},
+            {"type": "audio", "source": "Source 1"},
+            {"type": "audio", "source": "Source 1", "lang": ["jpn"]},
+            {"action": "drop", "codec": "A_DTS"}
+        ]));
+
+        let evaluation = evaluate_layout_rules(&rules, &track_info()).unwrap();
+
+        assert_eq!(placed(&evaluation).len(), 2);
+        assert_eq!(evaluation.explanations.len(), 4);
+        assert!(evaluation.explanations[0].starts_with("Rule 5: dropped nothing"));
+        assert!(evaluation.explanations[1].contains("no track matched type=audio, lang=fre"));
+        assert!(evaluation.explanations[2].starts_with("Rule 2 (Dub video)"));
+        assert!(evaluation.explanations[2].contains("only come from Source 1"));
+        assert!(evaluation.explanations[3].contains("already placed by an earlier rule"));
+    }
+
+    #[test]
+    fn drop_rule_covered_by_an_earlier_drop_is_explained() {
+        let rules = rules_from(json!([
+            {"action": "drop", "name_matches": "commentary"},
+            {"action": "drop", "source": "Source 1", "name_matches": "comment"}
+        ]));
+
+        let evaluation = evaluate_layout_rules(&rules, &track_info()).unwrap();
+
+        assert_eq!(
+            evaluation.explanations,
+            vec!["Rule 2: dropped nothing, every matching track was already dropped by an earlier rule."]
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error_and_rule_set_round_trips() {
+        let bad = rules_from(json!([{"name_matches": "("}]));
+        assert!(evaluate_layout_rules(&bad, &track_info()).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            LayoutRuleSet::load(dir.path()).unwrap(),
+            LayoutRuleSet::default()
+        );
+        let set = LayoutRuleSet {
+            rules: rules_from(json!([{"type": "audio", "lang": ["jpn"], "limit": 1}])),
+            ..Default::default()
+        };
+        set.save(dir.path()).unwrap();
+        assert_eq!(LayoutRuleSet::load(dir.path()).unwrap(), set);
+        assert!(LayoutRuleSet {
+            rules: bad,
+            ..Default::default()
+        }
+        .save(dir.path())
+        .is_err());
+    }
+}
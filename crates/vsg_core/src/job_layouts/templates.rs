@@ -0,0 +1,482 @@
+//! Layout templates — named, portable job layouts.
+//!
+//! Job layouts under `temp_root` are keyed by a hash of the source file
+//! names and only live for one queue. A template keeps the same layout,
+//! with its per-track options (style patches, font replacements, sync
+//! exclusions), the attachment sources, the source settings and the
+//! structure signature. It is stored by name under the config directory
+//! and exports to a single JSON file. Applying one goes through the
+//! tolerant track remapping, so a template made from one season fits the
+//! next one.
+//!
+//! Absolute paths only exist on the machine that made the template, so
+//! none are kept: source paths and extra attachments are left out, a
+//! replacement font is stored relative to the fonts folder and resolved
+//! through it when the template is applied, and external subtitle or
+//! chapter files are dropped and listed under `external_files` so applying
+//! the template can say which ones to add again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Local;
+use serde_json::{json, Value};
+
+use super::validation::LayoutValidator;
+
+/// `format` marker identifying template files.
+pub const TEMPLATE_FORMAT: &str = "vsg-layout-template";
+/// Current template format version.
+pub const TEMPLATE_VERSION: i64 = 1;
+
+/// Log callback type.
+type LogCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Per-source settings that point at a file of the original job.
+const SOURCE_FILE_SETTINGS: [&str; 2] = ["chapter_file", "generated_chapter_file"];
+
+/// Builds a template from saved job layout data; `fonts_dir` is the fonts
+/// folder replacement fonts are stored relative to.
+pub fn template_from_layout(name: &str, layout_data: &Value, fonts_dir: &Path) -> Value {
+    let mut external_files = Vec::new();
+    let mut enhanced_layout = Vec::new();
+    for item in layout_data["enhanced_layout"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        if item.get("source").and_then(|v| v.as_str()) == Some("External") {
+            external_files.extend(
+                item.get("original_path")
+                    .and_then(|v| v.as_str())
+                    .map(file_name),
+            );
+            continue;
+        }
+        let mut item = item.clone();
+        if let Some(obj) = item.as_object_mut() {
+            obj.remove("original_path");
+        }
+        for repl in font_replacements(&mut item) {
+            let Some(path) = repl.remove("font_file_path") else {
+                continue;
+            };
+            if let Some(path) = path.as_str().filter(|p| !p.is_empty()) {
+                repl.insert(
+                    "font_file".to_string(),
+                    json!(relative_font_path(path, fonts_dir)),
+                );
+            }
+        }
+        enhanced_layout.push(item);
+    }
+
+    let mut source_settings = layout_data
+        .get("source_settings")
+        .cloned()
+        .unwrap_or(json!({}));
+    for settings in source_settings
+        .as_object_mut()
+        .into_iter()
+        .flat_map(|s| s.values_mut())
+    {
+        let Some(settings) = settings.as_object_mut() else {
+            continue;
+        };
+        for key in SOURCE_FILE_SETTINGS {
+            if let Some(path) = settings.remove(key) {
+                external_files.extend(
+                    path.as_str()
+                        .filter(|p| !p.trim().is_empty())
+                        .map(file_name),
+                );
+            }
+        }
+    }
+
+    json!({
+        "format": TEMPLATE_FORMAT,
+        "version": TEMPLATE_VERSION,
+        "name": name.trim(),
+        "created": Local::now().to_rfc3339(),
+        "enhanced_layout": enhanced_layout,
+        "attachment_sources": layout_data.get("attachment_sources").cloned().unwrap_or(json!([])),
+        "source_settings": source_settings,
+        "external_files": external_files,
+        "track_signature": layout_data.get("track_signature").cloned().unwrap_or(json!({})),
+        "structure_signature": layout_data["structure_signature"],
+    })
+}
+
+/// Resolves a template's replacement fonts through `fonts_dir`, giving the
+/// layout to apply plus warnings for missing fonts and for the external
+/// files the template left out.
+pub(super) fn resolve_template(template: &Value, fonts_dir: &Path) -> (Value, Vec<String>) {
+    let mut resolved = template.clone();
+    let mut warnings = Vec::new();
+    for item in resolved["enhanced_layout"]
+        .as_array_mut()
+        .into_iter()
+        .flatten()
+    {
+        for repl in font_replacements(item) {
+            let Some(font_file) = repl.remove("font_file") else {
+                continue;
+            };
+            let font_file = font_file.as_str().unwrap_or_default();
+            // Canonical paths so `..` or a symlink can't leave the fonts folder
+            let path = fonts_dir
+                .canonicalize()
+                .and_then(|dir| Ok((fonts_dir.join(font_file).canonicalize()?, dir)))
+                .ok()
+                .filter(|(path, dir)| path.starts_with(dir) && path.is_file());
+            if let Some((path, _)) = path {
+                repl.insert("font_file_path".to_string(), json!(path.to_string_lossy()));
+            } else {
+                warnings.push(format!(
+                    "Replacement font '{font_file}' is not in the fonts folder {}",
+                    fonts_dir.display()
+                ));
+            }
+        }
+    }
+    for name in template["external_files"].as_array().into_iter().flatten() {
+        warnings.push(format!(
+            "'{}' belonged to the original job and was not applied; add it again if needed",
+            name.as_str().unwrap_or_default()
+        ));
+    }
+    (resolved, warnings)
+}
+
+/// The per-style entries of an item's `font_replacements`.
+fn font_replacements(
+    item: &mut Value,
+) -> impl Iterator<Item = &mut serde_json::Map<String, Value>> {
+    item.get_mut("font_replacements")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(|styles| styles.values_mut())
+        .filter_map(Value::as_object_mut)
+}
+
+/// `path` relative to `fonts_dir` with `/` separators, or just its file
+/// name when it lies outside the fonts folder.
+fn relative_font_path(path: &str, fonts_dir: &Path) -> String {
+    match Path::new(path).strip_prefix(fonts_dir) {
+        Ok(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => file_name(path),
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// File stem for a template name, e.g. `"Season 2 (BD)"` → `season-2-bd`.
+fn template_stem(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() {
+            stem.extend(c.to_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    let stem = stem.trim_end_matches('-');
+    if stem.is_empty() {
+        "template".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Stores named layout templates under the config directory — `LayoutTemplateStore`.
+pub struct LayoutTemplateStore {
+    templates_dir: PathBuf,
+    log: LogCallback,
+}
+
+impl LayoutTemplateStore {
+    /// Create a store in `config_dir/layout_templates`.
+    pub fn new(config_dir: &Path, log: LogCallback) -> Self {
+        Self {
+            templates_dir: config_dir.join("layout_templates"),
+            log,
+        }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        self.templates_dir
+            .join(format!("{}.json", template_stem(name)))
+    }
+
+    /// Names of all stored templates, sorted.
+    pub fn list(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.templates_dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .filter_map(|s| serde_json::from_str::<Value>(&s).ok())
+            .filter_map(|t| t.get("name").and_then(|v| v.as_str()).map(String::from))
+            .collect();
+        names.sort_by_key(|n| n.to_lowercase());
+        names
+    }
+
+    /// Validates and stores a template, replacing one with the same name.
+    ///
+    /// Refuses a name whose file is already used by a different template,
+    /// e.g. "Season 2" when "season-2" is stored.
+    pub fn save(&self, template: &Value) -> Result<(), String> {
+        let (is_valid, reason) = LayoutValidator::validate_template(template);
+        if !is_valid {
+            return Err(format!("Invalid layout template: {reason}"));
+        }
+        let name = template["name"].as_str().unwrap_or_default();
+        let path = self.path_for(name);
+        let existing = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+            .and_then(|t| t.get("name").and_then(|v| v.as_str()).map(String::from));
+        if let Some(existing) = existing.filter(|n| n.trim() != name.trim()) {
+            return Err(format!(
+                "Template '{name}' would replace the stored template '{existing}'; \
+                 choose another name or delete '{existing}' first."
+            ));
+        }
+        fs::create_dir_all(&self.templates_dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(template).map_err(|e| e.to_string())?;
+        fs::write(&path, json).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+        (self.log)(&format!("[LayoutTemplates] Saved template '{name}'"));
+        Ok(())
+    }
+
+    /// Loads and validates a stored template.
+    pub fn load(&self, name: &str) -> Result<Value, String> {
+        Self::read_template(&self.path_for(name))
+    }
+
+    /// Deletes a stored template.
+    pub fn delete(&self, name: &str) -> bool {
+        let path = self.path_for(name);
+        match fs::remove_file(&path) {
+            Ok(()) => true,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => true,
+            Err(e) => {
+                (self.log)(&format!(
+                    "[LayoutTemplates] Error deleting template '{name}': {e}"
+                ));
+                false
+            }
+        }
+    }
+
+    /// Writes a stored template to a single shareable file.
+    pub fn export(&self, name: &str, dest: &Path) -> Result<(), String> {
+        let template = self.load(name)?;
+        let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+        fs::write(dest, json).map_err(|e| format!("Could not write {}: {e}", dest.display()))
+    }
+
+    /// Validates a template file and stores it. Returns its name.
+    pub fn import(&self, src: &Path) -> Result<String, String> {
+        let template = Self::read_template(src)?;
+        self.save(&template)?;
+        Ok(template["name"].as_str().unwrap_or_default().to_string())
+    }
+
+    fn read_template(path: &Path) -> Result<Value, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let template: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("{} is not valid JSON: {e}", path.display()))?;
+        let (is_valid, reason) = LayoutValidator::validate_template(&template);
+        if is_valid {
+            Ok(template)
+        } else {
+            Err(format!(
+                "Invalid layout template {}: {reason}",
+                path.display()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::job_layouts::JobLayoutManager;
+
+    fn track(id: i64, ttype: &str, lang: &str, name: &str) -> Value {
+        json!({"source": "Source 1", "original_path": "/media/ep01.mkv", "id": id,
+               "type": ttype, "codec_id": "X", "lang": lang, "name": name})
+    }
+
+    #[test]
+    fn template_exports_imports_and_applies_to_another_episode() {
+        let dir = tempfile::tempdir().unwrap();
+        let log: LogCallback = Arc::new(|_: &str| {});
+        let manager =
+            JobLayoutManager::new(&dir.path().join("temp").to_string_lossy(), log.clone());
+
+        let episode = vec![
+            track(0, "video", "und", ""),
+            track(1, "audio", "jpn", ""),
+            track(2, "subtitles", "eng", "Signs"),
+        ];
+        let info = HashMap::from([("Source 1".to_string(), episode.clone())]);
+        let sources = HashMap::from([("Source 1".to_string(), "/media/ep01.mkv".to_string())]);
+        let fonts = dir.path().join("fonts");
+        fs::create_dir_all(fonts.join("anime")).unwrap();
+        fs::write(fonts.join("anime/Signs.ttf"), b"").unwrap();
+        let mut layout = episode.clone();
+        layout[2]["style_patch"] = json!({"Default": {"fontsize": 60}});
+        layout[2]["font_replacements"] = json!({"Default": {
+            "original_font": "Arial", "new_font_name": "Signs",
+            "font_file_path": fonts.join("anime/Signs.ttf").to_string_lossy(),
+        }});
+        layout.push(
+            json!({"source": "External", "original_path": "/media/ep01.eng.ass",
+                           "id": 0, "type": "subtitles", "codec_id": "S_TEXT/ASS"}),
+        );
+        let settings = HashMap::from([(
+            "Source 1".to_string(),
+            json!({"chapter_file": "/media/ep01.chapters.xml"}),
+        )]);
+        assert!(manager.save_job_layout(
+            "ep01",
+            &layout,
+            &[],
+            &[],
+            &sources,
+            &info,
+            Some(&settings)
+        ));
+
+        let template = manager
+            .create_template("ep01", "Season 2 (BD)", &fonts)
+            .unwrap();
+        let items = template["enhanced_layout"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|item| item.get("original_path").is_none()));
+        assert_eq!(
+            items[2]["font_replacements"]["Default"],
+            json!({"original_font": "Arial", "new_font_name": "Signs",
+                   "font_file": "anime/Signs.ttf"})
+        );
+        assert!(template["source_settings"]["Source 1"]
+            .get("chapter_file")
+            .is_none());
+        assert_eq!(
+            template["external_files"],
+            json!(["ep01.eng.ass", "ep01.chapters.xml"])
+        );
+
+        // Export from one store, import into another
+        let store = LayoutTemplateStore::new(&dir.path().join("a"), log.clone());
+        store.save(&template).unwrap();
+        assert_eq!(store.list(), vec!["Season 2 (BD)".to_string()]);
+        let file = dir.path().join("shared.json");
+        store.export("Season 2 (BD)", &file).unwrap();
+        let other = LayoutTemplateStore::new(&dir.path().join("b"), log);
+        assert_eq!(other.import(&file).unwrap(), "Season 2 (BD)");
+
+        // Episode with an extra audio track still gets the template
+        let mut next = episode;
+        next.insert(2, track(2, "audio", "eng", ""));
+        next[3]["id"] = json!(3);
+        let next_info = HashMap::from([("Source 1".to_string(), next)]);
+        let imported = other.load("Season 2 (BD)").unwrap();
+        let warnings = manager
+            .apply_template(&imported, "ep02", &sources, &next_info, &fonts)
+            .unwrap();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("ep01.eng.ass"));
+
+        let applied = manager.load_job_layout("ep02").unwrap();
+        assert_eq!(applied["enhanced_layout"][2]["id"], 3);
+        assert_eq!(
+            applied["enhanced_layout"][2]["style_patch"]["Default"]["fontsize"],
+            60
+        );
+        assert_eq!(
+            applied["enhanced_layout"][2]["font_replacements"]["Default"]["font_file_path"],
+            json!(fonts
+                .join("anime/Signs.ttf")
+                .canonicalize()
+                .unwrap()
+                .to_string_lossy())
+        );
+
+        // A font missing from this machine's fonts folder is reported
+        let elsewhere = dir.path().join("other-fonts");
+        let warnings = manager
+            .apply_template(&imported, "ep03", &sources, &next_info, &elsewhere)
+            .unwrap();
+        assert!(warnings[0].contains("anime/Signs.ttf"));
+    }
+
+    #[test]
+    fn import_rejects_non_template_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LayoutTemplateStore::new(dir.path(), Arc::new(|_: &str| {}));
+        let file = dir.path().join("layout.json");
+        fs::write(&file, r#"{"job_id": "abc", "enhanced_layout": []}"#).unwrap();
+        assert!(store.import(&file).is_err());
+        assert!(store.list().is_empty());
+        assert_eq!(template_stem("  Season 2 (BD) "), "season-2-bd");
+    }
+
+    #[test]
+    fn names_sharing_a_file_do_not_overwrite_each_other() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LayoutTemplateStore::new(dir.path(), Arc::new(|_: &str| {}));
+        let template = |name: &str| {
+            json!({"format": TEMPLATE_FORMAT, "version": TEMPLATE_VERSION, "name": name,
+                   "enhanced_layout": [], "structure_signature": {"structure": {}}})
+        };
+
+        store.save(&template("Season 2")).unwrap();
+        store.save(&template("Season 2")).unwrap();
+        let err = store.save(&template("season-2")).unwrap_err();
+        assert!(err.contains("'Season 2'"), "{err}");
+        assert_eq!(store.list(), vec!["Season 2".to_string()]);
+
+        let file = dir.path().join("shared.json");
+        fs::write(&file, template("season-2").to_string()).unwrap();
+        assert!(store.import(&file).is_err());
+    }
+
+    #[test]
+    fn fonts_outside_the_fonts_folder_are_not_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let fonts = dir.path().join("fonts");
+        fs::create_dir_all(&fonts).unwrap();
+        fs::write(dir.path().join("secret.ttf"), b"").unwrap();
+        let template = json!({"enhanced_layout": [
+            {"font_replacements": {"Default": {"font_file": "../secret.ttf"}}}
+        ]});
+
+        let (resolved, warnings) = resolve_template(&template, &fonts);
+        assert_eq!(
+            resolved["enhanced_layout"][0]["font_replacements"]["Default"],
+            json!({})
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("../secret.ttf"));
+    }
+}
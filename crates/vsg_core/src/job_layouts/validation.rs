@@ -2,8 +2,12 @@
 //!
 //! Validates that loaded layout data is well-formed.
 
+use std::path::{Component, Path};
+
 use serde_json::Value;
 
+use super::templates::{TEMPLATE_FORMAT, TEMPLATE_VERSION};
+
 /// Validates that loaded layout data is well-formed — `LayoutValidator`.
 pub struct LayoutValidator;
 
@@ -31,7 +35,105 @@ impl LayoutValidator {
             }
         }
 
-        let enhanced_layout = match obj.get("enhanced_layout").and_then(|v| v.as_array()) {
+        Self::validate_layout_items(obj.get("enhanced_layout"))
+    }
+
+    /// Validates a portable layout template before it is imported or applied.
+    ///
+    /// Besides the layout items, checks the template header, the structure
+    /// signature used for track remapping, and the types of the per-track
+    /// options a template carries.
+    pub fn validate_template(template: &Value) -> (bool, String) {
+        let obj = match template.as_object() {
+            Some(o) => o,
+            None => return (false, "Template is not a dictionary.".to_string()),
+        };
+
+        if obj.get("format").and_then(|v| v.as_str()) != Some(TEMPLATE_FORMAT) {
+            return (false, "Not a layout template file.".to_string());
+        }
+        match obj.get("version").and_then(|v| v.as_i64()) {
+            Some(v) if v <= TEMPLATE_VERSION => {}
+            Some(v) => return (false, format!("Unsupported template version: {v}")),
+            None => return (false, "Missing required field: version".to_string()),
+        }
+        if obj
+            .get("name")
+            .and_then(|v| v.as_str())
+            .is_none_or(|n| n.trim().is_empty())
+        {
+            return (
+                false,
+                "Template name must be a non-empty string.".to_string(),
+            );
+        }
+        if obj
+            .get("structure_signature")
+            .and_then(|v| v.get("structure"))
+            .and_then(|v| v.as_object())
+            .is_none()
+        {
+            return (
+                false,
+                "Missing required field: structure_signature".to_string(),
+            );
+        }
+
+        let (valid, reason) = Self::validate_layout_items(obj.get("enhanced_layout"));
+        if !valid {
+            return (false, reason);
+        }
+
+        type TypeCheck = fn(&Value) -> bool;
+        let option_types: [(&str, TypeCheck); 5] = [
+            ("style_patch", Value::is_object),
+            ("font_replacements", Value::is_object),
+            ("sync_exclusion_styles", Value::is_array),
+            ("sync_exclusion_mode", Value::is_string),
+            ("sync_exclusion_original_style_list", Value::is_array),
+        ];
+        for (i, item) in obj["enhanced_layout"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            for (field, is_valid_type) in &option_types {
+                if item
+                    .get(*field)
+                    .is_some_and(|v| !v.is_null() && !is_valid_type(v))
+                {
+                    return (false, format!("Layout item {i} has an invalid {field}."));
+                }
+            }
+            // Replacement fonts must stay inside the fonts folder
+            let font_files = item
+                .get("font_replacements")
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flat_map(|styles| styles.values())
+                .filter_map(|repl| repl.get("font_file"));
+            for font_file in font_files {
+                let is_relative = font_file.as_str().is_some_and(|f| {
+                    Path::new(f)
+                        .components()
+                        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+                });
+                if !is_relative {
+                    return (
+                        false,
+                        format!("Layout item {i} has a font_file outside the fonts folder."),
+                    );
+                }
+            }
+        }
+
+        (true, "Valid".to_string())
+    }
+
+    /// Checks that `enhanced_layout` is a list of well-formed items.
+    fn validate_layout_items(enhanced_layout: Option<&Value>) -> (bool, String) {
+        let enhanced_layout = match enhanced_layout.and_then(|v| v.as_array()) {
             Some(arr) => arr,
             None => return (false, "Enhanced layout must be a list.".to_string()),
         };
@@ -86,4 +188,43 @@ mod tests {
         assert!(!valid);
         assert!(reason.contains("enhanced_layout"));
     }
+
+    #[test]
+    fn test_template_validation() {
+        let mut template = json!({
+            "format": TEMPLATE_FORMAT,
+            "version": TEMPLATE_VERSION,
+            "name": "Season 2",
+            "enhanced_layout": [
+                {"source": "Source 2", "id": 3, "type": "subtitles", "user_order_index": 0,
+                 "style_patch": {"Default": {"fontsize": 60}}}
+            ],
+            "structure_signature": {"structure": {}, "hash": ""}
+        });
+        assert!(LayoutValidator::validate_template(&template).0);
+
+        template["enhanced_layout"][0]["sync_exclusion_styles"] = json!("Signs");
+        let (valid, reason) = LayoutValidator::validate_template(&template);
+        assert!(!valid);
+        assert!(reason.contains("sync_exclusion_styles"));
+
+        template["enhanced_layout"][0]["sync_exclusion_styles"] = json!(["Signs"]);
+        for font_file in [
+            "anime/Signs.ttf",
+            "/usr/share/fonts/Signs.ttf",
+            "../Signs.ttf",
+        ] {
+            template["enhanced_layout"][0]["font_replacements"] =
+                json!({"Default": {"font_file": font_file}});
+            let (valid, reason) = LayoutValidator::validate_template(&template);
+            assert_eq!(
+                valid,
+                font_file == "anime/Signs.ttf",
+                "{font_file}: {reason}"
+            );
+        }
+
+        template["format"] = json!("something-else");
+        assert!(!LayoutValidator::validate_template(&template).0);
+    }
 }
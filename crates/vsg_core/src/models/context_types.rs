@@ -3,10 +3,13 @@
 //! Python TypedDicts become Rust structs. Fields with `total=False` in Python
 //! become `Option<T>` in Rust. Fields with `Required` stay non-optional.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use super::enums::AuditSeverity;
+use super::media::Track;
+
 // ─── Manual Layout Types ─────────────────────────────────────────────────────
 
 /// A single track selection from the user's manual layout — `ManualLayoutItem`
@@ -105,6 +108,8 @@ pub struct SourceNSettings {
     pub correlation_source_track: Option<i32>,
     #[serde(default)]
     pub use_source_separation: Option<bool>,
+    #[serde(default)]
+    pub stepping_edl_override: Option<String>,
 }
 
 // ─── Stepping Correction Types ───────────────────────────────────────────────
@@ -132,6 +137,9 @@ pub struct SegmentFlagsEntry {
     pub stepping_data_path: Option<String>,
     #[serde(default)]
     pub audit_metadata: Option<Vec<serde_json::Value>>,
+    /// User-supplied EDL (JSON or CMX3600) replacing detection and refinement
+    #[serde(default)]
+    pub edl_override: Option<String>,
 }
 
 // ─── Drift Correction Types ─────────────────────────────────────────────────
@@ -141,6 +149,12 @@ pub struct SegmentFlagsEntry {
 pub struct DriftFlagsEntry {
     #[serde(default)]
     pub rate: Option<f64>,
+    /// Source speed relative to the reference (speed-ratio correction only)
+    #[serde(default)]
+    pub ratio: Option<f64>,
+    /// Known broadcast ratio label, e.g. `25→24`
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 // ─── Quality Issue Types ─────────────────────────────────────────────────────
@@ -231,6 +245,173 @@ pub struct SyncStabilityIssue {
     pub reason: Option<String>,
 }
 
+// ─── Post-Mux Sync Types ─────────────────────────────────────────────────────
+
+/// Sync re-measured on one audio track of the final MKV — `PostMuxSyncCheck`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostMuxSyncCheck {
+    /// Track index in the final MKV
+    pub track_index: usize,
+    pub source: String,
+    pub track_id: i32,
+    #[serde(default)]
+    pub name: String,
+    /// Remaining offset against Source 1 (positive = audio still early);
+    /// `None` when too few windows matched to measure
+    #[serde(default)]
+    pub residual_ms: Option<f64>,
+    #[serde(default)]
+    pub accepted_windows: usize,
+    #[serde(default)]
+    pub total_windows: usize,
+    pub threshold_ms: f64,
+    /// "passed", "failed" or "inconclusive"
+    pub status: String,
+}
+
+// ─── Linked Timeline Types ───────────────────────────────────────────────────
+
+/// A source read through its virtually assembled linked timeline — `VirtualTimeline`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VirtualTimeline {
+    /// ffmpeg concat playlist playing the assembled timeline
+    pub playlist: String,
+    /// Flat chapter XML on the assembled timeline
+    pub chapters_xml: String,
+}
+
+// ─── Provenance Types ────────────────────────────────────────────────────────
+
+/// A source file recorded in the output's provenance tags — `SourceProvenance`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceProvenance {
+    /// Source key, e.g. "Source 2"
+    pub key: String,
+    pub file_name: String,
+    /// Partial SHA-256 (size, first and last MiB) — see `mux::provenance`
+    #[serde(default)]
+    pub hash: String,
+    #[serde(default)]
+    pub delay_ms: i32,
+    #[serde(default)]
+    pub raw_delay_ms: f64,
+}
+
+/// Per-track provenance — `TrackProvenance`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrackProvenance {
+    /// Track index in the output file
+    pub index: usize,
+    pub source: String,
+    pub source_track_id: i32,
+    /// The `--sync` offset the track was muxed with, after any timestamp
+    /// rebase was folded in
+    #[serde(default)]
+    pub applied_delay_ms: i32,
+    /// "stepping", "linear", "pal" or "speed" when the audio was corrected
+    #[serde(default)]
+    pub correction: Option<String>,
+}
+
+/// Everything needed to know how an output was synced — `SyncProvenance`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncProvenance {
+    pub tool_version: String,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub sync_mode: String,
+    #[serde(default)]
+    pub global_shift_ms: i32,
+    /// Offset subtracted from every track by the lossless finalizer; add it
+    /// back to `applied_delay_ms` for the sync delay
+    #[serde(default)]
+    pub timestamp_rebase_ms: i32,
+    /// Short SHA-256 of the settings the job ran with
+    #[serde(default)]
+    pub settings_profile: String,
+    #[serde(default)]
+    pub sources: Vec<SourceProvenance>,
+    #[serde(default)]
+    pub tracks: Vec<TrackProvenance>,
+}
+
+// ─── Audit Types ─────────────────────────────────────────────────────────────
+
+/// Track an audit finding refers to — `AuditTrackRef`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditTrackRef {
+    /// Track index in the final MKV, when the auditor knows it
+    #[serde(default)]
+    pub index: Option<usize>,
+    pub source: String,
+    pub id: i32,
+    pub track_type: String,
+}
+
+/// One structured result from a post-merge auditor — `AuditFinding`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    /// Auditor key, e.g. "track_flags" (filled in by `FinalAuditor`)
+    #[serde(default)]
+    pub auditor: String,
+    /// Stable machine-readable code, e.g. "delay_mismatch"
+    pub code: String,
+    pub severity: AuditSeverity,
+    pub message: String,
+    #[serde(default)]
+    pub track: Option<AuditTrackRef>,
+    #[serde(default)]
+    pub expected: Option<String>,
+    #[serde(default)]
+    pub actual: Option<String>,
+}
+
+impl AuditFinding {
+    pub fn new(severity: AuditSeverity, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            severity,
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn warning(code: &str, message: impl Into<String>) -> Self {
+        Self::new(AuditSeverity::Warning, code, message)
+    }
+
+    pub fn error(code: &str, message: impl Into<String>) -> Self {
+        Self::new(AuditSeverity::Error, code, message)
+    }
+
+    /// Attach the planned track the finding is about.
+    pub fn with_track(mut self, track: &Track) -> Self {
+        self.track = Some(AuditTrackRef {
+            index: None,
+            source: track.source.clone(),
+            id: track.id,
+            track_type: track.track_type.to_string(),
+        });
+        self
+    }
+
+    /// Attach the track at `index` in the final MKV.
+    pub fn with_final_track(mut self, index: usize, track: &Track) -> Self {
+        self = self.with_track(track);
+        if let Some(track_ref) = self.track.as_mut() {
+            track_ref.index = Some(index);
+        }
+        self
+    }
+
+    pub fn with_values(mut self, expected: impl ToString, actual: impl ToString) -> Self {
+        self.expected = Some(expected.to_string());
+        self.actual = Some(actual.to_string());
+        self
+    }
+}
+
 // ─── PlanItem Style Types ────────────────────────────────────────────────────
 
 /// ASS subtitle style attributes that can be patched — `ASSStyleAttributes`
@@ -326,3 +507,28 @@ pub struct VideoVerifiedResult {
     #[serde(default)]
     pub details: Option<serde_json::Value>,
 }
+
+// ─── Tool Capability Types ───────────────────────────────────────────────────
+
+/// Version reported by an external tool's `--version`/`-version` output.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolVersion {
+    /// First line of the version output, verbatim.
+    #[serde(default)]
+    pub raw: String,
+    /// Dotted version number parsed from `raw`, e.g. "6.1.1" or "82.0".
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Detected tool versions and FFmpeg features for a job.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCapabilities {
+    /// Versions keyed by tool name.
+    #[serde(default)]
+    pub versions: BTreeMap<String, ToolVersion>,
+    /// FFmpeg features (`rubberband`, `atempo`, `soxr`) → available.
+    /// Empty when FFmpeg could not be probed.
+    #[serde(default)]
+    pub ffmpeg_features: BTreeMap<String, bool>,
+}
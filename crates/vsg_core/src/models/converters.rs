@@ -46,6 +46,10 @@ pub fn tracks_from_dialog_info(
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
+            let audio_channels = t
+                .get("audio_channels")
+                .and_then(|v| v.as_i64())
+                .map(|c| c as i32);
 
             tracks.push(Track {
                 source: source_key.clone(),
@@ -55,6 +59,7 @@ pub fn tracks_from_dialog_info(
                     codec_id,
                     lang,
                     name,
+                    audio_channels,
                 },
             });
         }
@@ -109,6 +114,7 @@ pub fn realize_plan_from_manual_layout(
             is_preserved: false,
             is_corrected: false,
             correction_source: None,
+            correction_transcode: None,
             perform_ocr: sel.perform_ocr.unwrap_or(false),
             container_delay_ms: 0,
             custom_lang: sel.custom_lang.clone().unwrap_or_default(),
@@ -67,6 +67,32 @@ impl std::fmt::Display for SnapMode {
     }
 }
 
+// ─── Linked timeline mode ────────────────────────────────────────────────────
+
+/// How sources with ordered chapters pulling in linked segments are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkedTimelineMode {
+    /// Warn about the linked segments; use the main segment as-is
+    #[default]
+    Off,
+    /// Read the linked timeline through an ffmpeg concat playlist; remux it
+    /// only when the layout takes tracks from that source
+    Virtual,
+    /// Always remux the linked timeline into a single file
+    Physical,
+}
+
+impl std::fmt::Display for LinkedTimelineMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Virtual => write!(f, "virtual"),
+            Self::Physical => write!(f, "physical"),
+        }
+    }
+}
+
 // ─── Subtitle sync mode ─────────────────────────────────────────────────────
 
 /// Subtitle sync method — `SubtitleSyncModeStr`
@@ -434,6 +460,26 @@ impl std::fmt::Display for SteppingFilteredFallback {
     }
 }
 
+/// Gap fill where stepping assembly inserts audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SteppingGapFill {
+    /// Digital silence
+    #[default]
+    Silence,
+    /// Low-level room tone copied from the splice's silence zone
+    RoomTone,
+}
+
+impl std::fmt::Display for SteppingGapFill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Silence => write!(f, "silence"),
+            Self::RoomTone => write!(f, "room_tone"),
+        }
+    }
+}
+
 /// Stepping boundary mode — `SteppingBoundaryModeStr`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -464,6 +510,8 @@ pub enum ResampleEngine {
     Aresample,
     Atempo,
     Rubberband,
+    /// In-process sinc varispeed / WSOLA time-stretch (`correction::native`)
+    Native,
 }
 
 impl std::fmt::Display for ResampleEngine {
@@ -472,6 +520,7 @@ impl std::fmt::Display for ResampleEngine {
             Self::Aresample => write!(f, "aresample"),
             Self::Atempo => write!(f, "atempo"),
             Self::Rubberband => write!(f, "rubberband"),
+            Self::Native => write!(f, "native"),
         }
     }
 }
@@ -496,6 +545,57 @@ impl std::fmt::Display for RubberbandTransients {
     }
 }
 
+// ─── Speed-ratio correction ──────────────────────────────────────────────────
+
+/// How linear drift is turned into a speed-ratio correction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedCorrectionMode {
+    /// Plain linear drift correction (audio only)
+    #[default]
+    Disabled,
+    /// Snap the fitted slope to the nearest known broadcast ratio
+    Snap,
+    /// Use the exact fitted ratio
+    Exact,
+}
+
+impl std::fmt::Display for SpeedCorrectionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "disabled"),
+            Self::Snap => write!(f, "snap"),
+            Self::Exact => write!(f, "exact"),
+        }
+    }
+}
+
+// ─── Corrected audio encoding ────────────────────────────────────────────────
+
+/// Codec for audio after correction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorrectedAudioEncode {
+    /// Keep the lossless FLAC the correctors produce
+    #[default]
+    KeepFlac,
+    /// Re-encode to the original codec at the original bitrate
+    MatchOriginal,
+    Opus,
+    Aac,
+}
+
+impl std::fmt::Display for CorrectedAudioEncode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeepFlac => write!(f, "keep_flac"),
+            Self::MatchOriginal => write!(f, "match_original"),
+            Self::Opus => write!(f, "opus"),
+            Self::Aac => write!(f, "aac"),
+        }
+    }
+}
+
 // ─── Sync stability ──────────────────────────────────────────────────────────
 
 /// Outlier detection mode — `SyncStabilityOutlierModeStr`
@@ -572,6 +672,75 @@ impl std::fmt::Display for OcrBinarizationMethod {
     }
 }
 
+// ─── Output naming ───────────────────────────────────────────────────────────
+
+/// What to do when the rendered output path already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputCollision {
+    #[default]
+    Overwrite,
+    /// Append " (1)", " (2)", ... to the file name
+    Suffix,
+    /// Leave the existing file and skip the job
+    Skip,
+}
+
+impl std::fmt::Display for OutputCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overwrite => write!(f, "overwrite"),
+            Self::Suffix => write!(f, "suffix"),
+            Self::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+// ─── Post-mux finalization ───────────────────────────────────────────────────
+
+/// How the merged file's timestamps are rebased to start at zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampFinalizer {
+    /// Full FFmpeg remux with `-avoid_negative_ts make_zero`
+    #[default]
+    Ffmpeg,
+    /// Fold the rebase into the mkvmerge `--sync` offsets; any residual is
+    /// removed by an mkvmerge pass that keeps every element
+    Mkvmerge,
+}
+
+impl std::fmt::Display for TimestampFinalizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ffmpeg => write!(f, "ffmpeg"),
+            Self::Mkvmerge => write!(f, "mkvmerge"),
+        }
+    }
+}
+
+// ─── Audit ───────────────────────────────────────────────────────────────────
+
+/// Severity of a post-merge audit finding (ordered, `Error` is worst)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditSeverity {
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
 // ─── Job status ──────────────────────────────────────────────────────────────
 
 /// Status of a completed job.
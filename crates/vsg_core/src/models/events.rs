@@ -0,0 +1,99 @@
+//! Job events — typed progress/result stream emitted alongside the log and
+//! progress callbacks.
+//!
+//! Each event serializes to a single JSON object tagged by `"event"`, so a
+//! job's stream can be written as JSON lines and tailed by external tools.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// Event callback type — shared between the pipeline, steps and runners.
+pub type EventCallback = Arc<dyn Fn(&JobEvent) + Send + Sync>;
+
+/// Returns an event callback that discards everything.
+pub fn noop_event_callback() -> EventCallback {
+    Arc::new(|_event: &JobEvent| {})
+}
+
+/// A typed job progress/result event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JobEvent {
+    /// A pipeline step began.
+    StepStarted { step: String },
+    /// A pipeline step ended; `error` is set when it failed.
+    StepFinished {
+        step: String,
+        ok: bool,
+        duration_ms: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// One correlation chunk was measured for a source.
+    ChunkCorrelated {
+        source: String,
+        index: usize,
+        start_s: f64,
+        delay_ms: i32,
+        raw_delay_ms: f64,
+        match_pct: f64,
+        accepted: bool,
+    },
+    /// The final delay for a source was decided (after global shift).
+    DelayChosen {
+        source: String,
+        delay_ms: i32,
+        raw_delay_ms: f64,
+        global_shift_ms: i32,
+    },
+    /// An auditor reported issues on the final file.
+    AuditorFinding { auditor: String, issues: i32 },
+    /// An external tool was run.
+    ToolInvoked {
+        tool: String,
+        args: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i32>,
+        ok: bool,
+        duration_ms: u64,
+    },
+    /// The job ended — `status` matches `PipelineResult.status`.
+    JobFinished {
+        name: String,
+        status: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        duration_ms: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_tagged_snake_case() {
+        let ev = JobEvent::StepStarted {
+            step: "analysis".to_string(),
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        assert_eq!(json, r#"{"event":"step_started","step":"analysis"}"#);
+    }
+
+    #[test]
+    fn optional_fields_round_trip() {
+        let ev = JobEvent::StepFinished {
+            step: "mux".to_string(),
+            ok: true,
+            duration_ms: 12,
+            error: None,
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        assert!(!json.contains("error"));
+        let back: JobEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ev);
+    }
+}
@@ -6,7 +6,8 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use super::context_types::{
-    FilterConfig, FontReplacements, SteppingQualityIssue, StylePatch, SyncStabilityIssue,
+    AuditFinding, FilterConfig, FontReplacements, PostMuxSyncCheck, SteppingQualityIssue,
+    StylePatch, SyncStabilityIssue,
 };
 use super::media::Track;
 
@@ -59,6 +60,9 @@ pub struct PlanItem {
     pub is_corrected: bool,
     #[serde(default)]
     pub correction_source: Option<String>,
+    /// Codec and bitrate of the re-encode applied after correction, e.g. "Opus 256k"
+    #[serde(default)]
+    pub correction_transcode: Option<String>,
     #[serde(default)]
     pub perform_ocr: bool,
     #[serde(default)]
@@ -128,7 +132,7 @@ pub struct MergePlan {
 /// Detailed result from pipeline.run_job() — `PipelineResult`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineResult {
-    /// "Merged", "Analyzed", or "Failed"
+    /// "Merged", "Analyzed", "Skipped", or "Failed"
     pub status: String,
     pub name: String,
     #[serde(default)]
@@ -149,6 +153,14 @@ pub struct PipelineResult {
     pub stepping_quality_issues: Vec<SteppingQualityIssue>,
     #[serde(default)]
     pub sync_stability_issues: Vec<SyncStabilityIssue>,
+    #[serde(default)]
+    pub post_mux_sync: Vec<PostMuxSyncCheck>,
+    /// Structured post-merge audit findings
+    #[serde(default)]
+    pub audit_findings: Vec<AuditFinding>,
+    /// Keys of the auditors that ran
+    #[serde(default)]
+    pub audits_run: Vec<String>,
 }
 
 #[cfg(test)]
@@ -14,6 +14,9 @@ pub struct StreamProps {
     pub lang: String,
     #[serde(default)]
     pub name: String,
+    /// Audio channel count, when known (audio tracks only)
+    #[serde(default)]
+    pub audio_channels: Option<i32>,
 }
 
 fn default_und() -> String {
@@ -26,6 +29,7 @@ impl StreamProps {
             codec_id: codec_id.into(),
             lang: "und".to_string(),
             name: String::new(),
+            audio_channels: None,
         }
     }
 }
@@ -1,6 +1,7 @@
 pub mod context_types;
 pub mod converters;
 pub mod enums;
+pub mod events;
 pub mod jobs;
 pub mod media;
 pub mod settings;
@@ -9,12 +9,13 @@
 use serde::{Deserialize, Serialize};
 
 use super::enums::{
-    AnalysisMode, CorrelationMethod, CorrelationMethodSourceSep, DelaySelectionMode,
-    FilteringMethod, FrameComparisonMethod, FrameHashAlgorithm, OcrBinarizationMethod, OcrEngine,
-    OcrOutputFormat, ResampleEngine, RubberbandTransients, SnapMode, SourceSeparationDevice,
-    SourceSeparationMode, SteppingBoundaryMode, SteppingCorrectionMode, SteppingFilteredFallback,
-    SteppingQualityMode, SubtitleRounding, SubtitleSyncMode, SyncMode,
-    SyncStabilityOutlierMode, VideoVerifiedMethod,
+    AnalysisMode, CorrectedAudioEncode, CorrelationMethod, CorrelationMethodSourceSep,
+    DelaySelectionMode, FilteringMethod, OutputCollision, FrameComparisonMethod, FrameHashAlgorithm,
+    LinkedTimelineMode, OcrBinarizationMethod, OcrEngine, OcrOutputFormat, ResampleEngine, RubberbandTransients,
+    SnapMode, SourceSeparationDevice, SourceSeparationMode, SpeedCorrectionMode,
+    SteppingBoundaryMode, SteppingCorrectionMode, SteppingFilteredFallback, SteppingGapFill, SteppingQualityMode,
+    SubtitleRounding, SubtitleSyncMode, SyncMode, SyncStabilityOutlierMode, TimestampFinalizer,
+    VideoVerifiedMethod,
 };
 
 /// Sentinel value for paths that need runtime resolution.
@@ -46,6 +47,14 @@ pub struct AppSettings {
     pub last_ter_path: String,
     #[serde(default = "default_path_sentinel")]
     pub source_separation_model_dir: String,
+    /// Output path template relative to `output_folder`, e.g.
+    /// "{name}/{name}< - E{episode:02}> [{resolution}]"; empty keeps the
+    /// Source 1 file name.
+    #[serde(default)]
+    pub output_name_template: String,
+    /// What to do when the output file already exists.
+    #[serde(default)]
+    pub output_collision: OutputCollision,
 
     // ─── Analysis Settings ───────────────────────────────────────────────────
     #[serde(default)]
@@ -92,6 +101,20 @@ pub struct AppSettings {
     pub snap_threshold_ms: i32,
     #[serde(default = "default_true")]
     pub snap_starts_only: bool,
+    /// Generate chapters from recurring OP/ED audio across the batch when
+    /// no source provides any.
+    #[serde(default)]
+    pub generate_chapters: bool,
+    /// Shortest recurring segment treated as an opening or ending.
+    #[serde(default = "default_chapter_gen_min_theme_s")]
+    pub chapter_gen_min_theme_s: f64,
+    /// Audio below this level counts as silence when looking for the
+    /// Part A / Part B break.
+    #[serde(default = "default_chapter_gen_silence_db")]
+    pub chapter_gen_silence_db: f64,
+    /// How ordered chapters that pull in linked segments are followed.
+    #[serde(default)]
+    pub linked_timeline_mode: LinkedTimelineMode,
 
     // ─── Muxing Settings ─────────────────────────────────────────────────────
     #[serde(default)]
@@ -100,12 +123,42 @@ pub struct AppSettings {
     pub disable_track_statistics_tags: bool,
     #[serde(default = "default_true")]
     pub disable_header_compression: bool,
+    /// Attach fonts from `fonts_directory` that subtitles use but no
+    /// attachment source provides.
+    #[serde(default)]
+    pub font_auto_attach: bool,
+    /// Only attach fonts that a final subtitle track actually renders with.
+    #[serde(default)]
+    pub attachments_referenced_fonts_only: bool,
 
     // ─── Post-Mux Settings ───────────────────────────────────────────────────
     #[serde(default)]
     pub post_mux_normalize_timestamps: bool,
+    /// Tool that rebases timestamps when normalization is on.
+    #[serde(default)]
+    pub post_mux_finalizer: TimestampFinalizer,
     #[serde(default)]
     pub post_mux_strip_tags: bool,
+    /// Re-measure audio sync in the final MKV against Source 1. Opt-in:
+    /// it decodes the reference and every synced track again.
+    #[serde(default)]
+    pub post_mux_sync_verify: bool,
+    /// Residual offset (ms) beyond which a track fails the sync audit.
+    #[serde(default = "default_post_mux_sync_threshold_ms")]
+    pub post_mux_sync_threshold_ms: f64,
+    /// Correlation hop (s) for the post-mux sync audit.
+    #[serde(default = "default_post_mux_sync_hop_s")]
+    pub post_mux_sync_hop_s: f64,
+    /// Per-auditor overrides, e.g. "dolby_vision=off, track_order=error".
+    /// Values: off, on, info, warning, error.
+    #[serde(default)]
+    pub audit_overrides: String,
+    /// Write a JUnit XML file of the audit findings next to the output.
+    #[serde(default)]
+    pub audit_junit_export: bool,
+    /// Tag the output with its sources, delays, corrections and tool version.
+    #[serde(default = "default_true")]
+    pub write_provenance_tags: bool,
 
     // ─── Logging Settings ────────────────────────────────────────────────────
     #[serde(default = "default_true")]
@@ -126,6 +179,9 @@ pub struct AppSettings {
     pub log_audio_drift: bool,
     #[serde(default = "default_true")]
     pub archive_logs: bool,
+    /// Write a per-job `{job}.events.jsonl` event stream next to the job log.
+    #[serde(default)]
+    pub log_event_stream: bool,
 
     // ─── Timing Sync Settings ────────────────────────────────────────────────
     #[serde(default)]
@@ -279,6 +335,17 @@ pub struct AppSettings {
     #[serde(default = "default_drift_slope_lossless")]
     pub drift_detection_slope_threshold_lossless: f64,
 
+    // Speed-ratio correction
+    /// Treat linear drift as a speed mismatch and retime subtitles/chapters too.
+    #[serde(default)]
+    pub speed_correction_mode: SpeedCorrectionMode,
+    /// Max slope distance (ms/s) to snap to a known broadcast ratio.
+    #[serde(default = "default_speed_correction_snap_tolerance")]
+    pub speed_correction_snap_tolerance_ms_s: f64,
+    /// Keep pitch (rubberband tempo); off retimes like varispeed.
+    #[serde(default = "default_true")]
+    pub speed_correction_pitch_correct: bool,
+
     // ─── Stepping Correction Settings ────────────────────────────────────────
     #[serde(default = "default_true")]
     pub stepping_adjust_subtitles: bool,
@@ -321,7 +388,26 @@ pub struct AppSettings {
     #[serde(default = "default_stepping_video_snap_max_offset_s")]
     pub stepping_video_snap_max_offset_s: f64,
 
+    // Assembly — splice joins and gap fill
+    /// Equal-power crossfade across each splice instead of a hard cut.
+    #[serde(default)]
+    pub stepping_crossfade_enabled: bool,
+    #[serde(default = "default_stepping_crossfade_ms")]
+    pub stepping_crossfade_ms: f64,
+    /// What fills the gap where the delay increases.
+    #[serde(default)]
+    pub stepping_gap_fill: SteppingGapFill,
+    /// Loudest silence zone (dB) still usable as room tone.
+    #[serde(default = "default_stepping_room_tone_max_db")]
+    pub stepping_room_tone_max_db: f64,
+
+    // EDL export
+    /// Write the final EDL (JSON + CMX3600) to the output folder.
+    #[serde(default)]
+    pub stepping_export_edl: bool,
+
     // Track naming
+    /// Label for corrected tracks; `{codec}` expands to the final codec.
     #[serde(default)]
     pub stepping_corrected_track_label: String,
     #[serde(default)]
@@ -375,6 +461,18 @@ pub struct AppSettings {
     #[serde(default = "default_true")]
     pub segment_rb_pitchq: bool,
 
+    // ─── Corrected Audio Encode Settings ─────────────────────────────────────
+    /// Codec for corrected audio tracks (the correctors produce FLAC).
+    #[serde(default)]
+    pub corrected_audio_encode: CorrectedAudioEncode,
+    /// Per codec family overrides, e.g. `ac3=match_original, aac=opus,
+    /// lossless=keep_flac`. Families: lossless, ac3, dts, aac, opus, vorbis, mp3.
+    #[serde(default)]
+    pub corrected_audio_encode_overrides: String,
+    /// Bitrate for Opus/AAC re-encodes in kb/s (0 = scaled by channel count).
+    #[serde(default)]
+    pub corrected_audio_bitrate_kbps: i32,
+
     // ─── OCR Settings ────────────────────────────────────────────────────────
     #[serde(default = "default_true")]
     pub ocr_enabled: bool,
@@ -440,6 +538,46 @@ pub struct AppSettings {
     pub ocr_generate_report: bool,
     #[serde(default = "default_ocr_max_workers")]
     pub ocr_max_workers: i32,
+
+    // ─── External Tool Settings ──────────────────────────────────────────────
+    /// Default per-command timeout in seconds (0 = no timeout). Off by
+    /// default: OCR and source separation can legitimately run for hours,
+    /// so hung-tool protection is opt-in via this or the overrides.
+    #[serde(default = "default_tool_timeout_s")]
+    pub tool_timeout_s: i32,
+    /// Per-tool/per-step timeout overrides, e.g. `ffmpeg=1800, analysis=600,
+    /// analysis.ffmpeg=300`.
+    #[serde(default)]
+    pub tool_timeout_overrides: String,
+    /// Retries for transient tool failures (0 = no retry).
+    #[serde(default = "default_tool_retry_count")]
+    pub tool_retry_count: i32,
+    /// Initial retry back-off in milliseconds, doubled per attempt.
+    #[serde(default = "default_tool_retry_backoff_ms")]
+    pub tool_retry_backoff_ms: i32,
+    /// Also retry mkvtoolnix runs that exit with warnings (code 1).
+    #[serde(default)]
+    pub tool_retry_warnings: bool,
+    /// `nice` level for child processes (0 = unchanged).
+    #[serde(default)]
+    pub tool_nice_level: i32,
+    /// `ionice` class for child processes (0 = unchanged, 2 = best-effort, 3 = idle).
+    #[serde(default)]
+    pub tool_ionice_class: i32,
+    /// Address-space limit for child processes in MB (0 = unlimited).
+    #[serde(default)]
+    pub tool_memory_limit_mb: i32,
+
+    // ─── Disk Space Settings ─────────────────────────────────────────────────
+    /// Check free temp/output space after analysis, before extracting.
+    #[serde(default = "default_true")]
+    pub preflight_disk_check: bool,
+    /// Safety margin added to the space estimate, in percent.
+    #[serde(default = "default_preflight_space_margin_pct")]
+    pub preflight_space_margin_pct: f64,
+    /// Remove orphaned `orch_*` job temp dirs older than this at startup (0 = never).
+    #[serde(default = "default_temp_retention_hours")]
+    pub temp_retention_hours: f64,
 }
 
 // ─── Default value functions ─────────────────────────────────────────────────
@@ -487,6 +625,22 @@ fn default_snap_threshold_ms() -> i32 {
     250
 }
 
+fn default_chapter_gen_min_theme_s() -> f64 {
+    30.0
+}
+
+fn default_chapter_gen_silence_db() -> f64 {
+    -50.0
+}
+
+// Post-mux
+fn default_post_mux_sync_threshold_ms() -> f64 {
+    15.0
+}
+fn default_post_mux_sync_hop_s() -> f64 {
+    30.0
+}
+
 // Logging
 fn default_log_error_tail() -> i32 {
     20
@@ -572,6 +726,9 @@ fn default_drift_slope_lossy() -> f64 {
 fn default_drift_slope_lossless() -> f64 {
     0.2
 }
+fn default_speed_correction_snap_tolerance() -> f64 {
+    0.5
+}
 
 // Stepping
 fn default_stepping_triage_std_dev_ms() -> i32 {
@@ -601,6 +758,12 @@ fn default_stepping_fusion_weight_duration() -> i32 {
 fn default_stepping_video_snap_max_offset_s() -> f64 {
     2.0
 }
+fn default_stepping_crossfade_ms() -> f64 {
+    20.0
+}
+fn default_stepping_room_tone_max_db() -> f64 {
+    -45.0
+}
 fn default_stepping_min_cluster_percentage() -> f64 {
     5.0
 }
@@ -660,6 +823,25 @@ fn default_ocr_max_workers() -> i32 {
     1
 }
 
+// External tools
+fn default_tool_timeout_s() -> i32 {
+    0
+}
+fn default_tool_retry_count() -> i32 {
+    2
+}
+fn default_tool_retry_backoff_ms() -> i32 {
+    1000
+}
+
+// Disk space
+fn default_preflight_space_margin_pct() -> f64 {
+    10.0
+}
+fn default_temp_retention_hours() -> f64 {
+    24.0
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         // Use TOML round-trip to ensure serde defaults are applied consistently
@@ -680,6 +862,8 @@ impl AppSettings {
             "last_sec_path",
             "last_ter_path",
             "source_separation_model_dir",
+            "output_name_template",
+            "output_collision",
             "analysis_mode",
             "analysis_lang_source1",
             "analysis_lang_others",
@@ -699,11 +883,24 @@ impl AppSettings {
             "snap_mode",
             "snap_threshold_ms",
             "snap_starts_only",
+            "generate_chapters",
+            "chapter_gen_min_theme_s",
+            "chapter_gen_silence_db",
+            "linked_timeline_mode",
             "apply_dialog_norm_gain",
             "disable_track_statistics_tags",
             "disable_header_compression",
+            "font_auto_attach",
+            "attachments_referenced_fonts_only",
             "post_mux_normalize_timestamps",
+            "post_mux_finalizer",
             "post_mux_strip_tags",
+            "post_mux_sync_verify",
+            "post_mux_sync_threshold_ms",
+            "post_mux_sync_hop_s",
+            "audit_overrides",
+            "audit_junit_export",
+            "write_provenance_tags",
             "log_compact",
             "log_autoscroll",
             "log_error_tail",
@@ -713,6 +910,7 @@ impl AppSettings {
             "log_show_options_json",
             "log_audio_drift",
             "archive_logs",
+            "log_event_stream",
             "auto_apply_strict",
             "sync_mode",
             "stepping_enabled",
@@ -778,6 +976,9 @@ impl AppSettings {
             "drift_detection_r2_threshold_lossless",
             "drift_detection_slope_threshold_lossy",
             "drift_detection_slope_threshold_lossless",
+            "speed_correction_mode",
+            "speed_correction_snap_tolerance_ms_s",
+            "speed_correction_pitch_correct",
             "stepping_adjust_subtitles",
             "stepping_adjust_subtitles_no_audio",
             "stepping_boundary_mode",
@@ -793,6 +994,11 @@ impl AppSettings {
             "stepping_fusion_weight_duration",
             "stepping_snap_to_video_frames",
             "stepping_video_snap_max_offset_s",
+            "stepping_crossfade_enabled",
+            "stepping_crossfade_ms",
+            "stepping_gap_fill",
+            "stepping_room_tone_max_db",
+            "stepping_export_edl",
             "stepping_corrected_track_label",
             "stepping_preserved_track_label",
             "stepping_correction_mode",
@@ -815,6 +1021,9 @@ impl AppSettings {
             "segment_rb_transients",
             "segment_rb_smoother",
             "segment_rb_pitchq",
+            "corrected_audio_encode",
+            "corrected_audio_encode_overrides",
+            "corrected_audio_bitrate_kbps",
             "ocr_enabled",
             "ocr_engine",
             "ocr_language",
@@ -843,6 +1052,17 @@ impl AppSettings {
             "ocr_font_size_ratio",
             "ocr_generate_report",
             "ocr_max_workers",
+            "tool_timeout_s",
+            "tool_timeout_overrides",
+            "tool_retry_count",
+            "tool_retry_backoff_ms",
+            "tool_retry_warnings",
+            "tool_nice_level",
+            "tool_ionice_class",
+            "tool_memory_limit_mb",
+            "preflight_disk_check",
+            "preflight_space_margin_pct",
+            "temp_retention_hours",
         ]
     }
 }
@@ -894,6 +1114,11 @@ mod tests {
         assert_eq!(s.rename_chapters, false);
         assert_eq!(s.snap_mode, SnapMode::Previous);
         assert_eq!(s.snap_threshold_ms, 250);
+        assert!(!s.generate_chapters);
+        assert_eq!(s.chapter_gen_min_theme_s, 30.0);
+
+        // Post-mux checks are opt-in
+        assert!(!s.post_mux_sync_verify);
 
         // OCR defaults
         assert_eq!(s.ocr_engine, OcrEngine::Tesseract);
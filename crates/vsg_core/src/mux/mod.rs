@@ -1 +1,2 @@
 pub mod options_builder;
+pub mod provenance;
@@ -174,6 +174,49 @@ impl MkvmergeOptionsBuilder {
 
         Ok(tokens)
     }
+
+    /// Shift every `--sync` offset so the earliest track starts at zero.
+    ///
+    /// This is the rebase FFmpeg's `-avoid_negative_ts make_zero` would
+    /// apply afterwards, done in the mux itself so no second pass is
+    /// needed. Chapters get the same shift through `--chapter-sync`.
+    /// Returns the amount (ms) subtracted.
+    pub fn fold_rebase(tokens: &mut Vec<String>) -> i32 {
+        let sync_positions: Vec<usize> = tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| *t == "--sync")
+            .map(|(i, _)| i + 1)
+            .filter(|&i| i < tokens.len())
+            .collect();
+        let offsets: Vec<Option<i32>> = sync_positions
+            .iter()
+            .map(|&i| parse_sync_offset(&tokens[i]))
+            .collect();
+
+        let rebase = match offsets.iter().flatten().min() {
+            Some(&min) if min > 0 && offsets.iter().all(Option::is_some) => min,
+            _ => return 0,
+        };
+        for (&i, offset) in sync_positions.iter().zip(&offsets) {
+            if let Some(offset) = offset {
+                tokens[i] = format!("0:{:+}", offset - rebase);
+            }
+        }
+        if let Some(i) = tokens.iter().position(|t| t == "--chapters") {
+            tokens.splice(i..i, ["--chapter-sync".to_string(), format!("-{rebase}")]);
+        }
+        rebase
+    }
+}
+
+/// Offset (ms) of a `0:+120` style `--sync` argument.
+pub(crate) fn parse_sync_offset(arg: &str) -> Option<i32> {
+    let (tid, rest) = arg.split_once(':')?;
+    if tid != "0" || rest.contains([',', '/']) {
+        return None;
+    }
+    rest.parse().ok()
 }
 
 /// Find index of first matching item — `_first_index`
@@ -262,6 +305,7 @@ mod tests {
                 codec_id: codec.to_string(),
                 lang: "eng".to_string(),
                 name: String::new(),
+                audio_channels: None,
             },
         }
     }
@@ -283,6 +327,7 @@ mod tests {
             is_preserved: false,
             is_corrected: false,
             correction_source: None,
+            correction_transcode: None,
             perform_ocr: false,
             container_delay_ms: 0,
             custom_lang: String::new(),
@@ -395,4 +440,21 @@ mod tests {
         );
         assert_eq!(effective_delay_ms(&plan, &item), -300);
     }
+
+    #[test]
+    fn fold_rebase_moves_earliest_track_to_zero() {
+        let mut tokens: Vec<String> = "--chapters ch.xml --sync 0:+120 ( a ) --sync 0:+80"
+            .split(' ')
+            .map(String::from)
+            .collect();
+        assert_eq!(MkvmergeOptionsBuilder::fold_rebase(&mut tokens), 80);
+        let chapters = ["--chapter-sync", "-80", "--chapters", "ch.xml"];
+        assert_eq!(tokens[..4], chapters);
+        assert_eq!(tokens[5], "0:+40");
+        assert_eq!(tokens[10], "0:+0");
+
+        // A track already at zero (or earlier) leaves nothing to fold
+        let mut tokens = vec!["--sync".to_string(), "0:+0".to_string()];
+        assert_eq!(MkvmergeOptionsBuilder::fold_rebase(&mut tokens), 0);
+    }
 }
@@ -0,0 +1,629 @@
+//! Sync provenance tags.
+//!
+//! Records in the output MKV which sources were merged, the delays and
+//! corrections applied and which tool version and settings did it, as a
+//! global tag plus one tag per track, written onto the finished file with
+//! mkvpropedit.
+//! `read_provenance` parses them back so a later re-sync or audit can start
+//! from what was actually done.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use chrono::Local;
+use quick_xml::escape::{escape, resolve_predefined_entity};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use sha2::{Digest, Sha256};
+
+use crate::io::runner::{CommandRunner, RunError};
+use crate::models::context_types::{SourceProvenance, SyncProvenance, TrackProvenance};
+use crate::models::jobs::PlanItem;
+use crate::models::settings::AppSettings;
+use crate::orchestrator::steps::context::Context;
+
+use super::options_builder::parse_sync_offset;
+
+/// Bytes hashed from each end of a source file.
+const HASH_CHUNK: u64 = 1024 * 1024;
+
+/// Build the provenance for this job from its mkvmerge tokens.
+pub fn build_provenance(ctx: &Context, tokens: &[String]) -> SyncProvenance {
+    let items = ctx.extracted_items.as_deref().unwrap_or(&[]);
+    let delays = ctx.delays.clone().unwrap_or_default();
+
+    let mut source_keys: Vec<&String> = ctx.sources.keys().collect();
+    source_keys.sort();
+    let sources = source_keys
+        .into_iter()
+        .map(|key| {
+            let path = Path::new(&ctx.sources[key]);
+            SourceProvenance {
+                key: key.clone(),
+                file_name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                hash: source_hash(path).unwrap_or_default(),
+                delay_ms: delays.source_delays_ms.get(key).copied().unwrap_or(0),
+                raw_delay_ms: delays.raw_source_delays_ms.get(key).copied().unwrap_or(0.0),
+            }
+        })
+        .collect();
+
+    let settings_json = serde_json::to_string(&ctx.settings).unwrap_or_default();
+    let mut provenance = SyncProvenance {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Local::now().to_rfc3339(),
+        sync_mode: ctx.sync_mode.clone(),
+        global_shift_ms: delays.global_shift_ms,
+        timestamp_rebase_ms: ctx.timestamp_rebase_ms,
+        settings_profile: format!("{:x}", Sha256::digest(settings_json.as_bytes()))[..16]
+            .to_string(),
+        sources,
+        tracks: Vec::new(),
+    };
+
+    // Each track is "<options> ( path )", in output track order
+    let mut group_start = 0;
+    let mut index = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token == ")" {
+            group_start = i + 1;
+        }
+        if token != "(" {
+            continue;
+        }
+        index += 1;
+        let Some(path) = tokens.get(i + 1) else {
+            continue;
+        };
+        let Some(item) = items.iter().find(|it| {
+            it.extracted_path
+                .as_ref()
+                .is_some_and(|p| p.to_string_lossy() == path.as_str())
+        }) else {
+            continue;
+        };
+        let applied_delay_ms = tokens[group_start..i]
+            .windows(2)
+            .find(|w| w[0] == "--sync")
+            .and_then(|w| parse_sync_offset(&w[1]))
+            .unwrap_or(0);
+
+        provenance.tracks.push(TrackProvenance {
+            index: index - 1,
+            source: item.track.source.clone(),
+            source_track_id: item.track.id,
+            applied_delay_ms,
+            correction: correction_type(ctx, item).map(String::from),
+        });
+    }
+
+    provenance
+}
+
+/// Tag a finished MKV with `provenance` (mkvpropedit `--tags global:` and
+/// `--tags track:`).
+///
+/// Runs on the final file: an FFmpeg remux flattens nested tags and
+/// `post_mux_strip_tags` deletes all of them, so both happen first.
+/// Replacing a track's tags drops its statistics tags, which are
+/// regenerated afterwards unless they are disabled.
+pub fn write_provenance(
+    mkv_path: &Path,
+    provenance: &SyncProvenance,
+    temp_dir: &Path,
+    settings: &AppSettings,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<(), String> {
+    let write_tags = |name: String, xml: String| {
+        let path = temp_dir.join(name);
+        std::fs::write(&path, xml)
+            .map(|()| path.to_string_lossy().to_string())
+            .map_err(|e| format!("Failed to write provenance tags: {e}"))
+    };
+    let mkv = mkv_path.to_string_lossy().to_string();
+    let global = write_tags(
+        "provenance_global.xml".to_string(),
+        global_tags_xml(provenance),
+    )?;
+    let mut args = vec![
+        "mkvpropedit".to_string(),
+        mkv.clone(),
+        "--tags".to_string(),
+        format!("global:{global}"),
+    ];
+    for track in &provenance.tracks {
+        let path = write_tags(
+            format!("provenance_track_{}.xml", track.index),
+            track_tags_xml(track),
+        )?;
+        args.push("--tags".to_string());
+        args.push(format!("track:{}:{path}", track.index + 1));
+    }
+
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    accept_warnings(runner.run(&arg_refs, tool_paths))
+        .map_err(|e| format!("Could not write provenance tags: {e}"))?;
+    if !settings.disable_track_statistics_tags {
+        accept_warnings(runner.run(
+            &["mkvpropedit", &mkv, "--add-track-statistics-tags"],
+            tool_paths,
+        ))
+        .map_err(|e| format!("Could not restore track statistics tags: {e}"))?;
+    }
+    Ok(())
+}
+
+/// mkvpropedit still applied its changes when it only warned.
+fn accept_warnings(result: Result<String, RunError>) -> Result<(), RunError> {
+    match result {
+        Ok(_) | Err(RunError::Warnings { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read the provenance tags back from a merged MKV.
+///
+/// `Ok(None)` when the file carries no provenance.
+pub fn read_provenance(
+    mkv_path: &Path,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Result<Option<SyncProvenance>, String> {
+    let out = runner
+        .run(
+            &["mkvextract", &mkv_path.to_string_lossy(), "tags", "-"],
+            tool_paths,
+        )
+        .map_err(|e| format!("Could not extract tags from {}: {e}", mkv_path.display()))?;
+    parse_provenance(&out)
+}
+
+/// Parse provenance from Matroska tags XML.
+pub fn parse_provenance(tags_xml: &str) -> Result<Option<SyncProvenance>, String> {
+    let mut provenance: Option<SyncProvenance> = None;
+    let mut tracks = Vec::new();
+
+    for tag in parse_tags(tags_xml)? {
+        let value = |name: &str| tag.iter().find(|s| s.name == name).map(|s| s.value.clone());
+        if let Some(tool_version) = value("VSG_VERSION") {
+            let sources = tag
+                .iter()
+                .filter(|s| s.name == "VSG_SOURCE")
+                .map(|s| {
+                    let child = |name: &str| {
+                        s.children
+                            .iter()
+                            .find(|c| c.name == name)
+                            .map(|c| c.value.clone())
+                            .unwrap_or_default()
+                    };
+                    SourceProvenance {
+                        key: s.value.clone(),
+                        file_name: child("FILENAME"),
+                        hash: child("HASH"),
+                        delay_ms: child("DELAY_MS").parse().unwrap_or(0),
+                        raw_delay_ms: child("RAW_DELAY_MS").parse().unwrap_or(0.0),
+                    }
+                })
+                .collect();
+            provenance = Some(SyncProvenance {
+                tool_version,
+                created_at: value("VSG_CREATED").unwrap_or_default(),
+                sync_mode: value("VSG_SYNC_MODE").unwrap_or_default(),
+                global_shift_ms: value("VSG_GLOBAL_SHIFT_MS")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                timestamp_rebase_ms: value("VSG_TIMESTAMP_REBASE_MS")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                settings_profile: value("VSG_SETTINGS_PROFILE").unwrap_or_default(),
+                sources,
+                tracks: Vec::new(),
+            });
+        } else if let Some(index) = value("VSG_TRACK_INDEX").and_then(|v| v.parse().ok()) {
+            tracks.push(TrackProvenance {
+                index,
+                source: value("VSG_SOURCE").unwrap_or_default(),
+                source_track_id: value("VSG_SOURCE_TRACK_ID")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                applied_delay_ms: value("VSG_APPLIED_DELAY_MS")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                correction: value("VSG_CORRECTION"),
+            });
+        }
+    }
+
+    Ok(provenance.map(|mut p| {
+        tracks.sort_by_key(|t| t.index);
+        p.tracks = tracks;
+        p
+    }))
+}
+
+/// Correction applied to a track's audio, from the analysis flags.
+///
+/// Flags are keyed by the analysis track (`Source N_id`) and the correction
+/// steps apply them to every audio track of that source, in this order.
+fn correction_type(ctx: &Context, item: &PlanItem) -> Option<&'static str> {
+    if !item.is_corrected {
+        return None;
+    }
+    let source = &item.track.source;
+    if flags_source(&ctx.pal_drift_flags, source) {
+        Some("pal")
+    } else if flags_source(&ctx.speed_ratio_flags, source) {
+        Some("speed")
+    } else if flags_source(&ctx.linear_drift_flags, source) {
+        Some("linear")
+    } else if flags_source(&ctx.segment_flags, source) {
+        Some("stepping")
+    } else {
+        None
+    }
+}
+
+/// Whether any analysis flag belongs to `source`.
+fn flags_source<V>(flags: &HashMap<String, V>, source: &str) -> bool {
+    flags
+        .keys()
+        .any(|key| key.split('_').next() == Some(source))
+}
+
+/// SHA-256 over the file size and its first and last MiB.
+///
+/// Hashing whole remuxes would take longer than the sync itself; size plus
+/// both ends is enough to tell whether a source is the same file.
+pub(crate) fn source_hash(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+    let mut chunk = Vec::with_capacity(HASH_CHUNK as usize);
+    (&mut file)
+        .take(HASH_CHUNK)
+        .read_to_end(&mut chunk)
+        .map_err(|e| e.to_string())?;
+    hasher.update(&chunk);
+    if size > HASH_CHUNK {
+        chunk.clear();
+        file.seek(SeekFrom::Start(
+            size.saturating_sub(HASH_CHUNK).max(HASH_CHUNK),
+        ))
+        .map_err(|e| e.to_string())?;
+        file.read_to_end(&mut chunk).map_err(|e| e.to_string())?;
+        hasher.update(&chunk);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn global_tags_xml(p: &SyncProvenance) -> String {
+    let mut simple = vec![
+        simple_tag("VSG_VERSION", &p.tool_version, ""),
+        simple_tag("VSG_CREATED", &p.created_at, ""),
+        simple_tag("VSG_SYNC_MODE", &p.sync_mode, ""),
+        simple_tag("VSG_GLOBAL_SHIFT_MS", &p.global_shift_ms.to_string(), ""),
+        simple_tag(
+            "VSG_TIMESTAMP_REBASE_MS",
+            &p.timestamp_rebase_ms.to_string(),
+            "",
+        ),
+        simple_tag("VSG_SETTINGS_PROFILE", &p.settings_profile, ""),
+    ];
+    for source in &p.sources {
+        let children = [
+            simple_tag("FILENAME", &source.file_name, ""),
+            simple_tag("HASH", &source.hash, ""),
+            simple_tag("DELAY_MS", &source.delay_ms.to_string(), ""),
+            simple_tag("RAW_DELAY_MS", &source.raw_delay_ms.to_string(), ""),
+        ]
+        .concat();
+        simple.push(simple_tag("VSG_SOURCE", &source.key, &children));
+    }
+    tags_document(&simple.concat())
+}
+
+fn track_tags_xml(t: &TrackProvenance) -> String {
+    let mut simple = vec![
+        simple_tag("VSG_TRACK_INDEX", &t.index.to_string(), ""),
+        simple_tag("VSG_SOURCE", &t.source, ""),
+        simple_tag("VSG_SOURCE_TRACK_ID", &t.source_track_id.to_string(), ""),
+        simple_tag("VSG_APPLIED_DELAY_MS", &t.applied_delay_ms.to_string(), ""),
+    ];
+    if let Some(correction) = &t.correction {
+        simple.push(simple_tag("VSG_CORRECTION", correction, ""));
+    }
+    tags_document(&simple.concat())
+}
+
+fn simple_tag(name: &str, value: &str, children: &str) -> String {
+    format!(
+        "<Simple><Name>{name}</Name><String>{}</String>{children}</Simple>",
+        escape(value)
+    )
+}
+
+fn tags_document(simple: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE Tags SYSTEM \"matroskatags.dtd\">\n\
+         <Tags><Tag><Targets><TargetTypeValue>50</TargetTypeValue></Targets>{simple}</Tag></Tags>\n"
+    )
+}
+
+/// A `<Simple>` tag with its nested tags.
+#[derive(Debug, Default)]
+struct SimpleTag {
+    name: String,
+    value: String,
+    children: Vec<SimpleTag>,
+}
+
+/// Top-level `<Simple>` tags of every `<Tag>` in the document.
+fn parse_tags(xml: &str) -> Result<Vec<Vec<SimpleTag>>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut tags: Vec<Vec<SimpleTag>> = Vec::new();
+    // Open <Simple> elements, innermost last
+    let mut stack: Vec<SimpleTag> = Vec::new();
+    let mut field: Option<&'static str> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"Tag" => tags.push(Vec::new()),
+                b"Simple" => stack.push(SimpleTag::default()),
+                b"Name" => field = Some("name"),
+                b"String" => field = Some("value"),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) => {
+                let text = e.decode().map_err(|e| e.to_string())?;
+                push_text(&mut stack, field, &text);
+            }
+            Ok(Event::GeneralRef(ref e)) => {
+                let name = e.decode().map_err(|e| e.to_string())?;
+                let resolved = match e.resolve_char_ref() {
+                    Ok(Some(ch)) => ch.to_string(),
+                    _ => resolve_predefined_entity(&name)
+                        .unwrap_or_default()
+                        .to_string(),
+                };
+                push_text(&mut stack, field, &resolved);
+            }
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"Name" | b"String" => field = None,
+                b"Simple" => {
+                    let Some(done) = stack.pop() else {
+                        continue;
+                    };
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(done),
+                        None => {
+                            if let Some(tag) = tags.last_mut() {
+                                tag.push(done);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Invalid tags XML: {e}")),
+            _ => {}
+        }
+    }
+    Ok(tags)
+}
+
+fn push_text(stack: &mut [SimpleTag], field: Option<&str>, text: &str) {
+    if let Some(current) = stack.last_mut() {
+        match field {
+            Some("name") => current.name.push_str(text),
+            Some("value") => current.value.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::context_types::DriftFlagsEntry;
+    use crate::models::enums::TimestampFinalizer;
+    use crate::models::events::noop_event_callback;
+
+    fn plan_item(source: &str, id: i32, is_corrected: bool) -> PlanItem {
+        serde_json::from_value(serde_json::json!({
+            "track": {"source": source, "id": id, "type": "audio",
+                      "props": {"codec_id": "A_AC3", "lang": "eng"}},
+            "is_corrected": is_corrected,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn correction_type_matches_flags_by_source() {
+        let mut ctx = Context::new(
+            AppSettings::default(),
+            HashMap::new(),
+            Box::new(|_: &str| {}),
+            Box::new(|_| {}),
+            noop_event_callback(),
+            String::new(),
+            std::env::temp_dir(),
+            HashMap::new(),
+            true,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            HashMap::new(),
+        );
+        // Flagged on the analysis track, applied to another track of the source
+        ctx.linear_drift_flags
+            .insert("Source 2_1".to_string(), DriftFlagsEntry::default());
+
+        assert_eq!(
+            correction_type(&ctx, &plan_item("Source 2", 3, true)),
+            Some("linear")
+        );
+        assert_eq!(
+            correction_type(&ctx, &plan_item("Source 2", 3, false)),
+            None
+        );
+        assert_eq!(correction_type(&ctx, &plan_item("Source 3", 1, true)), None);
+    }
+
+    #[test]
+    fn provenance_round_trips_through_tags_xml() {
+        let provenance = SyncProvenance {
+            tool_version: "1.2.3".to_string(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            sync_mode: "positive_only".to_string(),
+            global_shift_ms: 150,
+            timestamp_rebase_ms: 40,
+            settings_profile: "0123456789abcdef".to_string(),
+            sources: vec![SourceProvenance {
+                key: "Source 2".to_string(),
+                file_name: "Show & Tell <JP>.mkv".to_string(),
+                hash: "ab12".to_string(),
+                delay_ms: -150,
+                raw_delay_ms: -150.25,
+            }],
+            tracks: vec![TrackProvenance {
+                index: 1,
+                source: "Source 2".to_string(),
+                source_track_id: 2,
+                applied_delay_ms: 0,
+                correction: Some("stepping".to_string()),
+            }],
+        };
+
+        // mkvextract returns every tag in one document, statistics included
+        let global = global_tags_xml(&provenance);
+        let track = track_tags_xml(&provenance.tracks[0]);
+        let stats = "<Tag><Targets><TrackUID>1</TrackUID></Targets>\
+                     <Simple><Name>BPS</Name><String>9000</String></Simple></Tag>";
+        let body = |doc: &str| {
+            let start = doc.find("<Tag>").unwrap();
+            let end = doc.rfind("</Tags>").unwrap();
+            doc[start..end].to_string()
+        };
+        let combined = format!("<Tags>{}{stats}{}</Tags>", body(&global), body(&track));
+
+        assert_eq!(parse_provenance(&combined).unwrap(), Some(provenance));
+        assert_eq!(
+            parse_provenance(&format!("<Tags>{stats}</Tags>")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn provenance_survives_ffmpeg_finalize_and_tag_stripping() {
+        use crate::pipeline_components::sync_executor::SyncExecutor;
+
+        // Needs the real tools; nothing to check without them
+        let tools = ["ffmpeg", "ffprobe", "mkvmerge", "mkvpropedit", "mkvextract"];
+        if tools.iter().any(|t| which::which(t).is_err()) {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source.mkv");
+        let merged = dir.path().join("merged.mkv");
+        let output = dir.path().join("output.mkv");
+        let path = |p: &Path| p.to_string_lossy().to_string();
+
+        let status = std::process::Command::new("ffmpeg")
+            .args([
+                "-v",
+                "error",
+                "-f",
+                "lavfi",
+                "-i",
+                "testsrc=d=1:s=64x48:r=10",
+            ])
+            .args([
+                "-f", "lavfi", "-i", "sine=d=1", "-c:v", "mpeg4", "-c:a", "flac",
+            ])
+            .arg(path(&source))
+            .status()
+            .unwrap();
+        assert!(status.success(), "ffmpeg could not create the test source");
+        // A delayed video track makes the FFmpeg finalizer rebase
+        let status = std::process::Command::new("mkvmerge")
+            .args([
+                "-q",
+                "-o",
+                &path(&merged),
+                "--sync",
+                "0:500",
+                &path(&source),
+            ])
+            .status()
+            .unwrap();
+        assert!(status.success(), "mkvmerge could not create the test merge");
+
+        let settings = AppSettings {
+            post_mux_normalize_timestamps: true,
+            post_mux_finalizer: TimestampFinalizer::Ffmpeg,
+            post_mux_strip_tags: true,
+            ..AppSettings::default()
+        };
+        let runner = CommandRunner::new(settings.clone(), Box::new(|_: &str| {}));
+        let tool_paths = HashMap::new();
+        SyncExecutor::finalize_output(&merged, &output, &settings, &tool_paths, &runner).unwrap();
+
+        let provenance = SyncProvenance {
+            tool_version: "1.2.3".to_string(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            sync_mode: "positive_only".to_string(),
+            global_shift_ms: 0,
+            timestamp_rebase_ms: 0,
+            settings_profile: "0123456789abcdef".to_string(),
+            sources: vec![SourceProvenance {
+                key: "Source 1".to_string(),
+                file_name: "source.mkv".to_string(),
+                hash: "ab12".to_string(),
+                delay_ms: 0,
+                raw_delay_ms: 0.0,
+            }],
+            tracks: vec![
+                TrackProvenance {
+                    index: 0,
+                    source: "Source 1".to_string(),
+                    source_track_id: 0,
+                    applied_delay_ms: 500,
+                    correction: None,
+                },
+                TrackProvenance {
+                    index: 1,
+                    source: "Source 1".to_string(),
+                    source_track_id: 1,
+                    applied_delay_ms: 0,
+                    correction: Some("linear".to_string()),
+                },
+            ],
+        };
+        write_provenance(
+            &output,
+            &provenance,
+            dir.path(),
+            &settings,
+            &runner,
+            &tool_paths,
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_provenance(&output, &runner, &tool_paths).unwrap(),
+            Some(provenance)
+        );
+    }
+}
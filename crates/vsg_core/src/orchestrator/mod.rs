@@ -1,3 +1,4 @@
 pub mod pipeline;
+pub mod preflight;
 pub mod steps;
 pub mod validation;
@@ -4,9 +4,12 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use crate::models::context_types::ManualLayoutItem;
+use crate::audit::trail::AuditTrail;
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::{ManualLayoutItem, ToolCapabilities};
+use crate::models::events::{EventCallback, JobEvent};
 use crate::models::settings::AppSettings;
 
 use super::steps::analysis_step::AnalysisStep;
@@ -15,7 +18,9 @@ use super::steps::audio_correction_step::AudioCorrectionStep;
 use super::steps::chapters_step::ChaptersStep;
 use super::steps::context::Context;
 use super::steps::extract_step::ExtractStep;
+use super::steps::linked_timeline_step::LinkedTimelineStep;
 use super::steps::mux_step::MuxStep;
+use super::preflight::check_disk_space;
 use super::steps::subtitles_step::SubtitlesStep;
 use super::validation::StepValidator;
 
@@ -29,13 +34,16 @@ impl Orchestrator {
         &self,
         settings: &AppSettings,
         tool_paths: &HashMap<String, String>,
+        tools: &ToolCapabilities,
         log: Box<dyn Fn(&str) + Send + Sync>,
         progress: Box<dyn Fn(f64) + Send + Sync>,
+        events: EventCallback,
         sources: &HashMap<String, String>,
         and_merge: bool,
         output_dir: &str,
         manual_layout: Vec<ManualLayoutItem>,
         attachment_sources: Vec<String>,
+        extra_attachments: Vec<String>,
         source_settings: HashMap<String, serde_json::Value>,
     ) -> Result<Context, String> {
         let source1_file = sources
@@ -66,120 +74,190 @@ impl Orchestrator {
             tool_paths.clone(),
             log,
             progress,
+            events,
             output_dir.to_string(),
             job_temp,
             sources.clone(),
             and_merge,
             manual_layout,
             attachment_sources,
+            extra_attachments,
             source_settings,
         );
 
-        // Helper: create a runner from current settings
-        // Note: Steps also use ctx.log directly for important messages
-        let make_runner = |settings: &AppSettings| -> crate::io::runner::CommandRunner {
-            crate::io::runner::CommandRunner::new(
-                settings.clone(),
-                Box::new(|_msg: &str| {}),
-            )
-        };
-
-        // --- Analysis Phase ---
-        (ctx.log)("--- Analysis Phase ---");
-        (ctx.progress)(0.10);
-        {
-            let runner = make_runner(&ctx.settings);
-            AnalysisStep.run(&mut ctx, &runner)
-                .map_err(|e| format!("Analysis phase failed: {e}"))?;
-        }
-        StepValidator::validate_analysis(&ctx)
-            .map_err(|e| format!("Analysis validation failed: {e}"))?;
-        (ctx.log)("[Validation] Analysis phase validated successfully.");
-
-        if !and_merge {
-            (ctx.log)("--- Analysis Complete (No Merge) ---");
-            (ctx.progress)(1.0);
-            return Ok(ctx);
+        let mut audit = AuditTrail::new(Path::new(output_dir), &ctx.temp_dir, &stem);
+        audit.record_tools(tools);
+        let mut source_keys: Vec<&String> = sources.keys().collect();
+        source_keys.sort();
+        for key in source_keys {
+            audit.record_source(key, &sources[key]);
         }
+        ctx.audit = Some(audit);
 
-        // --- Extraction Phase ---
-        (ctx.log)("--- Extraction Phase ---");
-        (ctx.progress)(0.40);
-        {
-            let runner = make_runner(&ctx.settings);
-            ExtractStep.run(&mut ctx, &runner)
-                .map_err(|e| format!("Extraction phase failed: {e}"))?;
-        }
-        StepValidator::validate_extraction(&ctx)
-            .map_err(|e| format!("Extraction validation failed: {e}"))?;
-        (ctx.log)("[Validation] Extraction phase validated successfully.");
-
-        // --- Audio Correction Phase (conditional) ---
-        if ctx.settings.stepping_enabled
-            && (!ctx.segment_flags.is_empty()
-                || !ctx.pal_drift_flags.is_empty()
-                || !ctx.linear_drift_flags.is_empty())
-        {
-            (ctx.log)("--- Advanced Audio Correction Phase ---");
-            (ctx.progress)(0.50);
-            {
-                let runner = make_runner(&ctx.settings);
-                AudioCorrectionStep.run(&mut ctx, &runner)
-                    .map_err(|e| format!("Audio correction phase failed: {e}"))?;
+        match run_phases(&mut ctx, and_merge) {
+            Ok(()) => Ok(ctx),
+            Err(e) => {
+                if let Some(audit) = ctx.audit.as_mut() {
+                    audit.append_event("error", &e, None);
+                    audit.finalize(None, false);
+                }
+                Err(e)
             }
-            StepValidator::validate_correction(&ctx)
-                .map_err(|e| format!("Audio correction validation failed: {e}"))?;
-            (ctx.log)("[Validation] Audio correction phase validated successfully.");
         }
+    }
+}
 
-        // --- Subtitle Processing Phase ---
-        (ctx.log)("--- Subtitle Processing Phase ---");
-        {
-            let runner = make_runner(&ctx.settings);
-            SubtitlesStep.run(&mut ctx, &runner)
-                .map_err(|e| format!("Subtitle processing phase failed: {e}"))?;
-        }
-        StepValidator::validate_subtitles(&ctx)
-            .map_err(|e| format!("Subtitle processing validation failed: {e}"))?;
-        (ctx.log)("[Validation] Subtitle processing phase validated successfully.");
+/// Runs the phases in order, stopping at the first fatal failure.
+fn run_phases(ctx: &mut Context, and_merge: bool) -> Result<(), String> {
+    // Helper: create a runner from current settings
+    // Note: Steps also use ctx.log directly for important messages
+    let make_runner = |ctx: &Context, step: &str| -> CommandRunner {
+        let mut runner = CommandRunner::new(
+            ctx.settings.clone(),
+            Box::new(|_msg: &str| {}),
+        );
+        runner.set_event_callback(ctx.events.clone());
+        runner.set_step(step);
+        runner
+    };
 
-        // --- Chapters Phase (non-fatal) ---
-        (ctx.log)("--- Chapters Phase ---");
-        {
-            let runner = make_runner(&ctx.settings);
-            if let Err(e) = ChaptersStep.run(&mut ctx, &runner) {
-                (ctx.log)(&format!("[WARNING] Chapters phase had issues (non-fatal): {e}"));
-            } else {
-                (ctx.log)("[Validation] Chapters phase completed.");
-            }
-        }
+    // --- Linked Timeline Phase ---
+    {
+        let runner = make_runner(ctx, "linked_timeline");
+        run_step(ctx, "linked_timeline", |ctx| {
+            LinkedTimelineStep.run(ctx, &runner)
+        })
+        .map_err(|e| format!("Linked timeline phase failed: {e}"))?;
+    }
+
+    // --- Analysis Phase ---
+    (ctx.log)("--- Analysis Phase ---");
+    (ctx.progress)(0.10);
+    {
+        let runner = make_runner(ctx, "analysis");
+        run_step(ctx, "analysis", |ctx| AnalysisStep.run(ctx, &runner))
+            .map_err(|e| format!("Analysis phase failed: {e}"))?;
+    }
+    StepValidator::validate_analysis(ctx)
+        .map_err(|e| format!("Analysis validation failed: {e}"))?;
+    (ctx.log)("[Validation] Analysis phase validated successfully.");
+
+    if !and_merge {
+        (ctx.log)("--- Analysis Complete (No Merge) ---");
+        (ctx.progress)(1.0);
+        return Ok(());
+    }
+
+    // --- Preflight Disk Space Check ---
+    {
+        let runner = make_runner(ctx, "preflight");
+        run_step(ctx, "preflight", |ctx| check_disk_space(ctx, &runner))
+            .map_err(|e| format!("Preflight check failed: {e}"))?;
+    }
+
+    // --- Extraction Phase ---
+    (ctx.log)("--- Extraction Phase ---");
+    (ctx.progress)(0.40);
+    {
+        let runner = make_runner(ctx, "extraction");
+        run_step(ctx, "extraction", |ctx| ExtractStep.run(ctx, &runner))
+            .map_err(|e| format!("Extraction phase failed: {e}"))?;
+    }
+    StepValidator::validate_extraction(ctx)
+        .map_err(|e| format!("Extraction validation failed: {e}"))?;
+    (ctx.log)("[Validation] Extraction phase validated successfully.");
 
-        // --- Attachments Phase (non-fatal) ---
-        (ctx.log)("--- Attachments Phase ---");
-        (ctx.progress)(0.60);
+    // --- Audio Correction Phase (conditional) ---
+    if AudioCorrectionStep::is_enabled(&ctx.settings)
+        && (!ctx.segment_flags.is_empty()
+            || !ctx.pal_drift_flags.is_empty()
+            || !ctx.speed_ratio_flags.is_empty()
+            || !ctx.linear_drift_flags.is_empty())
+    {
+        (ctx.log)("--- Advanced Audio Correction Phase ---");
+        (ctx.progress)(0.50);
         {
-            let runner = make_runner(&ctx.settings);
-            if let Err(e) = AttachmentsStep.run(&mut ctx, &runner) {
-                (ctx.log)(&format!("[WARNING] Attachments phase had issues (non-fatal): {e}"));
-            } else {
-                (ctx.log)("[Validation] Attachments phase completed.");
-            }
+            let runner = make_runner(ctx, "audio_correction");
+            run_step(ctx, "audio_correction", |ctx| {
+                AudioCorrectionStep.run(ctx, &runner)
+            })
+            .map_err(|e| format!("Audio correction phase failed: {e}"))?;
         }
+        StepValidator::validate_correction(ctx)
+            .map_err(|e| format!("Audio correction validation failed: {e}"))?;
+        (ctx.log)("[Validation] Audio correction phase validated successfully.");
+    }
 
-        // --- Merge Planning Phase ---
-        (ctx.log)("--- Merge Planning Phase ---");
-        (ctx.progress)(0.75);
-        {
-            let runner = make_runner(&ctx.settings);
-            MuxStep.run(&mut ctx, &runner)
-                .map_err(|e| format!("Merge planning phase failed: {e}"))?;
+    // --- Subtitle Processing Phase ---
+    (ctx.log)("--- Subtitle Processing Phase ---");
+    {
+        let runner = make_runner(ctx, "subtitles");
+        run_step(ctx, "subtitles", |ctx| SubtitlesStep.run(ctx, &runner))
+            .map_err(|e| format!("Subtitle processing phase failed: {e}"))?;
+    }
+    StepValidator::validate_subtitles(ctx)
+        .map_err(|e| format!("Subtitle processing validation failed: {e}"))?;
+    (ctx.log)("[Validation] Subtitle processing phase validated successfully.");
+
+    // --- Chapters Phase (non-fatal) ---
+    (ctx.log)("--- Chapters Phase ---");
+    {
+        let runner = make_runner(ctx, "chapters");
+        let result = run_step(ctx, "chapters", |ctx| ChaptersStep.run(ctx, &runner));
+        if let Err(e) = result {
+            (ctx.log)(&format!("[WARNING] Chapters phase had issues (non-fatal): {e}"));
+        } else {
+            (ctx.log)("[Validation] Chapters phase completed.");
         }
-        StepValidator::validate_mux(&ctx)
-            .map_err(|e| format!("Merge planning validation failed: {e}"))?;
-        (ctx.log)("[Validation] Merge planning phase validated successfully.");
+    }
 
-        (ctx.progress)(0.80);
+    // --- Attachments Phase (non-fatal) ---
+    (ctx.log)("--- Attachments Phase ---");
+    (ctx.progress)(0.60);
+    {
+        let runner = make_runner(ctx, "attachments");
+        let result =
+            run_step(ctx, "attachments", |ctx| AttachmentsStep.run(ctx, &runner));
+        if let Err(e) = result {
+            (ctx.log)(&format!("[WARNING] Attachments phase had issues (non-fatal): {e}"));
+        } else {
+            (ctx.log)("[Validation] Attachments phase completed.");
+        }
+    }
 
-        Ok(ctx)
+    // --- Merge Planning Phase ---
+    (ctx.log)("--- Merge Planning Phase ---");
+    (ctx.progress)(0.75);
+    {
+        let runner = make_runner(ctx, "mux");
+        run_step(ctx, "mux", |ctx| MuxStep.run(ctx, &runner))
+            .map_err(|e| format!("Merge planning phase failed: {e}"))?;
     }
+    StepValidator::validate_mux(ctx)
+        .map_err(|e| format!("Merge planning validation failed: {e}"))?;
+    (ctx.log)("[Validation] Merge planning phase validated successfully.");
+
+    (ctx.progress)(0.80);
+
+    Ok(())
+}
+
+/// Runs one step between `StepStarted`/`StepFinished` events.
+fn run_step(
+    ctx: &mut Context,
+    step: &str,
+    f: impl FnOnce(&mut Context) -> Result<(), String>,
+) -> Result<(), String> {
+    (ctx.events)(&JobEvent::StepStarted {
+        step: step.to_string(),
+    });
+    let started = Instant::now();
+    let result = f(ctx);
+    (ctx.events)(&JobEvent::StepFinished {
+        step: step.to_string(),
+        ok: result.is_ok(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        error: result.as_ref().err().cloned(),
+    });
+    result
 }
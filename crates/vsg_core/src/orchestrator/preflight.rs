@@ -0,0 +1,283 @@
+//! Preflight disk-space check.
+//!
+//! Runs after analysis (when the planned corrections are known) and before
+//! extraction: estimates the temp and output space the job needs from the
+//! track sizes reported by `mkvmerge -J`, compares it with the free space
+//! reported by `df`, and fails the job early when it won't fit.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::extraction::tracks::get_stream_info;
+use crate::io::runner::CommandRunner;
+
+use super::steps::context::Context;
+
+/// Bytes per sample assumed for decoded correction audio (24-bit PCM).
+const PCM_BYTES_PER_SAMPLE: f64 = 3.0;
+/// Typical FLAC size relative to the PCM it encodes.
+const FLAC_RATIO: f64 = 0.6;
+
+/// Estimated space a job needs, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpaceEstimate {
+    /// Extracted tracks plus correction intermediates under `temp_dir`.
+    pub temp_bytes: u64,
+    /// The final muxed file in the output directory.
+    pub output_bytes: u64,
+}
+
+/// Estimates the temp and output space for the job's layout and corrections.
+pub fn estimate_job_space(ctx: &Context, runner: &CommandRunner) -> SpaceEstimate {
+    let mut infos: HashMap<&str, (Value, HashMap<i32, u64>)> = HashMap::new();
+    for (source_key, path) in &ctx.sources {
+        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if let Some(info) = get_stream_info(path, runner, &ctx.tool_paths) {
+            let sizes = track_sizes(&info, file_size);
+            infos.insert(source_key.as_str(), (info, sizes));
+        }
+    }
+
+    let mut estimate = SpaceEstimate::default();
+
+    for item in &ctx.manual_layout {
+        let bytes = match (&item.original_path, item.source.as_deref(), item.id) {
+            (Some(path), _, _) => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+            (None, Some(source), Some(id)) => infos
+                .get(source)
+                .and_then(|(_, sizes)| sizes.get(&id).copied())
+                .unwrap_or(0),
+            _ => 0,
+        };
+        estimate.temp_bytes += bytes;
+        estimate.output_bytes += bytes;
+    }
+
+    let corrected_keys = ctx
+        .segment_flags
+        .keys()
+        .chain(ctx.pal_drift_flags.keys())
+        .chain(ctx.speed_ratio_flags.keys())
+        .chain(ctx.linear_drift_flags.keys());
+    for key in corrected_keys {
+        let Some((source, track_id)) = key.rsplit_once('_') else {
+            continue;
+        };
+        let Some((info, _)) = infos.get(source) else {
+            continue;
+        };
+        let pcm = track_id
+            .parse::<i32>()
+            .map(|id| decoded_pcm_bytes(info, id))
+            .unwrap_or(0);
+        let flac = (pcm as f64 * FLAC_RATIO) as u64;
+        // Decoded working copy plus the corrected FLAC in temp; FLAC is muxed.
+        estimate.temp_bytes += pcm + flac;
+        estimate.output_bytes += flac;
+    }
+
+    estimate
+}
+
+/// Fails when the temp or output filesystem lacks room for the job.
+pub fn check_disk_space(ctx: &Context, runner: &CommandRunner) -> Result<(), String> {
+    if !ctx.settings.preflight_disk_check {
+        return Ok(());
+    }
+
+    let estimate = estimate_job_space(ctx, runner);
+    let margin = 1.0 + ctx.settings.preflight_space_margin_pct.max(0.0) / 100.0;
+    let need_temp = (estimate.temp_bytes as f64 * margin) as u64;
+    let need_output = (estimate.output_bytes as f64 * margin) as u64;
+    (ctx.log)(&format!(
+        "[Preflight] Estimated space: temp {}, output {} (incl. {:.0}% margin)",
+        format_bytes(need_temp),
+        format_bytes(need_output),
+        (margin - 1.0) * 100.0
+    ));
+
+    let temp_free = free_space(&ctx.temp_dir, runner, &ctx.tool_paths);
+    let output_free = free_space(Path::new(&ctx.output_dir), runner, &ctx.tool_paths);
+
+    let mut checks: Vec<(&str, &Path, u64, u64)> = Vec::new();
+    match (&temp_free, &output_free) {
+        (Some((temp_avail, temp_mount)), Some((_, output_mount))) if temp_mount == output_mount => {
+            checks.push((
+                "temp/output",
+                &ctx.temp_dir,
+                need_temp + need_output,
+                *temp_avail,
+            ));
+        }
+        _ => {
+            if let Some((avail, _)) = temp_free {
+                checks.push(("temp", &ctx.temp_dir, need_temp, avail));
+            }
+            if let Some((avail, _)) = output_free {
+                checks.push(("output", Path::new(&ctx.output_dir), need_output, avail));
+            }
+        }
+    }
+    if temp_free.is_none() || output_free.is_none() {
+        (ctx.log)("[Preflight] Could not determine free space for every location; partial check.");
+    }
+
+    for (label, path, need, avail) in checks {
+        if need > avail {
+            return Err(format!(
+                "Not enough disk space in {label} location '{}': need ~{}, only {} free.",
+                path.display(),
+                format_bytes(need),
+                format_bytes(avail)
+            ));
+        }
+    }
+
+    (ctx.log)("[Preflight] Disk space OK.");
+    Ok(())
+}
+
+/// Bytes per track ID. Uses the `tag_number_of_bytes` statistics tag when
+/// present; tracks without it share whatever of the file size is left.
+fn track_sizes(info: &Value, file_size: u64) -> HashMap<i32, u64> {
+    let empty = vec![];
+    let tracks = info
+        .get("tracks")
+        .and_then(|v| v.as_array())
+        .unwrap_or(&empty);
+
+    let mut sizes = HashMap::new();
+    let mut unknown = Vec::new();
+    for track in tracks {
+        let id = track.get("id").and_then(|v| v.as_i64()).unwrap_or(0) as i32;
+        let tagged = track
+            .get("properties")
+            .and_then(|p| p.get("tag_number_of_bytes"))
+            .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()));
+        match tagged {
+            Some(bytes) => {
+                sizes.insert(id, bytes);
+            }
+            None => unknown.push(id),
+        }
+    }
+
+    if !unknown.is_empty() {
+        let known: u64 = sizes.values().sum();
+        let share = file_size.saturating_sub(known) / unknown.len() as u64;
+        for id in unknown {
+            sizes.insert(id, share);
+        }
+    }
+    sizes
+}
+
+/// Size of a track decoded to PCM, from container duration, rate and channels.
+fn decoded_pcm_bytes(info: &Value, track_id: i32) -> u64 {
+    let duration_s = info
+        .get("container")
+        .and_then(|c| c.get("properties"))
+        .and_then(|p| p.get("duration"))
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        / 1e9;
+    let props = info
+        .get("tracks")
+        .and_then(|v| v.as_array())
+        .and_then(|tracks| {
+            tracks
+                .iter()
+                .find(|t| t.get("id").and_then(|v| v.as_i64()) == Some(track_id as i64))
+        })
+        .and_then(|t| t.get("properties"));
+    let Some(props) = props else {
+        return 0;
+    };
+    let rate = props
+        .get("audio_sampling_frequency")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(48000.0);
+    let channels = props
+        .get("audio_channels")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(2.0);
+    (duration_s * rate * channels * PCM_BYTES_PER_SAMPLE) as u64
+}
+
+/// Free bytes and mount point for `path`, via `df -Pk`.
+fn free_space(
+    path: &Path,
+    runner: &CommandRunner,
+    tool_paths: &HashMap<String, String>,
+) -> Option<(u64, String)> {
+    let path_str = path.to_string_lossy();
+    let out = runner.run(&["df", "-Pk", &path_str], tool_paths).ok()?;
+    parse_df_output(&out)
+}
+
+/// Parses POSIX `df -Pk` output: available KiB (column 4) and mount point.
+fn parse_df_output(out: &str) -> Option<(u64, String)> {
+    let line = out.lines().nth(1)?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 6 {
+        return None;
+    }
+    let avail_kib: u64 = fields[3].parse().ok()?;
+    Some((avail_kib * 1024, fields[5..].join(" ")))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    let gib = bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+    if gib >= 1.0 {
+        format!("{gib:.1} GiB")
+    } else {
+        format!("{:.0} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn df_output_parses_available_and_mount() {
+        let out = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                   /dev/nvme0n1p2   490617784 211134220 254488912      46% /mnt/media disk\n";
+        let (avail, mount) = parse_df_output(out).unwrap();
+        assert_eq!(avail, 254488912 * 1024);
+        assert_eq!(mount, "/mnt/media disk");
+        assert_eq!(parse_df_output("garbage"), None);
+    }
+
+    #[test]
+    fn untagged_tracks_share_remaining_file_size() {
+        let info = json!({
+            "tracks": [
+                { "id": 0, "properties": { "tag_number_of_bytes": "700" } },
+                { "id": 1, "properties": {} },
+                { "id": 2, "properties": { "tag_number_of_bytes": 100 } },
+                { "id": 3 },
+            ]
+        });
+        let sizes = track_sizes(&info, 1000);
+        assert_eq!(sizes[&0], 700);
+        assert_eq!(sizes[&2], 100);
+        assert_eq!(sizes[&1], 100);
+        assert_eq!(sizes[&3], 100);
+    }
+
+    #[test]
+    fn pcm_estimate_uses_duration_rate_and_channels() {
+        let info = json!({
+            "container": { "properties": { "duration": 10_000_000_000u64 } },
+            "tracks": [
+                { "id": 1, "properties": { "audio_sampling_frequency": 48000, "audio_channels": 6 } },
+            ]
+        });
+        assert_eq!(decoded_pcm_bytes(&info, 1), 10 * 48000 * 6 * 3);
+        assert_eq!(decoded_pcm_bytes(&info, 9), 0);
+    }
+}
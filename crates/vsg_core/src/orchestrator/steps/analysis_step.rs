@@ -19,15 +19,18 @@ use crate::analysis::correlation::{
 use crate::analysis::delay_selection::{calculate_delay, find_first_stable_segment_delay};
 use crate::analysis::drift_detection::diagnose_audio_issue;
 use crate::analysis::global_shift::{apply_global_shift_to_delays, calculate_global_shift};
+use crate::analysis::speed_ratio::{known_ratio_label, ratio_for_slope};
 use crate::analysis::sync_stability::analyze_sync_stability;
 use crate::analysis::track_selection::{format_track_details, select_audio_track};
 use crate::analysis::types::{ChunkResult, ContainerDelayInfo, DiagnosisResult};
 use crate::analysis::videodiff::run_native_videodiff;
 use crate::correction::stepping::data_io::save_stepping_data;
+use crate::correction::stepping::edl_io::load_edl;
 use crate::extraction::tracks::get_stream_info;
 use crate::io::runner::CommandRunner;
 use crate::models::context_types::{DriftFlagsEntry, SegmentFlagsEntry};
 use crate::models::enums::{FilteringMethod, SourceSeparationMode, SyncMode};
+use crate::models::events::JobEvent;
 use crate::models::jobs::Delays;
 use crate::models::settings::AppSettings;
 
@@ -377,6 +380,15 @@ impl AnalysisStep {
 
         // Store calculated delays
         let sync_mode_str = ctx.sync_mode.clone();
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.record_global_shift(
+                shift.most_negative_raw_ms,
+                shift.most_negative_ms,
+                shift.raw_shift_ms,
+                shift.shift_ms,
+                &sync_mode_str,
+            );
+        }
         ctx.delays = Some(Delays {
             source_delays_ms: source_delays.clone(),
             raw_source_delays_ms: raw_source_delays.clone(),
@@ -398,6 +410,15 @@ impl AnalysisStep {
             (ctx.log)(&format!(
                 "  - {source_key}: {delay_ms:+}ms (raw: {raw_ms:+.6}ms)"
             ));
+            (ctx.events)(&JobEvent::DelayChosen {
+                source: (*source_key).clone(),
+                delay_ms,
+                raw_delay_ms: raw_ms,
+                global_shift_ms: shift.shift_ms,
+            });
+            if let Some(audit) = ctx.audit.as_mut() {
+                audit.record_final_delay(source_key, raw_ms, delay_ms, shift.applied);
+            }
         }
 
         if sync_mode_str == "allow_negative" && shift.shift_ms == 0 {
@@ -421,7 +442,7 @@ impl AnalysisStep {
 
     fn run_videodiff_analysis(
         &self,
-        ctx: &Context,
+        ctx: &mut Context,
         source_key: &str,
         source_file: &str,
         source1_file: &str,
@@ -470,6 +491,19 @@ impl AnalysisStep {
 
         source_delays.insert(source_key.to_string(), final_delay_ms);
         raw_source_delays.insert(source_key.to_string(), final_delay_raw);
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.record_delay_calculation(
+                source_key,
+                correlation_delay_raw,
+                correlation_delay_ms,
+                actual_container_delay,
+                final_delay_raw,
+                final_delay_ms,
+                "videodiff",
+                vd_result.inlier_count,
+                vd_result.matched_frames,
+            );
+        }
 
         Ok(())
     }
@@ -622,6 +656,38 @@ impl AnalysisStep {
             .unwrap_or("unknown")
             .to_string();
 
+        // --- Manual EDL override: skip correlation and stepping detection ---
+        let edl_override = per_source_settings
+            .as_ref()
+            .and_then(|v| v.get("stepping_edl_override"))
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.is_empty())
+            .map(str::to_string);
+        if let Some(edl_path) = edl_override {
+            if ctx.settings.stepping_enabled {
+                let container_delay = self.correlation_container_delay(
+                    ctx,
+                    source1_audio_container_delay,
+                    source1_container_info,
+                    source1_stream_info,
+                    correlation_ref_track,
+                );
+                return self.apply_edl_override(
+                    ctx,
+                    source_key,
+                    target_track_id,
+                    &edl_path,
+                    container_delay,
+                    source_delays,
+                    raw_source_delays,
+                    stepping_sources,
+                );
+            }
+            (ctx.log)(&format!(
+                "[Stepping] Manual EDL for {source_key} ignored: stepping correction is disabled."
+            ));
+        }
+
         // --- Decode, separate, filter, chunk, correlate ---
         let results = self.decode_and_correlate(
             ctx,
@@ -634,6 +700,28 @@ impl AnalysisStep {
             tgt_lang.as_deref(),
             use_source_separated_settings,
         )?;
+        for (index, chunk) in results.iter().enumerate() {
+            (ctx.events)(&JobEvent::ChunkCorrelated {
+                source: source_key.to_string(),
+                index,
+                start_s: chunk.start_s,
+                delay_ms: chunk.delay_ms,
+                raw_delay_ms: chunk.raw_delay_ms,
+                match_pct: chunk.match_pct,
+                accepted: chunk.accepted,
+            });
+            if let Some(audit) = ctx.audit.as_mut() {
+                audit.record_correlation_chunk(
+                    source_key,
+                    index as i32,
+                    chunk.start_s,
+                    chunk.delay_ms,
+                    chunk.raw_delay_ms,
+                    chunk.match_pct,
+                    chunk.accepted,
+                );
+            }
+        }
 
         // --- Detect stepping BEFORE calculating mode delay ---
         let diagnosis = diagnose_audio_issue(
@@ -752,24 +840,13 @@ impl AnalysisStep {
         }
 
         // --- Calculate final delay chain ---
-        let mut actual_container_delay = source1_audio_container_delay;
-
-        if let (Some(info), Some(si)) = (source1_container_info, source1_stream_info) {
-            let ref_lang_str = &ctx.settings.analysis_lang_source1;
-            let ref_lang_opt = if ref_lang_str.is_empty() {
-                None
-            } else {
-                Some(ref_lang_str.as_str())
-            };
-            actual_container_delay = find_actual_correlation_track_delay(
-                info,
-                Some(si),
-                correlation_ref_track,
-                ref_lang_opt,
-                source1_audio_container_delay,
-                &*ctx.log,
-            );
-        }
+        let actual_container_delay = self.correlation_container_delay(
+            ctx,
+            source1_audio_container_delay,
+            source1_container_info,
+            source1_stream_info,
+            correlation_ref_track,
+        );
 
         let (final_delay_ms, final_delay_raw) = calculate_delay_chain(
             correlation_delay_ms,
@@ -781,6 +858,19 @@ impl AnalysisStep {
 
         source_delays.insert(source_key.to_string(), final_delay_ms);
         raw_source_delays.insert(source_key.to_string(), final_delay_raw);
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.record_delay_calculation(
+                source_key,
+                correlation_delay_raw,
+                correlation_delay_ms,
+                actual_container_delay,
+                final_delay_raw,
+                final_delay_ms,
+                &effective_delay_mode,
+                results.iter().filter(|r| r.accepted).count(),
+                results.len(),
+            );
+        }
 
         // --- Handle drift detection flags ---
         self.record_drift_flags(
@@ -832,6 +922,118 @@ impl AnalysisStep {
         Ok(())
     }
 
+    /// Container delay of the Source 1 track used for correlation.
+    fn correlation_container_delay(
+        &self,
+        ctx: &Context,
+        source1_audio_container_delay: f64,
+        source1_container_info: Option<&ContainerDelayInfo>,
+        source1_stream_info: Option<&serde_json::Value>,
+        correlation_ref_track: Option<i32>,
+    ) -> f64 {
+        let (Some(info), Some(si)) = (source1_container_info, source1_stream_info) else {
+            return source1_audio_container_delay;
+        };
+        let ref_lang_str = &ctx.settings.analysis_lang_source1;
+        let ref_lang_opt = if ref_lang_str.is_empty() {
+            None
+        } else {
+            Some(ref_lang_str.as_str())
+        };
+        find_actual_correlation_track_delay(
+            info,
+            Some(si),
+            correlation_ref_track,
+            ref_lang_opt,
+            source1_audio_container_delay,
+            &*ctx.log,
+        )
+    }
+
+    /// Use a user-supplied EDL instead of detection for this source.
+    ///
+    /// The first segment's delay becomes the source delay; the EDL itself
+    /// goes straight to assembly, QA and subtitle stepping.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_edl_override(
+        &self,
+        ctx: &mut Context,
+        source_key: &str,
+        target_track_id: i32,
+        edl_path: &str,
+        container_delay: f64,
+        source_delays: &mut HashMap<String, i32>,
+        raw_source_delays: &mut HashMap<String, f64>,
+        stepping_sources: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let edl = load_edl(Path::new(edl_path))
+            .map_err(|e| format!("Manual EDL for {source_key} could not be loaded: {e}"))?;
+
+        (ctx.log)(&format!(
+            "[Stepping] Manual EDL for {source_key}: {} segment(s) from {edl_path}. \
+             Skipping correlation and stepping detection.",
+            edl.len()
+        ));
+
+        let (final_delay_ms, final_delay_raw) = calculate_delay_chain(
+            edl[0].delay_ms,
+            edl[0].delay_raw,
+            container_delay,
+            &*ctx.log,
+            source_key,
+        );
+        source_delays.insert(source_key.to_string(), final_delay_ms);
+        raw_source_delays.insert(source_key.to_string(), final_delay_raw);
+        stepping_sources.push(source_key.to_string());
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.record_delay_calculation(
+                source_key,
+                edl[0].delay_raw,
+                edl[0].delay_ms,
+                container_delay,
+                final_delay_raw,
+                final_delay_ms,
+                "manual_edl",
+                0,
+                0,
+            );
+        }
+
+        let source_has_audio = ctx.manual_layout.iter().any(|item| {
+            item.source.as_deref() == Some(source_key)
+                && item.track_type.as_deref() == Some("audio")
+        });
+        let source_has_subs = ctx.manual_layout.iter().any(|item| {
+            item.source.as_deref() == Some(source_key)
+                && item.track_type.as_deref() == Some("subtitles")
+        });
+        if !source_has_audio && !source_has_subs {
+            (ctx.log)(&format!(
+                "[Stepping] No audio or subtitle tracks from {source_key} are \
+                 being used. Manual EDL will not be applied."
+            ));
+            return Ok(());
+        }
+
+        ctx.segment_flags.insert(
+            format!("{source_key}_{target_track_id}"),
+            SegmentFlagsEntry {
+                base_delay: final_delay_ms,
+                cluster_details: Vec::new(),
+                valid_clusters: HashMap::new(),
+                invalid_clusters: HashMap::new(),
+                validation_results: HashMap::new(),
+                correction_mode: Some("manual_edl".to_string()),
+                fallback_mode: None,
+                subs_only: Some(!source_has_audio),
+                stepping_data_path: None,
+                audit_metadata: None,
+                edl_override: Some(edl_path.to_string()),
+            },
+        );
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn decode_and_correlate(
         &self,
@@ -938,17 +1140,27 @@ impl AnalysisStep {
             .unwrap_or_default()
             .to_string_lossy();
 
+        // Virtually linked sources decode their assembled timeline
+        let decode_path = |key: &str, file: &str| -> String {
+            ctx.virtual_timelines
+                .get(key)
+                .map(|v| v.playlist.clone())
+                .unwrap_or_else(|| file.to_string())
+        };
+        let ref_path = decode_path("Source 1", source1_file);
+        let tgt_path = decode_path(source_key, source_file);
+
         log(&format!(
             "[DECODE DEBUG] Decoding ref: -map 0:a:{idx_ref} from {ref_name}"
         ));
         let mut ref_pcm =
-            decode_audio(source1_file, idx_ref, DEFAULT_SR, use_soxr, runner, &ctx.tool_paths)?;
+            decode_audio(&ref_path, idx_ref, DEFAULT_SR, use_soxr, runner, &ctx.tool_paths)?;
 
         log(&format!(
             "[DECODE DEBUG] Decoding tgt: -map 0:a:{idx_tgt} from {tgt_name}"
         ));
         let mut tgt_pcm =
-            decode_audio(source_file, idx_tgt, DEFAULT_SR, use_soxr, runner, &ctx.tool_paths)?;
+            decode_audio(&tgt_path, idx_tgt, DEFAULT_SR, use_soxr, runner, &ctx.tool_paths)?;
 
         // Log audio stats
         let ref_min = ref_pcm.iter().cloned().fold(f32::INFINITY, f32::min);
@@ -1281,7 +1493,10 @@ impl AnalysisStep {
                     if source_has_audio {
                         ctx.pal_drift_flags.insert(
                             analysis_track_key,
-                            DriftFlagsEntry { rate: Some(*rate) },
+                            DriftFlagsEntry {
+                                rate: Some(*rate),
+                                ..Default::default()
+                            },
                         );
                     } else {
                         (ctx.log)(&format!(
@@ -1312,7 +1527,10 @@ impl AnalysisStep {
                     if source_has_audio {
                         ctx.linear_drift_flags.insert(
                             analysis_track_key,
-                            DriftFlagsEntry { rate: Some(*rate) },
+                            DriftFlagsEntry {
+                                rate: Some(*rate),
+                                ..Default::default()
+                            },
                         );
                     } else {
                         (ctx.log)(&format!(
@@ -1324,6 +1542,47 @@ impl AnalysisStep {
                     }
                 }
             }
+            DiagnosisResult::Drift {
+                ref diagnosis,
+                rate,
+            } if diagnosis == "SPEED_RATIO" => {
+                let ratio = ratio_for_slope(*rate);
+                let label = known_ratio_label(ratio);
+                if use_source_separated_settings {
+                    (ctx.log)(&format!(
+                        "[Speed Ratio Detected] Speed mismatch detected in \
+                         {source_key}, but source separation is enabled. \
+                         Speed correction is unreliable on separated stems - skipping."
+                    ));
+                } else {
+                    let source_has_audio = ctx.manual_layout.iter().any(|item| {
+                        item.source.as_deref() == Some(source_key)
+                            && item.track_type.as_deref() == Some("audio")
+                    });
+                    if source_has_audio {
+                        (ctx.log)(&format!(
+                            "[Speed Ratio] {source_key} runs at {ratio:.6}x the reference ({}). \
+                             Audio, subtitles and chapters from this source will be retimed.",
+                            label.as_deref().unwrap_or("exact fit")
+                        ));
+                        ctx.speed_ratio_flags.insert(
+                            analysis_track_key,
+                            DriftFlagsEntry {
+                                rate: Some(*rate),
+                                ratio: Some(ratio),
+                                label,
+                            },
+                        );
+                    } else {
+                        (ctx.log)(&format!(
+                            "[Speed Ratio Detected] Speed mismatch detected in \
+                             {source_key}, but no audio tracks from this \
+                             source are being used. Skipping speed correction \
+                             for {source_key}."
+                        ));
+                    }
+                }
+            }
             DiagnosisResult::Stepping {
                 ref cluster_details,
                 ref valid_clusters,
@@ -1373,6 +1632,7 @@ impl AnalysisStep {
                                 subs_only: Some(false),
                                 stepping_data_path: None,
                                 audit_metadata: None,
+                                edl_override: None,
                             },
                         );
                         (ctx.log)(&format!(
@@ -1415,6 +1675,7 @@ impl AnalysisStep {
                                 subs_only: Some(true),
                                 stepping_data_path: None,
                                 audit_metadata: None,
+                                edl_override: None,
                             },
                         );
                         (ctx.log)(
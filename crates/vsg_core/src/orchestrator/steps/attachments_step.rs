@@ -1,9 +1,18 @@
 //! Attachments step — 1:1 port of `vsg_core/orchestrator/steps/attachments_step.py`.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use crate::extraction::attachments::extract_attachments;
+use sha2::{Digest, Sha256};
+
+use crate::extraction::attachments::{
+    extract_attachments, is_font_attachment, original_attachment_name,
+};
 use crate::io::runner::CommandRunner;
+use crate::subtitles::font_coverage::{
+    collect_plan_font_usage, is_parseable_font, load_font_faces, match_font,
+    referenced_font_files, FontMatch,
+};
 
 use super::context::Context;
 
@@ -16,6 +25,9 @@ impl AttachmentsStep {
         if !ctx.and_merge || ctx.attachment_sources.is_empty() {
             ctx.attachments = Some(Vec::new());
             self.add_replacement_fonts(ctx, runner);
+            self.add_extra_attachments(ctx, runner);
+            self.add_missing_fonts(ctx, runner);
+            self.merge_attachments(ctx, runner);
             return Ok(());
         }
 
@@ -37,6 +49,9 @@ impl AttachmentsStep {
 
         ctx.attachments = Some(all_attachments);
         self.add_replacement_fonts(ctx, runner);
+        self.add_extra_attachments(ctx, runner);
+        self.add_missing_fonts(ctx, runner);
+        self.merge_attachments(ctx, runner);
 
         Ok(())
     }
@@ -113,4 +128,257 @@ impl AttachmentsStep {
             ));
         }
     }
+
+    /// Add the job's loose attachment files — `_add_extra_attachments`
+    fn add_extra_attachments(&self, ctx: &mut Context, runner: &CommandRunner) {
+        if ctx.extra_attachments.is_empty() {
+            return;
+        }
+
+        let mut added: Vec<String> = Vec::new();
+        for file in &ctx.extra_attachments {
+            if Path::new(file).is_file() {
+                added.push(file.clone());
+            } else {
+                runner.log_message(&format!(
+                    "[Attachments] WARNING: Attachment file not found: {file}"
+                ));
+            }
+        }
+
+        if !added.is_empty() {
+            let count = added.len();
+            ctx.attachments.get_or_insert_with(Vec::new).extend(added);
+            runner.log_message(&format!("[Attachments] Added {count} extra file(s)."));
+        }
+    }
+
+    /// Stage every planned attachment under its original name — dropping
+    /// byte-identical duplicates across sources and renaming different files
+    /// that share a name — then apply the referenced-fonts-only filter.
+    fn merge_attachments(&self, ctx: &mut Context, runner: &CommandRunner) {
+        let planned = ctx.attachments.take().unwrap_or_default();
+        if planned.is_empty() {
+            ctx.attachments = Some(planned);
+            return;
+        }
+
+        let staging_dir = ctx.temp_dir.join("attachments");
+        let _ = std::fs::create_dir_all(&staging_dir);
+
+        // Content hash -> staged name of the first copy
+        let mut staged_by_hash: HashMap<String, String> = HashMap::new();
+        let mut used_names: Vec<String> = Vec::new();
+        let mut merged: Vec<String> = Vec::new();
+        let mut duplicates = 0;
+
+        for file in &planned {
+            let src_path = Path::new(file);
+            let data = match std::fs::read(src_path) {
+                Ok(data) => data,
+                Err(e) => {
+                    runner.log_message(&format!(
+                        "[Attachments] WARNING: Cannot read {file}: {e}"
+                    ));
+                    continue;
+                }
+            };
+            let name = original_attachment_name(src_path);
+            let hash = format!("{:x}", Sha256::digest(&data));
+            if let Some(kept) = staged_by_hash.get(&hash) {
+                runner.log_message(&format!(
+                    "[Attachments] Skipping {name}: identical to {kept}"
+                ));
+                duplicates += 1;
+                continue;
+            }
+
+            let unique = unique_attachment_name(&name, &used_names);
+            if unique != name {
+                runner.log_message(&format!(
+                    "[Attachments] Renamed {name} to {unique}: a different file has that name"
+                ));
+            }
+            let dst_path = staging_dir.join(&unique);
+            if let Err(e) = std::fs::write(&dst_path, &data) {
+                runner.log_message(&format!(
+                    "[Attachments] WARNING: Failed to stage {name}: {e}"
+                ));
+                continue;
+            }
+            used_names.push(unique.to_lowercase());
+            staged_by_hash.insert(hash, unique);
+            merged.push(dst_path.to_string_lossy().to_string());
+        }
+
+        if duplicates > 0 {
+            runner.log_message(&format!(
+                "[Attachments] Removed {duplicates} duplicate attachment(s)."
+            ));
+        }
+
+        if ctx.settings.attachments_referenced_fonts_only {
+            let items = ctx.extracted_items.as_deref().unwrap_or_default();
+            match referenced_font_files(items, &merged) {
+                Some(referenced) => {
+                    let before = merged.len();
+                    merged.retain(|file| {
+                        let path = Path::new(file);
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        // Fonts that can't be parsed can't be ruled out either
+                        !is_font_attachment("", &name)
+                            || !is_parseable_font(path)
+                            || referenced.contains(path)
+                    });
+                    runner.log_message(&format!(
+                        "[Attachments] Dropped {} font(s) not used by the subtitle tracks.",
+                        before - merged.len()
+                    ));
+                }
+                None => runner.log_message(
+                    "[Attachments] WARNING: Could not analyze subtitle fonts; keeping all fonts.",
+                ),
+            }
+        }
+
+        ctx.attachments = Some(merged);
+    }
+
+    /// Attach faces from the fonts directory for fonts the subtitles use
+    /// but no attachment provides.
+    fn add_missing_fonts(&self, ctx: &mut Context, runner: &CommandRunner) {
+        if !ctx.settings.font_auto_attach {
+            return;
+        }
+        let items = match &ctx.extracted_items {
+            Some(items) => items,
+            None => return,
+        };
+        let usage = collect_plan_font_usage(items);
+        if usage.is_empty() {
+            return;
+        }
+
+        let attached = load_font_faces(ctx.attachments.as_deref().unwrap_or_default());
+        let unresolved: Vec<_> = usage
+            .iter()
+            .filter(|(request, chars)| {
+                !matches!(
+                    match_font(request, chars, &attached),
+                    FontMatch::Found { exact_style: true, .. }
+                )
+            })
+            .collect();
+        if unresolved.is_empty() {
+            return;
+        }
+
+        let fonts_dir = PathBuf::from(&ctx.settings.fonts_directory);
+        if ctx.settings.fonts_directory.is_empty() || !fonts_dir.is_dir() {
+            runner.log_message(&format!(
+                "[Font] WARNING: {} subtitle font(s) not attached and no fonts directory is set.",
+                unresolved.len()
+            ));
+            return;
+        }
+
+        let mut font_files = Vec::new();
+        collect_font_files(&fonts_dir, &mut font_files);
+        let library = load_font_faces(&font_files);
+
+        let auto_dir = ctx.temp_dir.join("auto_fonts");
+        let _ = std::fs::create_dir_all(&auto_dir);
+
+        let mut added: Vec<String> = Vec::new();
+        for (request, chars) in unresolved {
+            let face = match match_font(request, chars, &library) {
+                FontMatch::Found {
+                    face,
+                    exact_style,
+                    ..
+                } => {
+                    // Only take a synthesized match when nothing was attached at all
+                    let already_attached = attached
+                        .iter()
+                        .any(|f| f.matches_family(&request.family));
+                    if !exact_style && already_attached {
+                        continue;
+                    }
+                    &library[face]
+                }
+                FontMatch::Missing => {
+                    runner.log_message(&format!(
+                        "[Font] WARNING: Font {request} not found in fonts directory"
+                    ));
+                    continue;
+                }
+            };
+
+            let Some(file_name) = face.file_path.file_name() else {
+                continue;
+            };
+            let dst_path = auto_dir.join(file_name);
+            let dst = dst_path.to_string_lossy().to_string();
+            if added.contains(&dst) {
+                continue;
+            }
+            match std::fs::copy(&face.file_path, &dst_path) {
+                Ok(_) => {
+                    runner.log_message(&format!(
+                        "[Font] Auto-attaching {} for {request}",
+                        file_name.to_string_lossy()
+                    ));
+                    added.push(dst);
+                }
+                Err(e) => {
+                    runner.log_message(&format!(
+                        "[Font] WARNING: Failed to copy {}: {e}",
+                        file_name.to_string_lossy()
+                    ));
+                }
+            }
+        }
+
+        if !added.is_empty() {
+            let count = added.len();
+            ctx.attachments.get_or_insert_with(Vec::new).extend(added);
+            runner.log_message(&format!("[Font] Auto-attached {count} font(s)."));
+        }
+    }
+}
+
+/// `name`, or `name (2).ext`, `name (3).ext`, ... when it is already in
+/// `used` (lowercase, as Matroska players often compare case-insensitively).
+fn unique_attachment_name(name: &str, used: &[String]) -> String {
+    if !used.contains(&name.to_lowercase()) {
+        return name.to_string();
+    }
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| format!("{stem} ({n}){ext}"))
+        .find(|candidate| !used.contains(&candidate.to_lowercase()))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Recursively collect parseable font files under `dir`.
+fn collect_font_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_font_files(&path, files);
+        } else if is_parseable_font(&path) {
+            files.push(path);
+        }
+    }
 }
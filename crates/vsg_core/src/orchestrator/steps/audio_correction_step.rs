@@ -4,9 +4,11 @@
 
 use crate::correction::linear::run_linear_correction;
 use crate::correction::pal::run_pal_correction;
+use crate::correction::speed::run_speed_correction;
 use crate::correction::stepping::run::run_stepping_correction;
 use crate::io::runner::CommandRunner;
 use crate::models::enums::TrackType;
+use crate::models::settings::AppSettings;
 
 use super::context::Context;
 
@@ -14,8 +16,14 @@ use super::context::Context;
 pub struct AudioCorrectionStep;
 
 impl AudioCorrectionStep {
+    /// Whether any correction (PAL, speed, linear drift or stepping) can
+    /// run under these settings; all of them are gated on stepping.
+    pub fn is_enabled(settings: &AppSettings) -> bool {
+        settings.stepping_enabled
+    }
+
     pub fn run(&self, ctx: &mut Context, runner: &CommandRunner) -> Result<(), String> {
-        if !ctx.and_merge || !ctx.settings.stepping_enabled {
+        if !ctx.and_merge || !Self::is_enabled(&ctx.settings) {
             return Ok(());
         }
 
@@ -23,6 +31,10 @@ impl AudioCorrectionStep {
             runner.log_message("--- PAL Drift Audio Correction Phase ---");
             run_pal_correction(ctx, runner);
             self.validate_pal_correction(ctx, runner)?;
+        } else if !ctx.speed_ratio_flags.is_empty() {
+            runner.log_message("--- Speed Ratio Audio Correction Phase ---");
+            run_speed_correction(ctx, runner);
+            self.validate_speed_correction(ctx, runner)?;
         } else if !ctx.linear_drift_flags.is_empty() {
             runner.log_message("--- Linear Drift Audio Correction Phase ---");
             run_linear_correction(ctx, runner);
@@ -74,6 +86,44 @@ impl AudioCorrectionStep {
         Ok(())
     }
 
+    fn validate_speed_correction(&self, ctx: &Context, runner: &CommandRunner) -> Result<(), String> {
+        let items = match &ctx.extracted_items {
+            Some(items) => items,
+            None => return Ok(()),
+        };
+        for analysis_key in ctx.speed_ratio_flags.keys() {
+            let source_key = analysis_key.split('_').next().unwrap_or("");
+            let audio_tracks: Vec<_> = items.iter()
+                .filter(|item| item.track.source == source_key && item.track.track_type == TrackType::Audio && !item.is_preserved)
+                .collect();
+
+            if audio_tracks.is_empty() {
+                runner.log_message(&format!(
+                    "[Validation] Speed correction skipped for {source_key}: No audio tracks in layout."
+                ));
+                continue;
+            }
+
+            let corrected: Vec<_> = audio_tracks.iter().filter(|item| item.is_corrected).collect();
+            if corrected.is_empty() {
+                return Err(format!("Speed correction failed for {source_key}: No corrected track created."));
+            }
+
+            for item in corrected {
+                if let Some(ref path) = item.extracted_path {
+                    if !path.exists() {
+                        return Err(format!("Speed correction failed for {source_key}: File not created at {}", path.display()));
+                    }
+                }
+                runner.log_message(&format!(
+                    "[Validation] Speed correction verified for {source_key}: {}",
+                    item.extracted_path.as_ref().map(|p| p.file_name().unwrap_or_default().to_string_lossy().to_string()).unwrap_or_default()
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn validate_linear_correction(&self, ctx: &Context, runner: &CommandRunner) -> Result<(), String> {
         let items = match &ctx.extracted_items {
             Some(items) => items,
@@ -2,12 +2,53 @@
 
 use std::collections::HashMap;
 
-use crate::chapters::process::process_chapters;
+use crate::chapters::process::{process_chapters, ChapterInput};
 use crate::io::runner::CommandRunner;
 
 use super::context::Context;
 
-/// Extracts/modifies chapter XML from Source 1 — `ChaptersStep`
+/// Picks where chapters come from using the per-source settings.
+///
+/// A source's `chapter_file` (an external file on that source's timeline)
+/// wins, then a source flagged `use_chapters`; otherwise Source 1's own
+/// chapters are used. Generated chapters are only a fallback (see `run`).
+fn chapter_source(ctx: &Context, source1_file: &str) -> (String, ChapterInput) {
+    let mut keys: Vec<&String> = ctx.sources.keys().collect();
+    keys.sort();
+
+    let setting = |key: &str, name: &str| ctx.source_settings.get(key).and_then(|s| s.get(name));
+    for key in &keys {
+        if let Some(path) = setting(key, "chapter_file")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            return (key.to_string(), ChapterInput::File(path.to_string()));
+        }
+    }
+    for key in &keys {
+        if setting(key, "use_chapters").and_then(|v| v.as_bool()) == Some(true) {
+            return (
+                key.to_string(),
+                container_chapters(ctx, key, &ctx.sources[*key]),
+            );
+        }
+    }
+    (
+        "Source 1".to_string(),
+        container_chapters(ctx, "Source 1", source1_file),
+    )
+}
+
+/// A source's own chapters, flattened if its timeline is read virtually.
+fn container_chapters(ctx: &Context, key: &str, file: &str) -> ChapterInput {
+    match ctx.virtual_timelines.get(key) {
+        Some(timeline) => ChapterInput::File(timeline.chapters_xml.clone()),
+        None => ChapterInput::Container(file.to_string()),
+    }
+}
+
+/// Extracts/modifies chapter XML from the chosen source — `ChaptersStep`
 pub struct ChaptersStep;
 
 impl ChaptersStep {
@@ -45,14 +86,55 @@ impl ChaptersStep {
         // Convert tool_paths for the function signature
         let tool_paths: HashMap<String, String> = ctx.tool_paths.clone();
 
-        match process_chapters(
-            &source1_file,
-            &ctx.temp_dir,
+        let (source_key, input) = chapter_source(ctx, &source1_file);
+        let mut result = self.process_from(
+            ctx,
             runner,
             &tool_paths,
-            &ctx.settings,
+            &source_key,
+            &input,
+            &source1_file,
             shift_ms,
-        ) {
+        );
+        if result.is_none() && input != ChapterInput::Container(source1_file.clone()) {
+            runner.log_message(&format!(
+                "[Chapters] No usable chapters from {source_key}; falling back to Source 1"
+            ));
+            let fallback = ChapterInput::Container(source1_file.clone());
+            result = self.process_from(
+                ctx,
+                runner,
+                &tool_paths,
+                "Source 1",
+                &fallback,
+                &source1_file,
+                shift_ms,
+            );
+        }
+
+        // Last resort: chapters generated from the batch's OP/ED structure
+        if result.is_none() {
+            if let Some(generated) = ctx
+                .source_settings
+                .get("Source 1")
+                .and_then(|s| s.get("generated_chapter_file"))
+                .and_then(|v| v.as_str())
+            {
+                runner.log_message("[Chapters] Using chapters generated for this episode");
+                let input = ChapterInput::File(generated.to_string());
+                result = self.process_from(
+                    ctx,
+                    runner,
+                    &tool_paths,
+                    "Source 1",
+                    &input,
+                    &source1_file,
+                    shift_ms,
+                );
+            }
+        }
+
+        match result {
             Some(xml_path) => {
                 runner.log_message(&format!(
                     "[Chapters] Successfully processed chapters: {xml_path}"
@@ -67,4 +149,43 @@ impl ChaptersStep {
 
         Ok(())
     }
+
+    /// Process chapters read from `input`, which is on `source_key`'s timeline.
+    #[allow(clippy::too_many_arguments)]
+    fn process_from(
+        &self,
+        ctx: &Context,
+        runner: &CommandRunner,
+        tool_paths: &HashMap<String, String>,
+        source_key: &str,
+        input: &ChapterInput,
+        source1_file: &str,
+        shift_ms: i32,
+    ) -> Option<String> {
+        // Another source's delay already includes the global shift; only
+        // the remainder moves its chapters onto Source 1's video timeline
+        let offset_ms = if source_key == "Source 1" {
+            0
+        } else {
+            ctx.delays
+                .as_ref()
+                .and_then(|d| d.source_delays_ms.get(source_key))
+                .map_or(0, |delay| delay - shift_ms)
+        };
+
+        // Retime chapters the same way as their source's audio was
+        let speed_ratio = ctx.speed_ratio_for_source(source_key);
+
+        process_chapters(
+            input,
+            source1_file,
+            &ctx.temp_dir,
+            runner,
+            tool_paths,
+            &ctx.settings,
+            offset_ms,
+            shift_ms,
+            speed_ratio,
+        )
+    }
 }
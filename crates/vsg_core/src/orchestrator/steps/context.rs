@@ -5,10 +5,12 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::audit::trail::AuditTrail;
 use crate::models::context_types::{
     DriftFlagsEntry, ManualLayoutItem, SegmentFlagsEntry, SteppingQualityIssue,
-    SyncStabilityIssue, VideoVerifiedResult,
+    SyncProvenance, SyncStabilityIssue, VideoVerifiedResult, VirtualTimeline,
 };
+use crate::models::events::EventCallback;
 use crate::models::jobs::{Delays, PlanItem};
 use crate::models::settings::AppSettings;
 
@@ -19,16 +21,23 @@ pub struct Context {
     pub tool_paths: HashMap<String, String>,
     pub log: Box<dyn Fn(&str) + Send + Sync>,
     pub progress: Box<dyn Fn(f64) + Send + Sync>,
+    /// Typed event sink, emitted alongside `log`/`progress`.
+    pub events: EventCallback,
     pub output_dir: String,
     pub temp_dir: PathBuf,
     pub sources: HashMap<String, String>,
     pub and_merge: bool,
     pub manual_layout: Vec<ManualLayoutItem>,
     pub attachment_sources: Vec<String>,
+    /// Loose files (fonts, cover art) attached to this job directly.
+    pub extra_attachments: Vec<String>,
 
     /// Per-source correlation settings (from job layout).
     pub source_settings: HashMap<String, serde_json::Value>,
 
+    /// Sources with ordered chapters read through a virtual linked timeline.
+    pub virtual_timelines: HashMap<String, VirtualTimeline>,
+
     // Filled along the pipeline
     pub delays: Option<Delays>,
     pub extracted_items: Option<Vec<PlanItem>>,
@@ -45,6 +54,9 @@ pub struct Context {
     /// Flags for tracks needing linear drift correction.
     pub linear_drift_flags: HashMap<String, DriftFlagsEntry>,
 
+    /// Flags for tracks needing speed-ratio correction (`ratio` is set).
+    pub speed_ratio_flags: HashMap<String, DriftFlagsEntry>,
+
     /// Source 1's reference audio container delay.
     pub source1_audio_container_delay_ms: f64,
 
@@ -89,6 +101,19 @@ pub struct Context {
     /// Cached video properties per source.
     pub video_properties: HashMap<String, serde_json::Value>,
 
+    /// Audit trail for this job (written next to the job log).
+    pub audit: Option<AuditTrail>,
+
+    /// Offset (ms) subtracted from every track by the lossless finalizer.
+    pub timestamp_rebase_ms: i32,
+
+    /// `mkvmerge -J` of the merged file before finalization, for the
+    /// remux integrity audit.
+    pub pre_finalize_mkvmerge: Option<serde_json::Value>,
+
+    /// Provenance tags to write onto the final file once it is finalized.
+    pub provenance: Option<SyncProvenance>,
+
     // Results/summaries
     pub out_file: Option<String>,
     pub tokens: Option<Vec<String>>,
@@ -102,12 +127,14 @@ impl Context {
         tool_paths: HashMap<String, String>,
         log: Box<dyn Fn(&str) + Send + Sync>,
         progress: Box<dyn Fn(f64) + Send + Sync>,
+        events: EventCallback,
         output_dir: String,
         temp_dir: PathBuf,
         sources: HashMap<String, String>,
         and_merge: bool,
         manual_layout: Vec<ManualLayoutItem>,
         attachment_sources: Vec<String>,
+        extra_attachments: Vec<String>,
         source_settings: HashMap<String, serde_json::Value>,
     ) -> Self {
         Self {
@@ -115,13 +142,16 @@ impl Context {
             tool_paths,
             log,
             progress,
+            events,
             output_dir,
             temp_dir,
             sources,
             and_merge,
             manual_layout,
             attachment_sources,
+            extra_attachments,
             source_settings,
+            virtual_timelines: HashMap::new(),
             delays: None,
             extracted_items: None,
             chapters_xml: None,
@@ -129,6 +159,7 @@ impl Context {
             segment_flags: HashMap::new(),
             pal_drift_flags: HashMap::new(),
             linear_drift_flags: HashMap::new(),
+            speed_ratio_flags: HashMap::new(),
             source1_audio_container_delay_ms: 0.0,
             container_delays: HashMap::new(),
             global_shift_is_required: false,
@@ -143,8 +174,30 @@ impl Context {
             subtitle_delays_ms: HashMap::new(),
             frame_audit_results: HashMap::new(),
             video_properties: HashMap::new(),
+            audit: None,
+            timestamp_rebase_ms: 0,
+            pre_finalize_mkvmerge: None,
+            provenance: None,
             out_file: None,
             tokens: None,
         }
     }
+
+    /// Speed ratio a source's audio was retimed by, for retiming its
+    /// subtitles and chapters the same way. `None` until the audio has
+    /// actually been corrected.
+    pub fn speed_ratio_for_source(&self, source_key: &str) -> Option<f64> {
+        let retimed = self.extracted_items.as_ref().is_some_and(|items| {
+            items
+                .iter()
+                .any(|item| item.track.source == source_key && item.is_corrected)
+        });
+        if !retimed {
+            return None;
+        }
+        self.speed_ratio_flags
+            .iter()
+            .find(|(key, _)| key.split('_').next() == Some(source_key))
+            .and_then(|(_, entry)| entry.ratio)
+    }
 }
@@ -117,7 +117,7 @@ impl ExtractStep {
                 &["ffprobe", "-v", "error", "-show_streams", "-of", "json", source_path],
                 &ctx.tool_paths,
             );
-            if let Some(out) = ffprobe_out {
+            if let Ok(out) = ffprobe_out {
                 if let Ok(data) = serde_json::from_str::<serde_json::Value>(&out) {
                     let mut ratios = std::collections::HashMap::new();
                     if let Some(streams) = data.get("streams").and_then(|v| v.as_array()) {
@@ -197,6 +197,7 @@ impl ExtractStep {
                             codec_id: sel.codec_id.clone().unwrap_or_default(),
                             lang: sel.lang.clone().unwrap_or_else(|| "und".to_string()),
                             name: sel.name.clone().unwrap_or_default(),
+                            audio_channels: None,
                         },
                     },
                     extracted_path: Some(temp_path),
@@ -247,6 +248,7 @@ impl ExtractStep {
                             codec_id: trk.get("codec_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
                             lang: trk.get("lang").and_then(|v| v.as_str()).unwrap_or("und").to_string(),
                             name: trk.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                            audio_channels: trk.get("audio_channels").and_then(|v| v.as_i64()).map(|c| c as i32),
                         },
                     },
                     extracted_path: Some(PathBuf::from(path_str)),
@@ -414,6 +416,7 @@ impl PlanItem {
             is_preserved: false,
             is_corrected: false,
             correction_source: None,
+            correction_transcode: None,
             perform_ocr: false,
             container_delay_ms: 0,
             custom_lang: String::new(),
@@ -0,0 +1,129 @@
+//! Linked timeline step — resolves ordered chapters before analysis.
+//!
+//! Sources whose ordered edition pulls ranges from other segments are
+//! either read through an ffmpeg concat playlist (virtual) or remuxed into
+//! one file (physical), so analysis, subtitles and chapters all work on
+//! the timeline a viewer actually sees.
+
+use crate::chapters::ordered::{assemble_linked_timeline, detect_linked_timeline, LinkedTimeline};
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::VirtualTimeline;
+use crate::models::enums::LinkedTimelineMode;
+
+use super::context::Context;
+
+/// Detects and assembles linked segment timelines — `LinkedTimelineStep`
+pub struct LinkedTimelineStep;
+
+impl LinkedTimelineStep {
+    /// Run the linked timeline step.
+    pub fn run(&self, ctx: &mut Context, runner: &CommandRunner) -> Result<(), String> {
+        let mut keys: Vec<String> = ctx.sources.keys().cloned().collect();
+        keys.sort();
+
+        for key in keys {
+            let source_file = ctx.sources[&key].clone();
+            let timeline = match detect_linked_timeline(&source_file, runner, &ctx.tool_paths) {
+                Ok(Some(t)) => t,
+                Ok(None) => continue,
+                Err(e) => {
+                    (ctx.log)(&format!(
+                        "[WARNING] {key} has ordered chapters with linked segments, but \
+                         its timeline could not be resolved: {e}"
+                    ));
+                    continue;
+                }
+            };
+
+            let linked: Vec<&str> = timeline.files[1..]
+                .iter()
+                .map(|f| f.path.as_str())
+                .collect();
+            (ctx.log)(&format!(
+                "[Linked Timeline] {key} uses ordered chapters linking {} segment(s): {}",
+                linked.len(),
+                linked.join(", ")
+            ));
+
+            let physical = match ctx.settings.linked_timeline_mode {
+                LinkedTimelineMode::Off => {
+                    (ctx.log)(&format!(
+                        "[WARNING] Linked timeline mode is off; {key} is analysed and muxed \
+                         without its linked segments."
+                    ));
+                    continue;
+                }
+                LinkedTimelineMode::Physical => true,
+                // Tracks can only be muxed from a real file
+                LinkedTimelineMode::Virtual => {
+                    ctx.and_merge
+                        && ctx
+                            .manual_layout
+                            .iter()
+                            .any(|item| item.source.as_deref() == Some(key.as_str()))
+                }
+            };
+
+            if physical {
+                self.assemble(ctx, runner, &key, &timeline)?;
+            } else {
+                self.stage_virtual(ctx, &key, &timeline)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remux the linked timeline and use it as the source from here on.
+    fn assemble(
+        &self,
+        ctx: &mut Context,
+        runner: &CommandRunner,
+        key: &str,
+        timeline: &LinkedTimeline,
+    ) -> Result<(), String> {
+        let out_path = ctx.temp_dir.join(format!(
+            "{}_linked.mkv",
+            key.replace(' ', "_").to_lowercase()
+        ));
+        (ctx.log)(&format!(
+            "[Linked Timeline] Assembling {key} into {} ({} part(s))...",
+            out_path.display(),
+            timeline.parts.len()
+        ));
+        assemble_linked_timeline(timeline, &out_path, runner, &ctx.tool_paths)
+            .map_err(|e| format!("Could not assemble the linked timeline of {key}: {e}"))?;
+        ctx.sources
+            .insert(key.to_string(), out_path.to_string_lossy().to_string());
+        Ok(())
+    }
+
+    /// Write the concat playlist and flat chapters for virtual reading.
+    fn stage_virtual(
+        &self,
+        ctx: &mut Context,
+        key: &str,
+        timeline: &LinkedTimeline,
+    ) -> Result<(), String> {
+        let stem = key.replace(' ', "_").to_lowercase();
+        let playlist = ctx.temp_dir.join(format!("{stem}_linked.ffconcat"));
+        let chapters_xml = ctx.temp_dir.join(format!("{stem}_linked_chapters.xml"));
+        std::fs::write(&playlist, timeline.ffconcat())
+            .map_err(|e| format!("Could not write {}: {e}", playlist.display()))?;
+        std::fs::write(&chapters_xml, timeline.flat_chapters_xml()?)
+            .map_err(|e| format!("Could not write {}: {e}", chapters_xml.display()))?;
+
+        (ctx.log)(&format!(
+            "[Linked Timeline] Reading {key} virtually through {}",
+            playlist.display()
+        ));
+        ctx.virtual_timelines.insert(
+            key.to_string(),
+            VirtualTimeline {
+                playlist: playlist.to_string_lossy().to_string(),
+                chapters_xml: chapters_xml.to_string_lossy().to_string(),
+            },
+        );
+        Ok(())
+    }
+}
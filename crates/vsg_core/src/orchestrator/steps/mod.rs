@@ -4,5 +4,6 @@ pub mod audio_correction_step;
 pub mod chapters_step;
 pub mod context;
 pub mod extract_step;
+pub mod linked_timeline_step;
 pub mod mux_step;
 pub mod subtitles_step;
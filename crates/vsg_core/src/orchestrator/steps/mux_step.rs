@@ -3,8 +3,10 @@
 use std::path::PathBuf;
 
 use crate::io::runner::CommandRunner;
+use crate::models::enums::TimestampFinalizer;
 use crate::models::jobs::MergePlan;
 use crate::mux::options_builder::MkvmergeOptionsBuilder;
+use crate::mux::provenance::build_provenance;
 
 use super::context::Context;
 
@@ -13,7 +15,7 @@ pub struct MuxStep;
 
 impl MuxStep {
     /// Run the mux planning step.
-    pub fn run(&self, ctx: &mut Context, _runner: &CommandRunner) -> Result<(), String> {
+    pub fn run(&self, ctx: &mut Context, runner: &CommandRunner) -> Result<(), String> {
         let plan = MergePlan {
             items: ctx.extracted_items.clone().unwrap_or_default(),
             delays: ctx.delays.clone().unwrap_or_default(),
@@ -26,8 +28,34 @@ impl MuxStep {
             subtitle_delays_ms: ctx.subtitle_delays_ms.clone(),
         };
 
-        let tokens = MkvmergeOptionsBuilder::build(&plan, &ctx.settings)?;
+        let mut tokens = MkvmergeOptionsBuilder::build(&plan, &ctx.settings)?;
 
+        // Lossless finalizer: rebase in the mux instead of remuxing afterwards
+        if ctx.settings.post_mux_normalize_timestamps
+            && ctx.settings.post_mux_finalizer == TimestampFinalizer::Mkvmerge
+        {
+            let rebase_ms = MkvmergeOptionsBuilder::fold_rebase(&mut tokens);
+            ctx.timestamp_rebase_ms = rebase_ms;
+            if rebase_ms > 0 {
+                runner.log_message(&format!(
+                    "[Mux] Folded a -{rebase_ms}ms timestamp rebase into the --sync offsets."
+                ));
+            }
+        }
+
+        if ctx.settings.write_provenance_tags {
+            let provenance = build_provenance(ctx, &tokens);
+            runner.log_message(&format!(
+                "[Mux] Recorded sync provenance ({} sources, {} tracks) for the final file.",
+                provenance.sources.len(),
+                provenance.tracks.len()
+            ));
+            ctx.provenance = Some(provenance);
+        }
+
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.record_mux_tokens(&tokens);
+        }
         ctx.out_file = None;
         ctx.tokens = Some(tokens);
         Ok(())
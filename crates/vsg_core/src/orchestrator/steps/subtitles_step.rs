@@ -3,8 +3,12 @@
 //! Unified subtitle processing step using SubtitleData.
 //! Pure coordinator — delegates to subtitle modules.
 
+use std::path::{Path, PathBuf};
+
 use crate::io::runner::CommandRunner;
 use crate::models::enums::TrackType;
+use crate::subtitles::data::SubtitleData;
+use crate::subtitles::operations::speed::apply_speed_ratio;
 
 use super::context::Context;
 
@@ -62,7 +66,7 @@ impl SubtitlesStep {
         }
 
         // Process Each Subtitle Track
-        for item in &items {
+        for (idx, item) in items.iter().enumerate() {
             if item.track.track_type != TrackType::Subtitles {
                 continue;
             }
@@ -91,9 +95,29 @@ impl SubtitlesStep {
                     runner.log_message(&format!(
                         "[Subtitles] Track {track_id}: Processing through SubtitleData pipeline"
                     ));
-                    // The actual processing is delegated to track_processor::process_subtitle_track
-                    // which handles: load → filter → stepping → sync → style ops → save
+                    // Only the speed-ratio retime runs here; the rest of the
+                    // SubtitleData flow (track_processor) isn't wired into this step
+                    if let Some(ratio) = ctx.speed_ratio_for_source(&item.track.source) {
+                        (ctx.log)(&format!(
+                            "[Subtitles] Track {track_id}: Retiming by speed ratio {ratio:.6}"
+                        ));
+                        let rounding = ctx.settings.subtitle_rounding.to_string();
+                        let retimed = retime_text_subtitle(path, ratio, &rounding, &*ctx.log)
+                            .map_err(|e| format!("Track {track_id}: speed retime failed: {e}"))?;
+                        if let Some(plan) =
+                            ctx.extracted_items.as_mut().and_then(|v| v.get_mut(idx))
+                        {
+                            plan.extracted_path = Some(retimed);
+                        }
+                    }
                 } else if matches!(ext.as_str(), "sub" | "sup") {
+                    if let Some(ratio) = ctx.speed_ratio_for_source(&item.track.source) {
+                        (ctx.log)(&format!(
+                            "[WARNING] Subtitles Track {track_id}: bitmap .{ext} cannot be retimed \
+                             by speed ratio {ratio:.6}; it will drift against the corrected audio. \
+                             Enable OCR for this track to retime it."
+                        ));
+                    }
                     // Bitmap subtitles — can use video-verified for delay but can't process text
                     if subtitle_sync_mode == "video-verified" {
                         runner.log_message(&format!(
@@ -128,8 +152,113 @@ impl SubtitlesStep {
             || subtitle_sync_mode != "time-based"
             || use_raw_values
             || (ctx.stepping_edls.contains_key(&item.track.source)
-                && ctx.settings.stepping_adjust_subtitles);
+                && ctx.settings.stepping_adjust_subtitles)
+            || ctx.speed_ratio_for_source(&item.track.source).is_some();
 
         !needs_subtitle_data && bypass_subtitle_data && subtitle_sync_mode == "time-based"
     }
 }
+
+/// Scale a text subtitle's timing by `ratio` and save it. WebVTT has no
+/// writer, so it is saved as SRT next to the original; returns the new path.
+fn retime_text_subtitle(
+    path: &Path,
+    ratio: f64,
+    rounding: &str,
+    log: &dyn Fn(&str),
+) -> Result<PathBuf, String> {
+    let mut data = SubtitleData::from_file(path)?;
+    let result = apply_speed_ratio(&mut data, ratio, Some(log));
+    if !result.success {
+        return Err(result.error.unwrap_or_else(|| "unknown error".to_string()));
+    }
+
+    let is_vtt = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("vtt"));
+    let out = if is_vtt {
+        path.with_extension("srt")
+    } else {
+        path.to_path_buf()
+    };
+    data.save(&out, Some(rounding))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::models::context_types::DriftFlagsEntry;
+    use crate::models::events::noop_event_callback;
+    use crate::models::jobs::PlanItem;
+    use crate::models::settings::AppSettings;
+
+    fn plan_item(id: i32, track_type: &str, path: &Path, is_corrected: bool) -> PlanItem {
+        serde_json::from_value(serde_json::json!({
+            "track": {"source": "Source 2", "id": id, "type": track_type,
+                      "props": {"codec_id": "X", "lang": "eng"}},
+            "extracted_path": path,
+            "is_corrected": is_corrected,
+        }))
+        .unwrap()
+    }
+
+    fn context(dir: &Path, items: Vec<PlanItem>, lines: Arc<Mutex<Vec<String>>>) -> Context {
+        let mut ctx = Context::new(
+            AppSettings::default(),
+            HashMap::new(),
+            Box::new(move |msg: &str| lines.lock().unwrap().push(msg.to_string())),
+            Box::new(|_| {}),
+            noop_event_callback(),
+            String::new(),
+            dir.to_path_buf(),
+            HashMap::new(),
+            true,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            HashMap::new(),
+        );
+        ctx.extracted_items = Some(items);
+        ctx.speed_ratio_flags.insert(
+            "Source 2_1".to_string(),
+            DriftFlagsEntry {
+                ratio: Some(1.5),
+                ..Default::default()
+            },
+        );
+        ctx
+    }
+
+    #[test]
+    fn speed_ratio_retimes_text_and_warns_for_bitmap() {
+        let dir = tempfile::tempdir().unwrap();
+        let srt = dir.path().join("track_3.srt");
+        std::fs::write(&srt, "1\n00:00:10,000 --> 00:00:12,000\nHello\n\n").unwrap();
+        let sup = dir.path().join("track_4.sup");
+        std::fs::write(&sup, b"PG").unwrap();
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let items = vec![
+            plan_item(1, "audio", &dir.path().join("track_1.flac"), true),
+            plan_item(3, "subtitles", &srt, false),
+            plan_item(4, "subtitles", &sup, false),
+        ];
+        let mut ctx = context(dir.path(), items, Arc::clone(&lines));
+        let runner = CommandRunner::new(AppSettings::default(), Box::new(|_| {}));
+        SubtitlesStep.run(&mut ctx, &runner).unwrap();
+
+        let retimed = std::fs::read_to_string(&srt).unwrap();
+        assert!(
+            retimed.contains("00:00:15,000 --> 00:00:18,000"),
+            "{retimed}"
+        );
+        let lines = lines.lock().unwrap();
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("[WARNING]") && l.contains("Track 4")));
+    }
+}
@@ -99,6 +99,12 @@ impl StepValidator {
             Self::check_corrected_tracks(items, source_key, "PAL drift", &mut errors);
         }
 
+        // Check speed-ratio corrections
+        for analysis_key in ctx.speed_ratio_flags.keys() {
+            let source_key = analysis_key.split('_').next().unwrap_or("");
+            Self::check_corrected_tracks(items, source_key, "Speed ratio", &mut errors);
+        }
+
         // Check linear drift corrections
         for analysis_key in ctx.linear_drift_flags.keys() {
             let source_key = analysis_key.split('_').next().unwrap_or("");
@@ -156,7 +162,7 @@ impl StepValidator {
         }
 
         for item in corrected {
-            if item.track.props.codec_id != "FLAC" {
+            if item.track.props.codec_id != "FLAC" && item.correction_transcode.is_none() {
                 errors.push(format!(
                     "{correction_type} corrected track for {source_key} is not FLAC: {}",
                     item.track.props.codec_id
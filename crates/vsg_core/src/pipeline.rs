@@ -6,22 +6,36 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
+use crate::chapters::generate::generate_batch_chapters;
 use crate::io::runner::CommandRunner;
 use crate::models::context_types::ManualLayoutItem;
+use crate::models::events::{noop_event_callback, EventCallback, JobEvent};
 use crate::models::jobs::PipelineResult;
 use crate::models::settings::AppSettings;
+use crate::mux::provenance::write_provenance;
+use crate::orchestrator::steps::context::Context;
+use crate::pipeline_components::event_stream::EventStream;
 use crate::pipeline_components::log_manager::LogManager;
+use crate::pipeline_components::output_naming::{uses_planned_fields, OutputNameFields};
 use crate::pipeline_components::output_writer::OutputWriter;
+use crate::pipeline_components::result_auditor::ResultAuditor;
 use crate::pipeline_components::sync_executor::SyncExecutor;
 use crate::pipeline_components::tool_validator::ToolValidator;
+use crate::postprocess::auditors::base::get_metadata;
+use crate::reporting::junit::report_to_junit;
+use crate::reporting::report_writer::ReportWriter;
 
 /// Orchestrates video sync job execution — `JobPipeline`
 pub struct JobPipeline {
     pub settings: AppSettings,
     gui_log_callback: Arc<dyn Fn(&str) + Send + Sync>,
     progress: Arc<dyn Fn(f64) + Send + Sync>,
+    event_callback: Option<EventCallback>,
     tool_paths: HashMap<String, String>,
+    /// Chapter file generated for each Source 1 by `generate_batch_chapters`.
+    generated_chapters: HashMap<String, String>,
 }
 
 impl JobPipeline {
@@ -35,10 +49,57 @@ impl JobPipeline {
             settings: config,
             gui_log_callback: Arc::from(log_callback),
             progress: Arc::from(progress_callback),
+            event_callback: None,
             tool_paths: HashMap::new(),
+            generated_chapters: HashMap::new(),
         }
     }
 
+    /// Generate chapters for a whole batch before its jobs run.
+    ///
+    /// Opening/ending detection compares the episodes with each other, so it
+    /// cannot happen inside a single job. Each job whose sources have no
+    /// chapters then falls back to the file generated for its Source 1.
+    pub fn generate_batch_chapters(&mut self, source1_files: &[String]) {
+        self.generated_chapters.clear();
+        if !self.settings.generate_chapters {
+            return;
+        }
+
+        let gui_cb = Arc::clone(&self.gui_log_callback);
+        let runner = CommandRunner::new(
+            self.settings.clone(),
+            Box::new(move |msg: &str| gui_cb(msg)),
+        );
+        match ToolValidator::validate_tools() {
+            Ok(paths) => self.tool_paths = paths,
+            Err(e) => {
+                runner.log_message(&format!("[ERROR] {e}"));
+                return;
+            }
+        }
+
+        let base_temp = if !self.settings.temp_root.is_empty() {
+            PathBuf::from(&self.settings.temp_root)
+        } else {
+            std::env::current_dir()
+                .unwrap_or_else(|_| PathBuf::from("."))
+                .join("temp_work")
+        };
+        self.generated_chapters = generate_batch_chapters(
+            source1_files,
+            &base_temp.join("generated_chapters"),
+            &runner,
+            &self.tool_paths,
+            &self.settings,
+        );
+    }
+
+    /// Receive typed `JobEvent`s alongside the log and progress callbacks.
+    pub fn set_event_callback(&mut self, event_callback: Box<dyn Fn(&JobEvent) + Send + Sync>) {
+        self.event_callback = Some(Arc::from(event_callback));
+    }
+
     /// Run a complete sync job — `run_job()`
     pub fn run_job(
         &mut self,
@@ -47,7 +108,48 @@ impl JobPipeline {
         output_dir_str: &str,
         manual_layout: Option<Vec<ManualLayoutItem>>,
         attachment_sources: Option<Vec<String>>,
+        extra_attachments: Option<Vec<String>>,
+        source_settings: Option<HashMap<String, serde_json::Value>>,
+    ) -> PipelineResult {
+        let started = Instant::now();
+        let mut events = self
+            .event_callback
+            .clone()
+            .unwrap_or_else(noop_event_callback);
+
+        let result = self.execute_job(
+            sources,
+            and_merge,
+            output_dir_str,
+            manual_layout,
+            attachment_sources,
+            extra_attachments,
+            source_settings,
+            &mut events,
+        );
+
+        events(&JobEvent::JobFinished {
+            name: result.name.clone(),
+            status: result.status.clone(),
+            output: result.output.clone(),
+            error: result.error.clone(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+        result
+    }
+
+    /// Job body for `run_job`; swaps `events` for the file sink once the
+    /// job's output directory is known.
+    fn execute_job(
+        &mut self,
+        sources: &HashMap<String, String>,
+        and_merge: bool,
+        output_dir_str: &str,
+        manual_layout: Option<Vec<ManualLayoutItem>>,
+        attachment_sources: Option<Vec<String>>,
+        extra_attachments: Option<Vec<String>>,
         source_settings: Option<HashMap<String, serde_json::Value>>,
+        events: &mut EventCallback,
     ) -> PipelineResult {
         // --- 1. Input Validation ---
         let source1_file = match sources.get("Source 1") {
@@ -91,6 +193,21 @@ impl JobPipeline {
                 };
             }
         };
+        let log_to_all: Arc<dyn Fn(&str) + Send + Sync> = Arc::from(log_to_all);
+
+        // --- 2b. Setup Event Stream (optional) ---
+        if self.settings.log_event_stream {
+            match EventStream::setup_job_stream(&job_name, &output_dir, Some(events.clone())) {
+                Ok((handle, emit)) => {
+                    log_to_all(&format!(
+                        "[Events] Writing event stream to {}",
+                        handle.path().display()
+                    ));
+                    *events = emit;
+                }
+                Err(e) => log_to_all(&format!("[WARNING] {e}")),
+            }
+        }
 
         // --- 3. Validate Tools ---
         match ToolValidator::validate_tools() {
@@ -106,6 +223,24 @@ impl JobPipeline {
             }
         }
 
+        // --- 3b. Detect Tool Versions / Capabilities ---
+        let tools = {
+            let runner = CommandRunner::new(self.settings.clone(), Box::new(|_msg: &str| {}));
+            ToolValidator::detect_capabilities(&self.tool_paths, &runner)
+        };
+        for line in ToolValidator::describe(&tools) {
+            log_to_all(&line);
+        }
+        if let Err(e) = ToolValidator::check_requirements(&self.settings, &tools) {
+            log_to_all(&format!("[ERROR] {e}"));
+            return PipelineResult {
+                status: "Failed".to_string(),
+                name: source1_name,
+                error: Some(e),
+                ..PipelineResult::empty()
+            };
+        }
+
         log_to_all(&format!("=== Starting Job: {source1_name} ==="));
         (self.progress)(0.0);
 
@@ -121,24 +256,76 @@ impl JobPipeline {
             };
         }
 
+        let mut runner = CommandRunner::new(
+            self.settings.clone(),
+            Box::new(|_msg: &str| {}),
+        );
+        runner.set_event_callback(events.clone());
+        runner.set_step("merge");
+        let source1_info = || get_metadata(&source1_file, "mkvmerge", &runner, &self.tool_paths);
+
+        // --- 4b. Resolve Output Path (unless the name needs the analysis) ---
+        let early_output_path =
+            if and_merge && !uses_planned_fields(&self.settings.output_name_template) {
+                let name_fields =
+                    OutputNameFields::from_job(sources, &[], None, source1_info().as_ref());
+                match OutputWriter::resolve_output_path(
+                    &output_dir,
+                    &source1_name,
+                    &self.settings,
+                    &name_fields,
+                ) {
+                    Ok(Some(path)) => Some(path),
+                    Ok(None) => return skip_job(None, source1_name, &*log_to_all),
+                    Err(e) => {
+                        log_to_all(&format!("[ERROR] {e}"));
+                        return PipelineResult {
+                            status: "Failed".to_string(),
+                            name: source1_name,
+                            error: Some(e),
+                            ..PipelineResult::empty()
+                        };
+                    }
+                }
+            } else {
+                None
+            };
+
         // --- 5. Plan Sync (via Orchestrator) ---
+        let mut source_settings = source_settings.unwrap_or_default();
+        if let Some(chapter_file) = self.generated_chapters.get(&source1_file) {
+            let entry = source_settings
+                .entry("Source 1".to_string())
+                .or_insert_with(|| serde_json::json!({}));
+            if let Some(obj) = entry.as_object_mut() {
+                obj.insert(
+                    "generated_chapter_file".to_string(),
+                    serde_json::json!(chapter_file),
+                );
+            }
+        }
+
         let orch = crate::orchestrator::pipeline::Orchestrator;
         let progress = Arc::clone(&self.progress);
+        let orch_log = Arc::clone(&log_to_all);
 
         let ctx_result = orch.run(
             &self.settings,
             &self.tool_paths,
-            Box::new(move |msg: &str| log_to_all(msg)),
+            &tools,
+            Box::new(move |msg: &str| orch_log(msg)),
             Box::new(move |pct: f64| progress(pct)),
+            events.clone(),
             sources,
             and_merge,
             output_dir_str,
             manual_layout.unwrap_or_default(),
             attachment_sources.unwrap_or_default(),
-            source_settings.unwrap_or_default(),
+            extra_attachments.unwrap_or_default(),
+            source_settings,
         );
 
-        let ctx = match ctx_result {
+        let mut ctx = match ctx_result {
             Ok(c) => c,
             Err(e) => {
                 return PipelineResult {
@@ -152,6 +339,9 @@ impl JobPipeline {
 
         // --- 6. Return Early if Analysis Only ---
         if !and_merge {
+            if let Some(audit) = ctx.audit.as_mut() {
+                audit.finalize(None, true);
+            }
             (self.progress)(1.0);
             return PipelineResult {
                 status: "Analyzed".to_string(),
@@ -169,19 +359,32 @@ impl JobPipeline {
         let tokens = match ctx.tokens {
             Some(ref t) => t.clone(),
             None => {
-                return PipelineResult {
-                    status: "Failed".to_string(),
-                    name: source1_name,
-                    error: Some("Internal error: mkvmerge tokens were not generated.".to_string()),
-                    ..PipelineResult::empty()
-                };
+                let e = "Internal error: mkvmerge tokens were not generated.".to_string();
+                return fail_job(&mut ctx, source1_name, e);
             }
         };
 
-        let final_output_path = OutputWriter::prepare_output_path(
-            &output_dir,
-            &source1_name,
-        );
+        let final_output_path = match early_output_path {
+            Some(path) => path,
+            None => {
+                let name_fields = OutputNameFields::from_job(
+                    &ctx.sources,
+                    ctx.extracted_items.as_deref().unwrap_or(&[]),
+                    ctx.delays.as_ref(),
+                    source1_info().as_ref(),
+                );
+                match OutputWriter::resolve_output_path(
+                    &output_dir,
+                    &source1_name,
+                    &self.settings,
+                    &name_fields,
+                ) {
+                    Ok(Some(path)) => path,
+                    Ok(None) => return skip_job(Some(&mut ctx), source1_name, &*log_to_all),
+                    Err(e) => return fail_job(&mut ctx, source1_name, e),
+                }
+            }
+        };
         let temp_output_name = format!("temp_{source1_name}");
         let mkvmerge_output_path = ctx.temp_dir.join(&temp_output_name);
 
@@ -191,11 +394,6 @@ impl JobPipeline {
         ];
         full_tokens.extend(tokens);
 
-        let runner = CommandRunner::new(
-            self.settings.clone(),
-            Box::new(|_msg: &str| {}),
-        );
-
         let opts_path = match OutputWriter::write_mkvmerge_options(
             &full_tokens,
             &ctx.temp_dir,
@@ -203,48 +401,91 @@ impl JobPipeline {
             &runner,
         ) {
             Ok(p) => p,
-            Err(e) => {
-                return PipelineResult {
-                    status: "Failed".to_string(),
-                    name: source1_name,
-                    error: Some(e),
-                    ..PipelineResult::empty()
-                };
-            }
+            Err(e) => return fail_job(&mut ctx, source1_name, e),
         };
 
-        if !SyncExecutor::execute_merge(&opts_path, &self.tool_paths, &runner) {
-            return PipelineResult {
-                status: "Failed".to_string(),
-                name: source1_name,
-                error: Some("mkvmerge execution failed.".to_string()),
-                ..PipelineResult::empty()
-            };
+        events(&JobEvent::StepStarted {
+            step: "merge".to_string(),
+        });
+        let merge_started = Instant::now();
+        let merged = SyncExecutor::execute_merge(&opts_path, &self.tool_paths, &runner)
+            .map_err(|e| format!("mkvmerge execution failed: {e}"));
+        events(&JobEvent::StepFinished {
+            step: "merge".to_string(),
+            ok: merged.is_ok(),
+            duration_ms: merge_started.elapsed().as_millis() as u64,
+            error: merged.as_ref().err().cloned(),
+        });
+        if let Err(e) = merged {
+            return fail_job(&mut ctx, source1_name, e);
         }
 
-        if let Err(e) = SyncExecutor::finalize_output(
+        events(&JobEvent::StepStarted {
+            step: "finalize".to_string(),
+        });
+        let finalize_started = Instant::now();
+        if self.settings.post_mux_normalize_timestamps {
+            ctx.pre_finalize_mkvmerge = get_metadata(
+                &mkvmerge_output_path.to_string_lossy(),
+                "mkvmerge",
+                &runner,
+                &self.tool_paths,
+            );
+        }
+        let finalized = SyncExecutor::finalize_output(
             &mkvmerge_output_path,
             &final_output_path,
             &self.settings,
             &self.tool_paths,
             &runner,
-        ) {
-            return PipelineResult {
-                status: "Failed".to_string(),
-                name: source1_name,
-                error: Some(e),
-                ..PipelineResult::empty()
-            };
+        );
+        events(&JobEvent::StepFinished {
+            step: "finalize".to_string(),
+            ok: finalized.is_ok(),
+            duration_ms: finalize_started.elapsed().as_millis() as u64,
+            error: finalized.as_ref().err().cloned(),
+        });
+        if let Err(e) = finalized {
+            return fail_job(&mut ctx, source1_name, e);
         }
 
-        // --- Cleanup ---
-        if ctx.temp_dir.exists() {
-            let _ = std::fs::remove_dir_all(&ctx.temp_dir);
+        // Provenance goes on last so finalization can't flatten or strip it
+        if let Some(provenance) = ctx.provenance.as_ref() {
+            if let Err(e) = write_provenance(
+                &final_output_path,
+                provenance,
+                &ctx.temp_dir,
+                &self.settings,
+                &runner,
+                &self.tool_paths,
+            ) {
+                log_to_all(&format!("[WARNING] {e}"));
+            }
+        }
+
+        // --- Post-Merge Audit ---
+        let audit_log = Arc::clone(&log_to_all);
+        let audit_runner = CommandRunner::new(
+            self.settings.clone(),
+            Box::new(move |msg: &str| audit_log(msg)),
+        );
+        let audit = ResultAuditor::audit_output(
+            &final_output_path,
+            &ctx,
+            &audit_runner,
+            &*log_to_all,
+        );
+
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.finalize(Some(&final_output_path.to_string_lossy()), true);
         }
+
+        // --- Cleanup ---
+        remove_temp_dir(&ctx);
         drop(log_handle);
 
         (self.progress)(1.0);
-        PipelineResult {
+        let result = PipelineResult {
             status: "Merged".to_string(),
             name: source1_name,
             output: Some(final_output_path.to_string_lossy().to_string()),
@@ -252,13 +493,81 @@ impl JobPipeline {
             stepping_sources: ctx.stepping_sources,
             stepping_detected_disabled: ctx.stepping_detected_disabled,
             stepping_detected_separated: ctx.stepping_detected_separated,
+            issues: audit.total_issues,
             stepping_quality_issues: ctx.stepping_quality_issues,
             sync_stability_issues: ctx.sync_stability_issues,
+            post_mux_sync: audit.post_mux_sync,
+            audit_findings: audit.findings,
+            audits_run: audit.auditors_run,
             ..PipelineResult::empty()
+        };
+
+        if self.settings.audit_junit_export {
+            let junit_path = output_dir.join(format!("{job_name}.audit.junit.xml"));
+            if let Err(e) = write_audit_junit(&result, &junit_path) {
+                log_to_all(&format!("[WARNING] {e}"));
+            }
         }
+        result
+    }
+}
+
+/// Failed result for a job whose orchestrator run completed.
+fn fail_job(ctx: &mut Context, name: String, error: String) -> PipelineResult {
+    if let Some(audit) = ctx.audit.as_mut() {
+        audit.append_event("error", &error, None);
+        audit.finalize(None, false);
+    }
+    remove_temp_dir(ctx);
+    PipelineResult {
+        status: "Failed".to_string(),
+        name,
+        error: Some(error),
+        ..PipelineResult::empty()
     }
 }
 
+/// Skipped result for a job whose output already exists. `ctx` is `None`
+/// when the collision was found before the orchestrator ran.
+fn skip_job(ctx: Option<&mut Context>, name: String, log: &dyn Fn(&str)) -> PipelineResult {
+    log(&format!(
+        "[Output] {name}: output already exists, skipping (collision policy: skip)."
+    ));
+    let delays = ctx.and_then(|ctx| {
+        if let Some(audit) = ctx.audit.as_mut() {
+            audit.finalize(None, true);
+        }
+        remove_temp_dir(ctx);
+        ctx.delays.as_ref().map(|d| d.source_delays_ms.clone())
+    });
+    PipelineResult {
+        status: "Skipped".to_string(),
+        name,
+        delays,
+        ..PipelineResult::empty()
+    }
+}
+
+fn remove_temp_dir(ctx: &Context) {
+    if ctx.temp_dir.exists() {
+        let _ = std::fs::remove_dir_all(&ctx.temp_dir);
+    }
+}
+
+/// Write one job's audit findings as a JUnit XML file.
+fn write_audit_junit(result: &PipelineResult, path: &Path) -> Result<(), String> {
+    let job_result: HashMap<String, serde_json::Value> = serde_json::to_value(result)
+        .ok()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or_else(|| "Could not serialize audit results".to_string())?;
+    let report = serde_json::json!({
+        "batch_name": result.name,
+        "jobs": [ReportWriter::job_entry(&job_result, 0)],
+    });
+    std::fs::write(path, report_to_junit(&report))
+        .map_err(|e| format!("Failed to write JUnit audit report: {e}"))
+}
+
 impl PipelineResult {
     /// Create an empty PipelineResult with defaults.
     pub fn empty() -> Self {
@@ -274,6 +583,9 @@ impl PipelineResult {
             stepping_detected_separated: Vec::new(),
             stepping_quality_issues: Vec::new(),
             sync_stability_issues: Vec::new(),
+            post_mux_sync: Vec::new(),
+            audit_findings: Vec::new(),
+            audits_run: Vec::new(),
         }
     }
 }
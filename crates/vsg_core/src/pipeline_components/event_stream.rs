@@ -0,0 +1,114 @@
+//! Event stream — per-job JSON-lines sink for `JobEvent`s.
+//!
+//! Mirrors `LogManager`: the job's events go to `{job_name}.events.jsonl`
+//! next to the job log, one JSON object per line, flushed as written so the
+//! file can be tailed while the job runs.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::models::events::{EventCallback, JobEvent};
+
+/// One line of the event stream: timestamp + job name + the event itself.
+#[derive(Serialize)]
+struct EventRecord<'a> {
+    ts: String,
+    job: &'a str,
+    #[serde(flatten)]
+    event: &'a JobEvent,
+}
+
+/// Creates per-job event stream files.
+pub struct EventStream;
+
+/// Handle to a job's event stream file. The file itself is owned by the
+/// returned callback and closes when the last clone of it is dropped.
+pub struct EventStreamHandle {
+    path: PathBuf,
+}
+
+impl EventStream {
+    /// Sets up the JSON-lines sink for a job.
+    ///
+    /// Returns the handle and a callback that appends to the file and then
+    /// forwards to `forward` (if any).
+    pub fn setup_job_stream(
+        job_name: &str,
+        dir: &Path,
+        forward: Option<EventCallback>,
+    ) -> Result<(EventStreamHandle, EventCallback), String> {
+        let _ = fs::create_dir_all(dir);
+        let path = dir.join(format!("{job_name}.events.jsonl"));
+        let file =
+            File::create(&path).map_err(|e| format!("Failed to create event stream file: {e}"))?;
+        let file = Arc::new(Mutex::new(file));
+
+        let handle = EventStreamHandle { path };
+
+        let job = job_name.to_string();
+        let emit: EventCallback = Arc::new(move |event: &JobEvent| {
+            let record = EventRecord {
+                ts: Local::now().to_rfc3339(),
+                job: &job,
+                event,
+            };
+            if let (Ok(line), Ok(mut f)) = (serde_json::to_string(&record), file.lock()) {
+                let _ = writeln!(f, "{line}");
+                let _ = f.flush();
+            }
+            if let Some(ref cb) = forward {
+                cb(event);
+            }
+        });
+
+        Ok((handle, emit))
+    }
+}
+
+impl EventStreamHandle {
+    /// Get the path to the event stream file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_one_json_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let seen = Arc::new(Mutex::new(0));
+        let seen_cb = Arc::clone(&seen);
+        let forward: EventCallback = Arc::new(move |_e: &JobEvent| {
+            *seen_cb.lock().unwrap() += 1;
+        });
+
+        let (handle, emit) =
+            EventStream::setup_job_stream("job", dir.path(), Some(forward)).unwrap();
+        emit(&JobEvent::StepStarted {
+            step: "analysis".to_string(),
+        });
+        emit(&JobEvent::AuditorFinding {
+            auditor: "Track Order".to_string(),
+            issues: 2,
+        });
+
+        let text = fs::read_to_string(handle.path()).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "step_started");
+        assert_eq!(lines[0]["job"], "job");
+        assert_eq!(lines[1]["issues"], 2);
+        assert_eq!(*seen.lock().unwrap(), 2);
+    }
+}
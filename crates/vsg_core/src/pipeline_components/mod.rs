@@ -1,4 +1,6 @@
+pub mod event_stream;
 pub mod log_manager;
+pub mod output_naming;
 pub mod output_writer;
 pub mod result_auditor;
 pub mod sync_executor;
@@ -0,0 +1,418 @@
+//! Output path templates.
+//!
+//! `output_name_template` is rendered relative to the output folder. Fields
+//! are written `{field}` or `{field:02}` (zero-padded numbers); `/` creates
+//! subfolders; text inside `<...>` is dropped when any field in it is empty,
+//! so `Show< - E{episode:02}>` works with and without an episode number.
+//!
+//! Fields: `source1`..`sourceN` (file stems), `name` (Source 1 stem),
+//! `season`, `episode`, `resolution`, `width`, `height`, `vcodec`, `acodec`,
+//! `audio_langs`, `sub_langs`, `delays`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::models::enums::{OutputCollision, TrackType};
+use crate::models::jobs::{Delays, PlanItem};
+
+/// Season/episode patterns, most specific first.
+static EPISODE_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"(?i)\bS(?P<season>\d{1,2})[ ._-]?E(?P<episode>\d{1,4})",
+        r"(?i)\b(?P<season>\d{1,2})x(?P<episode>\d{2,3})\b",
+        r"(?i)\b(?:EP?|Episode)[ ._]?(?P<episode>\d{1,4})\b",
+        r" - (?P<episode>\d{1,4})(?:v\d)?\b",
+    ]
+    .iter()
+    .map(|p| Regex::new(p).expect("valid episode pattern"))
+    .collect()
+});
+
+/// Fields only known after analysis and track planning.
+const PLANNED_FIELDS: [&str; 5] = ["vcodec", "acodec", "audio_langs", "sub_langs", "delays"];
+
+/// Whether `template` needs the analysis and plan to render, i.e. whether
+/// the output path can only be resolved after the job has run.
+pub fn uses_planned_fields(template: &str) -> bool {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(spec, _)| spec.split(':').next().unwrap_or_default().trim())
+        .any(|name| PLANNED_FIELDS.contains(&name))
+}
+
+/// Values available to an output template.
+#[derive(Debug, Clone, Default)]
+pub struct OutputNameFields {
+    values: HashMap<String, String>,
+}
+
+impl OutputNameFields {
+    /// Collect fields for a job from its sources, final plan and delays.
+    ///
+    /// `source1_info` is Source 1's `mkvmerge -J` output, used for the
+    /// video resolution.
+    pub fn from_job(
+        sources: &HashMap<String, String>,
+        items: &[PlanItem],
+        delays: Option<&Delays>,
+        source1_info: Option<&serde_json::Value>,
+    ) -> Self {
+        let mut fields = Self::default();
+
+        for (key, path) in sources {
+            let stem = Path::new(path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            fields.set(&key.to_lowercase().replace(' ', ""), stem);
+        }
+        let name = fields.get("source1").to_string();
+        let (season, episode) = parse_episode(&name);
+        fields.set("name", name);
+        fields.set("season", season.unwrap_or_default());
+        fields.set("episode", episode.unwrap_or_default());
+
+        let dims = source1_info
+            .and_then(|info| info.get("tracks")?.as_array())
+            .and_then(|tracks| {
+                tracks
+                    .iter()
+                    .find(|t| t.get("type").and_then(|v| v.as_str()) == Some("video"))
+            })
+            .and_then(|t| t["properties"]["pixel_dimensions"].as_str())
+            .and_then(|d| d.split_once('x'))
+            .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)));
+        if let Some((width, height)) = dims {
+            fields.set("width", width.to_string());
+            fields.set("height", height.to_string());
+            fields.set("resolution", resolution_label(width, height));
+        }
+
+        let first_codec = |kind: TrackType| {
+            items
+                .iter()
+                .find(|it| it.track.track_type == kind)
+                .map(|it| short_codec(&it.track.props.codec_id))
+                .unwrap_or_default()
+        };
+        fields.set("vcodec", first_codec(TrackType::Video));
+        fields.set("acodec", first_codec(TrackType::Audio));
+        fields.set("audio_langs", languages(items, TrackType::Audio));
+        fields.set("sub_langs", languages(items, TrackType::Subtitles));
+
+        if let Some(delays) = delays {
+            let mut parts: Vec<(&String, &i32)> = delays
+                .source_delays_ms
+                .iter()
+                .filter(|(key, _)| key.as_str() != "Source 1")
+                .collect();
+            parts.sort();
+            let summary: Vec<String> = parts
+                .into_iter()
+                .map(|(key, ms)| format!("{}{ms:+}", key.replace("Source ", "S")))
+                .collect();
+            fields.set("delays", summary.join("_"));
+        }
+
+        fields
+    }
+
+    pub fn set(&mut self, field: &str, value: impl Into<String>) {
+        self.values.insert(field.to_string(), value.into());
+    }
+
+    fn get(&self, field: &str) -> &str {
+        self.values.get(field).map(String::as_str).unwrap_or("")
+    }
+}
+
+/// Render `template` to a path relative to the output folder.
+///
+/// Unknown fields and unbalanced braces are errors so a typo in the
+/// template fails the job instead of misnaming the archive.
+pub fn render_template(template: &str, fields: &OutputNameFields) -> Result<PathBuf, String> {
+    let mut rendered = String::new();
+    // Text of the open `<...>` group and whether a field in it was empty
+    let mut group: Option<(String, bool)> = None;
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => return Err("Unclosed '{' in output template".to_string()),
+                    }
+                }
+                let (name, format) = spec.split_once(':').unwrap_or((spec.as_str(), ""));
+                let name = name.trim();
+                if name.is_empty() || name.contains('{') {
+                    return Err(format!("Invalid field '{{{spec}' in output template"));
+                }
+                if !fields.values.contains_key(name) {
+                    return Err(format!("Unknown output template field '{{{name}}}'"));
+                }
+                let value = apply_format(fields.get(name), format)?;
+                match group.as_mut() {
+                    Some((text, empty)) => {
+                        *empty |= value.is_empty();
+                        text.push_str(&value);
+                    }
+                    None => rendered.push_str(&value),
+                }
+            }
+            '<' if group.is_none() => group = Some((String::new(), false)),
+            '>' if group.is_some() => {
+                let (text, empty) = group.take().unwrap_or_default();
+                if !empty {
+                    rendered.push_str(&text);
+                }
+            }
+            '}' | '<' | '>' => {
+                return Err(format!("Unbalanced '{c}' in output template"));
+            }
+            _ => match group.as_mut() {
+                Some((text, _)) => text.push(c),
+                None => rendered.push(c),
+            },
+        }
+    }
+    if group.is_some() {
+        return Err("Unclosed '<' in output template".to_string());
+    }
+
+    if rendered.split('/').any(|part| part.trim() == "..") {
+        return Err("Output template may not leave the output folder".to_string());
+    }
+    let components: Vec<String> = rendered
+        .split('/')
+        .map(sanitize_component)
+        .filter(|part| !part.is_empty())
+        .collect();
+    let Some(last) = components.last() else {
+        return Err("Output template rendered an empty file name".to_string());
+    };
+
+    let mut path: PathBuf = components.iter().collect();
+    if !last.to_lowercase().ends_with(".mkv") {
+        path.set_file_name(format!("{last}.mkv"));
+    }
+    Ok(path)
+}
+
+/// Apply `policy` when `path` already exists; `None` means skip the job.
+pub fn resolve_collision(path: &Path, policy: OutputCollision) -> Option<PathBuf> {
+    if !path.exists() {
+        return Some(path.to_path_buf());
+    }
+    match policy {
+        OutputCollision::Overwrite => Some(path.to_path_buf()),
+        OutputCollision::Skip => None,
+        OutputCollision::Suffix => {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let ext = path
+                .extension()
+                .map(|e| format!(".{}", e.to_string_lossy()))
+                .unwrap_or_default();
+            (1..)
+                .map(|n| path.with_file_name(format!("{stem} ({n}){ext}")))
+                .find(|candidate| !candidate.exists())
+        }
+    }
+}
+
+/// Season and episode numbers from a file stem.
+fn parse_episode(stem: &str) -> (Option<String>, Option<String>) {
+    EPISODE_PATTERNS
+        .iter()
+        .find_map(|re| re.captures(stem))
+        .map(|caps| {
+            let number = |name: &str| {
+                caps.name(name)
+                    .and_then(|m| m.as_str().parse::<u32>().ok())
+                    .map(|n| n.to_string())
+            };
+            (number("season"), number("episode"))
+        })
+        .unwrap_or((None, None))
+}
+
+fn apply_format(value: &str, format: &str) -> Result<String, String> {
+    if format.is_empty() {
+        return Ok(value.to_string());
+    }
+    let width: usize = format
+        .strip_prefix('0')
+        .and_then(|w| w.parse().ok())
+        .ok_or_else(|| format!("Unsupported format ':{format}' in output template"))?;
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        Ok(format!("{value:0>width$}"))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Common name for the frame height, e.g. "1080p".
+fn resolution_label(width: u32, height: u32) -> String {
+    // Scope and cropped encodes keep their width but lose height
+    let standard = [
+        (3840, 2160),
+        (2560, 1440),
+        (1920, 1080),
+        (1280, 720),
+        (1024, 576),
+    ];
+    standard
+        .iter()
+        .find(|&&(w, h)| width == w || height == h)
+        .map(|&(_, h)| format!("{h}p"))
+        .unwrap_or_else(|| format!("{height}p"))
+}
+
+/// Short codec name from a Matroska codec ID.
+fn short_codec(codec_id: &str) -> String {
+    let upper = codec_id.to_uppercase();
+    let known = [
+        ("HEVC", "HEVC"),
+        ("AVC", "AVC"),
+        ("AV1", "AV1"),
+        ("VP9", "VP9"),
+        ("MPEG2", "MPEG2"),
+        ("TRUEHD", "TrueHD"),
+        ("EAC3", "EAC3"),
+        ("AC3", "AC3"),
+        ("DTS", "DTS"),
+        ("FLAC", "FLAC"),
+        ("OPUS", "Opus"),
+        ("AAC", "AAC"),
+        ("VORBIS", "Vorbis"),
+        ("PCM", "PCM"),
+        ("MPEG/L3", "MP3"),
+    ];
+    known
+        .iter()
+        .find(|(needle, _)| upper.contains(needle))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| codec_id.rsplit('/').next().unwrap_or(codec_id).to_string())
+}
+
+/// Distinct languages of one track type in output order, e.g. "jpn+eng".
+fn languages(items: &[PlanItem], kind: TrackType) -> String {
+    let mut langs: Vec<&str> = Vec::new();
+    for item in items.iter().filter(|it| it.track.track_type == kind) {
+        let lang = if item.custom_lang.is_empty() {
+            item.track.props.lang.as_str()
+        } else {
+            item.custom_lang.as_str()
+        };
+        if !langs.contains(&lang) {
+            langs.push(lang);
+        }
+    }
+    langs.join("+")
+}
+
+/// Make one path component safe on every platform.
+fn sanitize_component(part: &str) -> String {
+    let cleaned: String = part
+        .chars()
+        .map(|c| {
+            if "<>:\"\\|?*".contains(c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    cleaned.trim().trim_end_matches('.').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> OutputNameFields {
+        let mut f = OutputNameFields::default();
+        f.set("source1", "[Grp] Show - 05 (BD 1080p)");
+        f.set("episode", "5");
+        f.set("season", "");
+        f.set("resolution", "1080p");
+        f.set("audio_langs", "jpn+eng");
+        f
+    }
+
+    #[test]
+    fn renders_subfolders_padding_and_optional_groups() {
+        let path = render_template(
+            "Show/<Season {season}/>Show - E{episode:02}< S{season:02}> [{resolution}] [{audio_langs}]",
+            &fields(),
+        )
+        .unwrap();
+        assert_eq!(path, PathBuf::from("Show/Show - E05 [1080p] [jpn+eng].mkv"));
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_escapes() {
+        assert!(render_template("{episdoe}", &fields()).is_err());
+        assert!(render_template("../{source1}", &fields()).is_err());
+        assert!(render_template("{source1", &fields()).is_err());
+        assert!(render_template("{source1}/{episode", &fields()).is_err());
+        assert_eq!(
+            render_template("a:b/{source1}.mkv", &fields()).unwrap(),
+            PathBuf::from("a_b/[Grp] Show - 05 (BD 1080p).mkv")
+        );
+    }
+
+    #[test]
+    fn planned_fields_delay_path_resolution() {
+        assert!(!uses_planned_fields(""));
+        assert!(!uses_planned_fields(
+            "{name}/{name} - E{episode:02} [{resolution}]"
+        ));
+        assert!(uses_planned_fields("{name} [{ audio_langs }]"));
+        assert!(uses_planned_fields("{name} {delays:03}"));
+    }
+
+    #[test]
+    fn parses_common_episode_styles() {
+        assert_eq!(
+            parse_episode("Show.S02E07.1080p"),
+            (Some("2".to_string()), Some("7".to_string()))
+        );
+        assert_eq!(
+            parse_episode("[Grp] Show - 12v2 [ABCD]").1,
+            Some("12".to_string())
+        );
+        assert_eq!(parse_episode("Show EP03").1, Some("3".to_string()));
+        assert_eq!(parse_episode("Movie (2019)"), (None, None));
+    }
+
+    #[test]
+    fn suffix_policy_finds_a_free_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let taken = dir.path().join("ep.mkv");
+        std::fs::write(&taken, b"").unwrap();
+        std::fs::write(dir.path().join("ep (1).mkv"), b"").unwrap();
+
+        assert_eq!(
+            resolve_collision(&taken, OutputCollision::Suffix),
+            Some(dir.path().join("ep (2).mkv"))
+        );
+        assert_eq!(resolve_collision(&taken, OutputCollision::Skip), None);
+        assert_eq!(
+            resolve_collision(&taken, OutputCollision::Overwrite),
+            Some(taken)
+        );
+    }
+}
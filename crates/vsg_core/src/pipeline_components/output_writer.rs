@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 use crate::io::runner::CommandRunner;
 use crate::models::settings::AppSettings;
 
+use super::output_naming::{render_template, resolve_collision, OutputNameFields};
+
 /// Writes output files and mkvmerge configuration — `OutputWriter`
 pub struct OutputWriter;
 
@@ -46,4 +48,27 @@ impl OutputWriter {
     pub fn prepare_output_path(output_dir: &Path, source1_filename: &str) -> PathBuf {
         output_dir.join(source1_filename)
     }
+
+    /// Final output path from `output_name_template` and the collision
+    /// policy. `Ok(None)` means the job should be skipped.
+    pub fn resolve_output_path(
+        output_dir: &Path,
+        source1_filename: &str,
+        settings: &AppSettings,
+        fields: &OutputNameFields,
+    ) -> Result<Option<PathBuf>, String> {
+        let path = if settings.output_name_template.trim().is_empty() {
+            Self::prepare_output_path(output_dir, source1_filename)
+        } else {
+            output_dir.join(render_template(&settings.output_name_template, fields)?)
+        };
+        let Some(path) = resolve_collision(&path, settings.output_collision) else {
+            return Ok(None);
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output folder {}: {e}", parent.display()))?;
+        }
+        Ok(Some(path))
+    }
 }
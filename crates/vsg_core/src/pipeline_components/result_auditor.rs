@@ -6,7 +6,7 @@ use std::path::Path;
 
 use crate::io::runner::CommandRunner;
 use crate::orchestrator::steps::context::Context;
-use crate::postprocess::final_auditor::FinalAuditor;
+use crate::postprocess::final_auditor::{AuditSummary, FinalAuditor};
 
 /// Audits merged output files for quality and correctness — `ResultAuditor`
 pub struct ResultAuditor;
@@ -14,21 +14,24 @@ pub struct ResultAuditor;
 impl ResultAuditor {
     /// Audits the merged output file — `audit_output`
     ///
-    /// Returns the number of issues found (0 = no issues).
+    /// Returns the issue count (0 = no issues) and per-check details.
     pub fn audit_output(
         output_file: &Path,
         ctx: &Context,
         runner: &CommandRunner,
         log_callback: &dyn Fn(&str),
-    ) -> i32 {
+    ) -> AuditSummary {
         log_callback("--- Post-Merge: Running Final Audit ---");
 
-        let issues = FinalAuditor::run(ctx, runner, output_file);
+        let summary = FinalAuditor::run(ctx, runner, output_file);
 
-        if issues > 0 {
-            log_callback(&format!("[Audit] Found {issues} issue(s) in final output."));
+        if summary.total_issues > 0 {
+            log_callback(&format!(
+                "[Audit] Found {} issue(s) in final output.",
+                summary.total_issues
+            ));
         }
 
-        issues
+        summary
     }
 }
@@ -5,22 +5,35 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::io::runner::CommandRunner;
+use crate::io::runner::{CommandRunner, RunError};
+use crate::models::enums::TimestampFinalizer;
 use crate::models::settings::AppSettings;
-use crate::postprocess::finalizer::{check_if_rebasing_is_needed, finalize_merged_file};
+use crate::postprocess::finalizer::{
+    check_if_rebasing_is_needed, finalize_merged_file, finalize_with_mkvmerge,
+};
 
 /// Executes sync merges and finalizes output — `SyncExecutor`
 pub struct SyncExecutor;
 
 impl SyncExecutor {
     /// Executes mkvmerge with the provided options file — `execute_merge`
+    ///
+    /// Warnings are accepted, since mkvmerge still wrote a complete file;
+    /// the runner only retries them first when `tool_retry_warnings` is on.
     pub fn execute_merge(
         mkvmerge_options_path: &str,
         tool_paths: &HashMap<String, String>,
         runner: &CommandRunner,
-    ) -> bool {
+    ) -> Result<(), RunError> {
         let at_path = format!("@{mkvmerge_options_path}");
-        runner.run(&["mkvmerge", &at_path], tool_paths).is_some()
+        match runner.run(&["mkvmerge", &at_path], tool_paths) {
+            Ok(_) => Ok(()),
+            Err(RunError::Warnings { .. }) => {
+                runner.log_message("[WARNING] mkvmerge finished with warnings; keeping output.");
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
     }
 
     /// Finalizes the merged output file — `finalize_output`
@@ -33,7 +46,9 @@ impl SyncExecutor {
     ) -> Result<(), String> {
         let normalize_enabled = settings.post_mux_normalize_timestamps;
 
-        if normalize_enabled && check_if_rebasing_is_needed(temp_output_path, runner, tool_paths) {
+        if normalize_enabled && settings.post_mux_finalizer == TimestampFinalizer::Mkvmerge {
+            finalize_with_mkvmerge(temp_output_path, final_output_path, runner, settings, tool_paths)
+        } else if normalize_enabled && check_if_rebasing_is_needed(temp_output_path, runner, tool_paths) {
             finalize_merged_file(temp_output_path, final_output_path, runner, settings, tool_paths);
             Ok(())
         } else {
@@ -4,7 +4,8 @@
 
 use std::collections::HashMap;
 
-use crate::models::context_types::ManualLayoutItem;
+use crate::models::context_types::{ManualLayoutItem, ToolCapabilities};
+use crate::models::events::EventCallback;
 use crate::models::settings::AppSettings;
 use crate::orchestrator::pipeline::Orchestrator;
 use crate::orchestrator::steps::context::Context;
@@ -18,26 +19,32 @@ impl SyncPlanner {
     pub fn plan_sync(
         settings: &AppSettings,
         tool_paths: &HashMap<String, String>,
+        tools: &ToolCapabilities,
         log_callback: Box<dyn Fn(&str) + Send + Sync>,
         progress_callback: Box<dyn Fn(f64) + Send + Sync>,
+        event_callback: EventCallback,
         sources: &HashMap<String, String>,
         and_merge: bool,
         output_dir: &str,
         manual_layout: Vec<ManualLayoutItem>,
         attachment_sources: Vec<String>,
+        extra_attachments: Vec<String>,
         source_settings: HashMap<String, serde_json::Value>,
     ) -> Result<Context, String> {
         let orch = Orchestrator;
         orch.run(
             settings,
             tool_paths,
+            tools,
             log_callback,
             progress_callback,
+            event_callback,
             sources,
             and_merge,
             output_dir,
             manual_layout,
             attachment_sources,
+            extra_attachments,
             source_settings,
         )
     }
@@ -1,11 +1,25 @@
 //! Tool validation — 1:1 port of `vsg_core/pipeline_components/tool_validator.py`.
+//!
+//! Beyond locating the binaries, detects their versions and the FFmpeg
+//! features that settings depend on, so a job can fail before any work is
+//! done when a required capability is missing.
 
 use std::collections::HashMap;
 
+use regex::Regex;
+
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::{ToolCapabilities, ToolVersion};
+use crate::models::enums::{ResampleEngine, SpeedCorrectionMode};
+use crate::models::settings::AppSettings;
+use crate::orchestrator::steps::audio_correction_step::AudioCorrectionStep;
+
 /// Required external tools.
 const REQUIRED_TOOLS: &[&str] = &["ffmpeg", "ffprobe", "mkvmerge", "mkvextract", "mkvpropedit"];
 /// Optional external tools.
 const OPTIONAL_TOOLS: &[&str] = &["videodiff"];
+/// Minimum major versions: (tool, major, what depends on it).
+const MIN_VERSIONS: &[(&str, u32, &str)] = &[("mkvmerge", 54, "IETF BCP 47 language tags")];
 
 /// Validates and locates required external tools — `ToolValidator`
 pub struct ToolValidator;
@@ -39,4 +53,230 @@ impl ToolValidator {
 
         Ok(tool_paths)
     }
+
+    /// Detects versions of every located tool and FFmpeg's optional features.
+    pub fn detect_capabilities(
+        tool_paths: &HashMap<String, String>,
+        runner: &CommandRunner,
+    ) -> ToolCapabilities {
+        let mut caps = ToolCapabilities::default();
+
+        let mut tools: Vec<&String> = tool_paths.keys().collect();
+        tools.sort();
+        for tool in tools {
+            // FFmpeg tools take a single dash
+            let flag = if tool.starts_with("ff") {
+                "-version"
+            } else {
+                "--version"
+            };
+            if let Ok(out) = runner.run(&[tool, flag], tool_paths) {
+                caps.versions
+                    .insert(tool.clone(), parse_version_output(&out));
+
+                if tool == "ffmpeg" {
+                    caps.ffmpeg_features
+                        .insert("soxr".to_string(), out.contains("--enable-libsoxr"));
+                }
+            }
+        }
+
+        if tool_paths.contains_key("ffmpeg") {
+            if let Ok(out) = runner.run(&["ffmpeg", "-hide_banner", "-filters"], tool_paths) {
+                let filters = parse_filter_names(&out);
+                for name in ["rubberband", "atempo"] {
+                    caps.ffmpeg_features
+                        .insert(name.to_string(), filters.iter().any(|f| f == name));
+                }
+            }
+        }
+
+        caps
+    }
+
+    /// Fails when a setting needs a capability the detected tools lack.
+    ///
+    /// Unknown versions/features (probe failed) are not treated as missing.
+    pub fn check_requirements(
+        settings: &AppSettings,
+        caps: &ToolCapabilities,
+    ) -> Result<(), String> {
+        let missing = |feature: &str| caps.ffmpeg_features.get(feature) == Some(&false);
+
+        // (filter, what needs it)
+        let mut filters: Vec<(&str, String)> = Vec::new();
+        if AudioCorrectionStep::is_enabled(settings) {
+            // Stepping and linear drift correction both resample with this engine
+            let engine = settings.segment_resample_engine;
+            let needed_for = format!("segment_resample_engine = {engine}");
+            match engine {
+                ResampleEngine::Rubberband => filters.push(("rubberband", needed_for)),
+                ResampleEngine::Atempo => filters.push(("atempo", needed_for)),
+                ResampleEngine::Aresample | ResampleEngine::Native => {}
+            }
+            if settings.speed_correction_mode != SpeedCorrectionMode::Disabled
+                && settings.speed_correction_pitch_correct
+            {
+                filters.push(("rubberband", "speed_correction_pitch_correct".to_string()));
+            }
+            filters.push(("rubberband", "PAL drift correction".to_string()));
+        }
+        if let Some((filter, needed_for)) = filters.into_iter().find(|(f, _)| missing(f)) {
+            return Err(format!(
+                "{needed_for} requires the FFmpeg '{filter}' filter, \
+                 which this FFmpeg build does not provide."
+            ));
+        }
+
+        if settings.use_soxr && missing("soxr") {
+            return Err(
+                "use_soxr is enabled but this FFmpeg build was not configured with \
+                 --enable-libsoxr."
+                    .to_string(),
+            );
+        }
+
+        for (tool, min_major, needed_for) in MIN_VERSIONS {
+            let major = caps
+                .versions
+                .get(*tool)
+                .and_then(|v| v.version.as_deref())
+                .and_then(|v| v.split('.').next())
+                .and_then(|m| m.parse::<u32>().ok());
+            if let Some(major) = major.filter(|m| m < min_major) {
+                return Err(format!(
+                    "{tool} {major} is too old: version {min_major} or newer is required \
+                     for {needed_for}."
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// One log line per tool plus the FFmpeg feature summary.
+    pub fn describe(caps: &ToolCapabilities) -> Vec<String> {
+        let mut lines: Vec<String> = caps
+            .versions
+            .iter()
+            .map(|(tool, v)| match v.version {
+                Some(ref version) => format!("[Tools] {tool} {version}"),
+                None => format!("[Tools] {tool}: {}", v.raw),
+            })
+            .collect();
+        if !caps.ffmpeg_features.is_empty() {
+            let features: Vec<String> = caps
+                .ffmpeg_features
+                .iter()
+                .map(|(name, ok)| format!("{name}={}", if *ok { "yes" } else { "no" }))
+                .collect();
+            lines.push(format!("[Tools] FFmpeg features: {}", features.join(", ")));
+        }
+        lines
+    }
+}
+
+/// Parses the first line of a version banner, e.g.
+/// `ffmpeg version n6.1.1 Copyright ...` or `mkvmerge v82.0 ('...') 64-bit`.
+fn parse_version_output(output: &str) -> ToolVersion {
+    let raw = output.lines().next().unwrap_or("").trim().to_string();
+    let re = Regex::new(r"(?:^|\s)[vVnN]?(\d+(?:\.\d+)+)").unwrap();
+    let version = re.captures(&raw).map(|c| c[1].to_string());
+    ToolVersion { raw, version }
+}
+
+/// Filter names from `ffmpeg -filters` (lines like ` ..C rubberband  A->A  ...`).
+fn parse_filter_names(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let flags = parts.next()?;
+            let name = parts.next()?;
+            let io = parts.next()?;
+            (io.contains("->") && flags.chars().all(|c| c == '.' || c.is_ascii_uppercase()))
+                .then(|| name.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_version_banners() {
+        let ff =
+            parse_version_output("ffmpeg version n6.1.1 Copyright (c) 2000-2023\nbuilt with gcc");
+        assert_eq!(ff.version.as_deref(), Some("6.1.1"));
+        let mkv = parse_version_output("mkvmerge v82.0 ('I'm The President') 64-bit");
+        assert_eq!(mkv.version.as_deref(), Some("82.0"));
+        let git = parse_version_output("ffmpeg version N-112345-gabcdef Copyright");
+        assert_eq!(git.version, None);
+        assert!(git.raw.starts_with("ffmpeg version N-"));
+    }
+
+    #[test]
+    fn parses_filter_list() {
+        let out = "Filters:\n  T.. = Timeline support\n ------\n \
+                   ... atempo            A->A       Adjust audio tempo.\n \
+                   T.C volume            A->A       Change input volume.\n";
+        let names = parse_filter_names(out);
+        assert_eq!(names, vec!["atempo".to_string(), "volume".to_string()]);
+    }
+
+    #[test]
+    fn missing_rubberband_fails_fast() {
+        let settings = AppSettings {
+            stepping_enabled: true,
+            segment_resample_engine: ResampleEngine::Rubberband,
+            ..AppSettings::default()
+        };
+        let mut caps = ToolCapabilities::default();
+        assert!(ToolValidator::check_requirements(&settings, &caps).is_ok());
+
+        caps.ffmpeg_features.insert("rubberband".to_string(), false);
+        let err = ToolValidator::check_requirements(&settings, &caps).unwrap_err();
+        assert!(err.contains("rubberband"));
+
+        // Linear drift with the aresample engine still needs rubberband for PAL
+        let aresample = AppSettings {
+            segment_resample_engine: ResampleEngine::Aresample,
+            ..settings.clone()
+        };
+        let err = ToolValidator::check_requirements(&aresample, &caps).unwrap_err();
+        assert!(err.starts_with("PAL drift correction"));
+        let pitch = AppSettings {
+            speed_correction_mode: SpeedCorrectionMode::Snap,
+            speed_correction_pitch_correct: true,
+            ..aresample.clone()
+        };
+        let err = ToolValidator::check_requirements(&pitch, &caps).unwrap_err();
+        assert!(err.starts_with("speed_correction_pitch_correct"));
+        let no_correction = AppSettings {
+            stepping_enabled: false,
+            ..aresample
+        };
+        assert!(ToolValidator::check_requirements(&no_correction, &caps).is_ok());
+
+        let atempo = AppSettings {
+            segment_resample_engine: ResampleEngine::Atempo,
+            ..settings.clone()
+        };
+        caps.ffmpeg_features.insert("rubberband".to_string(), true);
+        caps.ffmpeg_features.insert("atempo".to_string(), false);
+        let err = ToolValidator::check_requirements(&atempo, &caps).unwrap_err();
+        assert!(err.contains("'atempo'"));
+        caps.ffmpeg_features.insert("atempo".to_string(), true);
+
+        caps.versions.insert(
+            "mkvmerge".to_string(),
+            ToolVersion {
+                raw: "mkvmerge v50.0".to_string(),
+                version: Some("50.0".to_string()),
+            },
+        );
+        let err = ToolValidator::check_requirements(&settings, &caps).unwrap_err();
+        assert!(err.contains("mkvmerge 50"));
+    }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,14 +21,14 @@ impl Auditor for AttachmentsAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let planned_attachments = match &ctx.attachments {
             Some(a) if !a.is_empty() => a,
             _ => {
                 // No attachments planned, nothing to verify
-                return 0;
+                return findings;
             }
         };
 
@@ -37,11 +38,13 @@ impl Auditor for AttachmentsAuditor {
         {
             Some(a) => a,
             None => {
-                runner.log_message(&format!(
-                    "  \u{26a0} Expected {} attachments but found none in final MKV",
+                let message = format!(
+                    "Expected {} attachments but found none in final MKV",
                     planned_attachments.len()
-                ));
-                return planned_attachments.len() as i32;
+                );
+                runner.log_message(&format!("  \u{26a0} {message}"));
+                return vec![AuditFinding::warning("attachments_missing", message)
+                    .with_values(planned_attachments.len(), 0)];
             }
         };
 
@@ -68,17 +71,23 @@ impl Auditor for AttachmentsAuditor {
                     "  \u{26a0} Missing attachment: {}",
                     filename
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::warning(
+                        "attachment_missing",
+                        format!("Missing attachment: {filename}"),
+                    )
+                    .with_values(&filename, ""),
+                );
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message(&format!(
                 "  \u{2714} All {} attachments verified",
                 planned_attachments.len()
             ));
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,22 +22,22 @@ impl Auditor for AudioChannelsAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let ffprobe = match final_ffprobe_data {
             Some(data) => data,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let streams = match ffprobe.get("streams").and_then(|s| s.as_array()) {
             Some(s) => s,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Get audio streams from ffprobe
@@ -62,7 +63,13 @@ impl Auditor for AudioChannelsAuditor {
                     "  \u{26a0} Audio track {} missing from final MKV",
                     i
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "audio_track_missing",
+                        format!("Audio track {i} missing from final MKV"),
+                    )
+                    .with_track(&plan_item.track),
+                );
                 continue;
             }
 
@@ -113,17 +120,24 @@ impl Auditor for AudioChannelsAuditor {
                                 "  \u{26a0} Audio track {}: channel count changed ({} -> {})",
                                 i, source_channels, final_channels
                             ));
-                            issues += 1;
+                            findings.push(
+                                AuditFinding::error(
+                                    "channel_count_changed",
+                                    format!("Audio track {i}: channel count changed"),
+                                )
+                                .with_track(&plan_item.track)
+                                .with_values(source_channels, final_channels),
+                            );
                         }
                     }
                 }
             }
         }
 
-        if issues == 0 && !audio_items.is_empty() {
+        if findings.is_empty() && !audio_items.is_empty() {
             runner.log_message("  \u{2714} Audio channel counts verified");
         }
 
-        issues
+        findings
     }
 }
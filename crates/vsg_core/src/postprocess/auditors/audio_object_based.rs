@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -28,12 +29,12 @@ impl Auditor for AudioObjectBasedAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Check if any audio tracks use object-based codecs
@@ -48,12 +49,12 @@ impl Auditor for AudioObjectBasedAuditor {
             .collect();
 
         if object_audio_items.is_empty() {
-            return 0;
+            return findings;
         }
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Verify object-based audio codecs are preserved in final
@@ -75,7 +76,14 @@ impl Auditor for AudioObjectBasedAuditor {
                     "  \u{26a0} Object-based audio codec '{}' not found in final MKV",
                     expected_codec
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "object_audio_missing",
+                        format!("Object-based audio codec '{expected_codec}' not found in final MKV"),
+                    )
+                    .with_track(&plan_item.track)
+                    .with_values(expected_codec, ""),
+                );
             }
         }
 
@@ -103,10 +111,10 @@ impl Auditor for AudioObjectBasedAuditor {
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Object-based audio metadata verified");
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,22 +22,22 @@ impl Auditor for AudioQualityAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let ffprobe = match final_ffprobe_data {
             Some(data) => data,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let final_streams = match ffprobe.get("streams").and_then(|s| s.as_array()) {
             Some(s) => s,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let final_audio: Vec<&serde_json::Value> = final_streams
@@ -62,6 +63,7 @@ impl Auditor for AudioQualityAuditor {
             // Skip drift-corrected tracks (re-encoded, quality may differ)
             let source_key = format!("{}_{}", plan_item.track.source, plan_item.track.id);
             if ctx.pal_drift_flags.contains_key(&source_key)
+                || ctx.speed_ratio_flags.contains_key(&source_key)
                 || ctx.linear_drift_flags.contains_key(&source_key)
             {
                 continue;
@@ -109,7 +111,14 @@ impl Auditor for AudioQualityAuditor {
                             "  \u{26a0} Audio track {}: sample rate changed ({} -> {})",
                             i, src_rate, final_rate
                         ));
-                        issues += 1;
+                        findings.push(
+                            AuditFinding::warning(
+                                "sample_rate_changed",
+                                format!("Audio track {i}: sample rate changed"),
+                            )
+                            .with_track(&plan_item.track)
+                            .with_values(src_rate, final_rate),
+                        );
                     }
 
                     // Check bit depth (bits_per_raw_sample)
@@ -136,17 +145,24 @@ impl Auditor for AudioQualityAuditor {
                                 "  \u{26a0} Audio track {}: bit depth changed ({} -> {})",
                                 i, src_b, final_b
                             ));
-                            issues += 1;
+                            findings.push(
+                                AuditFinding::warning(
+                                    "bit_depth_changed",
+                                    format!("Audio track {i}: bit depth changed"),
+                                )
+                                .with_track(&plan_item.track)
+                                .with_values(src_b, final_b),
+                            );
                         }
                     }
                 }
             }
         }
 
-        if issues == 0 && !audio_items.is_empty() {
+        if findings.is_empty() && !audio_items.is_empty() {
             runner.log_message("  \u{2714} Audio quality verified");
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,19 +22,22 @@ impl Auditor for AudioSyncAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
             None => {
                 runner.log_message("  \u{26a0} Could not read tracks from final MKV metadata");
-                return 1;
+                return vec![AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read tracks from final MKV metadata",
+                )];
             }
         };
 
@@ -73,14 +77,24 @@ impl Auditor for AudioSyncAuditor {
                     actual_delay_ms,
                     diff
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "delay_mismatch",
+                        format!("Track {i}: delay mismatch of {diff}ms"),
+                    )
+                    .with_final_track(i, &plan_item.track)
+                    .with_values(
+                        format!("{expected_delay}ms"),
+                        format!("{actual_delay_ms}ms"),
+                    ),
+                );
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Audio sync delays verified");
         }
 
-        issues
+        findings
     }
 }
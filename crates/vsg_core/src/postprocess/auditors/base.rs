@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::models::jobs::PlanItem;
 use crate::orchestrator::steps::context::Context;
@@ -17,7 +18,7 @@ pub trait Auditor {
         final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32;
+    ) -> Vec<AuditFinding>;
 }
 
 /// Get metadata from a source file — `_get_metadata`
@@ -28,18 +29,25 @@ pub fn get_metadata(
     tool_paths: &HashMap<String, String>,
 ) -> Option<serde_json::Value> {
     let out = if tool == "mkvmerge" {
-        runner.run(&["mkvmerge", "-J", file_path], tool_paths)?
+        runner.run(&["mkvmerge", "-J", file_path], tool_paths).ok()?
     } else {
         runner.run(
             &["ffprobe", "-v", "error", "-show_streams", "-show_format", "-of", "json", file_path],
             tool_paths,
-        )?
+        )
+        .ok()?
     };
     serde_json::from_str(&out).ok()
 }
 
 /// Calculate expected delay for a plan item — `_calculate_expected_delay`
+///
+/// Accounts for any rebase the lossless finalizer folded into the mux.
 pub fn calculate_expected_delay(ctx: &Context, plan_item: &PlanItem) -> i32 {
+    planned_delay(ctx, plan_item) - ctx.timestamp_rebase_ms
+}
+
+fn planned_delay(ctx: &Context, plan_item: &PlanItem) -> i32 {
     let tr = &plan_item.track;
     let delays = match &ctx.delays {
         Some(d) => d,
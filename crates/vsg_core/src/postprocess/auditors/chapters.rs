@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,13 +21,13 @@ impl Auditor for ChaptersAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         // If no chapters XML was generated, nothing to verify
         let chapters_xml = match &ctx.chapters_xml {
             Some(xml) if !xml.is_empty() => xml,
-            _ => return 0,
+            _ => return Vec::new(),
         };
 
         // Count expected chapters from XML (simple tag counting)
@@ -63,13 +64,22 @@ impl Auditor for ChaptersAuditor {
                 "  \u{26a0} Expected {} chapters but none found in final MKV",
                 expected_count
             ));
-            issues += 1;
+            findings.push(
+                AuditFinding::warning(
+                    "chapters_missing",
+                    format!("Expected {expected_count} chapters but none found in final MKV"),
+                )
+                .with_values(expected_count, 0),
+            );
         } else if expected_count != actual_count && expected_count > 0 {
             runner.log_message(&format!(
                 "  \u{26a0} Chapter count mismatch (expected={}, actual={})",
                 expected_count, actual_count
             ));
-            issues += 1;
+            findings.push(
+                AuditFinding::warning("chapter_count_mismatch", "Chapter count mismatch")
+                    .with_values(expected_count, actual_count),
+            );
         } else if actual_count > 0 {
             runner.log_message(&format!(
                 "  \u{2714} Chapters verified ({} chapters)",
@@ -94,13 +104,19 @@ impl Auditor for ChaptersAuditor {
                                 "  \u{26a0} Negative chapter timestamp detected: {}ns",
                                 timestamp
                             ));
-                            issues += 1;
+                            findings.push(
+                                AuditFinding::error(
+                                    "negative_chapter_timestamp",
+                                    format!("Negative chapter timestamp detected: {timestamp}ns"),
+                                )
+                                .with_values(">= 0", timestamp),
+                            );
                         }
                     }
                 }
             }
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,19 +22,22 @@ impl Auditor for CodecIntegrityAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
             None => {
                 runner.log_message("  \u{26a0} Could not read tracks from final MKV metadata");
-                return 1;
+                return vec![AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read tracks from final MKV metadata",
+                )];
             }
         };
 
@@ -63,6 +67,7 @@ impl Auditor for CodecIntegrityAuditor {
             // For audio tracks with drift correction, codec may change (re-encoding)
             let source_key = format!("{}_{}", plan_item.track.source, plan_item.track.id);
             let has_drift = ctx.pal_drift_flags.contains_key(&source_key)
+                || ctx.speed_ratio_flags.contains_key(&source_key)
                 || ctx.linear_drift_flags.contains_key(&source_key);
 
             if has_drift && plan_item.track.track_type == TrackType::Audio {
@@ -75,14 +80,18 @@ impl Auditor for CodecIntegrityAuditor {
                     "  \u{26a0} Track {}: codec mismatch (expected='{}', actual='{}')",
                     i, expected_codec, actual_codec
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error("codec_mismatch", format!("Track {i}: codec mismatch"))
+                        .with_final_track(i, &plan_item.track)
+                        .with_values(expected_codec, actual_codec),
+                );
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Codec integrity verified");
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,10 +22,10 @@ impl Auditor for DolbyVisionAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
+    ) -> Vec<AuditFinding> {
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Check if any source video track has Dolby Vision
@@ -38,21 +39,21 @@ impl Auditor for DolbyVisionAuditor {
 
         if !has_dv_source {
             // No HEVC video track, DV check not applicable
-            return 0;
+            return Vec::new();
         }
 
         // Check ffprobe data for DV side data
         let ffprobe = match final_ffprobe_data {
             Some(data) => data,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let streams = match ffprobe.get("streams").and_then(|s| s.as_array()) {
             Some(s) => s,
-            None => return 0,
+            None => return Vec::new(),
         };
 
-        let mut issues = 0;
+        let mut findings = Vec::new();
 
         for stream in streams {
             let codec_type = stream
@@ -101,13 +102,16 @@ impl Auditor for DolbyVisionAuditor {
                             runner.log_message(
                                 "  \u{26a0} Dolby Vision metadata may not be preserved"
                             );
-                            issues += 1;
+                            findings.push(AuditFinding::warning(
+                                "dolby_vision_lost",
+                                "Dolby Vision metadata may not be preserved",
+                            ));
                         }
                     }
                 }
             }
         }
 
-        issues
+        findings
     }
 }
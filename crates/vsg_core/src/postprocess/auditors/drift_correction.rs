@@ -1,10 +1,11 @@
 //! Drift correction auditor — 1:1 port of `vsg_core/postprocess/auditors/drift_correction.py`.
 //!
-//! Verifies that PAL and linear drift corrections were applied correctly.
+//! Verifies that PAL, speed-ratio and linear drift corrections were applied correctly.
 
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,12 +22,12 @@ impl Auditor for DriftCorrectionAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Check PAL drift corrections
@@ -50,6 +51,31 @@ impl Auditor for DriftCorrectionAuditor {
             }
         }
 
+        // Check speed-ratio corrections
+        for (source_key, drift_entry) in &ctx.speed_ratio_flags {
+            let Some(ratio) = drift_entry.ratio else {
+                continue;
+            };
+
+            let has_audio = plan_items.iter().any(|item| {
+                let key = format!("{}_{}", item.track.source, item.track.id);
+                key == *source_key && item.track.track_type == TrackType::Audio
+            });
+
+            if has_audio {
+                runner.log_message(&format!(
+                    "  \u{2139} Speed correction applied for {} (ratio: {:.6}{})",
+                    source_key,
+                    ratio,
+                    drift_entry
+                        .label
+                        .as_ref()
+                        .map(|l| format!(", {l}"))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+
         // Check linear drift corrections
         for (source_key, drift_entry) in &ctx.linear_drift_flags {
             let rate = drift_entry.rate.unwrap_or(0.0);
@@ -71,14 +97,20 @@ impl Auditor for DriftCorrectionAuditor {
         }
 
         // Verify drift-corrected audio tracks exist in final MKV
-        if !ctx.pal_drift_flags.is_empty() || !ctx.linear_drift_flags.is_empty() {
+        if !ctx.pal_drift_flags.is_empty()
+            || !ctx.speed_ratio_flags.is_empty()
+            || !ctx.linear_drift_flags.is_empty()
+        {
             let ffprobe = match final_ffprobe_data {
                 Some(data) => data,
                 None => {
                     runner.log_message(
                         "  \u{26a0} Cannot verify drift correction: no ffprobe data"
                     );
-                    return 1;
+                    return vec![AuditFinding::warning(
+                        "ffprobe_unavailable",
+                        "Cannot verify drift correction: no ffprobe data",
+                    )];
                 }
             };
 
@@ -109,16 +141,24 @@ impl Auditor for DriftCorrectionAuditor {
                      (expected={}, actual={})",
                     expected_audio, audio_count
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "audio_track_count_mismatch",
+                        "Audio track count mismatch after drift correction",
+                    )
+                    .with_values(expected_audio, audio_count),
+                );
             }
         }
 
-        if issues == 0
-            && (!ctx.pal_drift_flags.is_empty() || !ctx.linear_drift_flags.is_empty())
+        if findings.is_empty()
+            && (!ctx.pal_drift_flags.is_empty()
+                || !ctx.speed_ratio_flags.is_empty()
+                || !ctx.linear_drift_flags.is_empty())
         {
             runner.log_message("  \u{2714} Drift corrections verified");
         }
 
-        issues
+        findings
     }
 }
@@ -0,0 +1,114 @@
+//! Font coverage auditor.
+//!
+//! Checks that every font an ASS track renders with ships as an attachment:
+//! each family/bold/italic combination from styles and inline overrides
+//! must resolve to an attached face, and that face must have a glyph for
+//! every character drawn with it. Anything else renders with a fallback.
+
+use std::path::Path;
+
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
+use crate::orchestrator::steps::context::Context;
+use crate::subtitles::font_coverage::{load_font_faces, match_font, plan_font_usage, FontMatch};
+
+use super::base::Auditor;
+
+/// Missing glyphs listed in a finding before it is truncated.
+const MAX_LISTED_GLYPHS: usize = 20;
+
+/// Verifies attached fonts cover what the subtitles use — `FontCoverageAuditor`
+pub struct FontCoverageAuditor;
+
+impl Auditor for FontCoverageAuditor {
+    fn run(
+        &self,
+        ctx: &Context,
+        runner: &CommandRunner,
+        _final_mkv_path: &Path,
+        _final_mkvmerge_data: &serde_json::Value,
+        _final_ffprobe_data: Option<&serde_json::Value>,
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
+
+        let plan_items = match &ctx.extracted_items {
+            Some(items) => items,
+            None => return findings,
+        };
+        let tracks = plan_font_usage(plan_items);
+        if tracks.is_empty() {
+            return findings;
+        }
+
+        let attachments = ctx.attachments.clone().unwrap_or_default();
+        let faces = load_font_faces(&attachments);
+
+        let mut checked = 0;
+        for (item, usage) in &tracks {
+            for (request, chars) in usage {
+                checked += 1;
+                match match_font(request, chars, &faces) {
+                    FontMatch::Missing => {
+                        let message = format!("Font {request} is used but not attached");
+                        runner.log_message(&format!("  \u{26a0} {message}"));
+                        findings.push(
+                            AuditFinding::error("font_not_attached", message)
+                                .with_track(&item.track)
+                                .with_values(request.to_string(), ""),
+                        );
+                    }
+                    FontMatch::Found {
+                        face,
+                        exact_style,
+                        missing_glyphs,
+                    } => {
+                        let face = &faces[face];
+                        let file_name = face
+                            .file_path
+                            .file_name()
+                            .map(|f| f.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        if !exact_style {
+                            let message = format!(
+                                "No attached face matches {request}; {file_name} will be \
+                                 synthesized"
+                            );
+                            runner.log_message(&format!("  \u{26a0} {message}"));
+                            findings.push(
+                                AuditFinding::warning("font_style_not_attached", message)
+                                    .with_track(&item.track)
+                                    .with_values(request.to_string(), &file_name),
+                            );
+                        }
+                        if !missing_glyphs.is_empty() {
+                            let mut listed: String =
+                                missing_glyphs.iter().take(MAX_LISTED_GLYPHS).collect();
+                            if missing_glyphs.len() > MAX_LISTED_GLYPHS {
+                                listed.push('\u{2026}');
+                            }
+                            let message = format!(
+                                "{file_name} has no glyph for {} character(s) drawn with \
+                                 {request}: {listed}",
+                                missing_glyphs.len()
+                            );
+                            runner.log_message(&format!("  \u{26a0} {message}"));
+                            findings.push(
+                                AuditFinding::warning("font_glyphs_missing", message)
+                                    .with_track(&item.track)
+                                    .with_values(0, missing_glyphs.len()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            runner.log_message(&format!(
+                "  \u{2714} All {checked} subtitle font(s) attached with full glyph coverage"
+            ));
+        }
+
+        findings
+    }
+}
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,11 +21,11 @@ impl Auditor for FrameAuditAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         if ctx.frame_audit_results.is_empty() {
-            return 0;
+            return findings;
         }
 
         for (source, audit_data) in &ctx.frame_audit_results {
@@ -59,7 +60,13 @@ impl Auditor for FrameAuditAuditor {
                     "  \u{26a0} Frame audit FAILED for {}: {:.1}% match ({}/{} frames)",
                     source, match_rate, matched_frames, total_frames
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "frame_audit_failed",
+                        format!("Frame audit failed for {source}"),
+                    )
+                    .with_values(total_frames, matched_frames),
+                );
             }
 
             // Report individual mismatches if present
@@ -94,6 +101,6 @@ impl Auditor for FrameAuditAuditor {
             }
         }
 
-        issues
+        findings
     }
 }
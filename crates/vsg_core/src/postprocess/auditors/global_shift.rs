@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,29 +22,29 @@ impl Auditor for GlobalShiftAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let delays = match &ctx.delays {
             Some(d) => d,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let global_shift = delays.global_shift_ms;
 
         if global_shift == 0 && !ctx.global_shift_is_required {
             // No global shift needed or applied
-            return 0;
+            return findings;
         }
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Check Source 1 video track has the global shift applied
@@ -70,24 +71,32 @@ impl Auditor for GlobalShiftAuditor {
                 .unwrap_or(0);
             let actual_delay_ms = (actual_delay_ns as f64 / 1_000_000.0).round() as i32;
 
-            let diff = (actual_delay_ms - global_shift).abs();
+            let expected_ms = global_shift - ctx.timestamp_rebase_ms;
+            let diff = (actual_delay_ms - expected_ms).abs();
             if diff > 1 {
                 runner.log_message(&format!(
                     "  \u{26a0} Global shift mismatch on video track \
                      (expected={}ms, actual={}ms)",
-                    global_shift, actual_delay_ms
+                    expected_ms, actual_delay_ms
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error(
+                        "global_shift_mismatch",
+                        "Global shift mismatch on video track",
+                    )
+                    .with_final_track(i, &plan_item.track)
+                    .with_values(format!("{expected_ms}ms"), format!("{actual_delay_ms}ms")),
+                );
             }
         }
 
-        if issues == 0 && global_shift != 0 {
+        if findings.is_empty() && global_shift != 0 {
             runner.log_message(&format!(
                 "  \u{2714} Global shift verified ({}ms)",
                 global_shift
             ));
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,19 +21,22 @@ impl Auditor for LanguageTagsAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
             None => {
                 runner.log_message("  \u{26a0} Could not read tracks from final MKV metadata");
-                return 1;
+                return vec![AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read tracks from final MKV metadata",
+                )];
             }
         };
 
@@ -71,15 +75,22 @@ impl Auditor for LanguageTagsAuditor {
                         "  \u{26a0} Track {}: language mismatch (expected='{}', actual='{}')",
                         i, expected_lang, actual_lang
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::warning(
+                            "language_mismatch",
+                            format!("Track {i}: language mismatch"),
+                        )
+                        .with_final_track(i, &plan_item.track)
+                        .with_values(expected_lang, actual_lang),
+                    );
                 }
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Language tags verified");
         }
 
-        issues
+        findings
     }
 }
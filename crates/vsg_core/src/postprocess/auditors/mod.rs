@@ -8,10 +8,13 @@ pub mod chapters;
 pub mod codec_integrity;
 pub mod dolby_vision;
 pub mod drift_correction;
+pub mod font_coverage;
 pub mod frame_audit;
 pub mod global_shift;
 pub mod language_tags;
 pub mod neural_confidence;
+pub mod post_mux_sync;
+pub mod remux_integrity;
 pub mod stepping_correction;
 pub mod subtitle_clamping;
 pub mod subtitle_formats;
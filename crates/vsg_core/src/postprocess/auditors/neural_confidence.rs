@@ -5,6 +5,8 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
+use crate::models::enums::AuditSeverity;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,11 +22,11 @@ impl Auditor for NeuralConfidenceAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         if ctx.video_verified_sources.is_empty() {
-            return 0;
+            return findings;
         }
 
         for (source, result) in &ctx.video_verified_sources {
@@ -74,6 +76,14 @@ impl Auditor for NeuralConfidenceAuditor {
                     total_count
                 ));
                 // Low confidence is informational, not an issue
+                findings.push(
+                    AuditFinding::new(
+                        AuditSeverity::Info,
+                        "low_neural_confidence",
+                        format!("Low neural confidence for {source}"),
+                    )
+                    .with_values(">= 80%", format!("{:.1}%", confidence * 100.0)),
+                );
             } else {
                 runner.log_message(&format!(
                     "  \u{26a0} Very low neural confidence for {}: {:.1}% ({}/{} matches)",
@@ -82,7 +92,13 @@ impl Auditor for NeuralConfidenceAuditor {
                     match_count,
                     total_count
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::warning(
+                        "very_low_neural_confidence",
+                        format!("Very low neural confidence for {source}"),
+                    )
+                    .with_values(">= 50%", format!("{:.1}%", confidence * 100.0)),
+                );
             }
 
             // Report correction applied
@@ -100,6 +116,6 @@ impl Auditor for NeuralConfidenceAuditor {
             }
         }
 
-        issues
+        findings
     }
 }
@@ -0,0 +1,306 @@
+//! Post-mux sync auditor.
+//!
+//! The other auditors check the delays the final MKV declares. This one
+//! decodes each synced audio track from the final file and re-measures it
+//! against the Source 1 reference with a coarse dense correlation, so a
+//! wrong delay, a broken correction or a mux mishap shows up as a residual
+//! offset.
+
+use std::cell::RefCell;
+use std::path::Path;
+
+use crate::analysis::correlation::decode::{
+    decode_audio, get_audio_stream_info, normalize_lang, DEFAULT_SR,
+};
+use crate::analysis::correlation::dense::run_dense_correlation;
+use crate::analysis::correlation::gpu_backend::cleanup_gpu;
+use crate::analysis::correlation::run::resolve_method;
+use crate::analysis::types::ChunkResult;
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
+use crate::models::context_types::PostMuxSyncCheck;
+use crate::models::enums::TrackType;
+use crate::orchestrator::steps::context::Context;
+
+use super::base::Auditor;
+
+/// Fewest matching windows needed to report a residual.
+const MIN_ACCEPTED_WINDOWS: usize = 3;
+
+/// Re-measures audio sync in the final MKV — `PostMuxSyncAuditor`
+#[derive(Default)]
+pub struct PostMuxSyncAuditor {
+    checks: RefCell<Vec<PostMuxSyncCheck>>,
+}
+
+impl PostMuxSyncAuditor {
+    /// Per-track results of the last run.
+    pub fn take_checks(&self) -> Vec<PostMuxSyncCheck> {
+        self.checks.take()
+    }
+}
+
+impl Auditor for PostMuxSyncAuditor {
+    fn run(
+        &self,
+        ctx: &Context,
+        runner: &CommandRunner,
+        final_mkv_path: &Path,
+        final_mkvmerge_data: &serde_json::Value,
+        _final_ffprobe_data: Option<&serde_json::Value>,
+    ) -> Vec<AuditFinding> {
+        let settings = &ctx.settings;
+        if !settings.post_mux_sync_verify {
+            return Vec::new();
+        }
+
+        let plan_items = match &ctx.extracted_items {
+            Some(items) => items,
+            None => return Vec::new(),
+        };
+        let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        let Some(ref_path) = ctx.sources.get("Source 1") else {
+            return Vec::new();
+        };
+
+        // Synced audio only: Source 1 is the reference, preserved originals
+        // keep their uncorrected timing on purpose
+        let targets: Vec<(usize, i32)> = plan_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.track.track_type == TrackType::Audio)
+            .enumerate()
+            .filter(|(_, (_, item))| item.track.source != "Source 1" && !item.is_preserved)
+            .map(|(audio_index, (i, _))| (i, audio_index as i32))
+            .filter(|&(i, _)| i < tracks.len())
+            .collect();
+        if targets.is_empty() {
+            return Vec::new();
+        }
+
+        let ref_lang = normalize_lang(Some(&settings.analysis_lang_source1));
+        let (ref_index, _) =
+            get_audio_stream_info(ref_path, ref_lang.as_deref(), runner, &ctx.tool_paths);
+        let Some(ref_index) = ref_index else {
+            runner.log_message("  \u{26a0} Post-mux sync: no Source 1 audio to measure against");
+            return vec![AuditFinding::error(
+                "sync_reference_unavailable",
+                "No Source 1 audio to measure against",
+            )];
+        };
+        let ref_pcm = match decode_audio(
+            ref_path,
+            ref_index,
+            DEFAULT_SR,
+            settings.use_soxr,
+            runner,
+            &ctx.tool_paths,
+        ) {
+            Ok(pcm) => pcm,
+            Err(e) => {
+                runner.log_message(&format!(
+                    "  \u{26a0} Post-mux sync: reference decode failed: {e}"
+                ));
+                return vec![AuditFinding::error(
+                    "sync_reference_decode_failed",
+                    format!("Reference decode failed: {e}"),
+                )];
+            }
+        };
+
+        let method = resolve_method(settings, false);
+        // A rebase folded into the mux moved every track, Source 1 included
+        let global_shift_ms =
+            ctx.delays.as_ref().map_or(0, |d| d.global_shift_ms) - ctx.timestamp_rebase_ms;
+        let final_path = final_mkv_path.to_string_lossy().to_string();
+        let threshold_ms = settings.post_mux_sync_threshold_ms;
+        let mut findings = Vec::new();
+
+        for (i, audio_index) in targets {
+            let item = &plan_items[i];
+            let mut check = PostMuxSyncCheck {
+                track_index: i,
+                source: item.track.source.clone(),
+                track_id: item.track.id,
+                name: item.track.props.name.clone(),
+                threshold_ms,
+                ..Default::default()
+            };
+
+            let tgt_pcm = match decode_audio(
+                &final_path,
+                audio_index,
+                DEFAULT_SR,
+                settings.use_soxr,
+                runner,
+                &ctx.tool_paths,
+            ) {
+                Ok(pcm) => pcm,
+                Err(e) => {
+                    runner.log_message(&format!(
+                        "  \u{26a0} Track {i} ({}): decode failed: {e}",
+                        item.track.source
+                    ));
+                    check.status = "failed".to_string();
+                    self.checks.borrow_mut().push(check);
+                    findings.push(
+                        AuditFinding::error("sync_decode_failed", format!("Decode failed: {e}"))
+                            .with_final_track(i, &item.track),
+                    );
+                    continue;
+                }
+            };
+
+            let windows = run_dense_correlation(
+                &ref_pcm,
+                &tgt_pcm,
+                DEFAULT_SR,
+                method.as_ref(),
+                settings.dense_window_s,
+                settings.post_mux_sync_hop_s,
+                settings.min_match_pct,
+                settings.dense_silence_threshold_db,
+                settings.dense_outlier_threshold_ms,
+                settings.scan_start_percentage,
+                settings.scan_end_percentage.min(100.0),
+                None,
+                settings.detection_dbscan_epsilon_ms,
+                settings.detection_dbscan_min_samples_pct,
+            );
+            check.total_windows = windows.len();
+            check.accepted_windows = windows.iter().filter(|w| w.accepted).count();
+
+            let start_ms = tracks[i]
+                .get("properties")
+                .and_then(|p| p.get("minimum_timestamp"))
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0) as f64
+                / 1_000_000.0;
+
+            match median_accepted_delay(&windows) {
+                Some(delay_ms) => {
+                    let residual = residual_ms(
+                        delay_ms,
+                        ctx.source1_audio_container_delay_ms,
+                        global_shift_ms,
+                        start_ms,
+                    );
+                    check.residual_ms = Some(residual);
+                    if residual.abs() > threshold_ms {
+                        runner.log_message(&format!(
+                            "  \u{26a0} Track {i} ({}): residual sync offset {residual:+.1}ms \
+                             exceeds {threshold_ms:.1}ms ({}/{} windows)",
+                            item.track.source, check.accepted_windows, check.total_windows
+                        ));
+                        check.status = "failed".to_string();
+                        findings.push(
+                            AuditFinding::error(
+                                "sync_residual_exceeded",
+                                format!("Residual sync offset {residual:+.1}ms"),
+                            )
+                            .with_final_track(i, &item.track)
+                            .with_values(
+                                format!("<= {threshold_ms:.1}ms"),
+                                format!("{residual:+.1}ms"),
+                            ),
+                        );
+                    } else {
+                        runner.log_message(&format!(
+                            "  \u{2139} Track {i} ({}): residual sync offset {residual:+.1}ms",
+                            item.track.source
+                        ));
+                        check.status = "passed".to_string();
+                    }
+                }
+                None => {
+                    runner.log_message(&format!(
+                        "  \u{2139} Track {i} ({}): sync not measurable ({}/{} windows matched)",
+                        item.track.source, check.accepted_windows, check.total_windows
+                    ));
+                    check.status = "inconclusive".to_string();
+                }
+            }
+            self.checks.borrow_mut().push(check);
+        }
+
+        cleanup_gpu();
+
+        if findings.is_empty() {
+            runner.log_message("  \u{2714} Post-mux sync verified");
+        }
+        findings
+    }
+}
+
+/// Median delay of the accepted windows, if enough of them matched.
+fn median_accepted_delay(windows: &[ChunkResult]) -> Option<f64> {
+    let mut delays: Vec<f64> = windows
+        .iter()
+        .filter(|w| w.accepted)
+        .map(|w| w.raw_delay_ms)
+        .collect();
+    if delays.len() < MIN_ACCEPTED_WINDOWS {
+        return None;
+    }
+    delays.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = delays.len() / 2;
+    Some(if delays.len() % 2 == 1 {
+        delays[mid]
+    } else {
+        (delays[mid - 1] + delays[mid]) / 2.0
+    })
+}
+
+/// Offset the track would still need against Source 1.
+///
+/// `correlation_ms` is measured between the decoded streams, which start
+/// at their first sample, so the declared start of the final track
+/// (`start_ms`) and of the reference (`ref_container_delay_ms`) plus the
+/// global shift applied to Source 1 are folded back in — the same delay
+/// chain the analysis step uses.
+fn residual_ms(
+    correlation_ms: f64,
+    ref_container_delay_ms: f64,
+    global_shift_ms: i32,
+    start_ms: f64,
+) -> f64 {
+    correlation_ms + ref_container_delay_ms + global_shift_ms as f64 - start_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(raw_delay_ms: f64, accepted: bool) -> ChunkResult {
+        ChunkResult {
+            delay_ms: raw_delay_ms.round() as i32,
+            raw_delay_ms,
+            match_pct: if accepted { 90.0 } else { 5.0 },
+            start_s: 0.0,
+            accepted,
+        }
+    }
+
+    #[test]
+    fn median_ignores_rejected_windows() {
+        let windows = [
+            window(-1.0, true),
+            window(900.0, false),
+            window(2.0, true),
+            window(1.0, true),
+        ];
+        assert_eq!(median_accepted_delay(&windows), Some(1.0));
+        assert_eq!(median_accepted_delay(&windows[..2]), None);
+    }
+
+    #[test]
+    fn correctly_synced_track_has_no_residual() {
+        // Source 2 needed +250ms, Source 1 audio starts 10ms late and the
+        // global shift is 40ms: the track lands at 300ms in the final file
+        assert_eq!(residual_ms(250.0, 10.0, 40, 300.0), 0.0);
+        assert_eq!(residual_ms(250.0, 10.0, 40, 280.0), 20.0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Remux integrity auditor.
+//!
+//! Timestamp finalization may remux the merged file. This compares the
+//! `mkvmerge -J` identification taken before finalization with the final
+//! file and reports any track, track property, attachment, chapter or tag
+//! that did not survive.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
+use crate::orchestrator::steps::context::Context;
+
+use super::base::Auditor;
+
+/// Track properties that must be identical after a remux.
+const TRACK_PROPERTIES: &[&str] = &[
+    "language",
+    "language_ietf",
+    "track_name",
+    "default_track",
+    "forced_track",
+    "flag_original",
+    "flag_commentary",
+    "flag_hearing_impaired",
+    "flag_visual_impaired",
+    "codec_private_length",
+    "audio_channels",
+    "audio_sampling_frequency",
+    "pixel_dimensions",
+    "display_dimensions",
+];
+
+/// Proves finalization lost nothing — `RemuxIntegrityAuditor`
+pub struct RemuxIntegrityAuditor;
+
+impl Auditor for RemuxIntegrityAuditor {
+    fn run(
+        &self,
+        ctx: &Context,
+        runner: &CommandRunner,
+        _final_mkv_path: &Path,
+        final_mkvmerge_data: &serde_json::Value,
+        _final_ffprobe_data: Option<&serde_json::Value>,
+    ) -> Vec<AuditFinding> {
+        let Some(before) = &ctx.pre_finalize_mkvmerge else {
+            return Vec::new();
+        };
+
+        // Stripping tags is an explicit request, not a loss
+        let compare_tags = !ctx.settings.post_mux_strip_tags;
+        let findings = compare_containers(before, final_mkvmerge_data, compare_tags);
+        for finding in &findings {
+            runner.log_message(&format!("  \u{26a0} {}", finding.message));
+        }
+        if findings.is_empty() {
+            runner.log_message("  \u{2714} All tracks, attachments, chapters and tags preserved");
+        }
+        findings
+    }
+}
+
+/// Everything present in `before` but missing or changed in `after`.
+fn compare_containers(before: &Value, after: &Value, compare_tags: bool) -> Vec<AuditFinding> {
+    let mut findings = Vec::new();
+
+    let before_tracks = array(before, "tracks");
+    let after_tracks = array(after, "tracks");
+    if after_tracks.len() < before_tracks.len() {
+        findings.push(
+            AuditFinding::error(
+                "track_lost",
+                format!(
+                    "Finalization dropped {} track(s)",
+                    before_tracks.len() - after_tracks.len()
+                ),
+            )
+            .with_values(before_tracks.len(), after_tracks.len()),
+        );
+    }
+    for (i, (old, new)) in before_tracks.iter().zip(after_tracks).enumerate() {
+        if old.get("codec") != new.get("codec") {
+            findings.push(
+                AuditFinding::error(
+                    "track_codec_changed",
+                    format!("Track {i} codec changed during finalization"),
+                )
+                .with_values(text(old.get("codec")), text(new.get("codec"))),
+            );
+        }
+        for key in TRACK_PROPERTIES {
+            let old_value = old["properties"].get(*key);
+            let new_value = new["properties"].get(*key);
+            if old_value.is_some() && old_value != new_value {
+                findings.push(
+                    AuditFinding::error(
+                        "track_property_changed",
+                        format!("Track {i} {key} changed during finalization"),
+                    )
+                    .with_values(text(old_value), text(new_value)),
+                );
+            }
+        }
+    }
+
+    let attachment_key = |a: &Value| (text(a.get("file_name")), a["size"].as_i64().unwrap_or(0));
+    let after_attachments: Vec<_> = array(after, "attachments")
+        .iter()
+        .map(attachment_key)
+        .collect();
+    for (name, size) in array(before, "attachments").iter().map(attachment_key) {
+        if !after_attachments.contains(&(name.clone(), size)) {
+            findings.push(
+                AuditFinding::error(
+                    "attachment_lost",
+                    format!("Attachment '{name}' lost during finalization"),
+                )
+                .with_values(format!("{name} ({size} bytes)"), ""),
+            );
+        }
+    }
+
+    let chapters_before = entry_count(before, "chapters");
+    let chapters_after = entry_count(after, "chapters");
+    if chapters_after < chapters_before {
+        findings.push(
+            AuditFinding::error("chapters_lost", "Chapters lost during finalization")
+                .with_values(chapters_before, chapters_after),
+        );
+    }
+
+    if compare_tags {
+        for kind in ["global_tags", "track_tags"] {
+            let tags_before = entry_count(before, kind);
+            let tags_after = entry_count(after, kind);
+            if tags_after < tags_before {
+                findings.push(
+                    AuditFinding::warning("tags_lost", format!("{kind} lost during finalization"))
+                        .with_values(tags_before, tags_after),
+                );
+            }
+        }
+    }
+
+    findings
+}
+
+fn array<'a>(info: &'a Value, key: &str) -> &'a [Value] {
+    info.get(key)
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+/// Sum of `num_entries` across a chapters/tags section.
+fn entry_count(info: &Value, key: &str) -> i64 {
+    array(info, key)
+        .iter()
+        .filter_map(|e| e.get("num_entries").and_then(|n| n.as_i64()))
+        .sum()
+}
+
+fn text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn identify(track_name: &str, attachments: usize, chapters: i64) -> Value {
+        json!({
+            "tracks": [
+                {"codec": "HEVC/H.265/MPEG-H", "properties": {"language": "jpn", "codec_private_length": 2435}},
+                {"codec": "FLAC", "properties": {"language": "eng", "track_name": track_name}},
+            ],
+            "attachments": (0..attachments)
+                .map(|i| json!({"file_name": format!("font{i}.ttf"), "size": 1000 + i}))
+                .collect::<Vec<_>>(),
+            "chapters": [{"num_entries": chapters}],
+            "global_tags": [{"num_entries": 2}],
+        })
+    }
+
+    #[test]
+    fn identical_files_pass() {
+        let info = identify("Commentary", 2, 12);
+        assert!(compare_containers(&info, &info, true).is_empty());
+    }
+
+    #[test]
+    fn reports_every_lost_element() {
+        let before = identify("Commentary", 2, 12);
+        let mut after = identify("", 1, 0);
+        after["global_tags"] = json!([]);
+
+        let codes: Vec<String> = compare_containers(&before, &after, true)
+            .into_iter()
+            .map(|f| f.code)
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                "track_property_changed",
+                "attachment_lost",
+                "chapters_lost",
+                "tags_lost"
+            ]
+        );
+        assert!(!compare_containers(&before, &after, false)
+            .iter()
+            .any(|f| f.code == "tags_lost"));
+    }
+}
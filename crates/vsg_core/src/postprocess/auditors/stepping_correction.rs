@@ -5,7 +5,8 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
-use crate::models::enums::TrackType;
+use crate::models::context_types::AuditFinding;
+use crate::models::enums::{AuditSeverity, TrackType};
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -21,12 +22,12 @@ impl Auditor for SteppingCorrectionAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Report stepping sources
@@ -81,9 +82,18 @@ impl Auditor for SteppingCorrectionAuditor {
                 icon, qi.source, qi.message
             ));
 
-            if qi.severity == "error" {
-                issues += 1;
-            }
+            let severity = match qi.severity.as_str() {
+                "error" => AuditSeverity::Error,
+                "warning" => AuditSeverity::Warning,
+                _ => AuditSeverity::Info,
+            };
+            findings.push(
+                AuditFinding::new(
+                    severity,
+                    &format!("stepping_{}", qi.issue_type),
+                    format!("Stepping quality ({}): {}", qi.source, qi.message),
+                ),
+            );
         }
 
         // Verify segment flags metadata
@@ -118,7 +128,34 @@ impl Auditor for SteppingCorrectionAuditor {
                             "  \u{26a0} Stepping audit fail ({}): {}",
                             source_key, message
                         ));
-                        issues += 1;
+                        findings.push(AuditFinding::error(
+                            "stepping_audit_failed",
+                            format!("Stepping audit fail ({source_key}): {message}"),
+                        ));
+                    }
+
+                    if let Some(join) = entry.get("join").and_then(|v| v.as_str()) {
+                        let at = entry
+                            .get("target_time_s")
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(0.0);
+                        let mut method = match join {
+                            "crossfade" => format!(
+                                "crossfade {:.0}ms",
+                                entry
+                                    .get("crossfade_ms")
+                                    .and_then(|v| v.as_f64())
+                                    .unwrap_or(0.0)
+                            ),
+                            other => other.replace('_', " "),
+                        };
+                        if let Some(fill) = entry.get("gap_fill").and_then(|v| v.as_str()) {
+                            method.push_str(&format!(", {} gap fill", fill.replace('_', " ")));
+                        }
+                        runner.log_message(&format!(
+                            "  \u{2139} Splice @{:.3}s ({}): {}",
+                            at, source_key, method
+                        ));
                     }
                 }
             }
@@ -131,14 +168,18 @@ impl Auditor for SteppingCorrectionAuditor {
                     "  \u{26a0} No EDL found for stepping source: {}",
                     source
                 ));
-                issues += 1;
+                findings.push(AuditFinding::error(
+                    "stepping_edl_missing",
+                    format!("No EDL found for stepping source: {source}"),
+                ));
             }
         }
 
-        if issues == 0 && !ctx.stepping_sources.is_empty() {
+        let clean = findings.iter().all(|f| f.severity == AuditSeverity::Info);
+        if clean && !ctx.stepping_sources.is_empty() {
             runner.log_message("  \u{2714} Stepping corrections verified");
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,12 +22,12 @@ impl Auditor for SubtitleClampingAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         for plan_item in plan_items {
@@ -61,7 +62,14 @@ impl Auditor for SubtitleClampingAuditor {
                         total_events,
                         min_timestamp
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::warning(
+                            "subtitle_events_clamped",
+                            format!("{clamped_count} of {total_events} subtitle events clamped"),
+                        )
+                        .with_track(&plan_item.track)
+                        .with_values(0, clamped_count),
+                    );
                 }
 
                 let lost_count = clamping_info
@@ -77,15 +85,22 @@ impl Auditor for SubtitleClampingAuditor {
                         plan_item.track.id,
                         lost_count
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::error(
+                            "subtitle_events_lost",
+                            format!("{lost_count} subtitle events lost to negative timestamps"),
+                        )
+                        .with_track(&plan_item.track)
+                        .with_values(0, lost_count),
+                    );
                 }
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} No subtitle clamping issues");
         }
 
-        issues
+        findings
     }
 }
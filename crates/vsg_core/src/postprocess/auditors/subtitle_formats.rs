@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -27,17 +28,17 @@ impl Auditor for SubtitleFormatsAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let mut sub_idx = 0;
@@ -80,7 +81,14 @@ impl Auditor for SubtitleFormatsAuditor {
                         "  \u{26a0} Subtitle track {}: expected ASS format after conversion, got '{}'",
                         sub_idx, actual_codec
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::error(
+                            "ass_conversion_missing",
+                            format!("Subtitle track {sub_idx}: expected ASS format after conversion"),
+                        )
+                        .with_track(&plan_item.track)
+                        .with_values("S_TEXT/ASS", actual_codec),
+                    );
                 }
             } else if plan_item.perform_ocr {
                 // OCR output should be ASS or SRT
@@ -91,7 +99,14 @@ impl Auditor for SubtitleFormatsAuditor {
                         "  \u{26a0} Subtitle track {}: expected text format after OCR, got '{}'",
                         sub_idx, actual_codec
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::error(
+                            "ocr_output_not_text",
+                            format!("Subtitle track {sub_idx}: expected text format after OCR"),
+                        )
+                        .with_track(&plan_item.track)
+                        .with_values("S_TEXT/ASS or S_TEXT/UTF8", actual_codec),
+                    );
                 }
             } else {
                 // Codec should be preserved (or remuxed to MKV equivalent)
@@ -111,17 +126,24 @@ impl Auditor for SubtitleFormatsAuditor {
                         "  \u{26a0} Subtitle track {}: codec changed unexpectedly ('{}' -> '{}')",
                         sub_idx, original_codec, actual_codec
                     ));
-                    issues += 1;
+                    findings.push(
+                        AuditFinding::warning(
+                            "subtitle_codec_changed",
+                            format!("Subtitle track {sub_idx}: codec changed unexpectedly"),
+                        )
+                        .with_track(&plan_item.track)
+                        .with_values(original_codec, actual_codec),
+                    );
                 }
             }
 
             sub_idx += 1;
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Subtitle formats verified");
         }
 
-        issues
+        findings
     }
 }
@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,19 +21,22 @@ impl Auditor for TrackFlagsAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
             None => {
                 runner.log_message("  \u{26a0} Could not read tracks from final MKV metadata");
-                return 1;
+                return vec![AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read tracks from final MKV metadata",
+                )];
             }
         };
 
@@ -44,7 +48,11 @@ impl Auditor for TrackFlagsAuditor {
                     plan_items.len(),
                     tracks.len()
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error("track_missing", format!("Track {i} missing from final MKV"))
+                        .with_track(&plan_item.track)
+                        .with_values(plan_items.len(), tracks.len()),
+                );
                 continue;
             }
 
@@ -66,7 +74,14 @@ impl Auditor for TrackFlagsAuditor {
                     "  \u{26a0} Track {}: default flag mismatch (expected={}, actual={})",
                     i, expected_default, actual_default
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::warning(
+                        "default_flag_mismatch",
+                        format!("Track {i}: default flag mismatch"),
+                    )
+                    .with_final_track(i, &plan_item.track)
+                    .with_values(expected_default, actual_default),
+                );
             }
 
             // Check forced flag
@@ -81,14 +96,21 @@ impl Auditor for TrackFlagsAuditor {
                     "  \u{26a0} Track {}: forced flag mismatch (expected={}, actual={})",
                     i, expected_forced, actual_forced
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::warning(
+                        "forced_flag_mismatch",
+                        format!("Track {i}: forced flag mismatch"),
+                    )
+                    .with_final_track(i, &plan_item.track)
+                    .with_values(expected_forced, actual_forced),
+                );
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Track flags verified");
         }
 
-        issues
+        findings
     }
 }
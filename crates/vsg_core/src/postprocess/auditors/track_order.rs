@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::orchestrator::steps::context::Context;
 
 use super::base::Auditor;
@@ -20,19 +21,22 @@ impl Auditor for TrackOrderAuditor {
         _final_mkv_path: &Path,
         final_mkvmerge_data: &serde_json::Value,
         _final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let tracks = match final_mkvmerge_data.get("tracks").and_then(|t| t.as_array()) {
             Some(t) => t,
             None => {
                 runner.log_message("  \u{26a0} Could not read tracks from final MKV metadata");
-                return 1;
+                return vec![AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read tracks from final MKV metadata",
+                )];
             }
         };
 
@@ -43,7 +47,10 @@ impl Auditor for TrackOrderAuditor {
                 plan_items.len(),
                 tracks.len()
             ));
-            issues += 1;
+            findings.push(
+                AuditFinding::error("track_count_mismatch", "Track count mismatch")
+                    .with_values(plan_items.len(), tracks.len()),
+            );
         }
 
         // Check track type order
@@ -65,14 +72,18 @@ impl Auditor for TrackOrderAuditor {
                     "  \u{26a0} Track {}: type mismatch (expected={}, actual={})",
                     i, expected_type, actual_type
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::error("track_type_mismatch", format!("Track {i}: type mismatch"))
+                        .with_final_track(i, &plan_item.track)
+                        .with_values(&expected_type, actual_type),
+                );
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Track order verified");
         }
 
-        issues
+        findings
     }
 }
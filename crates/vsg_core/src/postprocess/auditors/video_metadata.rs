@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::AuditFinding;
 use crate::models::enums::TrackType;
 use crate::orchestrator::steps::context::Context;
 
@@ -21,12 +22,12 @@ impl Auditor for VideoMetadataAuditor {
         _final_mkv_path: &Path,
         _final_mkvmerge_data: &serde_json::Value,
         final_ffprobe_data: Option<&serde_json::Value>,
-    ) -> i32 {
-        let mut issues = 0;
+    ) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
 
         let plan_items = match &ctx.extracted_items {
             Some(items) => items,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Find the video plan item (usually first)
@@ -35,12 +36,12 @@ impl Auditor for VideoMetadataAuditor {
             .find(|item| item.track.track_type == TrackType::Video)
         {
             Some(item) => item,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let source_path = match ctx.sources.get(&video_item.track.source) {
             Some(p) => p,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Get source ffprobe data
@@ -49,12 +50,12 @@ impl Auditor for VideoMetadataAuditor {
 
         let source_ffprobe = match source_data {
             Some(data) => data,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         let final_ffprobe = match final_ffprobe_data {
             Some(data) => data,
-            None => return 0,
+            None => return Vec::new(),
         };
 
         // Find source video stream
@@ -63,7 +64,7 @@ impl Auditor for VideoMetadataAuditor {
 
         let (src, fin) = match (source_video, final_video) {
             (Some(s), Some(f)) => (s, f),
-            _ => return 0,
+            _ => return Vec::new(),
         };
 
         // Check resolution
@@ -77,7 +78,10 @@ impl Auditor for VideoMetadataAuditor {
                 "  \u{26a0} Video resolution changed ({}x{} -> {}x{})",
                 src_w, src_h, fin_w, fin_h
             ));
-            issues += 1;
+            findings.push(
+                AuditFinding::error("resolution_changed", "Video resolution changed")
+                    .with_values(format!("{src_w}x{src_h}"), format!("{fin_w}x{fin_h}")),
+            );
         }
 
         // Check color space
@@ -97,7 +101,13 @@ impl Auditor for VideoMetadataAuditor {
                     "  \u{26a0} Video {} changed ('{}' -> '{}')",
                     field, src_val, fin_val
                 ));
-                issues += 1;
+                findings.push(
+                    AuditFinding::warning(
+                        &format!("{field}_changed"),
+                        format!("Video {field} changed"),
+                    )
+                    .with_values(src_val, fin_val),
+                );
             }
         }
 
@@ -132,17 +142,23 @@ impl Auditor for VideoMetadataAuditor {
                             "  \u{26a0} HDR metadata '{}' missing from final MKV",
                             sd_type
                         ));
-                        issues += 1;
+                        findings.push(
+                            AuditFinding::error(
+                                "hdr_metadata_missing",
+                                format!("HDR metadata '{sd_type}' missing from final MKV"),
+                            )
+                            .with_values(sd_type, ""),
+                        );
                     }
                 }
             }
         }
 
-        if issues == 0 {
+        if findings.is_empty() {
             runner.log_message("  \u{2714} Video metadata verified");
         }
 
-        issues
+        findings
     }
 }
 
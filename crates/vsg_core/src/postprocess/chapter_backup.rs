@@ -15,10 +15,12 @@ pub fn extract_chapters_xml(
     runner: &CommandRunner,
     tool_paths: &HashMap<String, String>,
 ) -> Option<String> {
-    let out = runner.run(
-        &["mkvextract", &mkv_path.to_string_lossy(), "chapters", "-"],
-        tool_paths,
-    )?;
+    let out = runner
+        .run(
+            &["mkvextract", &mkv_path.to_string_lossy(), "chapters", "-"],
+            tool_paths,
+        )
+        .ok()?;
     let trimmed = out.trim();
     if trimmed.is_empty() {
         return None;
@@ -41,7 +43,7 @@ pub fn inject_chapters(
     }
 
     // Inject with mkvpropedit
-    runner.run(
+    let _ = runner.run(
         &[
             "mkvpropedit",
             &mkv_path.to_string_lossy(),
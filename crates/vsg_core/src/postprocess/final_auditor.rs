@@ -5,6 +5,9 @@
 use std::path::Path;
 
 use crate::io::runner::CommandRunner;
+use crate::models::context_types::{AuditFinding, PostMuxSyncCheck};
+use crate::models::enums::AuditSeverity;
+use crate::models::events::JobEvent;
 use crate::orchestrator::steps::context::Context;
 
 use super::auditors::base::{get_metadata, Auditor};
@@ -13,14 +16,29 @@ use super::auditors::*;
 /// Coordinates all post-merge validation — `FinalAuditor`
 pub struct FinalAuditor;
 
+/// Outcome of a final audit.
+#[derive(Debug, Clone, Default)]
+pub struct AuditSummary {
+    /// Findings at warning severity or above
+    pub total_issues: i32,
+    /// Every finding, in auditor run order
+    pub findings: Vec<AuditFinding>,
+    /// Keys of the auditors that ran (disabled ones are left out)
+    pub auditors_run: Vec<String>,
+    /// Per-track residuals from the post-mux sync re-measurement
+    pub post_mux_sync: Vec<PostMuxSyncCheck>,
+}
+
 impl FinalAuditor {
     /// Run all auditors against the final MKV file — `run`
     pub fn run(
         ctx: &Context,
         runner: &CommandRunner,
         final_mkv_path: &Path,
-    ) -> i32 {
+    ) -> AuditSummary {
         let mut total_issues = 0;
+        let mut findings = Vec::new();
+        let mut auditors_run = Vec::new();
 
         // Get metadata for the final file
         let mkvmerge_data = match get_metadata(
@@ -32,7 +50,16 @@ impl FinalAuditor {
             Some(data) => data,
             None => {
                 runner.log_message("[ERROR] Could not read final file metadata for audit.");
-                return 1;
+                let mut finding = AuditFinding::error(
+                    "metadata_unreadable",
+                    "Could not read final file metadata",
+                );
+                finding.auditor = "final_auditor".to_string();
+                return AuditSummary {
+                    total_issues: 1,
+                    findings: vec![finding],
+                    ..AuditSummary::default()
+                };
             }
         };
 
@@ -46,39 +73,70 @@ impl FinalAuditor {
         runner.log_message("--- Running Post-Merge Audit ---");
 
         // Run all auditors in order (matching Python's run order)
-        let auditors: Vec<(&str, Box<dyn Auditor>)> = vec![
-            ("Track Flags", Box::new(track_flags::TrackFlagsAuditor)),
-            ("Video Metadata", Box::new(video_metadata::VideoMetadataAuditor)),
-            ("Dolby Vision", Box::new(dolby_vision::DolbyVisionAuditor)),
-            ("Audio Object-Based", Box::new(audio_object_based::AudioObjectBasedAuditor)),
-            ("Codec Integrity", Box::new(codec_integrity::CodecIntegrityAuditor)),
-            ("Audio Channels", Box::new(audio_channels::AudioChannelsAuditor)),
-            ("Audio Quality", Box::new(audio_quality::AudioQualityAuditor)),
-            ("Drift Correction", Box::new(drift_correction::DriftCorrectionAuditor)),
-            ("Stepping Correction", Box::new(stepping_correction::SteppingCorrectionAuditor)),
-            ("Global Shift", Box::new(global_shift::GlobalShiftAuditor)),
-            ("Audio Sync", Box::new(audio_sync::AudioSyncAuditor)),
-            ("Subtitle Formats", Box::new(subtitle_formats::SubtitleFormatsAuditor)),
-            ("Neural Confidence", Box::new(neural_confidence::NeuralConfidenceAuditor)),
-            ("Frame Audit", Box::new(frame_audit::FrameAuditAuditor)),
-            ("Subtitle Clamping", Box::new(subtitle_clamping::SubtitleClampingAuditor)),
-            ("Chapters", Box::new(chapters::ChaptersAuditor)),
-            ("Track Order", Box::new(track_order::TrackOrderAuditor)),
-            ("Language Tags", Box::new(language_tags::LanguageTagsAuditor)),
-            ("Track Names", Box::new(track_names::TrackNamesAuditor)),
-            ("Attachments", Box::new(attachments::AttachmentsAuditor)),
+        let post_mux_sync = post_mux_sync::PostMuxSyncAuditor::default();
+        let auditors: Vec<(&str, &dyn Auditor)> = vec![
+            ("Track Flags", &track_flags::TrackFlagsAuditor),
+            ("Video Metadata", &video_metadata::VideoMetadataAuditor),
+            ("Dolby Vision", &dolby_vision::DolbyVisionAuditor),
+            ("Audio Object-Based", &audio_object_based::AudioObjectBasedAuditor),
+            ("Codec Integrity", &codec_integrity::CodecIntegrityAuditor),
+            ("Audio Channels", &audio_channels::AudioChannelsAuditor),
+            ("Audio Quality", &audio_quality::AudioQualityAuditor),
+            ("Drift Correction", &drift_correction::DriftCorrectionAuditor),
+            ("Stepping Correction", &stepping_correction::SteppingCorrectionAuditor),
+            ("Global Shift", &global_shift::GlobalShiftAuditor),
+            ("Audio Sync", &audio_sync::AudioSyncAuditor),
+            ("Post-Mux Sync", &post_mux_sync),
+            ("Subtitle Formats", &subtitle_formats::SubtitleFormatsAuditor),
+            ("Neural Confidence", &neural_confidence::NeuralConfidenceAuditor),
+            ("Frame Audit", &frame_audit::FrameAuditAuditor),
+            ("Subtitle Clamping", &subtitle_clamping::SubtitleClampingAuditor),
+            ("Chapters", &chapters::ChaptersAuditor),
+            ("Track Order", &track_order::TrackOrderAuditor),
+            ("Language Tags", &language_tags::LanguageTagsAuditor),
+            ("Track Names", &track_names::TrackNamesAuditor),
+            ("Attachments", &attachments::AttachmentsAuditor),
+            ("Font Coverage", &font_coverage::FontCoverageAuditor),
+            ("Remux Integrity", &remux_integrity::RemuxIntegrityAuditor),
         ];
 
+        let keys: Vec<String> = auditors.iter().map(|(name, _)| auditor_key(name)).collect();
+        for problem in override_problems(&ctx.settings.audit_overrides, &keys) {
+            runner.log_message(&format!(
+                "[WARN] audit_overrides: {problem}; entry ignored."
+            ));
+        }
+
         for (name, auditor) in &auditors {
-            let issues = auditor.run(
+            let key = auditor_key(name);
+            let action = auditor_override(&ctx.settings.audit_overrides, &key);
+            if action == OverrideAction::Off {
+                runner.log_message(&format!("[Audit] {name}: disabled by override"));
+                continue;
+            }
+            let mut auditor_findings = auditor.run(
                 ctx,
                 runner,
                 final_mkv_path,
                 &mkvmerge_data,
                 ffprobe_data.as_ref(),
             );
+            for finding in &mut auditor_findings {
+                finding.auditor = key.clone();
+                finding.severity = action.apply(finding.severity);
+            }
+            let issues = auditor_findings
+                .iter()
+                .filter(|f| f.severity >= AuditSeverity::Warning)
+                .count() as i32;
+            auditors_run.push(key);
+            findings.extend(auditor_findings);
             if issues > 0 {
                 runner.log_message(&format!("[Audit] {name}: {issues} issue(s)"));
+                (ctx.events)(&JobEvent::AuditorFinding {
+                    auditor: name.to_string(),
+                    issues,
+                });
             }
             total_issues += issues;
         }
@@ -91,6 +149,132 @@ impl FinalAuditor {
             ));
         }
 
-        total_issues
+        AuditSummary {
+            total_issues,
+            findings,
+            auditors_run,
+            post_mux_sync: post_mux_sync.take_checks(),
+        }
+    }
+}
+
+/// Settings key for an auditor, e.g. "Post-Mux Sync" -> "post_mux_sync".
+fn auditor_key(name: &str) -> String {
+    name.to_lowercase().replace([' ', '-'], "_")
+}
+
+/// What an `audit_overrides` entry does to one auditor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverrideAction {
+    Off,
+    On,
+    /// Run, and report its warnings and errors at this severity
+    Severity(AuditSeverity),
+}
+
+impl OverrideAction {
+    /// Severity to report for a finding; informational findings keep theirs.
+    fn apply(self, severity: AuditSeverity) -> AuditSeverity {
+        match self {
+            Self::Severity(forced) if severity >= AuditSeverity::Warning => forced,
+            _ => severity,
+        }
+    }
+}
+
+/// Look up `key` in a comma-separated `auditor=action` list; unknown or
+/// missing entries leave the auditor on.
+fn auditor_override(overrides: &str, key: &str) -> OverrideAction {
+    overrides
+        .split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(key))
+        .and_then(|(_, value)| parse_override(value.trim()))
+        .unwrap_or(OverrideAction::On)
+}
+
+/// Entries of `overrides` that name no known auditor or no known action.
+fn override_problems(overrides: &str, keys: &[String]) -> Vec<String> {
+    overrides
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let Some((name, value)) = entry.split_once('=') else {
+                return Some(format!("'{entry}' is not auditor=action"));
+            };
+            let (name, value) = (name.trim(), value.trim());
+            if !keys.iter().any(|k| k.eq_ignore_ascii_case(name)) {
+                Some(format!("unknown auditor '{name}'"))
+            } else if parse_override(value).is_none() {
+                Some(format!("unknown action '{value}' for '{name}'"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_override(value: &str) -> Option<OverrideAction> {
+    match value.to_lowercase().as_str() {
+        "off" | "disabled" | "false" => Some(OverrideAction::Off),
+        "on" | "enabled" | "true" => Some(OverrideAction::On),
+        "info" => Some(OverrideAction::Severity(AuditSeverity::Info)),
+        "warning" | "warn" => Some(OverrideAction::Severity(AuditSeverity::Warning)),
+        "error" => Some(OverrideAction::Severity(AuditSeverity::Error)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_disable_and_escalate_auditors() {
+        let overrides = "dolby_vision=off, Track_Order = error,subtitle_clamping=info";
+        assert_eq!(auditor_override(overrides, "dolby_vision"), OverrideAction::Off);
+        assert_eq!(
+            auditor_override(overrides, "track_order"),
+            OverrideAction::Severity(AuditSeverity::Error)
+        );
+        assert_eq!(
+            auditor_override(overrides, "subtitle_clamping"),
+            OverrideAction::Severity(AuditSeverity::Info)
+        );
+        assert_eq!(auditor_override(overrides, "chapters"), OverrideAction::On);
+        assert_eq!(auditor_override("chapters=bogus", "chapters"), OverrideAction::On);
+    }
+
+    #[test]
+    fn unrecognized_overrides_are_reported() {
+        let keys = vec!["chapters".to_string(), "track_order".to_string()];
+        assert!(override_problems("chapters=off, Track_Order=error", &keys).is_empty());
+        assert_eq!(
+            override_problems("chapter=off,track_order=loud,chapters", &keys),
+            vec![
+                "unknown auditor 'chapter'".to_string(),
+                "unknown action 'loud' for 'track_order'".to_string(),
+                "'chapters' is not auditor=action".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn severity_overrides_leave_info_findings_alone() {
+        let error = OverrideAction::Severity(AuditSeverity::Error);
+        assert_eq!(error.apply(AuditSeverity::Info), AuditSeverity::Info);
+        assert_eq!(error.apply(AuditSeverity::Warning), AuditSeverity::Error);
+
+        let info = OverrideAction::Severity(AuditSeverity::Info);
+        assert_eq!(info.apply(AuditSeverity::Error), AuditSeverity::Info);
+        let on = OverrideAction::On;
+        assert_eq!(on.apply(AuditSeverity::Warning), AuditSeverity::Warning);
+    }
+
+    #[test]
+    fn auditor_keys_are_snake_case() {
+        assert_eq!(auditor_key("Post-Mux Sync"), "post_mux_sync");
+        assert_eq!(auditor_key("Audio Object-Based"), "audio_object_based");
     }
 }
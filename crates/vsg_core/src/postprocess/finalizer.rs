@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::io::runner::CommandRunner;
+use crate::io::runner::{CommandRunner, RunError};
 use crate::models::settings::AppSettings;
 
 use super::chapter_backup::{extract_chapters_xml, inject_chapters};
@@ -27,7 +27,7 @@ pub fn check_if_rebasing_is_needed(
         tool_paths,
     );
 
-    if let Some(out) = out {
+    if let Ok(out) = out {
         if let Ok(pts) = out.trim().parse::<f64>() {
             if pts > 0.01 {
                 runner.log_message(&format!(
@@ -73,10 +73,10 @@ pub fn finalize_merged_file(
         tool_paths,
     );
 
-    if result.is_none() {
-        runner.log_message(
-            "[WARNING] Timestamp normalization with FFmpeg failed. Using original file.",
-        );
+    if let Err(e) = result {
+        runner.log_message(&format!(
+            "[WARNING] Timestamp normalization with FFmpeg failed ({e}). Using original file."
+        ));
         let _ = std::fs::rename(temp_output_path, final_output_path);
         return;
     }
@@ -92,10 +92,10 @@ pub fn finalize_merged_file(
         inject_chapters(temp_output_path, chapters_xml, runner, tool_paths);
     }
 
-    // Step 4: Optional tag stripping
+    // Step 4: Optional tag stripping (provenance tags are written afterwards)
     if settings.post_mux_strip_tags {
         runner.log_message("[Finalize] Step 2/2: Stripping ENCODER tag with mkvpropedit...");
-        runner.run(
+        let _ = runner.run(
             &["mkvpropedit", &temp_output_path.to_string_lossy(), "--tags", "all:"],
             tool_paths,
         );
@@ -107,6 +107,87 @@ pub fn finalize_merged_file(
     runner.log_message("[Finalize] Post-merge finalization complete.");
 }
 
+/// Finalize without FFmpeg — the lossless alternative to `finalize_merged_file`
+///
+/// The planned rebase is already folded into the mux's `--sync` offsets, so
+/// usually the file only needs moving. If the tracks still start late
+/// (e.g. B-frame reorder delay on the first video frame), the remaining
+/// offset is removed by an mkvmerge pass over the merged file, which keeps
+/// chapters (shifted along with the tracks), attachments, tags and codec
+/// private data intact.
+pub fn finalize_with_mkvmerge(
+    temp_output_path: &Path,
+    final_output_path: &Path,
+    runner: &CommandRunner,
+    settings: &AppSettings,
+    tool_paths: &HashMap<String, String>,
+) -> Result<(), String> {
+    runner.log_message("--- Post-Merge: Finalizing File (mkvmerge) ---");
+
+    let residual_ms = runner
+        .run(&["mkvmerge", "-J", &temp_output_path.to_string_lossy()], tool_paths)
+        .ok()
+        .and_then(|out| serde_json::from_str::<serde_json::Value>(&out).ok())
+        .and_then(|info| first_timestamp_ms(&info))
+        .unwrap_or(0.0);
+
+    // mkvmerge offsets are whole milliseconds
+    let rebase_ms = residual_ms.floor() as i64;
+    if residual_ms <= 10.0 || rebase_ms <= 0 {
+        runner.log_message(&format!(
+            "[Finalize] Tracks start at {residual_ms:.3}ms. No remux required."
+        ));
+        move_file(temp_output_path, final_output_path)?;
+        runner.log_message("[Finalize] Post-merge finalization complete.");
+        return Ok(());
+    }
+
+    runner.log_message(&format!(
+        "[Finalize] Tracks start at {residual_ms:.3}ms. Rebasing by -{rebase_ms}ms with mkvmerge..."
+    ));
+    let rebased = temp_output_path.with_extension("rebased.mkv");
+    let mut args: Vec<String> = vec![
+        "mkvmerge".to_string(),
+        "--output".to_string(),
+        rebased.to_string_lossy().to_string(),
+        "--sync".to_string(),
+        format!("-1:-{rebase_ms}"),
+        "--chapter-sync".to_string(),
+        format!("-{rebase_ms}"),
+    ];
+    if settings.disable_track_statistics_tags {
+        args.push("--disable-track-statistics-tags".to_string());
+    }
+    args.push(temp_output_path.to_string_lossy().to_string());
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match runner.run(&arg_refs, tool_paths) {
+        Ok(_) | Err(RunError::Warnings { .. }) => {
+            let _ = std::fs::remove_file(temp_output_path);
+            move_file(&rebased, final_output_path)?;
+        }
+        Err(e) => {
+            runner.log_message(&format!(
+                "[WARNING] Timestamp rebasing with mkvmerge failed ({e}). Using original file."
+            ));
+            let _ = std::fs::remove_file(&rebased);
+            move_file(temp_output_path, final_output_path)?;
+        }
+    }
+    runner.log_message("[Finalize] Post-merge finalization complete.");
+    Ok(())
+}
+
+/// Earliest track start (ms) in `mkvmerge -J` output.
+fn first_timestamp_ms(info: &serde_json::Value) -> Option<f64> {
+    info.get("tracks")?
+        .as_array()?
+        .iter()
+        .filter_map(|t| t.get("properties")?.get("minimum_timestamp")?.as_i64())
+        .min()
+        .map(|ns| ns as f64 / 1_000_000.0)
+}
+
 fn move_file(from: &Path, to: &Path) -> Result<(), String> {
     std::fs::rename(from, to).or_else(|_| {
         std::fs::copy(from, to)
@@ -115,3 +196,20 @@ fn move_file(from: &Path, to: &Path) -> Result<(), String> {
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn first_timestamp_is_earliest_track() {
+        let info = json!({"tracks": [
+            {"properties": {"minimum_timestamp": 83_000_000}},
+            {"properties": {"minimum_timestamp": 120_500_000}},
+            {"properties": {}},
+        ]});
+        assert_eq!(first_timestamp_ms(&info), Some(83.0));
+        assert_eq!(first_timestamp_ms(&json!({"tracks": []})), None);
+    }
+}
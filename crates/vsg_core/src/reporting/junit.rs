@@ -0,0 +1,203 @@
+//! JUnit XML export of audit findings.
+//!
+//! Each job becomes a `<testsuite>` and each auditor that ran becomes a
+//! `<testcase>`. Error findings are reported as `<failure>`s, warnings and
+//! info findings go to `<system-out>` so CI keeps them without failing the
+//! build. A job that failed before the audit gets a single `<error>` case.
+
+use serde_json::Value;
+
+/// Render a batch report (as written by `ReportWriter`) as JUnit XML.
+pub fn report_to_junit(report: &Value) -> String {
+    let jobs = report["jobs"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let suites: Vec<TestSuite> = jobs.iter().map(job_to_testsuite).collect();
+    let tests: usize = suites.iter().map(|s| s.tests).sum();
+    let failures: usize = suites.iter().map(|s| s.failures).sum();
+    let errors: usize = suites.iter().map(|s| s.errors).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\">\n",
+        escape(report["batch_name"].as_str().unwrap_or("vsg"))
+    ));
+    for suite in &suites {
+        xml.push_str(&suite.xml);
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// A rendered `<testsuite>` and its counts for the enclosing element.
+struct TestSuite {
+    xml: String,
+    tests: usize,
+    failures: usize,
+    errors: usize,
+}
+
+/// One `<testsuite>` for a report job entry.
+fn job_to_testsuite(job: &Value) -> TestSuite {
+    let name = escape(job["name"].as_str().unwrap_or("Unknown"));
+    let status = job["status"].as_str().unwrap_or("Unknown");
+
+    if status == "Failed" {
+        let message = escape(job["error"].as_str().unwrap_or("Job failed"));
+        let xml = format!(
+            "  <testsuite name=\"{name}\" tests=\"1\" failures=\"0\" errors=\"1\">\n    \
+             <testcase classname=\"{name}\" name=\"pipeline\">\n      \
+             <error message=\"{message}\"/>\n    </testcase>\n  </testsuite>\n"
+        );
+        return TestSuite {
+            xml,
+            tests: 1,
+            failures: 0,
+            errors: 1,
+        };
+    }
+
+    let findings = job["audit_results"]["details"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let mut auditors: Vec<String> = job["audit_results"]["auditors"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    // Older reports have no auditor list; fall back to those with findings
+    for finding in findings {
+        let auditor = finding["auditor"].as_str().unwrap_or("audit");
+        if !auditors.iter().any(|a| a == auditor) {
+            auditors.push(auditor.to_string());
+        }
+    }
+
+    let mut cases = String::new();
+    let mut failures = 0;
+    for auditor in &auditors {
+        let own: Vec<&Value> = findings
+            .iter()
+            .filter(|f| f["auditor"].as_str().unwrap_or("audit") == auditor)
+            .collect();
+        cases.push_str(&format!(
+            "    <testcase classname=\"{name}\" name=\"{}\">\n",
+            escape(auditor)
+        ));
+        let mut output = Vec::new();
+        for finding in own {
+            let line = finding_line(finding);
+            if finding["severity"].as_str() == Some("error") {
+                failures += 1;
+                cases.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                    escape(finding["message"].as_str().unwrap_or("")),
+                    escape(finding["code"].as_str().unwrap_or("")),
+                    escape(&line)
+                ));
+            } else {
+                output.push(line);
+            }
+        }
+        if !output.is_empty() {
+            cases.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape(&output.join("\n"))
+            ));
+        }
+        cases.push_str("    </testcase>\n");
+    }
+
+    let xml = format!(
+        "  <testsuite name=\"{name}\" tests=\"{}\" failures=\"{failures}\" errors=\"0\">\n\
+         {cases}  </testsuite>\n",
+        auditors.len()
+    );
+    TestSuite {
+        xml,
+        tests: auditors.len(),
+        failures,
+        errors: 0,
+    }
+}
+
+/// Human-readable one-liner for a finding.
+fn finding_line(finding: &Value) -> String {
+    let mut line = format!(
+        "[{}] {}: {}",
+        finding["severity"].as_str().unwrap_or("warning"),
+        finding["code"].as_str().unwrap_or(""),
+        finding["message"].as_str().unwrap_or("")
+    );
+    let track = &finding["track"];
+    if track.is_object() {
+        line.push_str(&format!(
+            " ({} track {} from {})",
+            track["track_type"].as_str().unwrap_or("?"),
+            track["id"].as_i64().unwrap_or(-1),
+            track["source"].as_str().unwrap_or("?")
+        ));
+    }
+    if let (Some(expected), Some(actual)) =
+        (finding["expected"].as_str(), finding["actual"].as_str())
+    {
+        line.push_str(&format!(" expected '{expected}', got '{actual}'"));
+    }
+    line
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn errors_fail_and_warnings_pass() {
+        let report = json!({
+            "batch_name": "Show <S01>",
+            "jobs": [
+                {
+                    "name": "ep01.mkv",
+                    "status": "Merged",
+                    "audit_results": {
+                        "auditors": ["track_flags", "audio_sync"],
+                        "details": [
+                            {"auditor": "audio_sync", "code": "delay_mismatch", "severity": "error",
+                             "message": "Delay mismatch", "expected": "100", "actual": "140",
+                             "track": {"index": 1, "source": "Source 2", "id": 1, "track_type": "audio"}},
+                            {"auditor": "track_flags", "code": "default_flag_mismatch",
+                             "severity": "warning", "message": "Default flag & forced"}
+                        ]
+                    }
+                },
+                {"name": "ep02.mkv", "status": "Failed", "error": "mkvmerge failed"}
+            ]
+        });
+        let xml = report_to_junit(&report);
+        assert!(xml.contains(
+            "<testsuites name=\"Show &lt;S01&gt;\" tests=\"3\" failures=\"1\" errors=\"1\">"
+        ));
+        assert!(xml.contains("<failure message=\"Delay mismatch\" type=\"delay_mismatch\">"));
+        assert!(xml.contains("expected &apos;100&apos;, got &apos;140&apos;"));
+        assert!(xml.contains("Default flag &amp; forced</system-out>"));
+        assert!(xml.contains("<error message=\"mkvmerge failed\"/>"));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod debug_manager;
 pub mod debug_paths;
+pub mod junit;
 pub mod report_writer;
@@ -53,7 +53,9 @@ impl ReportWriter {
             "is_batch": is_batch,
             "output_directory": output_dir,
             "total_jobs": total_jobs,
-            "summary": { "successful": 0, "warnings": 0, "failed": 0, "total_issues": 0 },
+            "summary": {
+                "successful": 0, "warnings": 0, "failed": 0, "skipped": 0, "total_issues": 0,
+            },
             "jobs": [],
         });
 
@@ -67,7 +69,16 @@ impl ReportWriter {
             return;
         }
 
-        let entry = json!({
+        let entry = Self::job_entry(job_result, job_index);
+        if let Some(jobs) = self.report_data["jobs"].as_array_mut() {
+            jobs.push(entry);
+        }
+        self.write_report();
+    }
+
+    /// Report entry for one job's results.
+    pub fn job_entry(job_result: &HashMap<String, Value>, job_index: usize) -> Value {
+        json!({
             "index": job_index,
             "name": job_result.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown"),
             "status": job_result.get("status").and_then(|v| v.as_str()).unwrap_or("Unknown"),
@@ -83,15 +94,12 @@ impl ReportWriter {
             },
             "audit_results": {
                 "total_issues": job_result.get("issues").and_then(|v| v.as_i64()).unwrap_or(0),
-                "details": job_result.get("audit_details").unwrap_or(&json!([])),
+                "details": job_result.get("audit_findings").unwrap_or(&json!([])),
+                "auditors": job_result.get("audits_run").unwrap_or(&json!([])),
+                "post_mux_sync": job_result.get("post_mux_sync").unwrap_or(&json!([])),
             },
             "sync_stability": job_result.get("sync_stability_issues").unwrap_or(&json!([])),
-        });
-
-        if let Some(jobs) = self.report_data["jobs"].as_array_mut() {
-            jobs.push(entry);
-        }
-        self.write_report();
+        })
     }
 
     /// Finalize the report — `finalize`
@@ -103,6 +111,7 @@ impl ReportWriter {
         let mut successful = 0i64;
         let mut warnings = 0i64;
         let mut failed = 0i64;
+        let mut skipped = 0i64;
         let mut total_issues = 0i64;
 
         if let Some(jobs) = self.report_data["jobs"].as_array() {
@@ -112,6 +121,8 @@ impl ReportWriter {
 
                 if status == "Failed" {
                     failed += 1;
+                } else if status == "Skipped" {
+                    skipped += 1;
                 } else if issues > 0 {
                     warnings += 1;
                 } else {
@@ -125,6 +136,7 @@ impl ReportWriter {
             "successful": successful,
             "warnings": warnings,
             "failed": failed,
+            "skipped": skipped,
             "total_issues": total_issues,
         });
         self.report_data["finalized_at"] = json!(Local::now().to_rfc3339());
@@ -174,7 +186,7 @@ impl ReportWriter {
 
     pub fn get_job_status_summary(job: &Value) -> String {
         let status = job["status"].as_str().unwrap_or("Unknown");
-        if status == "Failed" { return "Failed".to_string(); }
+        if status == "Failed" || status == "Skipped" { return status.to_string(); }
         let issues = job["audit_results"]["total_issues"].as_i64().unwrap_or(0);
         if issues > 0 {
             format!("Warning ({issues} issue{})", if issues != 1 { "s" } else { "" })
@@ -0,0 +1,404 @@
+//! Font coverage analysis for ASS tracks.
+//!
+//! Collects every family/bold/italic combination a script renders with —
+//! from its styles and inline `\fn`, `\b`, `\i` and `\r` overrides — along
+//! with the characters drawn in each. Those requests are then matched
+//! against the font faces planned for attachment, and the text is checked
+//! against the chosen face's `cmap`.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::font_manager::{read_font_faces, FontFace};
+use crate::models::enums::TrackType;
+use crate::models::jobs::PlanItem;
+
+use super::data::{SubtitleData, SubtitleStyle};
+use super::parsers::ass_parser::parse_ass_file;
+
+/// Font file extensions that can be parsed for coverage.
+const PARSEABLE_FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// A family/weight/slant combination a script renders with.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontRequest {
+    pub family: String,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl std::fmt::Display for FontRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}'", self.family)?;
+        match (self.bold, self.italic) {
+            (true, true) => write!(f, " bold italic"),
+            (true, false) => write!(f, " bold"),
+            (false, true) => write!(f, " italic"),
+            (false, false) => Ok(()),
+        }
+    }
+}
+
+/// Characters drawn with each font request.
+pub type FontUsage = BTreeMap<FontRequest, BTreeSet<char>>;
+
+/// How a font request resolves against the available faces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontMatch {
+    /// No face of the family is available
+    Missing,
+    /// A face of the family was chosen; `exact_style` is false when the
+    /// renderer will have to synthesize bold or italic
+    Found {
+        face: usize,
+        exact_style: bool,
+        missing_glyphs: Vec<char>,
+    },
+}
+
+/// Collect the fonts and characters an ASS script uses.
+pub fn collect_font_usage(data: &SubtitleData) -> FontUsage {
+    let mut usage = FontUsage::new();
+
+    for event in data.events.iter().filter(|e| !e.is_comment) {
+        let base = find_style(data, &event.style);
+        let mut current = style_request(base);
+        let mut drawing = false;
+
+        let mut rest = event.text.as_str();
+        while !rest.is_empty() {
+            if let Some(block) = rest.strip_prefix('{') {
+                let close = block.find('}').unwrap_or(block.len());
+                for tag in block[..close].split('\\').skip(1) {
+                    apply_tag(data, base, tag.trim(), &mut current, &mut drawing);
+                }
+                rest = block.get(close + 1..).unwrap_or("");
+                continue;
+            }
+
+            let next = rest.find('{').unwrap_or(rest.len());
+            if !drawing && !current.family.is_empty() {
+                let chars = usage.entry(current.clone()).or_default();
+                chars.extend(visible_chars(&rest[..next]));
+            }
+            rest = &rest[next..];
+        }
+    }
+
+    usage.retain(|_, chars| !chars.is_empty());
+    usage
+}
+
+/// Merge the usage of every ASS subtitle track in the plan.
+pub fn collect_plan_font_usage(items: &[PlanItem]) -> FontUsage {
+    let mut usage = FontUsage::new();
+    for (_, track_usage) in plan_font_usage(items) {
+        for (request, chars) in track_usage {
+            usage.entry(request).or_default().extend(chars);
+        }
+    }
+    usage
+}
+
+/// Per-track usage of the ASS subtitle tracks in the plan. Tracks that
+/// can't be parsed are skipped.
+pub fn plan_font_usage(items: &[PlanItem]) -> Vec<(&PlanItem, FontUsage)> {
+    ass_tracks(items)
+        .filter_map(|(item, path)| {
+            let data = parse_ass_file(path).ok()?;
+            Some((item, collect_font_usage(&data)))
+        })
+        .collect()
+}
+
+/// Font files among `paths` that some request of the plan's ASS tracks
+/// resolves to. `None` when a track can't be parsed, since its fonts are
+/// then unknown.
+pub fn referenced_font_files<P: AsRef<Path>>(
+    items: &[PlanItem],
+    paths: &[P],
+) -> Option<HashSet<PathBuf>> {
+    let mut usage = FontUsage::new();
+    for (_, path) in ass_tracks(items) {
+        let data = parse_ass_file(path).ok()?;
+        for (request, chars) in collect_font_usage(&data) {
+            usage.entry(request).or_default().extend(chars);
+        }
+    }
+
+    let faces = load_font_faces(paths);
+    let mut referenced = HashSet::new();
+    for (request, chars) in &usage {
+        if let FontMatch::Found { face, .. } = match_font(request, chars, &faces) {
+            referenced.insert(faces[face].file_path.clone());
+        }
+    }
+    Some(referenced)
+}
+
+/// Subtitle plan items whose processed file is ASS/SSA.
+fn ass_tracks(items: &[PlanItem]) -> impl Iterator<Item = (&PlanItem, &Path)> {
+    items
+        .iter()
+        .filter(|item| item.track.track_type == TrackType::Subtitles)
+        .filter_map(|item| {
+            let path = item.extracted_path.as_deref()?;
+            let ext = path.extension()?.to_string_lossy().to_lowercase();
+            (ext == "ass" || ext == "ssa").then_some((item, path))
+        })
+}
+
+/// Read every face of every parseable font among `paths`, skipping other
+/// files.
+pub fn load_font_faces<P: AsRef<Path>>(paths: &[P]) -> Vec<FontFace> {
+    paths
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|p| is_parseable_font(p))
+        .filter_map(|p| read_font_faces(p).ok())
+        .flatten()
+        .collect()
+}
+
+/// Whether `path` has a font extension whose tables can be read.
+pub fn is_parseable_font(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| PARSEABLE_FONT_EXTENSIONS.contains(&e.as_str()))
+}
+
+/// Resolve `request` against `faces` the way libass does, and check glyph
+/// coverage of `chars`.
+///
+/// A full or PostScript name selects that exact face. Otherwise every face
+/// whose family name (any language, or typographic) matches is a candidate
+/// and the closest slant, then weight, wins.
+pub fn match_font(request: &FontRequest, chars: &BTreeSet<char>, faces: &[FontFace]) -> FontMatch {
+    let wanted_weight: i32 = if request.bold { 700 } else { 400 };
+    let best = faces
+        .iter()
+        .enumerate()
+        .find(|(_, f)| f.matches_full_name(&request.family))
+        .or_else(|| {
+            faces
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.matches_family(&request.family))
+                .min_by_key(|(_, f)| {
+                    (
+                        f.italic != request.italic,
+                        (i32::from(f.weight) - wanted_weight).abs(),
+                    )
+                })
+        });
+
+    match best {
+        None => FontMatch::Missing,
+        Some((index, face)) => FontMatch::Found {
+            face: index,
+            exact_style: face.bold == request.bold && face.italic == request.italic,
+            missing_glyphs: chars.iter().copied().filter(|&c| !face.covers(c)).collect(),
+        },
+    }
+}
+
+/// The style an event renders with, falling back the way renderers do.
+fn find_style<'a>(data: &'a SubtitleData, name: &str) -> Option<&'a SubtitleStyle> {
+    let name = name.trim_start_matches('*');
+    data.styles
+        .iter()
+        .find(|(n, _)| n == name)
+        .or_else(|| data.styles.iter().find(|(n, _)| n == "Default"))
+        .or_else(|| data.styles.first())
+        .map(|(_, style)| style)
+}
+
+fn style_request(style: Option<&SubtitleStyle>) -> FontRequest {
+    match style {
+        Some(style) => FontRequest {
+            family: clean_family(&style.fontname),
+            bold: style.bold != 0,
+            italic: style.italic != 0,
+        },
+        None => FontRequest {
+            family: String::new(),
+            bold: false,
+            italic: false,
+        },
+    }
+}
+
+/// Apply one override tag (without its leading backslash).
+fn apply_tag(
+    data: &SubtitleData,
+    base: Option<&SubtitleStyle>,
+    tag: &str,
+    current: &mut FontRequest,
+    drawing: &mut bool,
+) {
+    let base_request = style_request(base);
+    if let Some(family) = tag.strip_prefix("fn") {
+        current.family = if family.trim().is_empty() {
+            base_request.family
+        } else {
+            clean_family(family)
+        };
+    } else if let Some(value) = numeric_arg(tag, 'b') {
+        current.bold = match value {
+            None => base_request.bold,
+            Some(weight) => weight == 1 || weight >= 700,
+        };
+    } else if let Some(value) = numeric_arg(tag, 'i') {
+        current.italic = value.map_or(base_request.italic, |v| v != 0);
+    } else if let Some(value) = numeric_arg(tag, 'p') {
+        *drawing = value.unwrap_or(0) > 0;
+    } else if let Some(style_name) = tag.strip_prefix('r') {
+        *current = if style_name.trim().is_empty() {
+            base_request
+        } else {
+            style_request(find_style(data, style_name.trim()))
+        };
+    }
+}
+
+/// `Some(None)` for a bare `\x` tag, `Some(Some(n))` for `\xN`, and `None`
+/// when the tag is a different one that merely starts with the same letter.
+fn numeric_arg(tag: &str, name: char) -> Option<Option<i64>> {
+    let rest = tag.strip_prefix(name)?;
+    if rest.is_empty() {
+        return Some(None);
+    }
+    rest.parse().ok().map(Some)
+}
+
+/// Strip the vertical-layout `@` prefix renderers ignore when matching.
+fn clean_family(name: &str) -> String {
+    name.trim().trim_start_matches('@').to_string()
+}
+
+/// Characters that need a glyph, with `\N`, `\n` and `\h` escapes resolved.
+fn visible_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    let mut chars = text.chars().peekable();
+    std::iter::from_fn(move || loop {
+        let c = chars.next()?;
+        if c == '\\' {
+            match chars.peek() {
+                Some('N') | Some('n') => {
+                    chars.next();
+                    continue;
+                }
+                Some('h') => {
+                    chars.next();
+                    return Some('\u{a0}');
+                }
+                _ => {}
+            }
+        }
+        if !c.is_whitespace() && !c.is_control() {
+            return Some(c);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_manager::FontName;
+    use crate::subtitles::data::SubtitleEvent;
+
+    fn request(family: &str, bold: bool, italic: bool) -> FontRequest {
+        FontRequest {
+            family: family.to_string(),
+            bold,
+            italic,
+        }
+    }
+
+    fn script(events: &[(&str, &str)]) -> SubtitleData {
+        let mut data = SubtitleData::new();
+        let mut default = SubtitleStyle::new("Default");
+        default.fontname = "Open Sans".to_string();
+        default.bold = -1;
+        let mut sign = SubtitleStyle::new("Sign");
+        sign.fontname = "@Kozuka Gothic".to_string();
+        data.styles = vec![("Default".to_string(), default), ("Sign".to_string(), sign)];
+        for (style, text) in events {
+            let mut event = SubtitleEvent::new(0.0, 1000.0, text);
+            event.style = style.to_string();
+            data.events.push(event);
+        }
+        data
+    }
+
+    #[test]
+    fn collects_styles_and_inline_overrides() {
+        let data = script(&[
+            ("Default", r"Hi {\i1}there{\b0\fnComic Sans}ok{\r}X"),
+            ("Sign", r"看板\N{\rDefault\blur2\iclip(1,2,3,4)}Z"),
+            ("Default", r"{\p1}m 0 0 l 10 10{\p0}Q"),
+        ]);
+        let usage = collect_font_usage(&data);
+
+        let chars = |r: FontRequest| usage[&r].iter().collect::<String>();
+        assert_eq!(chars(request("Open Sans", true, false)), "HQXZi");
+        assert_eq!(chars(request("Open Sans", true, true)), "ehrt");
+        assert_eq!(chars(request("Comic Sans", false, true)), "ko");
+        assert_eq!(chars(request("Kozuka Gothic", false, false)), "板看");
+        assert_eq!(usage.len(), 4);
+    }
+
+    #[test]
+    fn matches_closest_face_and_reports_uncovered_glyphs() {
+        let face = |full_name: &str, weight, italic| FontFace {
+            names: [(1, "Open Sans"), (4, full_name)]
+                .into_iter()
+                .map(|(name_id, value)| FontName {
+                    name_id,
+                    platform_id: 3,
+                    language_id: 0x409,
+                    value: value.to_string(),
+                })
+                .collect(),
+            weight,
+            bold: weight >= 700,
+            italic,
+            coverage: vec![(0x20, 0x7e)],
+            ..FontFace::default()
+        };
+        let faces = vec![
+            face("Open Sans Regular", 400, false),
+            face("Open Sans Bold", 700, false),
+            face("Open Sans Light Italic", 300, true),
+        ];
+        let chars: BTreeSet<char> = "Aé".chars().collect();
+
+        assert_eq!(
+            match_font(&request("open sans", true, false), &chars, &faces),
+            FontMatch::Found {
+                face: 1,
+                exact_style: true,
+                missing_glyphs: vec!['é']
+            }
+        );
+        assert!(matches!(
+            match_font(&request("Open Sans", true, true), &chars, &faces),
+            FontMatch::Found {
+                face: 2,
+                exact_style: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            match_font(&request("OPEN SANS BOLD", false, false), &chars, &faces),
+            FontMatch::Found {
+                face: 1,
+                exact_style: false,
+                ..
+            }
+        ));
+        assert_eq!(
+            match_font(&request("Arial", false, false), &chars, &faces),
+            FontMatch::Missing
+        );
+    }
+}
@@ -16,6 +16,7 @@ pub mod writers;
 pub mod checkpoint_selection;
 pub mod diagnostics;
 pub mod edit_plan;
+pub mod font_coverage;
 #[allow(unused_imports, unused_variables, dead_code, clippy::all)]
 pub mod frame_utils;
 #[allow(unused_imports, unused_variables, dead_code, clippy::all)]
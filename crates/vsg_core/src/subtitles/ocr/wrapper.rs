@@ -94,6 +94,7 @@ pub fn process_ocr_with_preservation(
             } else {
                 format!("{} (Original)", track.props.name)
             },
+            audio_channels: None,
         },
     };
 
@@ -106,6 +107,7 @@ pub fn process_ocr_with_preservation(
             codec_id: "S_TEXT/ASS".to_string(),
             lang: track.props.lang.clone(),
             name: track.props.name.clone(),
+            audio_channels: None,
         },
     };
 
@@ -1,2 +1,3 @@
+pub mod speed;
 pub mod stepping;
 pub mod style_ops;
@@ -0,0 +1,53 @@
+//! Speed-ratio operation for SubtitleData.
+//!
+//! When a source's audio is retimed by a speed ratio, its subtitles are
+//! scaled by the same ratio so they stay on the stretched audio. Runs
+//! before stepping and sync, which work on the retimed timeline.
+
+use chrono::Local;
+
+use crate::subtitles::data::{OperationRecord, OperationResult, SubtitleData};
+
+/// Scale every event time by `ratio` (source speed / reference speed).
+pub fn apply_speed_ratio(
+    data: &mut SubtitleData,
+    ratio: f64,
+    log: Option<&dyn Fn(&str)>,
+) -> OperationResult {
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return OperationResult::err("speed_ratio", &format!("Invalid speed ratio {ratio}"));
+    }
+
+    let mut max_shift_ms: f64 = 0.0;
+    for event in &mut data.events {
+        let new_start = event.start_ms * ratio;
+        max_shift_ms = max_shift_ms.max((new_start - event.start_ms).abs());
+        event.start_ms = new_start;
+        event.end_ms *= ratio;
+    }
+
+    let events_affected = data.events.len() as i32;
+    let record = OperationRecord {
+        operation: "speed_ratio".to_string(),
+        timestamp: Local::now().to_rfc3339(),
+        parameters: serde_json::json!({ "ratio": ratio }),
+        events_affected,
+        styles_affected: 0,
+        summary: format!(
+            "Scaled {events_affected} events by {ratio:.6}, max shift {max_shift_ms:.1}ms"
+        ),
+    };
+    data.operations.push(record.clone());
+
+    if let Some(log_fn) = log {
+        log_fn(&format!("[SpeedRatio] {}", record.summary));
+    }
+
+    let mut result = OperationResult::ok("speed_ratio");
+    result.events_affected = events_affected;
+    result.summary = record.summary;
+    result
+        .details
+        .insert("max_shift_ms".to_string(), serde_json::json!(max_shift_ms));
+    result
+}
@@ -65,6 +65,8 @@ Dialog {
                 Label { text: String(logic.warnings); color: logic.warnings > 0 ? "#f39c12" : "gray" }
                 Label { text: "Failed:" }
                 Label { text: String(logic.failed); color: logic.failed > 0 ? "#e74c3c" : "gray" }
+                Label { text: "Skipped:"; visible: logic.skipped > 0 }
+                Label { text: String(logic.skipped); color: "gray"; visible: logic.skipped > 0 }
             }
         }
 
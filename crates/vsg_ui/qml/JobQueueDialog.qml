@@ -4,6 +4,7 @@
 import QtQuick 2.15
 import QtQuick.Controls 2.15
 import QtQuick.Layouts 1.15
+import QtQuick.Dialogs
 import com.vsg.ui 1.0
 
 Dialog {
@@ -15,6 +16,7 @@ Dialog {
     standardButtons: Dialog.Ok | Dialog.Cancel
 
     property string tempRoot: ""
+    property string configDir: ""
 
     JobQueueLogic {
         id: logic
@@ -24,6 +26,7 @@ Dialog {
 
     Component.onCompleted: {
         logic.initialize(tempRoot)
+        refreshTemplates()
     }
 
     footer: DialogButtonBox {
@@ -80,11 +83,60 @@ Dialog {
                 MouseArea {
                     anchors.fill: parent
                     onClicked: tableView.currentIndex = index
-                    onDoubleClicked: logic.configure_job_at_row(index)
+                    onDoubleClicked: configureJob(index)
                 }
             }
         }
 
+        // Layout templates
+        RowLayout {
+            Layout.fillWidth: true
+            spacing: 6
+
+            Label { text: "Layout Template:" }
+            ComboBox {
+                id: templateCombo
+                Layout.preferredWidth: 240
+                model: []
+            }
+            Button {
+                text: "Apply to Selected"
+                enabled: templateCombo.currentIndex >= 0 && tableView.currentIndex >= 0
+                onClicked: logic.apply_layout_template(configDir, templateCombo.currentText,
+                                                       JSON.stringify([tableView.currentIndex]))
+            }
+            Button {
+                text: "Save as Template..."
+                enabled: tableView.currentIndex >= 0
+                onClicked: {
+                    templateNameField.text = ""
+                    templateNameDialog.open()
+                }
+            }
+            Button {
+                text: "Delete"
+                enabled: templateCombo.currentIndex >= 0
+                onClicked: {
+                    logic.delete_layout_template(configDir, templateCombo.currentText)
+                    refreshTemplates()
+                }
+            }
+            Item { Layout.fillWidth: true }
+            Button {
+                text: "Import..."
+                onClicked: templateImportDialog.open()
+            }
+            Button {
+                text: "Export..."
+                enabled: templateCombo.currentIndex >= 0
+                onClicked: templateExportDialog.open()
+            }
+            Label {
+                id: templateStatus
+                color: "red"
+            }
+        }
+
         // Button row
         RowLayout {
             Layout.fillWidth: true
@@ -119,6 +171,11 @@ Dialog {
         }
     }
 
+    Component {
+        id: manualSelectionComponent
+        ManualSelectionDialog {}
+    }
+
     AddJobDialog {
         id: addJobDialog
         onAccepted: {
@@ -128,6 +185,80 @@ Dialog {
         }
     }
 
+    Dialog {
+        id: templateNameDialog
+        title: "Save Layout Template"
+        modal: true
+        anchors.centerIn: parent
+        standardButtons: Dialog.Ok | Dialog.Cancel
+
+        TextField {
+            id: templateNameField
+            width: 300
+            placeholderText: "Template name"
+        }
+
+        onAccepted: {
+            var error = logic.save_layout_template(configDir, tableView.currentIndex,
+                                                   templateNameField.text)
+            templateStatus.text = error
+            refreshTemplates()
+        }
+    }
+
+    FileDialog {
+        id: templateImportDialog
+        title: "Import Layout Template"
+        nameFilters: ["Layout templates (*.json)"]
+        onAccepted: {
+            var path = selectedFile.toString().replace("file://", "")
+            templateStatus.text = logic.import_layout_template(configDir, path)
+            refreshTemplates()
+        }
+    }
+
+    FileDialog {
+        id: templateExportDialog
+        title: "Export Layout Template"
+        fileMode: FileDialog.SaveFile
+        nameFilters: ["Layout templates (*.json)"]
+        onAccepted: {
+            var path = selectedFile.toString().replace("file://", "")
+            templateStatus.text = logic.export_layout_template(configDir, templateCombo.currentText, path)
+        }
+    }
+
+    function configureJob(row) {
+        var trackInfoJson = logic.get_track_info_for_job(row)
+        if (trackInfoJson === "")
+            return
+        var data = JSON.parse(logic.get_configure_data(row))
+        var dialog = manualSelectionComponent.createObject(root, {
+            trackInfoJson: trackInfoJson,
+            previousLayoutJson: JSON.stringify(data.previous_layout || []),
+            previousAttachmentsJson: JSON.stringify(data.previous_attachments || []),
+            previousSourceSettingsJson: JSON.stringify(data.previous_source_settings || {}),
+            previousExtraAttachmentsJson: JSON.stringify(data.previous_extra_attachments || []),
+            configDir: root.configDir
+        })
+        dialog.accepted.connect(function() {
+            var result = JSON.parse(dialog.getResult())
+            logic.save_job_layout(row, JSON.stringify(result.layout),
+                                  JSON.stringify(result.attachment_sources),
+                                  JSON.stringify(result.extra_attachments),
+                                  trackInfoJson,
+                                  JSON.stringify(result.source_settings))
+            tableView.model = buildTableModel()
+            dialog.destroy()
+        })
+        dialog.rejected.connect(function() { dialog.destroy() })
+        dialog.open()
+    }
+
+    function refreshTemplates() {
+        templateCombo.model = JSON.parse(logic.list_layout_templates(configDir))
+    }
+
     function buildTableModel() {
         var model = Qt.createQmlObject('import QtQuick 2.15; ListModel {}', root)
         for (var i = 0; i < logic.job_count; i++) {
@@ -72,6 +72,7 @@ ApplicationWindow {
             var settings = JSON.parse(controller.get_settings_json())
             return settings.temp_root || ""
         }
+        configDir: controller.get_config_dir()
         onAccepted: {
             var finalJobsJson = jobQueueDialog.getFinalJobs()
             var jobs = JSON.parse(finalJobsJson)
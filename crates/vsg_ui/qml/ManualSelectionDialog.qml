@@ -4,6 +4,7 @@
 import QtQuick 2.15
 import QtQuick.Controls 2.15
 import QtQuick.Layouts 1.15
+import QtQuick.Dialogs
 import com.vsg.ui 1.0
 
 Dialog {
@@ -18,6 +19,8 @@ Dialog {
     property string previousLayoutJson: "[]"
     property string previousAttachmentsJson: "[]"
     property string previousSourceSettingsJson: "{}"
+    property string previousExtraAttachmentsJson: "[]"
+    property string configDir: ""
 
     ManualSelectionLogic {
         id: logic
@@ -26,7 +29,9 @@ Dialog {
 
     Component.onCompleted: {
         logic.initialize(trackInfoJson, previousLayoutJson, previousAttachmentsJson, previousSourceSettingsJson)
+        logic.set_extra_attachments(previousExtraAttachmentsJson)
         populateSources()
+        refreshExtraAttachments()
     }
 
     SplitView {
@@ -98,6 +103,42 @@ Dialog {
                         }
                     }
                 }
+
+                // Loose attachment files
+                GroupBox {
+                    title: "Extra Attachments"
+                    Layout.fillWidth: true
+
+                    ColumnLayout {
+                        anchors.fill: parent
+
+                        Repeater {
+                            model: ListModel { id: extraAttachmentModel }
+                            delegate: RowLayout {
+                                Layout.fillWidth: true
+                                Label {
+                                    text: model.path
+                                    Layout.fillWidth: true
+                                    elide: Text.ElideMiddle
+                                }
+                                Button {
+                                    text: "Remove"
+                                    onClicked: {
+                                        logic.remove_extra_attachment(model.path)
+                                        refreshExtraAttachments()
+                                    }
+                                }
+                            }
+                        }
+
+                        Button {
+                            text: "Add Files..."
+                            ToolTip.visible: hovered
+                            ToolTip.text: "Attach fonts or cover art to this job's output."
+                            onClicked: extraAttachmentDialog.open()
+                        }
+                    }
+                }
             }
         }
 
@@ -112,6 +153,21 @@ Dialog {
                 font.pixelSize: 14
             }
 
+            Label {
+                text: logic.info_text
+                visible: text !== ""
+                Layout.fillWidth: true
+                wrapMode: Text.WordWrap
+            }
+
+            Label {
+                id: ruleExplanations
+                visible: text !== ""
+                Layout.fillWidth: true
+                wrapMode: Text.WordWrap
+                color: "orange"
+            }
+
             ListView {
                 id: finalList
                 Layout.fillWidth: true
@@ -168,6 +224,24 @@ Dialog {
                     onClicked: logic.move_track(finalList.currentIndex, finalList.currentIndex + 1)
                 }
                 Item { Layout.fillWidth: true }
+                Button {
+                    text: "Apply Rules"
+                    ToolTip.visible: hovered
+                    ToolTip.text: "Replace the layout with the result of the saved layout rules."
+                    onClicked: {
+                        var explanations = JSON.parse(logic.apply_layout_rules(configDir))
+                        ruleExplanations.text = explanations.join("\n")
+                    }
+                }
+                Button {
+                    text: "Edit Rules..."
+                    onClicked: {
+                        var loaded = JSON.parse(logic.get_layout_rules(configDir))
+                        rulesEditor.text = loaded.rules
+                        rulesError.text = loaded.error
+                        rulesDialog.open()
+                    }
+                }
                 Button {
                     text: "Remove"
                     enabled: finalList.currentIndex >= 0
@@ -177,6 +251,73 @@ Dialog {
         }
     }
 
+    Dialog {
+        id: rulesDialog
+        title: "Layout Rules"
+        width: 640
+        height: 520
+        modal: true
+        anchors.centerIn: parent
+        standardButtons: Dialog.Save | Dialog.Cancel
+
+        ColumnLayout {
+            anchors.fill: parent
+
+            Label {
+                text: "Include rules run in order; drop rules apply everywhere. Criteria: type, source, lang, name_matches, name_excludes, codec. Placement: limit, default, forced. Set \"action\": \"drop\" to exclude matches."
+                Layout.fillWidth: true
+                wrapMode: Text.WordWrap
+            }
+
+            ScrollView {
+                Layout.fillWidth: true
+                Layout.fillHeight: true
+                TextArea {
+                    id: rulesEditor
+                    font.family: "monospace"
+                }
+            }
+
+            Label {
+                id: rulesError
+                visible: text !== ""
+                Layout.fillWidth: true
+                wrapMode: Text.WordWrap
+                color: "red"
+            }
+        }
+
+        onAccepted: {
+            var error = logic.save_layout_rules(configDir, rulesEditor.text)
+            if (error !== "") {
+                rulesError.text = error
+                rulesDialog.open()
+            }
+        }
+    }
+
+    FileDialog {
+        id: extraAttachmentDialog
+        title: "Select Attachment Files"
+        fileMode: FileDialog.OpenFiles
+        onAccepted: {
+            var paths = []
+            for (var i = 0; i < selectedFiles.length; i++) {
+                paths.push(selectedFiles[i].toString().replace("file://", ""))
+            }
+            logic.add_extra_attachments(JSON.stringify(paths))
+            refreshExtraAttachments()
+        }
+    }
+
+    function refreshExtraAttachments() {
+        var paths = JSON.parse(logic.get_extra_attachments())
+        extraAttachmentModel.clear()
+        for (var i = 0; i < paths.length; i++) {
+            extraAttachmentModel.append({path: paths[i]})
+        }
+    }
+
     function populateSources() {
         var keys = JSON.parse(logic.get_source_keys())
         sourceModel.clear()
@@ -322,6 +322,18 @@ Dialog {
                             settingKey: "output_folder"
                             ToolTip.text: "The default directory where final merged files will be saved."
                         }
+                        SettingsTextField {
+                            label: "Output Name Template:"
+                            settingKey: "output_name_template"
+                            placeholderText: "{name}/{name}< - E{episode:02}> [{resolution}]"
+                            ToolTip.text: "Output path relative to the output directory. Fields: {name}, {source1}..{source4}, {season}, {episode}, {resolution}, {width}, {height}, {vcodec}, {acodec}, {audio_langs}, {sub_langs}, {delays}. Use / for subfolders, {field:02} to zero-pad, and <...> for text dropped when a field in it is empty. Empty keeps the Source 1 file name."
+                        }
+                        SettingsCombo {
+                            label: "If Output Exists:"
+                            settingKey: "output_collision"
+                            model: ["overwrite", "suffix", "skip"]
+                            ToolTip.text: "overwrite replaces the file, suffix adds (1), (2)... to the name, skip leaves the existing file and skips the job."
+                        }
                         SettingsPathRow {
                             label: "Temporary Directory:"
                             settingKey: "temp_root"
@@ -765,6 +777,30 @@ Dialog {
                             ToolTip.text: "Maximum acceptable drift slope for lossless audio sources."
                         }
 
+                        // ── Speed Ratio ──
+                        SectionHeader { text: "Speed Ratio Correction" }
+
+                        SettingsCombo {
+                            label: "Speed Correction:"
+                            settingKey: "speed_correction_mode"
+                            model: ["disabled", "snap", "exact"]
+                            ToolTip.text: "Treat linear drift as a speed mismatch. 'snap' uses the nearest broadcast ratio (24→25, 25→24, 29.97→25, 23.976→24...), 'exact' uses the fitted ratio. Subtitles and chapters of the source are retimed too."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Snap Tolerance:"
+                            settingKey: "speed_correction_snap_tolerance_ms_s"
+                            from: 0.05; to: 5.0; decimals: 2
+                            suffix: " ms/s"
+                            visible: root.settings.speed_correction_mode === "snap"
+                            ToolTip.text: "Largest slope difference from a known ratio that still snaps. Otherwise plain linear drift correction is used."
+                        }
+                        SettingsCheckBox {
+                            label: "Preserve pitch"
+                            settingKey: "speed_correction_pitch_correct"
+                            visible: root.settings.speed_correction_mode !== "disabled"
+                            ToolTip.text: "Retime with rubberband and keep pitch. Off changes speed and pitch together (varispeed)."
+                        }
+
                         // ── Quality Validation ──
                         SectionHeader { text: "Quality Validation" }
 
@@ -910,8 +946,14 @@ Dialog {
                         SettingsCombo {
                             label: "Resample Engine:"
                             settingKey: "segment_resample_engine"
-                            model: ["aresample", "atempo", "rubberband"]
-                            ToolTip.text: "FFmpeg audio filter used for time-stretching corrected segments."
+                            model: ["aresample", "atempo", "rubberband", "native"]
+                            ToolTip.text: "Engine used for time-stretching corrected segments. native resamples in-process without an FFmpeg filter pass."
+                        }
+                        SettingsCheckBox {
+                            label: "Preserve Pitch (WSOLA)"
+                            settingKey: "segment_rb_pitch_correct"
+                            visible: root.settings.segment_resample_engine === "native"
+                            ToolTip.text: "Time-stretch without changing pitch. Off resamples (varispeed), like aresample."
                         }
                         GroupBox {
                             title: "Rubberband Settings"
@@ -942,6 +984,62 @@ Dialog {
                                 }
                             }
                         }
+                        SettingsCombo {
+                            label: "Corrected Audio Codec:"
+                            settingKey: "corrected_audio_encode"
+                            model: ["keep_flac", "match_original", "opus", "aac"]
+                            ToolTip.text: "Codec for corrected audio tracks. match_original re-encodes to the source codec and bitrate."
+                        }
+                        SettingsTextField {
+                            label: "Per-Family Overrides:"
+                            settingKey: "corrected_audio_encode_overrides"
+                            placeholderText: "e.g. ac3=match_original, lossless=keep_flac"
+                            ToolTip.text: "Codec family overrides: lossless, ac3, dts, aac, opus, vorbis, mp3."
+                        }
+                        SettingsSpinBox {
+                            label: "Opus/AAC Bitrate:"
+                            settingKey: "corrected_audio_bitrate_kbps"
+                            from: 0; to: 1024
+                            suffix: " kb/s"
+                            visible: root.settings.corrected_audio_encode === "opus" || root.settings.corrected_audio_encode === "aac"
+                            ToolTip.text: "Bitrate for Opus/AAC re-encodes. 0 scales with the channel count."
+                        }
+
+                        // ── Splices ──
+                        SectionHeader { text: "Splices" }
+
+                        SettingsCheckBox {
+                            label: "Crossfade splices (equal-power)"
+                            settingKey: "stepping_crossfade_enabled"
+                            ToolTip.text: "Blend across each splice instead of a hard cut."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Crossfade Length:"
+                            settingKey: "stepping_crossfade_ms"
+                            from: 1.0; to: 500.0; decimals: 0
+                            suffix: " ms"
+                            visible: root.settings.stepping_crossfade_enabled
+                            ToolTip.text: "Total crossfade length, centred on the splice."
+                        }
+                        SettingsCombo {
+                            label: "Gap Fill:"
+                            settingKey: "stepping_gap_fill"
+                            model: ["silence", "room_tone"]
+                            ToolTip.text: "Fill inserted gaps with digital silence or room tone from the splice's silence zone."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Max Room Tone Level:"
+                            settingKey: "stepping_room_tone_max_db"
+                            from: -90.0; to: -10.0; decimals: 1
+                            suffix: " dB"
+                            visible: root.settings.stepping_gap_fill === "room_tone"
+                            ToolTip.text: "Silence zones louder than this fall back to digital silence."
+                        }
+                        SettingsCheckBox {
+                            label: "Export final EDL (JSON + CMX3600)"
+                            settingKey: "stepping_export_edl"
+                            ToolTip.text: "Write each source's final segment list to the output folder. Either file can be loaded back as a per-source EDL override."
+                        }
 
                         // ── Track Naming ──
                         SectionHeader { text: "Track Naming" }
@@ -950,7 +1048,7 @@ Dialog {
                             label: "Corrected Track Label:"
                             settingKey: "stepping_corrected_track_label"
                             placeholderText: "Leave empty for no label"
-                            ToolTip.text: "Label applied to the stepping-corrected audio track in the output."
+                            ToolTip.text: "Label applied to the stepping-corrected audio track in the output. {codec} expands to the final codec."
                         }
                         SettingsTextField {
                             label: "Preserved Track Label:"
@@ -1244,6 +1342,51 @@ Dialog {
                         }
                     }
                 }
+
+                GroupBox {
+                    title: "Chapter Generation"
+                    Layout.fillWidth: true
+                    ColumnLayout {
+                        anchors.fill: parent
+                        SettingsCheckBox {
+                            label: "Generate chapters when no source has any"
+                            settingKey: "generate_chapters"
+                            ToolTip.text: "Find the opening and ending shared by the episodes of a batch and place Prologue, Opening, Part A, Part B, Ending and Preview chapters. Needs two or more jobs."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Minimum OP/ED Length:"
+                            settingKey: "chapter_gen_min_theme_s"
+                            from: 5.0; to: 180.0; decimals: 1
+                            suffix: " s"
+                            ToolTip.text: "Shortest audio segment shared between episodes that counts as an opening or ending."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Break Silence Threshold:"
+                            settingKey: "chapter_gen_silence_db"
+                            from: -96.0; to: -10.0; decimals: 1
+                            suffix: " dB"
+                            ToolTip.text: "Audio below this level, together with black frames, marks the break between Part A and Part B."
+                        }
+                    }
+                }
+
+                GroupBox {
+                    title: "Linked Segments"
+                    Layout.fillWidth: true
+                    ColumnLayout {
+                        anchors.fill: parent
+                        SettingsCombo {
+                            label: "Linked Timeline:"
+                            settingKey: "linked_timeline_mode"
+                            model: [
+                                {text: "Off (warn only)", value: "off"},
+                                {text: "Virtual (read through playlist)", value: "virtual"},
+                                {text: "Physical (remux into one file)", value: "physical"}
+                            ]
+                            ToolTip.text: "How to handle sources whose ordered chapters play OP/ED from other files. Virtual analyses the assembled timeline without remuxing; tracks taken from such a source always use a physical remux."
+                        }
+                    }
+                }
             }
         }
 
@@ -1438,6 +1581,16 @@ Dialog {
                             settingKey: "disable_header_compression"
                             ToolTip.text: "Disable Matroska header removal compression for all tracks."
                         }
+                        SettingsCheckBox {
+                            label: "Auto-attach missing subtitle fonts from the fonts directory"
+                            settingKey: "font_auto_attach"
+                            ToolTip.text: "Attach fonts that ASS styles or \\fn overrides use but no attachment source provides, when a matching face is found in the fonts directory."
+                        }
+                        SettingsCheckBox {
+                            label: "Only attach fonts used by the subtitle tracks"
+                            settingKey: "attachments_referenced_fonts_only"
+                            ToolTip.text: "Drop fonts that no style or override in the final ASS tracks resolves to. Other attachments such as cover art are always kept."
+                        }
                     }
                 }
 
@@ -1451,11 +1604,56 @@ Dialog {
                             settingKey: "post_mux_normalize_timestamps"
                             ToolTip.text: "Run a post-mux FFmpeg pass to normalize timestamps and fix thumbnail generation."
                         }
+                        SettingsCombo {
+                            label: "Finalizer:"
+                            settingKey: "post_mux_finalizer"
+                            model: ["ffmpeg", "mkvmerge"]
+                            visible: root.settings.post_mux_normalize_timestamps
+                            ToolTip.text: "mkvmerge folds the rebase into the mux offsets and only remuxes with mkvmerge if a residual remains, keeping chapters, attachments, tags and Dolby Vision configuration intact."
+                        }
                         SettingsCheckBox {
                             label: "Strip ENCODER tag added by FFmpeg (requires mkvpropedit)"
                             settingKey: "post_mux_strip_tags"
+                            visible: root.settings.post_mux_finalizer !== "mkvmerge"
                             ToolTip.text: "Remove the ENCODER tag that FFmpeg adds during timestamp normalization."
                         }
+                        SettingsCheckBox {
+                            label: "Verify sync in the final file"
+                            settingKey: "post_mux_sync_verify"
+                            ToolTip.text: "Decode each synced audio track from the output and re-measure its offset against Source 1."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Sync Audit Threshold:"
+                            settingKey: "post_mux_sync_threshold_ms"
+                            from: 1.0; to: 500.0; decimals: 1
+                            suffix: " ms"
+                            visible: root.settings.post_mux_sync_verify
+                            ToolTip.text: "Residual offset beyond which a track fails the audit."
+                        }
+                        SettingsDoubleSpinBox {
+                            label: "Sync Audit Hop:"
+                            settingKey: "post_mux_sync_hop_s"
+                            from: 5.0; to: 300.0; decimals: 0
+                            suffix: " s"
+                            visible: root.settings.post_mux_sync_verify
+                            ToolTip.text: "Spacing between correlation windows. Larger is faster but measures fewer points."
+                        }
+                        SettingsTextField {
+                            label: "Auditor Overrides:"
+                            settingKey: "audit_overrides"
+                            placeholderText: "dolby_vision=off, track_order=error"
+                            ToolTip.text: "Comma-separated auditor=off|on|info|warning|error entries. Severity values replace the severity of that auditor's warnings and errors; informational findings are left as they are."
+                        }
+                        SettingsCheckBox {
+                            label: "Write sync provenance tags"
+                            settingKey: "write_provenance_tags"
+                            ToolTip.text: "Record sources, file hashes, applied delays, corrections and the tool version as Matroska tags in the output."
+                        }
+                        SettingsCheckBox {
+                            label: "Export audit findings as JUnit XML"
+                            settingKey: "audit_junit_export"
+                            ToolTip.text: "Write <job>.audit.junit.xml next to the output for CI systems."
+                        }
                     }
                 }
             }
@@ -1508,6 +1706,11 @@ Dialog {
                             settingKey: "log_show_options_json"
                             ToolTip.text: "Log the raw JSON representation of mkvmerge options."
                         }
+                        SettingsCheckBox {
+                            label: "Write per-job event stream (JSON lines)"
+                            settingKey: "log_event_stream"
+                            ToolTip.text: "Write a {job}.events.jsonl file next to each job log with step, chunk and delay events."
+                        }
                     }
                 }
 
@@ -1,5 +1,6 @@
 // SourceSettingsDialog.qml — 1:1 port of vsg_qt/source_settings_dialog/dialog.py
-// Per-source audio track settings (correlation track, source separation).
+// Per-source audio track settings (correlation track, source separation, manual EDL)
+// and chapter source selection.
 
 import QtQuick 2.15
 import QtQuick.Controls 2.15
@@ -10,7 +11,7 @@ Dialog {
     id: root
     title: "Source Settings"
     width: 500
-    height: 300
+    height: 420
     modal: true
     standardButtons: Dialog.Ok | Dialog.Cancel | Dialog.Reset
 
@@ -60,6 +61,49 @@ Dialog {
             checked: logic.use_source_separation
             onCheckedChanged: logic.use_source_separation = checked
         }
+
+        // Manual stepping EDL (Source 2/3 only)
+        RowLayout {
+            visible: logic.source_key !== "Source 1"
+            Label {
+                text: "Manual Stepping EDL:"
+                Layout.preferredWidth: 180
+            }
+            TextField {
+                Layout.fillWidth: true
+                text: logic.stepping_edl_override
+                placeholderText: "Empty = detect automatically"
+                onTextChanged: logic.stepping_edl_override = text
+                ToolTip.visible: hovered
+                ToolTip.text: "JSON or CMX3600 EDL. Skips stepping detection and goes straight to assembly, QA and subtitle stepping."
+            }
+        }
+
+        // Chapters from this source instead of Source 1 (Source 2/3 only)
+        CheckBox {
+            text: "Use Chapters From This Source"
+            visible: logic.source_key !== "Source 1"
+            checked: logic.use_chapters
+            onCheckedChanged: logic.use_chapters = checked
+            ToolTip.visible: hovered
+            ToolTip.text: "Take the job's chapters from this source's container, shifted by its delay. Falls back to Source 1 if it has none."
+        }
+
+        // External chapter file on this source's timeline
+        RowLayout {
+            Label {
+                text: "Chapter File:"
+                Layout.preferredWidth: 180
+            }
+            TextField {
+                Layout.fillWidth: true
+                text: logic.chapter_file
+                placeholderText: "Empty = chapters from the container"
+                onTextChanged: logic.chapter_file = text
+                ToolTip.visible: hovered
+                ToolTip.text: "Matroska XML, OGM, ffmetadata or timestamped text list timed against this source. Used instead of any container chapters."
+            }
+        }
     }
 
     function getResult() { return logic.get_result() }
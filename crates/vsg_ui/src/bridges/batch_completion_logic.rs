@@ -1,6 +1,6 @@
 //! Batch completion dialog — 1:1 port of `vsg_qt/report_dialogs/batch_completion_dialog.py`.
 //!
-//! Shows summary after batch processing: success/warning/fail counts,
+//! Shows summary after batch processing: success/warning/fail/skip counts,
 //! stepping info, and a button to open the report.
 
 #[cxx_qt::bridge]
@@ -13,6 +13,7 @@ pub mod ffi {
         #[qproperty(i32, successful)]
         #[qproperty(i32, warnings)]
         #[qproperty(i32, failed)]
+        #[qproperty(i32, skipped)]
         #[qproperty(QString, report_path)]
         #[qproperty(QString, stepping_jobs_json)]
         #[qproperty(QString, stepping_disabled_jobs_json)]
@@ -48,6 +49,7 @@ pub struct BatchCompletionLogicRust {
     successful: i32,
     warnings: i32,
     failed: i32,
+    skipped: i32,
     report_path: QString,
     stepping_jobs_json: QString,
     stepping_disabled_jobs_json: QString,
@@ -168,6 +168,9 @@ impl ffi::FontManagerLogic {
             .iter()
             .map(|f| serde_json::json!({
                 "family": f.family_name,
+                "style": f.subfamily,
+                "full_name": f.full_name,
+                "face_index": f.face_index,
                 "path": f.file_path.to_string_lossy(),
             }))
             .collect();
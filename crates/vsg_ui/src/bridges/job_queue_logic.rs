@@ -41,6 +41,53 @@ pub mod ffi {
         #[qinvokable]
         fn paste_layout(self: Pin<&mut JobQueueLogic>, rows_json: QString) -> i32;
 
+        /// List stored layout template names as a JSON array.
+        #[qinvokable]
+        fn list_layout_templates(self: Pin<&mut JobQueueLogic>, config_dir: QString) -> QString;
+
+        /// Save the layout of the job at row as a named template. Returns an error or "".
+        #[qinvokable]
+        fn save_layout_template(
+            self: Pin<&mut JobQueueLogic>,
+            config_dir: QString,
+            row: i32,
+            name: QString,
+        ) -> QString;
+
+        /// Apply a named template to jobs at indices (JSON array of ints). Returns count applied.
+        #[qinvokable]
+        fn apply_layout_template(
+            self: Pin<&mut JobQueueLogic>,
+            config_dir: QString,
+            name: QString,
+            rows_json: QString,
+        ) -> i32;
+
+        /// Delete a stored layout template.
+        #[qinvokable]
+        fn delete_layout_template(
+            self: Pin<&mut JobQueueLogic>,
+            config_dir: QString,
+            name: QString,
+        ) -> bool;
+
+        /// Export a stored template to a file. Returns an error or "".
+        #[qinvokable]
+        fn export_layout_template(
+            self: Pin<&mut JobQueueLogic>,
+            config_dir: QString,
+            name: QString,
+            path: QString,
+        ) -> QString;
+
+        /// Import a template file into the store. Returns an error or "".
+        #[qinvokable]
+        fn import_layout_template(
+            self: Pin<&mut JobQueueLogic>,
+            config_dir: QString,
+            path: QString,
+        ) -> QString;
+
         /// Move jobs by direction (-1 up, +1 down). rows_json is JSON array of ints.
         #[qinvokable]
         fn move_jobs(self: Pin<&mut JobQueueLogic>, rows_json: QString, direction: i32);
@@ -52,6 +99,7 @@ pub mod ffi {
             row: i32,
             layout_json: QString,
             attachments_json: QString,
+            extra_attachments_json: QString,
             track_info_json: QString,
             source_settings_json: QString,
         ) -> bool;
@@ -94,11 +142,13 @@ pub mod ffi {
 
 use core::pin::Pin;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use cxx_qt::CxxQtType;
 use cxx_qt_lib::QString;
+use vsg_core::config::{get_fonts_dir_path, AppConfig};
+use vsg_core::job_layouts::templates::LayoutTemplateStore;
 use vsg_core::job_layouts::JobLayoutManager;
 
 /// Backing Rust struct for JobQueueLogic.
@@ -161,6 +211,60 @@ fn get_source1_name(job: &serde_json::Value) -> String {
         .unwrap_or_default()
 }
 
+/// Track info for a job — the cached copy, or probed with mkvmerge/ffprobe.
+fn probe_track_info(job: &serde_json::Value) -> HashMap<String, Vec<serde_json::Value>> {
+    if let Some(cached) = job.get("track_info").filter(|c| !c.is_null()) {
+        return serde_json::from_value(cached.clone()).unwrap_or_default();
+    }
+
+    // Build tool paths from system PATH
+    let sources = get_sources(job);
+    let tool_names = ["mkvmerge", "mkvextract", "ffmpeg", "ffprobe"];
+    let tool_paths: HashMap<String, String> = tool_names
+        .iter()
+        .filter_map(|&name| {
+            which::which(name)
+                .ok()
+                .map(|p| (name.to_string(), p.to_string_lossy().to_string()))
+        })
+        .collect();
+
+    // Create a runner with default settings for track probing
+    let settings = vsg_core::models::settings::AppSettings::default();
+    let log_cb: Box<dyn Fn(&str) + Send + Sync> = Box::new(|_| {});
+    let runner = vsg_core::io::runner::CommandRunner::new(settings, log_cb);
+
+    vsg_core::extraction::tracks::get_track_info_for_dialog(&sources, &runner, &tool_paths)
+}
+
+/// Track info for a job, probed at most once: a fresh probe is stored back
+/// as the job's `track_info` so later pastes and template applies reuse it.
+fn cached_track_info(job: &mut serde_json::Value) -> HashMap<String, Vec<serde_json::Value>> {
+    let info = probe_track_info(job);
+    if let Some(obj) = job.as_object_mut() {
+        if obj.get("track_info").is_none_or(serde_json::Value::is_null) {
+            obj.insert("track_info".to_string(), serde_json::json!(info));
+        }
+    }
+    info
+}
+
+/// Template store in the given config directory.
+fn template_store(config_dir: &QString) -> LayoutTemplateStore {
+    let log_cb: Arc<dyn Fn(&str) + Send + Sync> = Arc::new(|_msg: &str| {});
+    LayoutTemplateStore::new(Path::new(&config_dir.to_string()), log_cb)
+}
+
+/// Fonts folder that template font replacements are stored relative to.
+fn fonts_dir(config_dir: &QString) -> PathBuf {
+    let config_dir = PathBuf::from(config_dir.to_string());
+    let script_dir = config_dir.parent().unwrap_or(&config_dir);
+    let setting = AppConfig::new(script_dir)
+        .map(|config| config.settings.fonts_directory)
+        .unwrap_or_default();
+    get_fonts_dir_path(script_dir, &setting)
+}
+
 impl ffi::JobQueueLogic {
     fn initialize(mut self: Pin<&mut Self>, temp_root: QString) {
         let temp_root_str = temp_root.to_string();
@@ -307,24 +411,149 @@ impl ffi::JobQueueLogic {
             None => return 0,
         };
 
+        let source_job_id = clipboard
+            .get("job_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
         let mut updated = 0i32;
         for &row in &rows {
             if row >= self.rust().jobs.len() {
                 continue;
             }
 
-            if let Some(lm) = &self.rust().layout_manager {
-                let target_sources = get_sources(&self.rust().jobs[row]);
-                let target_job_id = lm.generate_job_id(&target_sources);
+            let target_sources = get_sources(&self.rust().jobs[row]);
+            let target_info = cached_track_info(&mut self.as_mut().rust_mut().jobs[row]);
+            let name = get_source1_name(&self.rust().jobs[row]);
+            let result = match &self.rust().layout_manager {
+                Some(lm) => {
+                    let target_job_id = lm.generate_job_id(&target_sources);
+                    // Remap track by track when episodes differ slightly
+                    lm.copy_layout_between_jobs(
+                        &source_job_id,
+                        &target_job_id,
+                        &target_sources,
+                        &target_info,
+                        true,
+                    )
+                }
+                None => continue,
+            };
 
-                let mut target_data = clipboard.clone();
-                if let Some(obj) = target_data.as_object_mut() {
-                    obj.insert("job_id".to_string(), serde_json::json!(target_job_id));
-                    obj.insert("sources".to_string(), serde_json::json!(target_sources));
+            match result {
+                Ok(warnings) => {
+                    updated += 1;
+                    for warning in warnings {
+                        self.as_mut().log_message(QString::from(
+                            format!("[Queue] {name}: {warning}").as_str(),
+                        ));
+                    }
+                }
+                Err(e) => {
+                    self.as_mut()
+                        .log_message(QString::from(format!("[Queue] {name}: {e}").as_str()));
                 }
+            }
+        }
+
+        if updated > 0 {
+            self.as_mut().jobs_changed();
+        }
+        updated
+    }
 
-                if lm.persistence.save_layout(&target_job_id, &mut target_data) {
+    fn list_layout_templates(self: Pin<&mut Self>, config_dir: QString) -> QString {
+        let names = template_store(&config_dir).list();
+        let json = serde_json::to_string(&names).unwrap_or_else(|_| "[]".to_string());
+        QString::from(json.as_str())
+    }
+
+    fn save_layout_template(
+        mut self: Pin<&mut Self>,
+        config_dir: QString,
+        row: i32,
+        name: QString,
+    ) -> QString {
+        let idx = row as usize;
+        if idx >= self.rust().jobs.len() {
+            return QString::from("No job selected.");
+        }
+        let name = name.to_string();
+        let sources = get_sources(&self.rust().jobs[idx]);
+        let result = match &self.rust().layout_manager {
+            Some(lm) => lm
+                .create_template(
+                    &lm.generate_job_id(&sources),
+                    &name,
+                    &fonts_dir(&config_dir),
+                )
+                .and_then(|template| template_store(&config_dir).save(&template)),
+            None => Err("Layout manager is not initialized.".to_string()),
+        };
+        match result {
+            Ok(()) => {
+                self.as_mut().log_message(QString::from(
+                    format!("[Queue] Saved layout template '{name}'.").as_str(),
+                ));
+                QString::default()
+            }
+            Err(e) => QString::from(e.as_str()),
+        }
+    }
+
+    fn apply_layout_template(
+        mut self: Pin<&mut Self>,
+        config_dir: QString,
+        name: QString,
+        rows_json: QString,
+    ) -> i32 {
+        let rows: Vec<usize> = serde_json::from_str(&rows_json.to_string()).unwrap_or_default();
+        let template = match template_store(&config_dir).load(&name.to_string()) {
+            Ok(t) => t,
+            Err(e) => {
+                self.as_mut()
+                    .log_message(QString::from(format!("[Queue] {e}").as_str()));
+                return 0;
+            }
+        };
+
+        let fonts_dir = fonts_dir(&config_dir);
+        let mut updated = 0i32;
+        for &row in &rows {
+            if row >= self.rust().jobs.len() {
+                continue;
+            }
+
+            let target_sources = get_sources(&self.rust().jobs[row]);
+            let target_info = cached_track_info(&mut self.as_mut().rust_mut().jobs[row]);
+            let job_name = get_source1_name(&self.rust().jobs[row]);
+            let result = match &self.rust().layout_manager {
+                Some(lm) => {
+                    let target_job_id = lm.generate_job_id(&target_sources);
+                    lm.apply_template(
+                        &template,
+                        &target_job_id,
+                        &target_sources,
+                        &target_info,
+                        &fonts_dir,
+                    )
+                }
+                None => continue,
+            };
+
+            match result {
+                Ok(warnings) => {
                     updated += 1;
+                    for warning in warnings {
+                        self.as_mut().log_message(QString::from(
+                            format!("[Queue] {job_name}: {warning}").as_str(),
+                        ));
+                    }
+                }
+                Err(e) => {
+                    self.as_mut()
+                        .log_message(QString::from(format!("[Queue] {job_name}: {e}").as_str()));
                 }
             }
         }
@@ -335,6 +564,37 @@ impl ffi::JobQueueLogic {
         updated
     }
 
+    fn delete_layout_template(self: Pin<&mut Self>, config_dir: QString, name: QString) -> bool {
+        template_store(&config_dir).delete(&name.to_string())
+    }
+
+    fn export_layout_template(
+        self: Pin<&mut Self>,
+        config_dir: QString,
+        name: QString,
+        path: QString,
+    ) -> QString {
+        let result =
+            template_store(&config_dir).export(&name.to_string(), Path::new(&path.to_string()));
+        QString::from(result.err().unwrap_or_default().as_str())
+    }
+
+    fn import_layout_template(
+        mut self: Pin<&mut Self>,
+        config_dir: QString,
+        path: QString,
+    ) -> QString {
+        match template_store(&config_dir).import(Path::new(&path.to_string())) {
+            Ok(name) => {
+                self.as_mut().log_message(QString::from(
+                    format!("[Queue] Imported layout template '{name}'.").as_str(),
+                ));
+                QString::default()
+            }
+            Err(e) => QString::from(e.as_str()),
+        }
+    }
+
     fn move_jobs(mut self: Pin<&mut Self>, rows_json: QString, direction: i32) {
         let json_str = rows_json.to_string();
         let mut rows: Vec<usize> = serde_json::from_str(&json_str).unwrap_or_default();
@@ -363,6 +623,7 @@ impl ffi::JobQueueLogic {
         row: i32,
         layout_json: QString,
         attachments_json: QString,
+        extra_attachments_json: QString,
         track_info_json: QString,
         source_settings_json: QString,
     ) -> bool {
@@ -376,6 +637,8 @@ impl ffi::JobQueueLogic {
             serde_json::from_str(&layout_json.to_string()).unwrap_or_default();
         let attachments: Vec<String> =
             serde_json::from_str(&attachments_json.to_string()).unwrap_or_default();
+        let extra_attachments: Vec<String> =
+            serde_json::from_str(&extra_attachments_json.to_string()).unwrap_or_default();
         let track_info: HashMap<String, Vec<serde_json::Value>> =
             serde_json::from_str(&track_info_json.to_string()).unwrap_or_default();
         let source_settings: HashMap<String, serde_json::Value> =
@@ -387,6 +650,7 @@ impl ffi::JobQueueLogic {
                 &job_id,
                 &layout,
                 &attachments,
+                &extra_attachments,
                 &sources,
                 &track_info,
                 Some(&source_settings),
@@ -441,6 +705,13 @@ impl ffi::JobQueueLogic {
                                 .cloned()
                                 .unwrap_or(serde_json::json!([])),
                         );
+                        obj.insert(
+                            "extra_attachments".to_string(),
+                            layout_data
+                                .get("extra_attachments")
+                                .cloned()
+                                .unwrap_or(serde_json::json!([])),
+                        );
                         obj.insert(
                             "source_settings".to_string(),
                             layout_data
@@ -458,42 +729,13 @@ impl ffi::JobQueueLogic {
         QString::from(json.as_str())
     }
 
-    fn get_track_info_for_job(self: Pin<&mut Self>, row: i32) -> QString {
+    fn get_track_info_for_job(mut self: Pin<&mut Self>, row: i32) -> QString {
         let idx = row as usize;
         if idx >= self.rust().jobs.len() {
             return QString::from("{}");
         }
 
-        let job = &self.rust().jobs[idx];
-
-        // Check cache
-        if let Some(cached) = job.get("track_info") {
-            if !cached.is_null() {
-                let json = serde_json::to_string(cached).unwrap_or_default();
-                return QString::from(json.as_str());
-            }
-        }
-
-        // Build tool paths from system PATH
-        let sources = get_sources(job);
-        let tool_names = ["mkvmerge", "mkvextract", "ffmpeg", "ffprobe"];
-        let tool_paths: HashMap<String, String> = tool_names
-            .iter()
-            .filter_map(|&name| {
-                which::which(name)
-                    .ok()
-                    .map(|p| (name.to_string(), p.to_string_lossy().to_string()))
-            })
-            .collect();
-
-        // Create a runner with default settings for track probing
-        let settings = vsg_core::models::settings::AppSettings::default();
-        let log_cb: Box<dyn Fn(&str) + Send + Sync> = Box::new(|_| {});
-        let runner = vsg_core::io::runner::CommandRunner::new(settings, log_cb);
-
-        let info = vsg_core::extraction::tracks::get_track_info_for_dialog(
-            &sources, &runner, &tool_paths,
-        );
+        let info = cached_track_info(&mut self.as_mut().rust_mut().jobs[idx]);
         let json = serde_json::to_string(&info).unwrap_or_else(|_| "{}".to_string());
         QString::from(json.as_str())
     }
@@ -530,6 +772,9 @@ impl ffi::JobQueueLogic {
                 if let Some(att) = layout_data.get("attachment_sources") {
                     result["previous_attachments"] = att.clone();
                 }
+                if let Some(extra) = layout_data.get("extra_attachments") {
+                    result["previous_extra_attachments"] = extra.clone();
+                }
                 if let Some(ss) = layout_data.get("source_settings") {
                     result["previous_source_settings"] = ss.clone();
                 }
@@ -54,6 +54,10 @@ pub mod ffi {
         #[qinvokable]
         fn get_settings_json(self: Pin<&mut MainController>) -> QString;
 
+        /// Get the config directory path (for dialogs that store files there).
+        #[qinvokable]
+        fn get_config_dir(self: Pin<&mut MainController>) -> QString;
+
         /// Update settings from JSON (after options dialog saves).
         #[qinvokable]
         fn update_settings_from_json(self: Pin<&mut MainController>, json: QString);
@@ -174,10 +178,18 @@ impl ffi::MainController {
                 self.as_mut()
                     .set_archive_logs(config.settings.archive_logs);
 
+                let removed =
+                    config.cleanup_orphaned_job_temp(config.settings.temp_retention_hours);
+
                 // Store config
                 self.as_mut().rust_mut().config = Some(config);
 
                 self.as_mut().append_log("Configuration loaded.");
+                if removed > 0 {
+                    self.as_mut().append_log(&format!(
+                        "Removed {removed} orphaned job temp folder(s)."
+                    ));
+                }
             }
             Err(e) => {
                 self.as_mut()
@@ -302,6 +314,14 @@ impl ffi::MainController {
         }
     }
 
+    fn get_config_dir(self: Pin<&mut Self>) -> QString {
+        self.rust()
+            .config
+            .as_ref()
+            .map(|config| QString::from(config.get_config_dir().to_string_lossy().as_ref()))
+            .unwrap_or_default()
+    }
+
     /// Update settings from JSON — called after OptionsDialog saves.
     fn update_settings_from_json(mut self: Pin<&mut Self>, json: QString) {
         let json_str = json.to_string();
@@ -373,20 +393,22 @@ impl ffi::MainController {
         self.as_mut().set_worker_running(false);
 
         // Count statuses
-        let successful = results
-            .iter()
-            .filter(|r| {
-                r.get("status")
-                    .and_then(|s| s.as_str())
-                    .map(|s| s != "Failed")
-                    .unwrap_or(false)
-            })
-            .count();
-        let failed = total - successful;
+        let count = |status: &str| {
+            results
+                .iter()
+                .filter(|r| r.get("status").and_then(|s| s.as_str()) == Some(status))
+                .count()
+        };
+        let failed = count("Failed");
+        let skipped = count("Skipped");
+        let successful = total - failed - skipped;
 
-        let summary = format!(
+        let mut summary = format!(
             "\n--- Batch Summary ---\n  - Successful jobs: {successful}\n  - Failed jobs: {failed}\n"
         );
+        if skipped > 0 {
+            summary.push_str(&format!("  - Skipped jobs (output exists): {skipped}\n"));
+        }
         self.as_mut().append_log(&summary);
 
         // Signal QML to show batch completion dialog
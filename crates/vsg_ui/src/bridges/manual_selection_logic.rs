@@ -79,6 +79,22 @@ pub mod ffi {
         #[qinvokable]
         fn get_attachment_sources(self: Pin<&mut ManualSelectionLogic>) -> QString;
 
+        /// Replace the job's loose attachment files (JSON array of paths).
+        #[qinvokable]
+        fn set_extra_attachments(self: Pin<&mut ManualSelectionLogic>, paths_json: QString);
+
+        /// Add loose attachment files (JSON array of paths), skipping duplicates.
+        #[qinvokable]
+        fn add_extra_attachments(self: Pin<&mut ManualSelectionLogic>, paths_json: QString);
+
+        /// Remove a loose attachment file by path.
+        #[qinvokable]
+        fn remove_extra_attachment(self: Pin<&mut ManualSelectionLogic>, path: QString);
+
+        /// Get the job's loose attachment files as JSON array.
+        #[qinvokable]
+        fn get_extra_attachments(self: Pin<&mut ManualSelectionLogic>) -> QString;
+
         /// Update source settings for a source (JSON).
         #[qinvokable]
         fn set_source_settings(
@@ -152,6 +168,25 @@ pub mod ffi {
         #[qinvokable]
         fn is_blocked_video_at(self: Pin<&mut ManualSelectionLogic>, source_key: QString, track_index: i32) -> bool;
 
+        /// Get the saved layout rules for editing.
+        /// Returns JSON `{"rules": text, "error": message}`; when the saved file
+        /// can't be loaded, `rules` is its raw content and `error` says why.
+        #[qinvokable]
+        fn get_layout_rules(self: Pin<&mut ManualSelectionLogic>, config_dir: QString) -> QString;
+
+        /// Save layout rules from JSON. Returns an error message, empty on success.
+        #[qinvokable]
+        fn save_layout_rules(
+            self: Pin<&mut ManualSelectionLogic>,
+            config_dir: QString,
+            rules_json: QString,
+        ) -> QString;
+
+        /// Replace the layout with the saved rules' result.
+        /// Returns JSON array of explanations for rules that placed nothing.
+        #[qinvokable]
+        fn apply_layout_rules(self: Pin<&mut ManualSelectionLogic>, config_dir: QString) -> QString;
+
         /// Signal: layout changed, UI needs refresh.
         #[qsignal]
         fn layout_changed(self: Pin<&mut ManualSelectionLogic>);
@@ -168,6 +203,7 @@ use std::collections::HashMap;
 
 use cxx_qt::CxxQtType;
 use cxx_qt_lib::QString;
+use vsg_core::job_layouts::rules::{evaluate_layout_rules, LayoutRuleSet};
 
 /// Track type constants for matching.
 const TYPE_VIDEO: &str = "video";
@@ -182,6 +218,7 @@ pub struct ManualSelectionLogicRust {
     track_info: HashMap<String, Vec<serde_json::Value>>,
     layout_tracks: Vec<serde_json::Value>,
     attachment_sources: Vec<String>,
+    extra_attachments: Vec<String>,
     source_settings: HashMap<String, serde_json::Value>,
 }
 
@@ -472,6 +509,16 @@ fn normalize_forced(layout: &mut [serde_json::Value]) {
     }
 }
 
+/// Config directory holding the layout rules; an error when the dialog was
+/// opened without one.
+fn rules_dir(config_dir: &QString) -> Result<std::path::PathBuf, String> {
+    let dir = config_dir.to_string();
+    if dir.is_empty() {
+        return Err("No config directory set; layout rules are unavailable.".to_string());
+    }
+    Ok(std::path::PathBuf::from(dir))
+}
+
 impl ffi::ManualSelectionLogic {
     fn initialize(
         mut self: Pin<&mut Self>,
@@ -579,6 +626,7 @@ impl ffi::ManualSelectionLogic {
         let result = serde_json::json!({
             "layout": self.rust().layout_tracks,
             "attachment_sources": self.rust().attachment_sources,
+            "extra_attachments": self.rust().extra_attachments,
             "source_settings": self.rust().source_settings,
         });
         let json = serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string());
@@ -664,6 +712,37 @@ impl ffi::ManualSelectionLogic {
         QString::from(json.as_str())
     }
 
+    fn set_extra_attachments(mut self: Pin<&mut Self>, paths_json: QString) {
+        let paths: Vec<String> =
+            serde_json::from_str(&paths_json.to_string()).unwrap_or_default();
+        self.as_mut().rust_mut().extra_attachments = paths;
+    }
+
+    fn add_extra_attachments(mut self: Pin<&mut Self>, paths_json: QString) {
+        let paths: Vec<String> =
+            serde_json::from_str(&paths_json.to_string()).unwrap_or_default();
+        let extra = &mut self.as_mut().rust_mut().extra_attachments;
+        for path in paths {
+            if !extra.contains(&path) {
+                extra.push(path);
+            }
+        }
+    }
+
+    fn remove_extra_attachment(mut self: Pin<&mut Self>, path: QString) {
+        let path = path.to_string();
+        self.as_mut()
+            .rust_mut()
+            .extra_attachments
+            .retain(|p| p != &path);
+    }
+
+    fn get_extra_attachments(self: Pin<&mut Self>) -> QString {
+        let json = serde_json::to_string(&self.rust().extra_attachments)
+            .unwrap_or_else(|_| "[]".to_string());
+        QString::from(json.as_str())
+    }
+
     fn set_source_settings(
         mut self: Pin<&mut Self>,
         source_key: QString,
@@ -896,4 +975,87 @@ impl ffi::ManualSelectionLogic {
             .map(is_blocked_video)
             .unwrap_or(false)
     }
+
+    fn get_layout_rules(self: Pin<&mut Self>, config_dir: QString) -> QString {
+        let (rules, error) = match rules_dir(&config_dir) {
+            Err(e) => (String::new(), e),
+            Ok(dir) => match LayoutRuleSet::load(&dir) {
+                Ok(set) => (
+                    serde_json::to_string_pretty(&set).unwrap_or_else(|_| "{}".to_string()),
+                    String::new(),
+                ),
+                // Show the file as it is so Save doesn't replace it with defaults
+                Err(e) => (
+                    std::fs::read_to_string(LayoutRuleSet::path(&dir)).unwrap_or_default(),
+                    e,
+                ),
+            },
+        };
+        let json = serde_json::json!({"rules": rules, "error": error}).to_string();
+        QString::from(json.as_str())
+    }
+
+    fn save_layout_rules(
+        self: Pin<&mut Self>,
+        config_dir: QString,
+        rules_json: QString,
+    ) -> QString {
+        let result = rules_dir(&config_dir).and_then(|dir| {
+            serde_json::from_str::<LayoutRuleSet>(&rules_json.to_string())
+                .map_err(|e| format!("Invalid layout rules: {e}"))
+                .and_then(|set| set.save(&dir))
+        });
+        QString::from(result.err().unwrap_or_default().as_str())
+    }
+
+    fn apply_layout_rules(mut self: Pin<&mut Self>, config_dir: QString) -> QString {
+        let evaluation = rules_dir(&config_dir)
+            .and_then(|dir| LayoutRuleSet::load(&dir))
+            .and_then(|set| evaluate_layout_rules(&set.rules, &self.rust().track_info));
+        let evaluation = match evaluation {
+            Ok(e) => e,
+            Err(e) => {
+                let json = serde_json::to_string(&[e]).unwrap_or_else(|_| "[]".to_string());
+                return QString::from(json.as_str());
+            }
+        };
+
+        // Dialog layout entries are the full track records plus flags
+        let layout: Vec<serde_json::Value> = evaluation
+            .layout
+            .iter()
+            .filter_map(|item| {
+                let source = item.source.as_deref()?;
+                let mut track = self
+                    .rust()
+                    .track_info
+                    .get(source)?
+                    .iter()
+                    .find(|t| track_i64(t, "id") == item.id.map(i64::from))?
+                    .clone();
+                let obj = track.as_object_mut()?;
+                obj.insert(
+                    "is_default".to_string(),
+                    serde_json::Value::Bool(item.is_default.unwrap_or(false)),
+                );
+                obj.insert(
+                    "is_forced_display".to_string(),
+                    serde_json::Value::Bool(item.is_forced_display.unwrap_or(false)),
+                );
+                Some(track)
+            })
+            .collect();
+
+        self.as_mut().rust_mut().layout_tracks = layout;
+        self.as_mut().set_info_text(QString::from(
+            "Built from layout rules. Review the order before accepting.",
+        ));
+        let count = self.rust().layout_tracks.len() as i32;
+        self.as_mut().set_layout_track_count(count);
+        self.as_mut().layout_changed();
+
+        let json =
+            serde_json::to_string(&evaluation.explanations).unwrap_or_else(|_| "[]".to_string());
+        QString::from(json.as_str())
+    }
 }
@@ -34,7 +34,7 @@ pub mod ffi {
         #[qinvokable]
         fn get_job_delays_summary(self: Pin<&mut ReportViewerLogic>, index: i32) -> QString;
 
-        /// Get report summary as JSON {successful, warnings, failed, total}.
+        /// Get report summary as JSON {successful, warnings, failed, skipped, total}.
         #[qinvokable]
         fn get_summary(self: Pin<&mut ReportViewerLogic>) -> QString;
 
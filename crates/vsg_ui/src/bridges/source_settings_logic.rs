@@ -1,7 +1,8 @@
 //! Source settings dialog logic — 1:1 port of `vsg_qt/source_settings_dialog/dialog.py`.
 //!
 //! Per-source audio/video track settings (reference audio track selection,
-//! correlation track selection, source separation toggle).
+//! correlation track selection, source separation toggle, manual stepping EDL)
+//! and where the job's chapters come from.
 
 #[cxx_qt::bridge]
 pub mod ffi {
@@ -12,6 +13,9 @@ pub mod ffi {
         #[qproperty(QString, source_key)]
         #[qproperty(i32, selected_track)]
         #[qproperty(bool, use_source_separation)]
+        #[qproperty(QString, stepping_edl_override)]
+        #[qproperty(bool, use_chapters)]
+        #[qproperty(QString, chapter_file)]
         #[qproperty(bool, is_source1)]
         type SourceSettingsLogic = super::SourceSettingsLogicRust;
 
@@ -51,6 +55,9 @@ pub struct SourceSettingsLogicRust {
     source_key: QString,
     selected_track: i32, // -1 = Auto (Language Fallback), 0+ = track index
     use_source_separation: bool,
+    stepping_edl_override: QString, // empty = run stepping detection
+    use_chapters: bool,
+    chapter_file: QString, // empty = chapters from the container
     is_source1: bool,
     audio_tracks: Vec<serde_json::Value>,
 }
@@ -61,6 +68,9 @@ impl Default for SourceSettingsLogicRust {
             source_key: QString::from(""),
             selected_track: -1, // Auto
             use_source_separation: false,
+            stepping_edl_override: QString::from(""),
+            use_chapters: false,
+            chapter_file: QString::from(""),
             is_source1: false,
             audio_tracks: Vec::new(),
         }
@@ -89,6 +99,9 @@ impl ffi::SourceSettingsLogic {
 
         // Apply existing settings
         if let Some(settings) = data.get("current_settings").and_then(|v| v.as_object()) {
+            if let Some(path) = settings.get("chapter_file").and_then(|v| v.as_str()) {
+                self.as_mut().set_chapter_file(QString::from(path));
+            }
             if is_source1 {
                 if let Some(track) = settings.get("correlation_ref_track").and_then(|v| v.as_i64())
                 {
@@ -107,6 +120,15 @@ impl ffi::SourceSettingsLogic {
                 {
                     self.as_mut().set_use_source_separation(sep);
                 }
+                if let Some(path) = settings
+                    .get("stepping_edl_override")
+                    .and_then(|v| v.as_str())
+                {
+                    self.as_mut().set_stepping_edl_override(QString::from(path));
+                }
+                if let Some(chapters) = settings.get("use_chapters").and_then(|v| v.as_bool()) {
+                    self.as_mut().set_use_chapters(chapters);
+                }
             }
         }
     }
@@ -161,12 +183,31 @@ impl ffi::SourceSettingsLogic {
             serde_json::json!(track)
         };
 
+        let chapter_path = self.as_ref().chapter_file().to_string();
+        let chapter_value = if chapter_path.trim().is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::json!(chapter_path.trim())
+        };
+
         let result = if *self.as_ref().is_source1() {
-            serde_json::json!({"correlation_ref_track": track_value})
+            serde_json::json!({
+                "correlation_ref_track": track_value,
+                "chapter_file": chapter_value,
+            })
         } else {
+            let edl_path = self.as_ref().stepping_edl_override().to_string();
+            let edl_value = if edl_path.trim().is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::json!(edl_path.trim())
+            };
             serde_json::json!({
                 "correlation_source_track": track_value,
                 "use_source_separation": *self.as_ref().use_source_separation(),
+                "stepping_edl_override": edl_value,
+                "use_chapters": *self.as_ref().use_chapters(),
+                "chapter_file": chapter_value,
             })
         };
         let json = serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string());
@@ -174,15 +215,24 @@ impl ffi::SourceSettingsLogic {
     }
 
     fn has_non_default_settings(self: Pin<&mut Self>) -> bool {
+        let has_chapter_file = !self.as_ref().chapter_file().to_string().trim().is_empty();
         if *self.as_ref().is_source1() {
             *self.as_ref().selected_track() >= 0 // Non-auto
+                || has_chapter_file
         } else {
-            *self.as_ref().selected_track() >= 0 || *self.as_ref().use_source_separation()
+            *self.as_ref().selected_track() >= 0
+                || *self.as_ref().use_source_separation()
+                || !self.as_ref().stepping_edl_override().to_string().trim().is_empty()
+                || *self.as_ref().use_chapters()
+                || has_chapter_file
         }
     }
 
     fn reset_to_defaults(mut self: Pin<&mut Self>) {
         self.as_mut().set_selected_track(-1); // Auto
         self.as_mut().set_use_source_separation(false);
+        self.as_mut().set_stepping_edl_override(QString::from(""));
+        self.as_mut().set_use_chapters(false);
+        self.as_mut().set_chapter_file(QString::from(""));
     }
 }
@@ -465,7 +465,8 @@ impl ffi::TrackWidgetLogic {
                 .cloned()
                 .unwrap_or_default();
             let has_corr = ss.get("correlation_source_track").is_some_and(|v| !v.is_null())
-                || get_bool(&ss, "use_source_separation");
+                || get_bool(&ss, "use_source_separation")
+                || ss.get("stepping_edl_override").is_some_and(|v| v.is_string());
             if has_corr {
                 badges.push("Correlation Settings".to_string());
             }
@@ -606,6 +607,9 @@ impl ffi::TrackWidgetLogic {
             if get_bool(&ss, "use_source_separation") {
                 corr_parts.push("Source Separation".to_string());
             }
+            if ss.get("stepping_edl_override").is_some_and(|v| v.is_string()) {
+                corr_parts.push("Manual EDL".to_string());
+            }
             if !corr_parts.is_empty() {
                 parts.push(format!("Corr: {}", corr_parts.join(", ")));
             }
@@ -61,6 +61,18 @@ pub fn run_job_batch(config: JobRunnerConfig, signals: SignalCallbacks) {
     let mut all_results: Vec<serde_json::Value> = Vec::new();
     let total_jobs = config.jobs.len();
 
+    // Chapter generation compares episodes, so it runs once for the batch
+    if config.and_merge && config.settings.generate_chapters {
+        (signals.status)("Generating chapters...");
+        let episodes: Vec<String> = config
+            .jobs
+            .iter()
+            .filter_map(|job_data| extract_sources(job_data).remove("Source 1"))
+            .filter(|f| !f.is_empty())
+            .collect();
+        pipeline.generate_batch_chapters(&episodes);
+    }
+
     for (i, job_data) in config.jobs.iter().enumerate() {
         // Check for cancellation — 1:1 with `if self.cancelled: break`
         if config.cancelled.load(Ordering::Relaxed) {
@@ -99,6 +111,7 @@ pub fn run_job_batch(config: JobRunnerConfig, signals: SignalCallbacks) {
         // Extract optional fields from job_data
         let manual_layout = extract_manual_layout(job_data);
         let attachment_sources = extract_string_array(job_data, "attachment_sources");
+        let extra_attachments = extract_string_array(job_data, "extra_attachments");
         let source_settings = extract_source_settings(job_data);
 
         // Wrap pipeline.run_job in catch_unwind for panic safety
@@ -110,6 +123,7 @@ pub fn run_job_batch(config: JobRunnerConfig, signals: SignalCallbacks) {
                 &config.output_dir,
                 manual_layout,
                 attachment_sources,
+                extra_attachments,
                 source_settings,
             )
         }));